@@ -0,0 +1,124 @@
+//! Benchmarks for lap buffering in `LapHandler`
+//!
+//! Publishes several laps of frames through the event bus and waits for the
+//! completed laps, measuring:
+//! - Frame throughput of lap detection and buffering
+//! - Heap allocations per lap, counted by a wrapping global allocator
+//!
+//! Handling an event allocates once for the boxed handler future, so a lap
+//! costs at least one allocation per frame. The lap buffer is sized from the
+//! previous lap, so buffering should add only a handful on top of that.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use client_rs::events::{RacingEvent, RacingEventKind};
+use client_rs::handlers::LapHandler;
+use client_rs::telem::TelemetryFrame;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use eventbus::{EventBus, HandlerRegistry};
+use tokio::runtime::Runtime;
+
+/// Counts allocations made through the system allocator.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const LAPS: i32 = 5;
+
+/// Frames for `LAPS` complete laps after joining mid-lap. The partial first
+/// lap is too short to be reported.
+fn session_frames(frames_per_lap: usize) -> Vec<Arc<TelemetryFrame>> {
+    let frame = |lap_number, lap_distance_pct| {
+        Arc::new(TelemetryFrame {
+            lap_number,
+            lap_distance_pct,
+            track_surface: 3,
+            ..Default::default()
+        })
+    };
+
+    let mut frames = vec![frame(1, 0.99)];
+    for lap in 2..=LAPS + 1 {
+        frames.extend((0..frames_per_lap).map(|i| frame(lap, i as f32 / frames_per_lap as f32)));
+    }
+    frames.push(frame(LAPS + 2, 0.0));
+    frames
+}
+
+/// Run a `LapHandler` over `frames` and wait for every completed lap.
+async fn detect_laps(frames: &[Arc<TelemetryFrame>]) -> usize {
+    let bus = EventBus::new(frames.len());
+    let mut laps = bus.subscribe(RacingEventKind::LapComplete);
+
+    let mut registry = HandlerRegistry::new();
+    registry.register(LapHandler::new());
+    let handles = registry.run(bus.clone());
+
+    for frame in frames {
+        bus.publish(RacingEvent::TelemetryFrameCollected(frame.clone()))
+            .expect("handler should be subscribed");
+    }
+
+    let mut completed = 0;
+    while completed < LAPS as usize {
+        black_box(laps.recv().await.expect("lap should complete"));
+        completed += 1;
+    }
+
+    registry.shutdown();
+    for handle in handles {
+        let _ = handle.await;
+    }
+    completed
+}
+
+fn bench_lap_buffer(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("lap_buffer");
+
+    for frames_per_lap in [600, 3600, 6000] {
+        let frames = session_frames(frames_per_lap);
+
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        runtime.block_on(detect_laps(&frames));
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        println!(
+            "lap_buffer/{frames_per_lap}: {} allocations per lap",
+            allocations / LAPS as usize
+        );
+
+        group.throughput(Throughput::Elements(frames.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("frames_per_lap", frames_per_lap),
+            &frames,
+            |b, frames| b.iter(|| runtime.block_on(detect_laps(frames))),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_lap_buffer);
+criterion_main!(benches);
@@ -0,0 +1,210 @@
+//! Offline analysis of an IBT file.
+//!
+//! [`analyze_ibt`] runs a recording through the same lap detection as a
+//! live run, without uploading anything or contacting the server, and
+//! [`format_summary`] renders the laps it finds for the terminal.
+
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use eventbus::EventBus;
+use futures::StreamExt;
+use pitwall::{FrameAdapter, FramePacket, IbtReader, SessionInfo, UpdateRate};
+
+use crate::app::RacingCoachApp;
+use crate::config::{Config, UnitSystem};
+use crate::events::{LapCompletePayload, RacingEvent, RacingEventKind};
+use crate::source::{FrameStream, SessionStream, TelemetryProvider};
+use crate::telem::TelemetryFrame;
+
+/// How often a paused file checks whether handlers have caught up.
+const BACKPRESSURE_POLL: Duration = Duration::from_millis(1);
+
+/// Brake pressure that counts as braking, the analysis library's default.
+const BRAKE_THRESHOLD: f32 = 0.05;
+
+/// Read the IBT file at `path` as fast as possible and return the laps
+/// detected in it, in order.
+pub async fn analyze_ibt(path: &Path) -> pitwall::Result<Vec<LapCompletePayload>> {
+    let config = Config {
+        dry_run: true,
+        ..Default::default()
+    };
+    let app = RacingCoachApp::new(config);
+    let provider = IbtFile::read(path, app.bus().clone())?;
+    let mut events = app.bus().subscribe(RacingEventKind::LapComplete);
+
+    app.run_with(&provider).await;
+
+    let mut laps = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let RacingEvent::LapComplete(lap) = event {
+            laps.push(lap);
+        }
+    }
+    Ok(laps)
+}
+
+/// Every frame of an IBT file, played back once without pacing.
+///
+/// Unlike a replay connection, its frame stream ends with the file, so a run
+/// over it finishes on its own.
+struct IbtFile {
+    frames: Mutex<Vec<TelemetryFrame>>,
+    session: Option<Arc<SessionInfo>>,
+    /// Bus the frames are published to, watched for backpressure
+    bus: EventBus<RacingEvent>,
+}
+
+impl IbtFile {
+    fn read(path: &Path, bus: EventBus<RacingEvent>) -> pitwall::Result<Self> {
+        let mut reader = IbtReader::open(path)?;
+        let schema = Arc::new(reader.variables().clone());
+        let validation = TelemetryFrame::validate_schema(&schema)?;
+
+        let mut frames = Vec::with_capacity(reader.total_frames());
+        while let Some((data, tick, session_version)) = reader.read_next_frame()? {
+            let packet = FramePacket::new(data, tick, session_version, schema.clone());
+            frames.push(TelemetryFrame::adapt(&packet, &validation));
+        }
+
+        let session = match reader.session_yaml()? {
+            Some(yaml) => Some(Arc::new(SessionInfo::parse(&yaml)?)),
+            None => None,
+        };
+        Ok(Self {
+            frames: Mutex::new(frames),
+            session,
+            bus,
+        })
+    }
+}
+
+impl TelemetryProvider for IbtFile {
+    /// The file's frames; only the first subscriber receives them.
+    fn subscribe(&self, _rate: UpdateRate) -> FrameStream {
+        let frames = std::mem::take(&mut *self.frames.lock().unwrap());
+        let bus = self.bus.clone();
+        // Hold frames back while handlers catch up, so none of them lags
+        // behind the bus and misses frames
+        futures::stream::iter(frames)
+            .then(move |frame| {
+                let bus = bus.clone();
+                async move {
+                    while bus.metrics().queue_depth >= bus.capacity() / 2 {
+                        tokio::time::sleep(BACKPRESSURE_POLL).await;
+                    }
+                    frame
+                }
+            })
+            .boxed()
+    }
+
+    fn current_session(&self) -> Option<Arc<SessionInfo>> {
+        self.session.clone()
+    }
+
+    fn session_updates(&self) -> SessionStream {
+        futures::stream::empty().boxed()
+    }
+}
+
+/// A human-readable summary of `laps`, one block per lap, with speeds in
+/// `units`.
+pub fn format_summary(laps: &[LapCompletePayload], units: UnitSystem) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{} laps", laps.len());
+
+    for lap in laps {
+        let frames = &lap.frames[..];
+        let lap_time = frames
+            .iter()
+            .map(|frame| frame.current_lap_time)
+            .fold(0.0, f32::max);
+        let top_speed = frames.iter().map(|frame| frame.speed).fold(0.0, f32::max);
+        let validity = if lap.is_valid { "valid" } else { "invalid" };
+
+        let _ = writeln!(
+            out,
+            "Lap {} ({:?}, {}): {}, top speed {:.1} {}, {} frames",
+            lap.lap_number,
+            lap.lap_type,
+            validity,
+            format_lap_time(lap_time),
+            units.speed(top_speed.into()),
+            units.speed_unit(),
+            lap.frame_count
+        );
+
+        let (zones, peak) = braking_zones(frames);
+        let _ = writeln!(
+            out,
+            "  Braking: {} zones, peak pressure {:.0}%",
+            zones,
+            peak * 100.0
+        );
+    }
+    out
+}
+
+/// Number of separate stretches of braking in `frames` and the highest
+/// brake pressure reached.
+fn braking_zones(frames: &[Arc<TelemetryFrame>]) -> (usize, f32) {
+    let mut zones = 0;
+    let mut braking = false;
+    let mut peak = 0.0f32;
+
+    for frame in frames {
+        let now_braking = frame.brake >= BRAKE_THRESHOLD;
+        if now_braking && !braking {
+            zones += 1;
+        }
+        braking = now_braking;
+        peak = peak.max(frame.brake);
+    }
+    (zones, peak)
+}
+
+/// A lap time in seconds as `m:ss.mmm`.
+fn format_lap_time(secs: f32) -> String {
+    let millis = (secs.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{}:{:02}.{:03}",
+        millis / 60_000,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_IBT: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../sample_data/ligierjsp320_bathurst 2025-11-17 18-15-16.ibt"
+    );
+
+    #[tokio::test]
+    async fn test_analyze_summarizes_sample_laps() {
+        let laps = analyze_ibt(Path::new(SAMPLE_IBT)).await.unwrap();
+        assert!(!laps.is_empty(), "sample should contain laps");
+
+        let summary = format_summary(&laps, UnitSystem::Metric);
+
+        assert!(
+            summary.starts_with(&format!("{} laps\n", laps.len())),
+            "{summary}"
+        );
+        assert!(summary.contains("  Braking: "), "{summary}");
+        assert_eq!(summary.matches("km/h").count(), laps.len());
+    }
+
+    #[test]
+    fn test_lap_time_format() {
+        assert_eq!(format_lap_time(92.4567), "1:32.457");
+        assert_eq!(format_lap_time(5.0), "0:05.000");
+    }
+}
@@ -0,0 +1,624 @@
+//! Application wiring.
+//!
+//! [`RacingCoachApp`] connects the telemetry source from [`Config`] to the
+//! event bus and runs the handlers that detect and upload laps, reporting a
+//! [`RunSummary`] once the source ends. A [`RunHandle`] pauses, resumes,
+//! steps or stops a run from elsewhere, e.g. a GUI or [`step_on_input`].
+//! With health checks configured, a [`HealthMonitor`] watches the server for
+//! the length of the run.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use eventbus::{EventBus, EventHandler, HandlerRegistry};
+use pitwall::UpdateRate;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+use tokio::sync::{Semaphore, watch};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::config::{Config, LapOutput};
+use crate::events::RacingEvent;
+use crate::handlers::{
+    BestLapHandler, GhostHandler, JsonLinesUploader, LapHandler, LapUploadHandler, LapUploader,
+    LiveDeltaHandler, LogHandler, RunStats, RunSummary, SessionStateHandler, StatsHandler,
+    UploadLimit,
+};
+use crate::health::HealthMonitor;
+use crate::session::SessionState;
+use crate::source::{
+    CollectorControl, Connector, SourceMode, TelemetryProvider, TelemetrySource,
+    collect_with_control, collect_with_reconnect,
+};
+
+/// Channel capacity per event kind.
+const BUS_CAPACITY: usize = 10000;
+
+/// Controls a running [`RacingCoachApp`]. Cheap to clone.
+#[derive(Clone)]
+pub struct RunHandle {
+    stop: CancellationToken,
+    paused: Arc<watch::Sender<bool>>,
+    steps: Arc<Semaphore>,
+    finished: watch::Receiver<bool>,
+}
+
+impl RunHandle {
+    /// Stop publishing telemetry until [`RunHandle::resume`] is called.
+    ///
+    /// Steps not yet taken (see [`RunHandle::step`]) are discarded.
+    pub fn pause(&self) {
+        self.steps.forget_permits(usize::MAX);
+        self.paused.send_replace(true);
+    }
+
+    /// Publish the next `frames` frames while paused, then hold again.
+    pub fn step(&self, frames: usize) {
+        let room = Semaphore::MAX_PERMITS - self.steps.available_permits();
+        self.steps.add_permits(frames.min(room));
+    }
+
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// End the run and wait until handlers have processed the events already
+    /// published, including pending uploads.
+    pub async fn stop(&self) {
+        self.stop.cancel();
+        let mut finished = self.finished.clone();
+        // Errors only if the app was dropped, in which case nothing is running
+        let _ = finished.wait_for(|finished| *finished).await;
+    }
+}
+
+/// The telemetry client: a source, the event bus, and its handlers.
+pub struct RacingCoachApp {
+    config: Config,
+    bus: EventBus<RacingEvent>,
+    registry: HandlerRegistry<RacingEvent>,
+    stats: Arc<RunStats>,
+    stop: CancellationToken,
+    paused: Arc<watch::Sender<bool>>,
+    steps: Arc<Semaphore>,
+    finished: watch::Sender<bool>,
+    health: Option<HealthMonitor>,
+}
+
+impl RacingCoachApp {
+    /// Create the app without an upload destination; laps are only logged.
+    pub fn new(config: Config) -> Self {
+        Self::with_uploader(config, None)
+    }
+
+    /// Create the app, sending completed laps to the configured
+    /// [`LapOutput`].
+    ///
+    /// The client has no server uploader of its own, so with
+    /// [`LapOutput::Server`] laps are only logged, as with [`RacingCoachApp::new`].
+    /// Fails if the lap output file can't be opened.
+    pub fn from_config(config: Config) -> std::io::Result<Self> {
+        let uploader: Option<Arc<dyn LapUploader>> = match config.lap_output {
+            LapOutput::Server => None,
+            LapOutput::File => {
+                let path = config.lap_output_file.clone().unwrap_or_default();
+                Some(Arc::new(JsonLinesUploader::append_to(&path)?))
+            }
+            LapOutput::Stdout => Some(Arc::new(JsonLinesUploader::stdout())),
+        };
+        Ok(Self::with_uploader(config, uploader))
+    }
+
+    /// Create the app, uploading completed laps to `uploader` unless the
+    /// config is a dry run. The run starts paused if the config says so, and
+    /// checks the server's health if configured (never in a dry run).
+    pub fn with_uploader(config: Config, uploader: Option<Arc<dyn LapUploader>>) -> Self {
+        let stats = RunStats::new();
+        let upload_handler = match uploader {
+            Some(uploader) if !config.dry_run => LapUploadHandler::new(uploader),
+            _ => {
+                if config.dry_run {
+                    info!("Dry run: lap detection runs, uploads are disabled");
+                }
+                LapUploadHandler::disabled()
+            }
+        };
+
+        let mut registry = HandlerRegistry::new();
+        registry.register(LapHandler::new());
+        registry.register(LogHandler::default().with_units(config.units));
+        if let Some(dir) = &config.ghost_dir {
+            registry.register(GhostHandler::new(dir, config.ghost_points));
+        }
+        registry.register(
+            upload_handler
+                .with_stats(stats.clone())
+                .with_limit(UploadLimit::new(config.max_concurrent_uploads)),
+        );
+        for handler in StatsHandler::for_stats(&stats) {
+            registry.register(handler);
+        }
+        for handler in BestLapHandler::handlers() {
+            registry.register(handler);
+        }
+        for handler in LiveDeltaHandler::handlers() {
+            registry.register(handler);
+        }
+        let session_state = SessionState::new(config.lap_history);
+        registry.set_state(session_state.clone());
+        for handler in SessionStateHandler::for_state(&session_state) {
+            registry.register(handler);
+        }
+
+        let paused = watch::channel(config.start_paused).0;
+
+        let health = match config.health_check_secs {
+            Some(secs) if !config.dry_run => {
+                match HealthMonitor::new(&config.server_url, Duration::from_secs(secs)) {
+                    Ok(monitor) => Some(monitor),
+                    Err(error) => {
+                        warn!("Server health checks disabled: {}", error);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        Self {
+            config,
+            bus: EventBus::new(BUS_CAPACITY),
+            registry,
+            stats,
+            stop: CancellationToken::new(),
+            paused: Arc::new(paused),
+            steps: Arc::new(Semaphore::new(0)),
+            finished: watch::channel(false).0,
+            health,
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn bus(&self) -> &EventBus<RacingEvent> {
+        &self.bus
+    }
+
+    /// A handle for pausing or stopping the run from another task.
+    pub fn handle(&self) -> RunHandle {
+        RunHandle {
+            stop: self.stop.clone(),
+            paused: self.paused.clone(),
+            steps: self.steps.clone(),
+            finished: self.finished.subscribe(),
+        }
+    }
+
+    /// Register an additional handler to run alongside the built-in ones.
+    pub fn register<H: EventHandler<RacingEvent> + 'static>(&mut self, handler: H) {
+        self.registry.register(handler);
+    }
+
+    /// Connect to the configured telemetry source and run until it ends.
+    ///
+    /// Live telemetry is reconnected with the configured
+    /// [`ReconnectPolicy`](crate::source::ReconnectPolicy) when iRacing goes
+    /// away, so the run only ends once stopped or after the policy gives up.
+    pub async fn run(self) -> pitwall::Result<RunSummary> {
+        match self.config.source_mode() {
+            SourceMode::Live => self.run_reconnecting(SourceMode::Live).await,
+            mode => {
+                let provider = TelemetrySource::create(&mode).await?;
+                Ok(self.run_with(provider.as_ref()).await)
+            }
+        }
+    }
+
+    /// Run the handlers on telemetry from `provider` until its frames end or
+    /// the run is stopped through a [`RunHandle`].
+    pub async fn run_with(mut self, provider: &dyn TelemetryProvider) -> RunSummary {
+        let running = self.start();
+        let frames =
+            collect_with_control(provider, UpdateRate::Native, &self.bus, &running.control).await;
+        if self.stop.is_cancelled() {
+            info!("Run stopped after {} frames", frames);
+        } else {
+            info!("Telemetry source finished after {} frames", frames);
+        }
+        self.finish(running).await
+    }
+
+    /// Run the handlers on telemetry from `connector`, reconnecting whenever
+    /// its stream ends, until the run is stopped through a [`RunHandle`].
+    ///
+    /// Fails with the last connection error once the config's reconnect
+    /// policy gives up; handlers still finish the events already published.
+    pub async fn run_reconnecting<C: Connector>(
+        mut self,
+        connector: C,
+    ) -> pitwall::Result<RunSummary> {
+        let running = self.start();
+        let collected = collect_with_reconnect(
+            connector,
+            self.config.reconnect_policy(),
+            UpdateRate::Native,
+            &self.bus,
+            &running.control,
+        )
+        .await;
+        if let Ok(frames) = collected {
+            info!("Run stopped after {} frames", frames);
+        }
+        let summary = self.finish(running).await;
+        collected.map(|_| summary)
+    }
+
+    /// Start the handlers and health checks for a run.
+    fn start(&mut self) -> Running {
+        let health_stop = CancellationToken::new();
+        let health_task = self
+            .health
+            .take()
+            .map(|monitor| tokio::spawn(monitor.run(self.bus.clone(), health_stop.clone())));
+
+        Running {
+            started: Instant::now(),
+            handles: self.registry.run(self.bus.clone()),
+            control: CollectorControl::new(self.stop.clone(), self.paused.subscribe())
+                .with_steps(self.steps.clone()),
+            health_stop,
+            health_task,
+        }
+    }
+
+    /// Wait for handlers to finish what was published, then summarize the run.
+    async fn finish(self, running: Running) -> RunSummary {
+        running.health_stop.cancel();
+        if let Some(task) = running.health_task {
+            let _ = task.await;
+        }
+
+        // Let handlers finish queued events, including pending uploads
+        self.bus.shutdown_and_drain().await;
+        self.registry.shutdown();
+        for handle in running.handles {
+            let _ = handle.await;
+        }
+
+        let summary = self.stats.summary(running.started.elapsed());
+        info!("Run complete: {}", summary);
+        self.finished.send_replace(true);
+        summary
+    }
+}
+
+/// Tasks and signals of a run in progress, see [`RacingCoachApp::start`].
+struct Running {
+    started: Instant,
+    handles: Vec<JoinHandle<()>>,
+    control: CollectorControl,
+    health_stop: CancellationToken,
+    health_task: Option<JoinHandle<()>>,
+}
+
+/// Control a run from lines of `input`, typically stdin, until it ends.
+///
+/// An empty line steps one frame and a number steps that many (see
+/// [`RunHandle::step`]); `p` pauses and `r` resumes.
+pub async fn step_on_input<R: AsyncBufRead + Unpin>(handle: RunHandle, input: R) {
+    let mut lines = input.lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        match line.trim() {
+            "" => handle.step(1),
+            "p" => handle.pause(),
+            "r" => handle.resume(),
+            other => match other.parse() {
+                Ok(frames) => handle.step(frames),
+                Err(_) => warn!(
+                    "Unknown command {:?}: press Enter to step, or type a frame count, p or r",
+                    other
+                ),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TelemetryMode;
+    use crate::events::{LapCompletePayload, LapType, RacingEventKind};
+    use crate::handlers::{LapUploadResponse, UploadError};
+    use crate::test_utils::{FrameProvider, frame};
+    use async_trait::async_trait;
+    use futures::StreamExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Uploader that only counts calls.
+    #[derive(Default)]
+    struct CountingUploader {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LapUploader for CountingUploader {
+        async fn upload_lap(
+            &self,
+            _lap: &LapCompletePayload,
+        ) -> Result<LapUploadResponse, UploadError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(LapUploadResponse {
+                lap_id: uuid::Uuid::new_v4(),
+            })
+        }
+    }
+
+    /// Two complete laps after joining mid-lap.
+    fn session_frames() -> Vec<crate::telem::TelemetryFrame> {
+        let mut frames = vec![frame(1, 0.95)];
+        for lap in 2..=3 {
+            frames.extend((0..20).map(|i| frame(lap, i as f32 / 20.0)));
+        }
+        frames.push(frame(4, 0.0));
+        frames
+    }
+
+    async fn run_app(dry_run: bool) -> (usize, usize, RunSummary) {
+        let config = Config {
+            mode: TelemetryMode::Replay,
+            dry_run,
+            ..Default::default()
+        };
+        let uploader = Arc::new(CountingUploader::default());
+        let app = RacingCoachApp::with_uploader(config, Some(uploader.clone()));
+        let mut laps = app.bus().subscribe(RacingEventKind::LapComplete);
+
+        let summary = app.run_with(&FrameProvider::new(session_frames())).await;
+
+        let mut lap_count = 0;
+        while laps.try_recv().is_ok() {
+            lap_count += 1;
+        }
+        (lap_count, uploader.calls.load(Ordering::SeqCst), summary)
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_detects_laps_without_uploading() {
+        let (laps, uploads, _) = run_app(true).await;
+
+        assert!(laps >= 2, "laps should still be detected, got {laps}");
+        assert_eq!(uploads, 0);
+    }
+
+    #[tokio::test]
+    async fn test_laps_are_uploaded() {
+        let (laps, uploads, _) = run_app(false).await;
+
+        assert!(laps >= 2, "laps should be detected, got {laps}");
+        assert_eq!(uploads, laps);
+    }
+
+    #[tokio::test]
+    async fn test_summary_matches_published_events() {
+        let (laps, uploads, summary) = run_app(false).await;
+
+        assert_eq!(summary.frames_processed, session_frames().len() as u64);
+        assert_eq!(summary.laps_detected, laps);
+        assert_eq!(summary.laps_uploaded, uploads);
+        assert_eq!(summary.metrics_extracted, 0);
+    }
+
+    /// Provider that yields a frame every 10ms and never ends.
+    struct EndlessProvider;
+
+    impl TelemetryProvider for EndlessProvider {
+        fn subscribe(&self, _rate: UpdateRate) -> crate::source::FrameStream {
+            futures::stream::unfold(0, |i| async move {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Some((frame(1, (i % 100) as f32 / 100.0), i + 1))
+            })
+            .boxed()
+        }
+
+        fn current_session(&self) -> Option<Arc<pitwall::SessionInfo>> {
+            None
+        }
+
+        fn session_updates(&self) -> crate::source::SessionStream {
+            futures::stream::empty().boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stop_ends_run_promptly() {
+        let app = RacingCoachApp::new(Config::default());
+        let handle = app.handle();
+        let mut frames = app
+            .bus()
+            .subscribe(RacingEventKind::TelemetryFrameCollected);
+
+        let control = async {
+            for _ in 0..3 {
+                frames.recv().await.unwrap();
+            }
+            handle.stop().await;
+        };
+        let (summary, ()) = tokio::time::timeout(
+            Duration::from_secs(2),
+            futures::future::join(app.run_with(&EndlessProvider), control),
+        )
+        .await
+        .expect("run should end soon after stop");
+
+        assert!(summary.frames_processed >= 3);
+    }
+
+    #[tokio::test]
+    async fn test_pause_holds_frames_until_resumed() {
+        let app = RacingCoachApp::new(Config::default());
+        let handle = app.handle();
+        let mut frames = app
+            .bus()
+            .subscribe(RacingEventKind::TelemetryFrameCollected);
+
+        handle.pause();
+        assert!(handle.is_paused());
+
+        let control = async {
+            let held = tokio::time::timeout(Duration::from_millis(100), frames.recv()).await;
+            assert!(held.is_err(), "no frames should be published while paused");
+
+            handle.resume();
+            frames.recv().await.unwrap();
+            handle.stop().await;
+        };
+        tokio::time::timeout(
+            Duration::from_secs(2),
+            futures::future::join(app.run_with(&EndlessProvider), control),
+        )
+        .await
+        .expect("run should end soon after stop");
+    }
+
+    /// Run a paused app on [`EndlessProvider`] while `control` drives it,
+    /// returning the number of frames published.
+    async fn run_paused<F>(control: impl FnOnce(RunHandle) -> F) -> u64
+    where
+        F: Future<Output = ()>,
+    {
+        let config = Config {
+            start_paused: true,
+            ..Default::default()
+        };
+        let app = RacingCoachApp::new(config);
+        let handle = app.handle();
+        assert!(handle.is_paused());
+
+        let control = async {
+            control(handle.clone()).await;
+            handle.stop().await;
+        };
+        let (summary, ()) = tokio::time::timeout(
+            Duration::from_secs(2),
+            futures::future::join(app.run_with(&EndlessProvider), control),
+        )
+        .await
+        .expect("run should end soon after stop");
+        summary.frames_processed
+    }
+
+    #[tokio::test]
+    async fn test_step_publishes_exactly_that_many_frames() {
+        let frames = run_paused(|handle| async move {
+            handle.step(10);
+            // Long enough for far more than 10 frames at 100 Hz
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            assert!(handle.is_paused());
+        })
+        .await;
+
+        assert_eq!(frames, 10);
+    }
+
+    #[tokio::test]
+    async fn test_input_commands_step_frames() {
+        let frames = run_paused(|handle| async move {
+            step_on_input(handle, "\n3\nnonsense\n".as_bytes()).await;
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        })
+        .await;
+
+        assert_eq!(frames, 4);
+    }
+
+    /// Connector that serves [`session_frames`] for `connections` connections,
+    /// then fails as if iRacing had been closed.
+    struct FlakyConnector {
+        connections: usize,
+    }
+
+    #[async_trait]
+    impl Connector for FlakyConnector {
+        async fn connect(&mut self) -> pitwall::Result<Box<dyn TelemetryProvider>> {
+            if self.connections == 0 {
+                return Err(pitwall::TelemetryError::connection_failed(
+                    "sim not running",
+                ));
+            }
+            self.connections -= 1;
+            Ok(Box::new(FrameProvider::new(session_frames())))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconnecting_run_keeps_detecting_laps() {
+        let config = Config {
+            reconnect_attempts: 1,
+            ..Default::default()
+        };
+        let app = RacingCoachApp::new(config);
+        let mut frames = app
+            .bus()
+            .subscribe(RacingEventKind::TelemetryFrameCollected);
+        let mut sessions = app.bus().subscribe(RacingEventKind::SessionStart);
+        let mut laps = app.bus().subscribe(RacingEventKind::LapComplete);
+
+        let result = app
+            .run_reconnecting(FlakyConnector { connections: 2 })
+            .await;
+
+        assert!(result.is_err(), "the run ends once reconnecting gives up");
+        let mut frame_count = 0;
+        while let Ok(event) = frames.try_recv() {
+            if let RacingEvent::TelemetryFrameCollected(_) = event {
+                frame_count += 1;
+            }
+        }
+        let mut timed_laps = Vec::new();
+        while let Ok(RacingEvent::LapComplete(lap)) = laps.try_recv() {
+            if lap.lap_type == LapType::Timed {
+                timed_laps.push(lap.lap_number);
+            }
+        }
+        assert_eq!(timed_laps, [2, 3, 2, 3], "each connection's laps, whole");
+        let mut session_count = 0;
+        while sessions.try_recv().is_ok() {
+            session_count += 1;
+        }
+        assert_eq!(frame_count, 2 * session_frames().len());
+        assert_eq!(session_count, 2, "each connection starts a session");
+    }
+
+    #[tokio::test]
+    async fn test_stop_ends_reconnecting_run() {
+        let app = RacingCoachApp::new(Config::default());
+        let handle = app.handle();
+        let mut frames = app
+            .bus()
+            .subscribe(RacingEventKind::TelemetryFrameCollected);
+
+        let control = async {
+            frames.recv().await.unwrap();
+            handle.stop().await;
+        };
+        let (result, ()) = tokio::time::timeout(
+            Duration::from_secs(2),
+            futures::future::join(
+                app.run_reconnecting(FlakyConnector { connections: 1 }),
+                control,
+            ),
+        )
+        .await
+        .expect("run should end soon after stop");
+
+        assert!(result.unwrap().frames_processed >= 1);
+    }
+}
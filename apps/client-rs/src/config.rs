@@ -1,12 +1,864 @@
+//! Application configuration.
+//!
+//! Settings are merged from, in order of increasing precedence: built-in
+//! defaults, an optional TOML or YAML config file, environment variables, and
+//! command-line flags.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Deserialize;
+use thiserror::Error;
+use tracing::warn;
+
+use crate::ghost::DEFAULT_GHOST_POINTS;
+use crate::source::{ReconnectPolicy, SourceMode};
+use crate::utils::{mps_to_kmh, mps_to_mph};
+
+/// Where telemetry is read from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum TelemetryMode {
+    /// Live telemetry from a running iRacing session (Windows only)
+    #[default]
+    Live,
+    /// Replay of a recorded IBT file
+    Replay,
+}
+
+/// Where completed laps are sent.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LapOutput {
+    /// The Racing Coach server
+    #[default]
+    Server,
+    /// One JSON line per lap, appended to the lap output file
+    File,
+    /// One JSON line per lap on stdout
+    Stdout,
+}
+
+/// Units speeds are shown in. Stored and uploaded values are always SI.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitSystem {
+    /// km/h
+    #[default]
+    Metric,
+    /// mph
+    Imperial,
+}
+
+impl UnitSystem {
+    /// A speed in m/s converted to these units.
+    pub fn speed(self, mps: f64) -> f64 {
+        match self {
+            Self::Metric => mps_to_kmh(mps),
+            Self::Imperial => mps_to_mph(mps),
+        }
+    }
+
+    /// Label for speeds in these units.
+    pub fn speed_unit(self) -> &'static str {
+        match self {
+            Self::Metric => "km/h",
+            Self::Imperial => "mph",
+        }
+    }
+}
+
 /// Application configuration.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     pub server_url: String,
+    pub mode: TelemetryMode,
+    /// IBT file to read in replay mode.
+    pub ibt_file: Option<PathBuf>,
+    /// Replay speed multiplier.
+    pub speed: f64,
+    /// Restart replay from the beginning when the end of the IBT file is reached.
+    pub loop_playback: bool,
+    /// Run the full pipeline but log uploads instead of sending them.
+    pub dry_run: bool,
+    /// Start with telemetry paused, stepping through frames from stdin.
+    pub start_paused: bool,
+    /// Number of recent laps kept in memory for handlers to compare against.
+    pub lap_history: usize,
+    /// Where completed laps are sent.
+    pub lap_output: LapOutput,
+    /// File laps are appended to when `lap_output` is `file`.
+    pub lap_output_file: Option<PathBuf>,
+    /// Most lap uploads in flight at once.
+    pub max_concurrent_uploads: u32,
+    /// Seconds between server health checks; unset disables them.
+    pub health_check_secs: Option<u64>,
+    /// Consecutive failed attempts to reach live telemetry before giving up.
+    pub reconnect_attempts: u32,
+    /// Seconds to wait before the first reconnect attempt; doubles with
+    /// each failure.
+    pub reconnect_backoff_secs: u64,
+    /// Longest wait between reconnect attempts, in seconds.
+    pub reconnect_max_backoff_secs: u64,
+    /// Units speeds are logged in.
+    pub units: UnitSystem,
+    /// Directory a ghost of each completed lap is written to; unset writes
+    /// none.
+    pub ghost_dir: Option<PathBuf>,
+    /// Number of evenly spaced points in each ghost.
+    pub ghost_points: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            server_url: "http://localhost:8000".to_string(),
+            mode: TelemetryMode::default(),
+            ibt_file: None,
+            speed: 1.0,
+            loop_playback: false,
+            dry_run: false,
+            start_paused: false,
+            lap_history: 50,
+            lap_output: LapOutput::default(),
+            lap_output_file: None,
+            max_concurrent_uploads: 1,
+            health_check_secs: None,
+            reconnect_attempts: 10,
+            reconnect_backoff_secs: 1,
+            reconnect_max_backoff_secs: 30,
+            units: UnitSystem::default(),
+            ghost_dir: None,
+            ghost_points: DEFAULT_GHOST_POINTS,
+        }
+    }
+}
+
+/// Command-line flags. Each setting falls back to its environment variable.
+#[derive(Debug, Default, Parser)]
+#[command(version, about = "Racing Coach telemetry client")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// TOML or YAML config file (overridden by environment and flags)
+    #[arg(long, value_name = "PATH", env = "RACING_COACH_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Racing Coach server URL
+    #[arg(long, env = "SERVER_URL")]
+    pub server_url: Option<String>,
+
+    /// Telemetry source
+    #[arg(long, value_enum, env = "TELEMETRY_MODE")]
+    pub mode: Option<TelemetryMode>,
+
+    /// IBT file to read in replay mode
+    #[arg(long, value_name = "PATH", env = "IBT_FILE")]
+    pub ibt_file: Option<PathBuf>,
+
+    /// Replay speed multiplier
+    #[arg(long, env = "PLAYBACK_SPEED")]
+    pub speed: Option<f64>,
+
+    /// Restart replay from the beginning when the end of the IBT file is reached
+    #[arg(
+        long = "loop",
+        env = "LOOP_PLAYBACK",
+        num_args = 0..=1,
+        default_missing_value = "true"
+    )]
+    pub loop_playback: Option<bool>,
+
+    /// Detect laps and log what would be uploaded without contacting the server
+    #[arg(long, env = "DRY_RUN")]
+    pub dry_run: bool,
+
+    /// Start paused; press Enter to step one frame, or type a frame count,
+    /// p (pause) or r (resume)
+    #[arg(long, env = "START_PAUSED")]
+    pub start_paused: bool,
+
+    /// Number of recent laps kept in memory; the session best is always kept
+    #[arg(long, value_name = "LAPS", env = "LAP_HISTORY")]
+    pub lap_history: Option<usize>,
+
+    /// Where completed laps are sent
+    #[arg(long, value_enum, env = "LAP_OUTPUT")]
+    pub lap_output: Option<LapOutput>,
+
+    /// File laps are appended to with --lap-output file
+    #[arg(long, value_name = "PATH", env = "LAP_OUTPUT_FILE")]
+    pub lap_output_file: Option<PathBuf>,
+
+    /// Most lap uploads in flight at once; later laps wait for a slot
+    #[arg(long, value_name = "N", env = "MAX_CONCURRENT_UPLOADS")]
+    pub max_concurrent_uploads: Option<u32>,
+
+    /// Check the server's health every this many seconds, announcing when
+    /// it goes down or comes back
+    #[arg(long, value_name = "SECS", env = "HEALTH_CHECK_SECS")]
+    pub health_check_secs: Option<u64>,
+
+    /// Give up on live telemetry after this many failed connection attempts
+    /// in a row
+    #[arg(long, value_name = "N", env = "RECONNECT_ATTEMPTS")]
+    pub reconnect_attempts: Option<u32>,
+
+    /// Seconds to wait before reconnecting to live telemetry, doubling with
+    /// each failed attempt
+    #[arg(long, value_name = "SECS", env = "RECONNECT_BACKOFF_SECS")]
+    pub reconnect_backoff_secs: Option<u64>,
+
+    /// Longest wait between reconnect attempts, in seconds
+    #[arg(long, value_name = "SECS", env = "RECONNECT_MAX_BACKOFF_SECS")]
+    pub reconnect_max_backoff_secs: Option<u64>,
+
+    /// Units speeds are logged in
+    #[arg(long, value_enum, env = "UNITS")]
+    pub units: Option<UnitSystem>,
+
+    /// Write a ghost of each lap to this directory as it completes, for
+    /// overlaying in other tools
+    #[arg(long, value_name = "DIR", env = "GHOST_DIR")]
+    pub ghost_dir: Option<PathBuf>,
+
+    /// Number of evenly spaced points in each ghost written with --ghost-dir
+    #[arg(long, value_name = "N", env = "GHOST_POINTS")]
+    pub ghost_points: Option<usize>,
+}
+
+/// Something to do other than run the client.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Replay an IBT file through lap detection and print a summary of each
+    /// lap, without uploading anything
+    Analyze {
+        /// IBT file to analyze
+        #[arg(long, value_name = "PATH")]
+        file: PathBuf,
+
+        /// Also write a ghost of each lap to this directory, for overlaying
+        /// in other tools
+        #[arg(long, value_name = "DIR")]
+        ghost_dir: Option<PathBuf>,
+
+        /// Number of evenly spaced points in each ghost
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_GHOST_POINTS)]
+        ghost_points: usize,
+    },
+}
+
+/// Errors loading the configuration.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("failed to read config file {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("invalid config file {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    #[error("replay mode requires an IBT file; set --ibt-file or IBT_FILE")]
+    MissingIbtFile,
+
+    #[error("IBT file {path} does not exist")]
+    IbtFileNotFound { path: PathBuf },
+
+    #[error("IBT file {path} is not readable: {source}")]
+    IbtFileUnreadable {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("server URL {url:?} is invalid: {source}; expected e.g. http://localhost:8000")]
+    InvalidServerUrl {
+        url: String,
+        #[source]
+        source: url::ParseError,
+    },
+
+    #[error("server URL {url:?} must use http or https")]
+    UnsupportedUrlScheme { url: String },
+
+    #[error("replay speed must be a positive number, got {0}")]
+    InvalidSpeed(f64),
+
+    #[error("file lap output requires a path; set --lap-output-file or LAP_OUTPUT_FILE")]
+    MissingLapOutputFile,
+
+    #[error("health check interval must be at least one second")]
+    ZeroHealthCheckInterval,
+
+    #[error("at least one concurrent upload must be allowed")]
+    ZeroConcurrentUploads,
+
+    #[error("at least one reconnect attempt must be allowed")]
+    ZeroReconnectAttempts,
+
+    #[error("reconnect backoff must be at least one second")]
+    ZeroReconnectBackoff,
 }
 
 impl Config {
     pub fn new(server_url: impl Into<String>) -> Self {
         Self {
             server_url: server_url.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Load the configuration from the command line, environment, and the
+    /// config file named by `--config`, if any.
+    pub fn load() -> Result<Self, ConfigError> {
+        Self::load_from(Cli::parse())
+    }
+
+    /// Like [`Config::load`], with flags parsed already.
+    pub fn load_from(cli: Cli) -> Result<Self, ConfigError> {
+        let config = Self::from_cli(cli)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// The telemetry source these settings select.
+    ///
+    /// Call after [`Config::validate`]; a replay without an IBT file uses an
+    /// empty path and fails to open.
+    pub fn source_mode(&self) -> SourceMode {
+        match self.mode {
+            TelemetryMode::Live => SourceMode::Live,
+            TelemetryMode::Replay => SourceMode::Replay {
+                path: self.ibt_file.clone().unwrap_or_default(),
+                speed: self.speed,
+                loop_playback: self.loop_playback,
+            },
+        }
+    }
+
+    /// How live telemetry is reconnected when iRacing goes away.
+    pub fn reconnect_policy(&self) -> ReconnectPolicy {
+        ReconnectPolicy {
+            max_attempts: self.reconnect_attempts,
+            initial_backoff: Duration::from_secs(self.reconnect_backoff_secs),
+            max_backoff: Duration::from_secs(self.reconnect_max_backoff_secs),
+        }
+    }
+
+    /// Check that the settings are usable, returning the first problem found.
+    ///
+    /// Live mode on a platform other than Windows is only warned about, since
+    /// connecting will fail with its own error.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let url =
+            url::Url::parse(&self.server_url).map_err(|source| ConfigError::InvalidServerUrl {
+                url: self.server_url.clone(),
+                source,
+            })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(ConfigError::UnsupportedUrlScheme {
+                url: self.server_url.clone(),
+            });
+        }
+
+        if !(self.speed.is_finite() && self.speed > 0.0) {
+            return Err(ConfigError::InvalidSpeed(self.speed));
+        }
+
+        if self.lap_output == LapOutput::File && self.lap_output_file.is_none() {
+            return Err(ConfigError::MissingLapOutputFile);
+        }
+
+        if self.health_check_secs == Some(0) {
+            return Err(ConfigError::ZeroHealthCheckInterval);
+        }
+
+        if self.max_concurrent_uploads == 0 {
+            return Err(ConfigError::ZeroConcurrentUploads);
+        }
+
+        if self.reconnect_attempts == 0 {
+            return Err(ConfigError::ZeroReconnectAttempts);
         }
+
+        if self.reconnect_backoff_secs == 0 || self.reconnect_max_backoff_secs == 0 {
+            return Err(ConfigError::ZeroReconnectBackoff);
+        }
+
+        match self.mode {
+            TelemetryMode::Replay => {
+                let path = self.ibt_file.as_ref().ok_or(ConfigError::MissingIbtFile)?;
+                if !path.is_file() {
+                    return Err(ConfigError::IbtFileNotFound { path: path.clone() });
+                }
+                std::fs::File::open(path).map_err(|source| ConfigError::IbtFileUnreadable {
+                    path: path.clone(),
+                    source,
+                })?;
+            }
+            TelemetryMode::Live => {
+                if !cfg!(windows) {
+                    warn!("Live telemetry is only available on Windows; use --mode replay");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build the configuration from parsed flags, reading the config file
+    /// they name and applying the flags on top.
+    pub fn from_cli(cli: Cli) -> Result<Self, ConfigError> {
+        let config = match &cli.config {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
+        };
+
+        Ok(config.with_overrides(cli))
+    }
+
+    /// Read a config file. Files ending in `.yaml` or `.yml` are parsed as
+    /// YAML, anything else as TOML. Missing settings take their defaults.
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        let is_yaml = path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml");
+        let parsed = if is_yaml {
+            serde_yaml_ng::from_str(&contents).map_err(|e| e.to_string())
+        } else {
+            toml::from_str(&contents).map_err(|e| e.to_string())
+        };
+
+        parsed.map_err(|message| ConfigError::Parse {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    /// Replace settings with any given on the command line or environment.
+    fn with_overrides(mut self, cli: Cli) -> Self {
+        if let Some(server_url) = cli.server_url {
+            self.server_url = server_url;
+        }
+        if let Some(mode) = cli.mode {
+            self.mode = mode;
+        }
+        if let Some(ibt_file) = cli.ibt_file {
+            self.ibt_file = Some(ibt_file);
+        }
+        if let Some(speed) = cli.speed {
+            self.speed = speed;
+        }
+        if let Some(loop_playback) = cli.loop_playback {
+            self.loop_playback = loop_playback;
+        }
+        if cli.dry_run {
+            self.dry_run = true;
+        }
+        if cli.start_paused {
+            self.start_paused = true;
+        }
+        if let Some(lap_history) = cli.lap_history {
+            self.lap_history = lap_history;
+        }
+        if let Some(lap_output) = cli.lap_output {
+            self.lap_output = lap_output;
+        }
+        if let Some(lap_output_file) = cli.lap_output_file {
+            self.lap_output_file = Some(lap_output_file);
+        }
+        if let Some(max_concurrent_uploads) = cli.max_concurrent_uploads {
+            self.max_concurrent_uploads = max_concurrent_uploads;
+        }
+        if let Some(health_check_secs) = cli.health_check_secs {
+            self.health_check_secs = Some(health_check_secs);
+        }
+        if let Some(reconnect_attempts) = cli.reconnect_attempts {
+            self.reconnect_attempts = reconnect_attempts;
+        }
+        if let Some(reconnect_backoff_secs) = cli.reconnect_backoff_secs {
+            self.reconnect_backoff_secs = reconnect_backoff_secs;
+        }
+        if let Some(reconnect_max_backoff_secs) = cli.reconnect_max_backoff_secs {
+            self.reconnect_max_backoff_secs = reconnect_max_backoff_secs;
+        }
+        if let Some(units) = cli.units {
+            self.units = units;
+        }
+        if let Some(ghost_dir) = cli.ghost_dir {
+            self.ghost_dir = Some(ghost_dir);
+        }
+        if let Some(ghost_points) = cli.ghost_points {
+            self.ghost_points = ghost_points;
+        }
+        self
+    }
+}
+
+/// The URL of an endpoint, e.g. `/api/v1/health`, on the server at
+/// `server_url`.
+///
+/// The endpoint is appended to the server URL's path rather than replacing
+/// it, so a server mounted under a prefix behind a reverse proxy works:
+/// `https://host/coach` and `https://host/coach/` both become
+/// `https://host/coach/api/v1/health`. The query and fragment are dropped.
+///
+/// # Panics
+/// If `server_url` can't have a path, which no http(s) URL is.
+pub fn endpoint_url(server_url: &url::Url, endpoint: &str) -> url::Url {
+    let mut url = server_url.clone();
+    url.set_query(None);
+    url.set_fragment(None);
+    url.path_segments_mut()
+        .expect("http(s) URLs have a path")
+        .pop_if_empty()
+        .extend(endpoint.trim_start_matches('/').split('/'));
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"
+server_url = "http://coach.example:9000"
+mode = "replay"
+ibt_file = "session.ibt"
+speed = 4.0
+"#;
+
+    fn fixture_file(extension: &str, contents: &str) -> tempfile::NamedTempFile {
+        let file = tempfile::Builder::new()
+            .suffix(extension)
+            .tempfile()
+            .unwrap();
+        std::fs::write(file.path(), contents).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_from_file_reads_toml() {
+        let file = fixture_file(".toml", FIXTURE);
+
+        let config = Config::from_file(file.path()).unwrap();
+
+        assert_eq!(config.server_url, "http://coach.example:9000");
+        assert_eq!(config.mode, TelemetryMode::Replay);
+        assert_eq!(config.ibt_file, Some(PathBuf::from("session.ibt")));
+        assert_eq!(config.speed, 4.0);
+        // Unset settings keep their defaults
+        assert!(!config.loop_playback);
+    }
+
+    #[test]
+    fn test_from_file_reads_yaml() {
+        let file = fixture_file(
+            ".yaml",
+            "mode: replay\nloop_playback: true\nunits: imperial\n",
+        );
+
+        let config = Config::from_file(file.path()).unwrap();
+
+        assert_eq!(config.mode, TelemetryMode::Replay);
+        assert!(config.loop_playback);
+        assert_eq!(config.units, UnitSystem::Imperial);
+        assert_eq!(config.server_url, Config::default().server_url);
+    }
+
+    #[test]
+    fn test_cli_overrides_file() {
+        let file = fixture_file(".toml", FIXTURE);
+        let cli = Cli {
+            config: Some(file.path().to_path_buf()),
+            server_url: Some("http://localhost:1234".to_string()),
+            ..Default::default()
+        };
+
+        let config = Config::from_cli(cli).unwrap();
+
+        assert_eq!(config.server_url, "http://localhost:1234");
+        // Settings not given on the command line come from the file
+        assert_eq!(config.speed, 4.0);
+    }
+
+    #[test]
+    fn test_cli_flags_parse() {
+        let cli = Cli::try_parse_from([
+            "client-rs",
+            "--config",
+            "coach.toml",
+            "--server-url",
+            "http://localhost:1234",
+            "--mode",
+            "replay",
+            "--loop",
+            "--dry-run",
+            "--start-paused",
+            "--lap-history",
+            "10",
+            "--lap-output",
+            "file",
+            "--lap-output-file",
+            "laps.jsonl",
+            "--max-concurrent-uploads",
+            "4",
+            "--reconnect-attempts",
+            "3",
+            "--reconnect-backoff-secs",
+            "2",
+            "--reconnect-max-backoff-secs",
+            "8",
+            "--units",
+            "imperial",
+            "--ghost-dir",
+            "ghosts",
+            "--ghost-points",
+            "200",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.config, Some(PathBuf::from("coach.toml")));
+        assert_eq!(cli.server_url.as_deref(), Some("http://localhost:1234"));
+        assert_eq!(cli.mode, Some(TelemetryMode::Replay));
+        assert_eq!(cli.loop_playback, Some(true));
+        assert!(cli.dry_run);
+        assert!(cli.start_paused);
+        assert_eq!(cli.lap_history, Some(10));
+        assert_eq!(cli.lap_output, Some(LapOutput::File));
+        assert_eq!(cli.lap_output_file, Some(PathBuf::from("laps.jsonl")));
+        assert_eq!(cli.max_concurrent_uploads, Some(4));
+        assert_eq!(cli.reconnect_attempts, Some(3));
+        assert_eq!(cli.reconnect_backoff_secs, Some(2));
+        assert_eq!(cli.reconnect_max_backoff_secs, Some(8));
+        assert_eq!(cli.units, Some(UnitSystem::Imperial));
+        assert_eq!(cli.ghost_dir, Some(PathBuf::from("ghosts")));
+        assert_eq!(cli.ghost_points, Some(200));
+        assert!(cli.command.is_none());
+        assert!(
+            Config::from_cli(Cli {
+                dry_run: true,
+                ..Default::default()
+            })
+            .unwrap()
+            .dry_run
+        );
+    }
+
+    #[test]
+    fn test_analyze_subcommand_parses() {
+        let cli = Cli::try_parse_from([
+            "client-rs",
+            "--units",
+            "imperial",
+            "analyze",
+            "--file",
+            "a.ibt",
+        ])
+        .unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Some(Command::Analyze { file, ghost_dir: None, ghost_points: DEFAULT_GHOST_POINTS })
+                if file == Path::new("a.ibt")
+        ));
+        assert_eq!(cli.units, Some(UnitSystem::Imperial));
+        assert!(Cli::try_parse_from(["client-rs", "analyze"]).is_err());
+
+        let cli = Cli::try_parse_from([
+            "client-rs",
+            "analyze",
+            "--file",
+            "a.ibt",
+            "--ghost-dir",
+            "ghosts",
+            "--ghost-points",
+            "200",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Analyze { ghost_dir: Some(dir), ghost_points: 200, .. })
+                if dir == Path::new("ghosts")
+        ));
+    }
+
+    #[test]
+    fn test_invalid_file_reports_path() {
+        let file = fixture_file(".toml", "speed = \"fast\"");
+
+        let error = Config::from_file(file.path()).unwrap_err();
+
+        assert!(matches!(&error, ConfigError::Parse { path, .. } if path == file.path()));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let error = Config::from_file(Path::new("does-not-exist.toml")).unwrap_err();
+        assert!(matches!(error, ConfigError::Read { .. }));
+    }
+
+    fn replay_config(ibt_file: Option<PathBuf>) -> Config {
+        Config {
+            mode: TelemetryMode::Replay,
+            ibt_file,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_valid_replay_config() {
+        let file = fixture_file(".ibt", "");
+        let config = replay_config(Some(file.path().to_path_buf()));
+
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_replay_requires_ibt_file() {
+        let error = replay_config(None).validate().unwrap_err();
+        assert!(matches!(error, ConfigError::MissingIbtFile));
+    }
+
+    #[test]
+    fn test_replay_ibt_file_must_exist() {
+        let config = replay_config(Some(PathBuf::from("does-not-exist.ibt")));
+
+        let error = config.validate().unwrap_err();
+        assert!(matches!(error, ConfigError::IbtFileNotFound { .. }));
+    }
+
+    #[test]
+    fn test_replay_ibt_file_must_be_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = replay_config(Some(dir.path().to_path_buf()));
+
+        let error = config.validate().unwrap_err();
+        assert!(matches!(error, ConfigError::IbtFileNotFound { .. }));
+    }
+
+    #[test]
+    fn test_server_url_must_parse() {
+        let config = Config::new("localhost:8000/api");
+
+        let error = config.validate().unwrap_err();
+        assert!(matches!(error, ConfigError::UnsupportedUrlScheme { .. }));
+
+        let error = Config::new("not a url").validate().unwrap_err();
+        assert!(matches!(error, ConfigError::InvalidServerUrl { .. }));
+    }
+
+    #[test]
+    fn test_endpoint_url_keeps_path_prefix() {
+        let endpoint = |server_url: &str| {
+            let server_url = url::Url::parse(server_url).unwrap();
+            endpoint_url(&server_url, "/api/v1/health").to_string()
+        };
+
+        assert_eq!(
+            endpoint("http://localhost:8000"),
+            "http://localhost:8000/api/v1/health"
+        );
+        assert_eq!(
+            endpoint("https://host/coach"),
+            "https://host/coach/api/v1/health"
+        );
+        assert_eq!(
+            endpoint("https://host/coach/?debug=1"),
+            "https://host/coach/api/v1/health"
+        );
+    }
+
+    #[test]
+    fn test_speed_must_be_positive() {
+        for speed in [0.0, -1.0, f64::NAN] {
+            let config = Config {
+                speed,
+                ..Default::default()
+            };
+
+            let error = config.validate().unwrap_err();
+            assert!(matches!(error, ConfigError::InvalidSpeed(_)));
+        }
+    }
+
+    #[test]
+    fn test_file_lap_output_requires_path() {
+        let mut config = Config {
+            lap_output: LapOutput::File,
+            ..Default::default()
+        };
+
+        let error = config.validate().unwrap_err();
+        assert!(matches!(error, ConfigError::MissingLapOutputFile));
+
+        config.lap_output_file = Some(PathBuf::from("laps.jsonl"));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_health_check_interval_must_be_positive() {
+        let mut config = Config {
+            health_check_secs: Some(0),
+            ..Default::default()
+        };
+
+        let error = config.validate().unwrap_err();
+        assert!(matches!(error, ConfigError::ZeroHealthCheckInterval));
+
+        config.health_check_secs = Some(30);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_concurrent_uploads_must_be_positive() {
+        let mut config = Config {
+            max_concurrent_uploads: 0,
+            ..Default::default()
+        };
+
+        let error = config.validate().unwrap_err();
+        assert!(matches!(error, ConfigError::ZeroConcurrentUploads));
+
+        config.max_concurrent_uploads = 4;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_reconnect_policy_must_allow_retries() {
+        let mut config = Config {
+            reconnect_attempts: 0,
+            ..Default::default()
+        };
+        let error = config.validate().unwrap_err();
+        assert!(matches!(error, ConfigError::ZeroReconnectAttempts));
+
+        config.reconnect_attempts = 3;
+        config.reconnect_backoff_secs = 0;
+        let error = config.validate().unwrap_err();
+        assert!(matches!(error, ConfigError::ZeroReconnectBackoff));
+
+        config.reconnect_backoff_secs = 2;
+        config.reconnect_max_backoff_secs = 8;
+        assert!(config.validate().is_ok());
+        let policy = config.reconnect_policy();
+        assert_eq!(policy.max_attempts, 3);
+        assert_eq!(policy.initial_backoff, Duration::from_secs(2));
+        assert_eq!(policy.max_backoff, Duration::from_secs(8));
     }
 }
@@ -2,22 +2,48 @@ use std::sync::Arc;
 
 use crate::telem::TelemetryFrame;
 use eventbus::EventLike;
+use pitwall::SessionInfo;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 /// Discriminant enum for channel routing (no payload, just identifies event kind).
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RacingEventKind {
     TelemetryFrameCollected,
     LapComplete,
+    LapUploaded,
+    SessionStart,
+    SessionEnd,
+    NewBestLap,
+    ServerStatusChanged,
+    LiveDelta,
 }
 
 /// Main event enum for racing telemetry events.
 ///
 /// Large, frequent payloads (like TelemetryFrame) use Arc for zero-copy broadcast.
-/// Small, infrequent payloads (like LapCompletePayload) are cloned directly.
-#[derive(Clone, Debug)]
+/// Infrequent payloads (like LapCompletePayload) are cloned directly, with any
+/// bulk data behind an Arc.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum RacingEvent {
     TelemetryFrameCollected(Arc<TelemetryFrame>),
     LapComplete(LapCompletePayload),
+    LapUploaded(LapUploadedPayload),
+    SessionStart(SessionStartPayload),
+    SessionEnd(SessionEndPayload),
+    /// A session's last frame has been published.
+    ///
+    /// Sent on the `TelemetryFrameCollected` channel just before
+    /// `SessionEnd`, so frame handlers see it after every frame of the
+    /// session and before any of the next. `SessionEnd` has a channel of its
+    /// own and gives no such ordering.
+    SessionFramesEnded(SessionEndPayload),
+    /// A completed lap beat the session's best lap time
+    NewBestLap(LapCompletePayload),
+    /// The server started or stopped answering health checks
+    ServerStatusChanged(ServerStatusPayload),
+    /// Time gained or lost against the session's best lap at a point on track
+    LiveDelta(LiveDeltaPayload),
 }
 
 impl EventLike for RacingEvent {
@@ -27,6 +53,13 @@ impl EventLike for RacingEvent {
         match self {
             RacingEvent::TelemetryFrameCollected(_) => RacingEventKind::TelemetryFrameCollected,
             RacingEvent::LapComplete(_) => RacingEventKind::LapComplete,
+            RacingEvent::LapUploaded(_) => RacingEventKind::LapUploaded,
+            RacingEvent::SessionStart(_) => RacingEventKind::SessionStart,
+            RacingEvent::SessionEnd(_) => RacingEventKind::SessionEnd,
+            RacingEvent::SessionFramesEnded(_) => RacingEventKind::TelemetryFrameCollected,
+            RacingEvent::NewBestLap(_) => RacingEventKind::NewBestLap,
+            RacingEvent::ServerStatusChanged(_) => RacingEventKind::ServerStatusChanged,
+            RacingEvent::LiveDelta(_) => RacingEventKind::LiveDelta,
         }
     }
 
@@ -34,15 +67,92 @@ impl EventLike for RacingEvent {
         [
             RacingEventKind::TelemetryFrameCollected,
             RacingEventKind::LapComplete,
+            RacingEventKind::LapUploaded,
+            RacingEventKind::SessionStart,
+            RacingEventKind::SessionEnd,
+            RacingEventKind::NewBestLap,
+            RacingEventKind::ServerStatusChanged,
+            RacingEventKind::LiveDelta,
         ]
         .into_iter()
     }
 }
 
+/// Classification of a completed lap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LapType {
+    /// A full lap driven entirely off pit road
+    Timed,
+    /// Lap started on pit road (pit exit)
+    OutLap,
+    /// Lap ended on pit road (pit entry)
+    InLap,
+    /// Lap whose start was not observed (e.g. joined mid-lap)
+    Incomplete,
+}
+
 /// Completed lap data.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LapCompletePayload {
+    /// Id assigned when the lap completed, for correlating its logs. The
+    /// server may store the lap under another id, see [`LapUploadedPayload`].
+    pub lap_id: Uuid,
     pub lap_number: i32,
     pub lap_time_ms: Option<u64>,
     pub frame_count: usize,
+    pub lap_type: LapType,
+    /// True for timed laps that stayed on track the whole way without a
+    /// black or meatball flag
+    pub is_valid: bool,
+    /// Buffered frames for the lap, possibly down-sampled
+    pub frames: Arc<Vec<Arc<TelemetryFrame>>>,
+}
+
+impl LapCompletePayload {
+    /// Span to log work on this lap in, so logs from laps handled
+    /// concurrently can be told apart.
+    pub fn span(&self) -> tracing::Span {
+        tracing::info_span!("lap", lap_id = %self.lap_id, lap_number = self.lap_number)
+    }
+}
+
+/// A completed lap was accepted by the server.
+///
+/// The server may deduplicate or reassign lap ids, so anything uploaded for
+/// the lap afterwards must be keyed on `lap_id` from here.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LapUploadedPayload {
+    pub lap_number: i32,
+    /// Id the server stored the lap under
+    pub lap_id: Uuid,
+}
+
+/// A new session was detected (first session info, or a track/car/session change).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionStartPayload {
+    pub session_id: Uuid,
+    pub session: Arc<SessionInfo>,
+}
+
+/// The session with the given id has ended.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionEndPayload {
+    pub session_id: Uuid,
+}
+
+/// Whether the server is answering health checks, see [`crate::health`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ServerStatusPayload {
+    pub healthy: bool,
+}
+
+/// Time against the reference lap at a point on track, see
+/// [`crate::handlers::LiveDeltaHandler`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LiveDeltaPayload {
+    /// Lap distance (0-1) of the frame compared
+    pub distance_pct: f32,
+    /// Current lap time minus the reference lap's time at the same distance;
+    /// positive when behind
+    pub delta_seconds: f32,
 }
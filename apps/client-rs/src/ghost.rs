@@ -0,0 +1,224 @@
+//! Ghost laps: a lap resampled onto an even distance axis, for overlaying in
+//! external tools.
+//!
+//! The JSON matches the analysis library's `GhostLap`, so ghosts written
+//! here and ghosts exported from Python can be used interchangeably.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::events::LapCompletePayload;
+use crate::telem::TelemetryFrame;
+
+/// Default number of points in a ghost.
+pub const DEFAULT_GHOST_POINTS: usize = 500;
+
+/// A lap's driver inputs and speed at evenly spaced lap distances.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GhostLap {
+    /// Lap distance (0-1) of each point
+    pub distance: Vec<f32>,
+    /// Speed in m/s
+    pub speed: Vec<f32>,
+    /// Throttle position (0-1)
+    pub throttle: Vec<f32>,
+    /// Brake pressure (0-1)
+    pub brake: Vec<f32>,
+    /// Steering wheel angle in radians
+    pub steering: Vec<f32>,
+}
+
+impl GhostLap {
+    /// Resample `frames` onto `num_points` evenly spaced lap distances from
+    /// 0.0 to 1.0, interpolating between the frames either side of each.
+    ///
+    /// Frames are ordered by distance first and of several at one distance
+    /// only the first is used, so glitches don't fold the trace over itself.
+    /// Distances are clamped to 0.0-1.0. Points outside the distance the
+    /// frames cover hold the nearest frame's values. Empty if there are no
+    /// frames.
+    pub fn from_frames(frames: &[Arc<TelemetryFrame>], num_points: usize) -> Self {
+        let mut trace: Vec<(f32, &TelemetryFrame)> = frames
+            .iter()
+            .map(|frame| (frame.lap_distance_pct.clamp(0.0, 1.0), frame.as_ref()))
+            .collect();
+        // Stable, so the first of several frames at one distance stays first
+        trace.sort_by(|a, b| a.0.total_cmp(&b.0));
+        trace.dedup_by(|later, earlier| later.0 == earlier.0);
+        if trace.is_empty() {
+            return Self::default();
+        }
+
+        let step = if num_points > 1 {
+            1.0 / (num_points - 1) as f32
+        } else {
+            0.0
+        };
+        let mut ghost = Self::default();
+        for i in 0..num_points {
+            let distance = step * i as f32;
+            let upper = trace.partition_point(|&(pct, _)| pct < distance);
+            let ((a_pct, a), (b_pct, b)) = match upper {
+                0 => (trace[0], trace[0]),
+                n if n == trace.len() => (trace[n - 1], trace[n - 1]),
+                n => (trace[n - 1], trace[n]),
+            };
+            let span = b_pct - a_pct;
+            let fraction = if span > 0.0 {
+                (distance - a_pct) / span
+            } else {
+                0.0
+            };
+            let lerp =
+                |value: fn(&TelemetryFrame) -> f32| value(a) + (value(b) - value(a)) * fraction;
+
+            ghost.distance.push(distance);
+            ghost.speed.push(lerp(|frame| frame.speed));
+            ghost.throttle.push(lerp(|frame| frame.throttle));
+            ghost.brake.push(lerp(|frame| frame.brake));
+            ghost.steering.push(lerp(|frame| frame.steering_angle));
+        }
+        ghost
+    }
+}
+
+/// Name of the ghost file for a lap: `lap_<number>.ghost.json`.
+pub fn ghost_file_name(lap_number: i32) -> String {
+    format!("lap_{lap_number}.ghost.json")
+}
+
+/// Write a ghost of each lap with `num_points` points to `dir`, one
+/// [`ghost_file_name`] file per lap, creating `dir` if needed.
+///
+/// Returns the paths written, in lap order.
+pub fn write_ghosts(
+    laps: &[LapCompletePayload],
+    dir: &Path,
+    num_points: usize,
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    laps.iter()
+        .map(|lap| {
+            let ghost = GhostLap::from_frames(&lap.frames, num_points);
+            let path = dir.join(ghost_file_name(lap.lap_number));
+            fs::write(&path, serde_json::to_vec(&ghost)?)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    use crate::events::LapType;
+    use crate::test_utils::frame;
+
+    /// A lap of 41 frames: flat out for the first half, braking hard at
+    /// 50%, then turning in.
+    fn lap_frames() -> Vec<Arc<TelemetryFrame>> {
+        (0..=40)
+            .map(|i| {
+                let pct = i as f32 / 40.0;
+                let braking = (0.5..0.6).contains(&pct);
+                Arc::new(TelemetryFrame {
+                    speed: if pct < 0.5 { 80.0 } else { 40.0 },
+                    throttle: if pct < 0.5 { 1.0 } else { 0.0 },
+                    brake: if braking { 0.9 } else { 0.0 },
+                    steering_angle: if pct >= 0.6 { 0.3 } else { 0.0 },
+                    ..frame(1, pct)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ghost_arrays_share_a_distance_axis() {
+        let ghost = GhostLap::from_frames(&lap_frames(), 101);
+
+        for channel in [
+            &ghost.distance,
+            &ghost.speed,
+            &ghost.throttle,
+            &ghost.brake,
+            &ghost.steering,
+        ] {
+            assert_eq!(channel.len(), 101);
+        }
+        assert_eq!(ghost.distance[0], 0.0);
+        assert_eq!(ghost.distance[100], 1.0);
+        assert!(ghost.distance.windows(2).all(|pair| pair[1] > pair[0]));
+
+        assert_eq!(ghost.speed[20], 80.0);
+        assert_eq!(ghost.throttle[20], 1.0);
+        assert_eq!(ghost.brake[55], 0.9);
+        assert_eq!(ghost.steering[100], 0.3);
+    }
+
+    #[test]
+    fn test_distances_outside_the_lap_are_clamped() {
+        let at = |lap_distance_pct, speed| {
+            Arc::new(TelemetryFrame {
+                speed,
+                ..frame(1, lap_distance_pct)
+            })
+        };
+        // Glitched distances either side of the line count as on it
+        let frames = [at(-0.5, 0.0), at(0.5, 40.0), at(1.2, 100.0)];
+
+        let ghost = GhostLap::from_frames(&frames, 5);
+
+        assert_eq!(ghost.distance, [0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(ghost.speed, [0.0, 20.0, 40.0, 70.0, 100.0]);
+    }
+
+    #[test]
+    fn test_json_matches_the_analysis_library() {
+        // As written by the analysis library's `GhostLap.to_json()`
+        let fixture = r#"{"distance":[0.0,0.5,1.0],"speed":[80.0,40.0,60.0],"throttle":[1.0,0.0,0.5],"brake":[0.0,0.9,0.0],"steering":[0.0,0.3,-0.1]}"#;
+
+        let ghost: GhostLap = serde_json::from_str(fixture).unwrap();
+
+        assert_eq!(ghost.distance, [0.0, 0.5, 1.0]);
+        assert_eq!(ghost.brake, [0.0, 0.9, 0.0]);
+        assert_eq!(ghost.steering, [0.0, 0.3, -0.1]);
+        assert_eq!(serde_json::to_string(&ghost).unwrap(), fixture);
+    }
+
+    #[test]
+    fn test_no_frames_is_an_empty_ghost() {
+        assert_eq!(GhostLap::from_frames(&[], 50), GhostLap::default());
+    }
+
+    #[test]
+    fn test_one_file_per_lap() {
+        let dir = tempfile::tempdir().unwrap();
+        let lap = |lap_number| LapCompletePayload {
+            lap_id: Uuid::new_v4(),
+            lap_number,
+            lap_time_ms: Some(90_000),
+            frame_count: 41,
+            lap_type: LapType::Timed,
+            is_valid: true,
+            frames: Arc::new(lap_frames()),
+        };
+
+        let ghost_dir = dir.path().join("ghosts");
+        let paths = write_ghosts(&[lap(3), lap(4)], &ghost_dir, 20).unwrap();
+
+        assert_eq!(
+            paths,
+            [
+                ghost_dir.join("lap_3.ghost.json"),
+                ghost_dir.join("lap_4.ghost.json")
+            ]
+        );
+        let ghost: GhostLap = serde_json::from_slice(&fs::read(&paths[0]).unwrap()).unwrap();
+        assert_eq!(ghost, GhostLap::from_frames(&lap_frames(), 20));
+    }
+}
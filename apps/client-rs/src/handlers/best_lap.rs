@@ -0,0 +1,142 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::events::{RacingEvent, RacingEventKind};
+use eventbus::{EventHandler, HandlerContext};
+
+/// Announces each valid lap that beats the session's best lap time with a
+/// [`RacingEvent::NewBestLap`] event. The first valid lap sets the best.
+///
+/// Handlers only receive a single event kind; use [`BestLapHandler::handlers`]
+/// to create one for each kind it follows. The best resets on session start
+/// and end.
+pub struct BestLapHandler {
+    kind: RacingEventKind,
+    /// Best lap time this session in milliseconds
+    best_ms: Arc<Mutex<Option<u64>>>,
+}
+
+impl BestLapHandler {
+    /// Create the handlers tracking one best lap. Register all of them.
+    pub fn handlers() -> Vec<Self> {
+        let best_ms = Arc::new(Mutex::new(None));
+        [
+            RacingEventKind::SessionStart,
+            RacingEventKind::SessionEnd,
+            RacingEventKind::LapComplete,
+        ]
+        .into_iter()
+        .map(|kind| Self {
+            kind,
+            best_ms: best_ms.clone(),
+        })
+        .collect()
+    }
+}
+
+#[async_trait]
+impl EventHandler<RacingEvent> for BestLapHandler {
+    fn handles(&self) -> RacingEventKind {
+        self.kind
+    }
+
+    fn name(&self) -> &'static str {
+        "BestLapHandler"
+    }
+
+    async fn handle(&self, event: RacingEvent, ctx: &HandlerContext<RacingEvent>) {
+        match event {
+            RacingEvent::SessionStart(_) | RacingEvent::SessionEnd(_) => {
+                *self.best_ms.lock().unwrap() = None;
+            }
+            RacingEvent::LapComplete(lap) => {
+                let Some(lap_time_ms) = lap.lap_time_ms.filter(|_| lap.is_valid) else {
+                    return;
+                };
+                {
+                    let mut best_ms = self.best_ms.lock().unwrap();
+                    if best_ms.is_some_and(|best| best <= lap_time_ms) {
+                        return;
+                    }
+                    *best_ms = Some(lap_time_ms);
+                }
+                ctx.publish(RacingEvent::NewBestLap(lap));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use eventbus::{EventBus, HandlerRegistry};
+    use uuid::Uuid;
+
+    use crate::events::{LapCompletePayload, LapType, SessionEndPayload};
+
+    fn lap(lap_number: i32, lap_time_ms: u64) -> RacingEvent {
+        RacingEvent::LapComplete(LapCompletePayload {
+            lap_id: Uuid::new_v4(),
+            lap_number,
+            lap_time_ms: Some(lap_time_ms),
+            frame_count: 0,
+            lap_type: LapType::Timed,
+            is_valid: true,
+            frames: Arc::new(Vec::new()),
+        })
+    }
+
+    /// Publish `events` one at a time and return the lap numbers announced
+    /// as new bests.
+    async fn best_laps(events: Vec<RacingEvent>) -> Vec<i32> {
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe(RacingEventKind::NewBestLap);
+        let mut registry = HandlerRegistry::new();
+        for handler in BestLapHandler::handlers() {
+            registry.register(handler);
+        }
+        let handles = registry.run(bus.clone());
+
+        for event in events {
+            bus.publish(event).unwrap();
+            // Handlers of different kinds run separately, so let each event
+            // land before the next
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        bus.shutdown_and_drain().await;
+        registry.shutdown();
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        let mut laps = Vec::new();
+        while let Ok(RacingEvent::NewBestLap(lap)) = rx.try_recv() {
+            laps.push(lap.lap_number);
+        }
+        laps
+    }
+
+    #[tokio::test]
+    async fn test_only_improvements_are_announced() {
+        let laps = best_laps(vec![lap(1, 90_000), lap(2, 88_000), lap(3, 89_000)]).await;
+
+        assert_eq!(laps, [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_best_resets_with_the_session() {
+        let session_id = Uuid::new_v4();
+        let laps = best_laps(vec![
+            lap(1, 88_000),
+            RacingEvent::SessionEnd(SessionEndPayload { session_id }),
+            lap(1, 90_000),
+        ])
+        .await;
+
+        assert_eq!(laps, [1, 1]);
+    }
+}
@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+use crate::events::{RacingEvent, RacingEventKind};
+use crate::ghost::{GhostLap, ghost_file_name};
+use eventbus::{EventHandler, HandlerContext};
+
+/// Writes a ghost of each completed lap to a directory as it finishes, for
+/// overlaying in other tools.
+///
+/// Files are named by lap number (see [`ghost_file_name`]), so a lap of a
+/// looped replay overwrites the ghost from the previous pass. The directory
+/// is created on the first lap. Failed writes are logged and skipped.
+pub struct GhostHandler {
+    dir: PathBuf,
+    num_points: usize,
+}
+
+impl GhostHandler {
+    /// Write ghosts of `num_points` points to `dir`.
+    pub fn new(dir: impl Into<PathBuf>, num_points: usize) -> Self {
+        Self {
+            dir: dir.into(),
+            num_points,
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler<RacingEvent> for GhostHandler {
+    fn handles(&self) -> RacingEventKind {
+        RacingEventKind::LapComplete
+    }
+
+    fn name(&self) -> &'static str {
+        "GhostHandler"
+    }
+
+    async fn handle(&self, event: RacingEvent, _ctx: &HandlerContext<RacingEvent>) {
+        let RacingEvent::LapComplete(lap) = event else {
+            return;
+        };
+
+        let ghost = GhostLap::from_frames(&lap.frames, self.num_points);
+        let path = self.dir.join(ghost_file_name(lap.lap_number));
+        let written = async {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            tokio::fs::write(&path, serde_json::to_vec(&ghost)?).await
+        };
+        match written.await {
+            Ok(()) => info!(
+                "Wrote ghost of lap {} to {}",
+                lap.lap_number,
+                path.display()
+            ),
+            Err(e) => warn!("Failed to write ghost to {}: {e}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use eventbus::{EventBus, HandlerRegistry};
+    use uuid::Uuid;
+
+    use crate::events::{LapCompletePayload, LapType};
+    use crate::telem::TelemetryFrame;
+    use crate::test_utils::frame;
+
+    #[tokio::test]
+    async fn test_ghost_is_written_per_lap() {
+        let dir = tempfile::tempdir().unwrap();
+        let ghost_dir = dir.path().join("ghosts");
+        let frames: Vec<Arc<TelemetryFrame>> = (0..=10)
+            .map(|i| Arc::new(frame(2, i as f32 / 10.0)))
+            .collect();
+
+        let bus = EventBus::new(16);
+        let mut registry = HandlerRegistry::new();
+        registry.register(GhostHandler::new(&ghost_dir, 20));
+        let handles = registry.run(bus.clone());
+
+        bus.publish(RacingEvent::LapComplete(LapCompletePayload {
+            lap_id: Uuid::new_v4(),
+            lap_number: 2,
+            lap_time_ms: Some(90_000),
+            frame_count: frames.len(),
+            lap_type: LapType::Timed,
+            is_valid: true,
+            frames: Arc::new(frames.clone()),
+        }))
+        .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        bus.shutdown_and_drain().await;
+        registry.shutdown();
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        let written = std::fs::read(ghost_dir.join("lap_2.ghost.json")).unwrap();
+        let ghost: GhostLap = serde_json::from_slice(&written).unwrap();
+        assert_eq!(ghost, GhostLap::from_frames(&frames, 20));
+    }
+}
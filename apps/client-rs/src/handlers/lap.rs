@@ -1,33 +1,486 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
 use async_trait::async_trait;
 use tokio::sync::Mutex;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
 
-use crate::events::{LapCompletePayload, RacingEvent, RacingEventKind};
+use crate::events::{LapCompletePayload, LapType, RacingEvent, RacingEventKind};
+use crate::session::{SessionKey, SessionState};
+use crate::telem::TelemetryFrame;
 use eventbus::{EventHandler, HandlerContext};
+use pitwall::SessionInfo;
+
+/// Configuration for [`LapHandler`].
+#[derive(Clone, Debug)]
+pub struct LapHandlerConfig {
+    /// How the frame stream is split into laps.
+    pub split: LapSplit,
+
+    /// Minimum span of `lap_distance_pct` a lap must cover to be emitted.
+    ///
+    /// Laps cut short (e.g. a reset to the pits) are discarded. Not checked
+    /// with [`LapSplit::Interval`].
+    pub min_lap_distance_coverage: f32,
+
+    /// Minimum span of `lap_distance_pct` the lap in progress must cover to
+    /// be emitted as [`LapType::Incomplete`] when the session ends.
+    ///
+    /// Shorter partial laps are dropped. Not checked with
+    /// [`LapSplit::Interval`].
+    pub min_partial_lap_coverage: f32,
+
+    /// Minimum backward jump in `lap_distance_pct` that counts as crossing
+    /// the start/finish line. With [`LapSplit::LapNumber`] the lap number
+    /// must also increment.
+    pub wraparound_threshold: f32,
+
+    /// Number of consecutive out-of-order frames (backward distance jumps
+    /// beyond [`REVERSE_TOLERANCE`] or unexpected lap number changes)
+    /// ignored as glitches. A longer run is
+    /// treated as a reset and restarts lap tracking.
+    pub max_glitch_frames: usize,
+
+    /// Rate at which frames are kept in the lap buffer, selected by
+    /// `session_time`. `None` buffers every frame. Must be positive and
+    /// finite.
+    ///
+    /// The first and last frame of each lap are always kept so lap timing
+    /// stays accurate. Only the buffer is down-sampled; other handlers still
+    /// see every frame on the bus.
+    pub upload_hz: Option<f64>,
+
+    /// Maximum number of frames buffered for a single lap. `None` buffers
+    /// without limit.
+    ///
+    /// Bounds memory when a lap never completes (e.g. sitting in the garage
+    /// for a long practice session). See [`BufferOverflow`] for what happens
+    /// when the cap is hit.
+    pub max_buffer_frames: Option<usize>,
 
-/// Detects lap completion by monitoring lap_number changes
+    /// What to do with frames once the lap buffer is full.
+    pub overflow_policy: BufferOverflow,
+
+    /// Derive each lap's `lap_id` from the session and lap number instead of
+    /// generating a random one, so re-processing the same telemetry yields
+    /// the same ids and the server can deduplicate uploads.
+    ///
+    /// The session is identified by the iRacing subsession, session number,
+    /// track and car from [`SessionState`]. Without a known subsession (e.g.
+    /// offline, or before session info arrives) nothing tells one session
+    /// from another, so the lap gets a random id and a warning is logged.
+    pub deterministic_ids: bool,
+}
+
+/// How [`LapHandler`] decides where one lap ends and the next begins.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LapSplit {
+    /// Split when `lap_number` increments as `lap_distance_pct` wraps around
+    #[default]
+    LapNumber,
+    /// Split whenever `lap_distance_pct` wraps around, ignoring `lap_number`.
+    ///
+    /// For content that doesn't count laps reliably, such as oval pace laps.
+    /// Laps are numbered on from the first frame's lap number.
+    DistanceWraparound,
+    /// Split every time this much `session_time` has passed, ignoring track
+    /// position entirely.
+    ///
+    /// For content without a start/finish line, such as rallycross or
+    /// autocross. Laps are numbered on from the first frame's lap number.
+    Interval(Duration),
+}
+
+/// Behaviour of the [`LapHandler`] lap buffer once it holds
+/// [`LapHandlerConfig::max_buffer_frames`] frames.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BufferOverflow {
+    /// Drop the oldest frame for each new one, keeping the most recent frames
+    #[default]
+    DropOldest,
+    /// Keep the frames already buffered and discard new ones
+    DropNewest,
+}
+
+impl Default for LapHandlerConfig {
+    fn default() -> Self {
+        Self {
+            split: LapSplit::LapNumber,
+            min_lap_distance_coverage: 0.9,
+            min_partial_lap_coverage: 0.25,
+            wraparound_threshold: 0.5,
+            max_glitch_frames: 5,
+            upload_hz: None,
+            // 30 minutes at 60 Hz
+            max_buffer_frames: Some(108_000),
+            overflow_policy: BufferOverflow::DropOldest,
+            deterministic_ids: false,
+        }
+    }
+}
+
+/// Namespace of the UUIDv5 lap ids made with
+/// [`LapHandlerConfig::deterministic_ids`].
+const LAP_ID_NAMESPACE: Uuid = Uuid::from_u128(0x6f1c_52a4_8d3e_4b7a_9c05_e2d8_41b3_a760);
+
+/// The lap id [`LapHandlerConfig::deterministic_ids`] gives lap `lap_number`
+/// of `session`, or `None` unless `session` is an iRacing subsession.
+fn deterministic_lap_id(session: Option<&SessionInfo>, lap_number: i32) -> Option<Uuid> {
+    let session = session?;
+    // Offline sessions all have subsession 0
+    let sub_session_id = session.weekend_info.sub_session_id.filter(|&id| id > 0)?;
+    let key = SessionKey::from_session_info(session);
+    let name = format!(
+        "{sub_session_id}/{}/{:?}/{:?}/{lap_number}",
+        key.session_num, key.track_id, key.car_id
+    );
+    Some(Uuid::new_v5(&LAP_ID_NAMESPACE, name.as_bytes()))
+}
+
+/// Extra capacity reserved for the next lap's buffer, as a fraction
+/// (`1 / divisor`) of the last lap's frames, so slightly longer laps still fit.
+/// Largest backward step in `lap_distance_pct` between frames that is
+/// taken as the car actually reversing (backing out of a spin, moving in the
+/// pit box) rather than a glitch; about 10 m on a 5 km track.
+pub const REVERSE_TOLERANCE: f32 = 0.002;
+
+const LAP_CAPACITY_HEADROOM_DIVISOR: usize = 16;
+
+/// The most recently completed lap, shared between a [`LapHandler`] and
+/// readers such as a live overlay. See [`LapHandler::with_latest_lap`].
+pub type LatestLap = Arc<RwLock<Option<LapCompletePayload>>>;
+
+/// Detects lap completion by monitoring lap_number changes, or as configured
+/// by [`LapHandlerConfig::split`]
+///
+/// When a session ends ([`RacingEvent::SessionFramesEnded`]), the lap in
+/// progress is emitted as [`LapType::Incomplete`] instead of being dropped.
 pub struct LapHandler {
+    config: LapHandlerConfig,
     state: Mutex<LapHandlerState>,
+    latest: Option<LatestLap>,
 }
 
 struct LapHandlerState {
     current_lap: i32,
     frame_count: usize,
+    /// `session_time` of the first frame of the current lap
+    lap_start_time: f64,
     valid: bool,
+    /// Whether the first frame of the current lap was observed
+    lap_start_seen: bool,
+    started_on_pit_road: bool,
+    last_on_pit_road: bool,
+    min_distance_pct: f32,
+    max_distance_pct: f32,
+    last_distance_pct: f32,
+    /// Consecutive out-of-order frames seen since the last accepted frame
+    glitch_frames: usize,
+    /// Frames kept for the current lap
+    frames: VecDeque<Arc<TelemetryFrame>>,
+    /// Whether the lap buffer has hit its cap during the current lap
+    overflowed: bool,
+    /// Most recent frame skipped by down-sampling, kept as the lap's last frame
+    last_skipped: Option<Arc<TelemetryFrame>>,
+}
+
+impl LapHandlerState {
+    /// State before the first frame.
+    fn new() -> Self {
+        Self {
+            current_lap: -1,
+            frame_count: 0,
+            lap_start_time: 0.0,
+            valid: true,
+            lap_start_seen: false,
+            started_on_pit_road: false,
+            last_on_pit_road: false,
+            min_distance_pct: f32::MAX,
+            max_distance_pct: f32::MIN,
+            last_distance_pct: 0.0,
+            glitch_frames: 0,
+            frames: VecDeque::new(),
+            overflowed: false,
+            last_skipped: None,
+        }
+    }
+
+    fn lap_type(&self) -> LapType {
+        if !self.lap_start_seen {
+            LapType::Incomplete
+        } else if self.started_on_pit_road {
+            LapType::OutLap
+        } else if self.last_on_pit_road {
+            LapType::InLap
+        } else {
+            LapType::Timed
+        }
+    }
+
+    fn distance_coverage(&self) -> f32 {
+        (self.max_distance_pct - self.min_distance_pct).max(0.0)
+    }
+
+    /// Start tracking lap `lap_number` beginning at `frame`.
+    fn start_lap(&mut self, frame: &TelemetryFrame, lap_number: i32, lap_start_seen: bool) {
+        self.current_lap = lap_number;
+        self.frame_count = 0;
+        self.lap_start_time = frame.session_time;
+        self.valid = true;
+        self.lap_start_seen = lap_start_seen;
+        self.started_on_pit_road = frame.on_pit_road;
+        self.min_distance_pct = f32::MAX;
+        self.max_distance_pct = f32::MIN;
+        self.glitch_frames = 0;
+        self.frames.clear();
+        self.overflowed = false;
+        self.last_skipped = None;
+    }
+
+    /// Take the buffered frames for the lap, including its last frame.
+    ///
+    /// The buffer moves into the lap payload without copying, and is replaced
+    /// by one sized for a lap like this one, so the next lap fills it without
+    /// growing and reallocating along the way.
+    fn take_frames(&mut self, config: &LapHandlerConfig) -> Vec<Arc<TelemetryFrame>> {
+        if let Some(frame) = self.last_skipped.take() {
+            self.buffer_frame(frame, config);
+        }
+
+        let len = self.frames.len();
+        let capacity = (len + len / LAP_CAPACITY_HEADROOM_DIVISOR)
+            .min(config.max_buffer_frames.unwrap_or(usize::MAX));
+        std::mem::replace(&mut self.frames, VecDeque::with_capacity(capacity)).into()
+    }
+
+    /// Add a frame to the lap buffer, applying the overflow policy.
+    fn buffer_frame(&mut self, frame: Arc<TelemetryFrame>, config: &LapHandlerConfig) {
+        let Some(max_frames) = config.max_buffer_frames else {
+            self.frames.push_back(frame);
+            return;
+        };
+
+        if self.frames.len() >= max_frames {
+            if !self.overflowed {
+                self.overflowed = true;
+                warn!(
+                    "Lap {} buffer reached {} frames, applying {:?}",
+                    self.current_lap, max_frames, config.overflow_policy
+                );
+            }
+
+            match config.overflow_policy {
+                BufferOverflow::DropOldest => {
+                    self.frames.pop_front();
+                }
+                BufferOverflow::DropNewest => return,
+            }
+        }
+
+        if max_frames > 0 {
+            self.frames.push_back(frame);
+        }
+    }
+
+    fn record_frame(&mut self, frame: Arc<TelemetryFrame>, config: &LapHandlerConfig) {
+        self.frame_count += 1;
+        self.last_on_pit_road = frame.on_pit_road;
+        self.last_distance_pct = frame.lap_distance_pct;
+        self.min_distance_pct = self.min_distance_pct.min(frame.lap_distance_pct);
+        self.max_distance_pct = self.max_distance_pct.max(frame.lap_distance_pct);
+
+        if self.valid && (frame.is_off_track() || frame.has_penalty_flag()) {
+            self.valid = false;
+        }
+
+        let upload_interval = config.upload_hz.map(|hz| 1.0 / hz);
+        let keep = match (upload_interval, self.frames.back()) {
+            (Some(interval), Some(last)) => {
+                frame.session_time - last.session_time >= interval - f64::EPSILON.sqrt()
+            }
+            _ => true,
+        };
+
+        if keep {
+            self.buffer_frame(frame, config);
+            self.last_skipped = None;
+        } else {
+            self.last_skipped = Some(frame);
+        }
+    }
 }
 
 impl LapHandler {
     pub fn new() -> Self {
+        Self::with_config(LapHandlerConfig::default())
+    }
+
+    /// # Panics
+    /// If [`LapHandlerConfig::upload_hz`] is not a positive, finite rate.
+    pub fn with_config(config: LapHandlerConfig) -> Self {
+        if let Some(hz) = config.upload_hz {
+            assert!(
+                hz.is_finite() && hz > 0.0,
+                "upload rate must be positive and finite, got {hz}"
+            );
+        }
         Self {
-            state: Mutex::new(LapHandlerState {
-                current_lap: -1,
-                frame_count: 0,
-                valid: true,
-            }),
+            config,
+            state: Mutex::new(LapHandlerState::new()),
+            latest: None,
+        }
+    }
+
+    /// Also keep each completed lap in `latest`, so it can be read without
+    /// subscribing to [`RacingEventKind::LapComplete`]. Laps are still
+    /// published as usual.
+    pub fn with_latest_lap(mut self, latest: LatestLap) -> Self {
+        self.latest = Some(latest);
+        self
+    }
+
+    /// The most recently completed lap, if retained (see
+    /// [`LapHandler::with_latest_lap`]) and any lap has completed yet.
+    pub fn latest_lap(&self) -> Option<LapCompletePayload> {
+        self.latest.as_ref()?.read().unwrap().clone()
+    }
+
+    /// Where `frame` falls relative to the lap being tracked.
+    fn classify(&self, state: &LapHandlerState, frame: &TelemetryFrame) -> FrameOrder {
+        let wrapped =
+            state.last_distance_pct - frame.lap_distance_pct >= self.config.wraparound_threshold;
+        let backward = state.last_distance_pct - frame.lap_distance_pct > REVERSE_TOLERANCE;
+
+        match self.config.split {
+            LapSplit::LapNumber => {
+                let lap_changed = frame.lap_number != state.current_lap;
+                if lap_changed && frame.lap_number > state.current_lap && wrapped {
+                    // Canonical lap boundary: lap number increments as distance wraps
+                    FrameOrder::NewLap
+                } else if lap_changed || backward {
+                    FrameOrder::OutOfOrder
+                } else {
+                    FrameOrder::Continues
+                }
+            }
+            LapSplit::DistanceWraparound if wrapped => FrameOrder::NewLap,
+            LapSplit::DistanceWraparound if backward => FrameOrder::OutOfOrder,
+            LapSplit::DistanceWraparound => FrameOrder::Continues,
+            LapSplit::Interval(interval) => {
+                if frame.session_time - state.lap_start_time >= interval.as_secs_f64() {
+                    FrameOrder::NewLap
+                } else {
+                    FrameOrder::Continues
+                }
+            }
+        }
+    }
+
+    /// Number of the lap starting at `frame`. Only [`LapSplit::LapNumber`]
+    /// trusts the frame's lap number; other strategies count laps themselves.
+    fn next_lap_number(&self, state: &LapHandlerState, frame: &TelemetryFrame) -> i32 {
+        match self.config.split {
+            LapSplit::LapNumber => frame.lap_number,
+            LapSplit::DistanceWraparound | LapSplit::Interval(_) => state.current_lap + 1,
+        }
+    }
+
+    /// Whether the current lap covered less than `min_coverage` of the
+    /// track, logging that it's discarded if so.
+    fn too_short(&self, state: &LapHandlerState, min_coverage: f32) -> bool {
+        let coverage = state.distance_coverage();
+        let checks_coverage = !matches!(self.config.split, LapSplit::Interval(_));
+
+        let too_short = checks_coverage && coverage < min_coverage;
+        if too_short {
+            warn!(
+                "Discarding lap {}: covered {:.2} of the track (minimum {:.2})",
+                state.current_lap, coverage, min_coverage
+            );
+        }
+        too_short
+    }
+
+    /// Emit the current lap if it covered enough of the track.
+    fn complete_lap(&self, state: &mut LapHandlerState, ctx: &HandlerContext<RacingEvent>) {
+        if !self.too_short(state, self.config.min_lap_distance_coverage) {
+            let lap_type = state.lap_type();
+            self.publish_lap(state, ctx, lap_type);
+        }
+    }
+
+    /// Emit the lap in progress as [`LapType::Incomplete`] if it covered
+    /// enough of the track, and start over as if no frame had been seen.
+    fn flush_partial_lap(&self, state: &mut LapHandlerState, ctx: &HandlerContext<RacingEvent>) {
+        if state.current_lap < 0 {
+            return;
+        }
+
+        if !self.too_short(state, self.config.min_partial_lap_coverage) {
+            info!("Session ended during lap {}", state.current_lap);
+            self.publish_lap(state, ctx, LapType::Incomplete);
+        }
+        *state = LapHandlerState::new();
+    }
+
+    /// Publish the current lap's buffered frames as a completed lap.
+    fn publish_lap(
+        &self,
+        state: &mut LapHandlerState,
+        ctx: &HandlerContext<RacingEvent>,
+        lap_type: LapType,
+    ) {
+        let is_valid = state.valid && lap_type == LapType::Timed;
+        let buffered = state.frames.len() + usize::from(state.last_skipped.is_some());
+
+        let lap_id = if self.config.deterministic_ids {
+            let session = ctx
+                .state::<SessionState>()
+                .and_then(|state| state.session());
+            deterministic_lap_id(session.as_deref(), state.current_lap).unwrap_or_else(|| {
+                warn!(
+                    "No iRacing subsession known for lap {}, using a random lap id",
+                    state.current_lap
+                );
+                Uuid::new_v4()
+            })
+        } else {
+            Uuid::new_v4()
+        };
+        let payload = LapCompletePayload {
+            lap_id,
+            lap_number: state.current_lap,
+            lap_time_ms: None,
+            frame_count: state.frame_count,
+            lap_type,
+            is_valid,
+            frames: Arc::new(state.take_frames(&self.config)),
+        };
+        let _span = payload.span().entered();
+        info!(
+            "Lap {} complete after {} frames ({} buffered). Type: {:?}, Valid: {}",
+            payload.lap_number, payload.frame_count, buffered, lap_type, is_valid
+        );
+
+        if let Some(latest) = &self.latest {
+            *latest.write().unwrap() = Some(payload.clone());
         }
+        ctx.publish(RacingEvent::LapComplete(payload));
     }
 }
 
+/// How a frame relates to the lap being tracked, see [`LapHandler::classify`].
+enum FrameOrder {
+    /// The frame belongs to the current lap
+    Continues,
+    /// The frame is the first of a new lap
+    NewLap,
+    /// The frame is out of order: a glitch, or a reset if it persists
+    OutOfOrder,
+}
+
 impl Default for LapHandler {
     fn default() -> Self {
         Self::new()
@@ -45,36 +498,668 @@ impl EventHandler<RacingEvent> for LapHandler {
     }
 
     async fn handle(&self, event: RacingEvent, ctx: &HandlerContext<RacingEvent>) {
-        let RacingEvent::TelemetryFrameCollected(frame) = event else {
-            return;
+        let frame = match event {
+            RacingEvent::TelemetryFrameCollected(frame) => frame,
+            RacingEvent::SessionFramesEnded(_) => {
+                let mut state = self.state.lock().await;
+                self.flush_partial_lap(&mut state, ctx);
+                return;
+            }
+            _ => return,
         };
 
         let mut state = self.state.lock().await;
-        state.frame_count += 1;
 
-        if state.valid && frame.track_surface != 3 {
-            state.valid = false;
+        if state.current_lap < 0 {
+            // First frame: we joined mid-lap, unless laps are split on time alone
+            let lap_start_seen = matches!(self.config.split, LapSplit::Interval(_));
+            state.start_lap(&frame, frame.lap_number, lap_start_seen);
+        } else {
+            match self.classify(&state, &frame) {
+                FrameOrder::NewLap => {
+                    self.complete_lap(&mut state, ctx);
+                    let lap_number = self.next_lap_number(&state, &frame);
+                    state.start_lap(&frame, lap_number, true);
+                }
+                FrameOrder::OutOfOrder => {
+                    state.glitch_frames += 1;
+
+                    if state.glitch_frames <= self.config.max_glitch_frames {
+                        debug!(
+                            "Ignoring out-of-order frame: lap {} -> {}, pct {:.4} -> {:.4}",
+                            state.current_lap,
+                            frame.lap_number,
+                            state.last_distance_pct,
+                            frame.lap_distance_pct
+                        );
+                        return;
+                    }
+
+                    warn!(
+                        "Lap tracking reset on lap {} after {} out-of-order frames, discarding lap",
+                        state.current_lap, state.glitch_frames
+                    );
+                    // Self-counted laps reuse the discarded lap's number
+                    let lap_number = match self.config.split {
+                        LapSplit::LapNumber => frame.lap_number,
+                        LapSplit::DistanceWraparound | LapSplit::Interval(_) => state.current_lap,
+                    };
+                    state.start_lap(&frame, lap_number, false);
+                }
+                FrameOrder::Continues => state.glitch_frames = 0,
+            }
         }
 
-        // Detect lap change
-        if frame.lap_number != state.current_lap && state.current_lap >= 0 {
-            info!(
-                "Lap {} complete after {} frames. Valid: {}",
-                state.current_lap, state.frame_count, state.valid
-            );
+        state.record_frame(frame, &self.config);
+        debug!("Lap {} frame {}", state.current_lap, state.frame_count);
+    }
+}
 
-            // Publish LapComplete event (no Arc needed - small payload)
-            ctx.publish(RacingEvent::LapComplete(LapCompletePayload {
-                lap_number: state.current_lap,
-                lap_time_ms: None,
-                frame_count: state.frame_count,
-            }));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::SessionEndPayload;
+    use crate::test_utils::{collect_events, frame};
+    use eventbus::{EventBus, HandlerRegistry};
+    use pitwall::irsdk_flags::session_flags;
 
-            // Reset for new lap
-            state.frame_count = 0;
+    fn pit_frame(lap_number: i32, lap_distance_pct: f32) -> TelemetryFrame {
+        TelemetryFrame {
+            on_pit_road: true,
+            ..frame(lap_number, lap_distance_pct)
         }
+    }
 
-        state.current_lap = frame.lap_number;
-        debug!("Lap {} frame {}", state.current_lap, state.frame_count);
+    /// Frames covering a full lap, starting at the line.
+    fn full_lap(lap_number: i32) -> Vec<TelemetryFrame> {
+        [0.0, 0.5, 0.95]
+            .into_iter()
+            .map(|pct| frame(lap_number, pct))
+            .collect()
+    }
+
+    async fn completed_laps_with(
+        handler: LapHandler,
+        frames: Vec<TelemetryFrame>,
+    ) -> Vec<LapCompletePayload> {
+        collect_events(handler, frames, RacingEventKind::LapComplete)
+            .await
+            .into_iter()
+            .filter_map(|event| match event {
+                RacingEvent::LapComplete(payload) => Some(payload),
+                _ => None,
+            })
+            .collect()
+    }
+
+    async fn completed_laps(frames: Vec<TelemetryFrame>) -> Vec<LapCompletePayload> {
+        completed_laps_with(LapHandler::new(), frames).await
+    }
+
+    #[tokio::test]
+    async fn test_lap_handler_detects_completed_lap() {
+        let mut frames = vec![frame(1, 0.02), frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        frames.push(frame(3, 0.0));
+
+        let laps = completed_laps(frames).await;
+
+        assert_eq!(laps.len(), 2);
+        assert_eq!(laps[0].lap_type, LapType::Incomplete);
+        assert_eq!(laps[1].lap_number, 2);
+        assert_eq!(laps[1].frame_count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_clean_lap_is_timed() {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        frames.push(frame(3, 0.0));
+
+        let laps = completed_laps(frames).await;
+
+        assert_eq!(laps.len(), 1);
+        assert_eq!(laps[0].lap_type, LapType::Timed);
+        assert!(laps[0].is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_lap_starting_on_pit_road_is_out_lap() {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        frames[1].on_pit_road = true;
+        frames.push(frame(3, 0.0));
+
+        let laps = completed_laps(frames).await;
+
+        assert_eq!(laps[0].lap_type, LapType::OutLap);
+        assert!(!laps[0].is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_lap_ending_on_pit_road_is_in_lap() {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        frames[3].on_pit_road = true;
+        frames.push(pit_frame(3, 0.0));
+
+        let laps = completed_laps(frames).await;
+
+        assert_eq!(laps[0].lap_type, LapType::InLap);
+        assert!(!laps[0].is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_off_track_lap_is_invalid() {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        frames[2].track_surface = 0;
+        frames.extend(full_lap(3));
+        frames.push(frame(4, 0.0));
+
+        let laps = completed_laps(frames).await;
+
+        assert!(!laps[0].is_valid);
+        assert!(laps[1].is_valid, "validity should reset for the next lap");
+    }
+
+    #[tokio::test]
+    async fn test_penalty_flag_invalidates_lap() {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        frames[2].session_flags = session_flags::REPAIR;
+        frames.extend(full_lap(3));
+        // Session-wide flags don't affect validity
+        frames[5].session_flags = session_flags::GREEN | session_flags::YELLOW;
+        frames.push(frame(4, 0.0));
+
+        let laps = completed_laps(frames).await;
+
+        assert!(!laps[0].is_valid);
+        assert!(laps[1].is_valid);
+    }
+
+    /// Session info for iRacing subsession `sub_session_id`.
+    fn subsession(sub_session_id: i32) -> SessionInfo {
+        let mut session = SessionInfo::default();
+        session.weekend_info.sub_session_id = Some(sub_session_id);
+        session.weekend_info.track_id = Some(1);
+        session
+    }
+
+    /// Id of the one lap completed in `session`.
+    async fn lap_id_in(session: Option<SessionInfo>, deterministic_ids: bool) -> Uuid {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        frames.push(frame(3, 0.0));
+
+        let bus = EventBus::new(64);
+        let mut rx = bus.subscribe(RacingEventKind::LapComplete);
+        let mut registry = HandlerRegistry::new();
+        registry.register(LapHandler::with_config(LapHandlerConfig {
+            deterministic_ids,
+            ..Default::default()
+        }));
+        let session_state = SessionState::new(10);
+        if let Some(session) = session {
+            session_state.start_session(Arc::new(session));
+        }
+        registry.set_state(session_state);
+        let handles = registry.run(bus.clone());
+
+        for frame in frames {
+            bus.publish(RacingEvent::TelemetryFrameCollected(Arc::new(frame)))
+                .unwrap();
+        }
+        let RacingEvent::LapComplete(lap) = rx.recv().await.unwrap() else {
+            panic!("expected LapComplete");
+        };
+
+        registry.shutdown();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        lap.lap_id
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_lap_ids_repeat_across_runs() {
+        let session = || Some(subsession(42));
+
+        assert_eq!(
+            lap_id_in(session(), true).await,
+            lap_id_in(session(), true).await
+        );
+        assert_ne!(
+            lap_id_in(session(), false).await,
+            lap_id_in(session(), false).await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_lap_ids_differ_between_sessions() {
+        assert_ne!(
+            lap_id_in(Some(subsession(42)), true).await,
+            lap_id_in(Some(subsession(43)), true).await
+        );
+        // Nothing identifies these sessions, so their laps get random ids
+        assert_ne!(lap_id_in(None, true).await, lap_id_in(None, true).await);
+        assert_ne!(
+            lap_id_in(Some(subsession(0)), true).await,
+            lap_id_in(Some(subsession(0)), true).await
+        );
+    }
+
+    /// Laps emitted for `frames` followed by the end of the session.
+    async fn laps_through_session_end(frames: Vec<TelemetryFrame>) -> Vec<LapCompletePayload> {
+        let bus = EventBus::new(64);
+        let mut rx = bus.subscribe(RacingEventKind::LapComplete);
+        let mut registry = HandlerRegistry::new();
+        registry.register(LapHandler::new());
+        let handles = registry.run(bus.clone());
+
+        for frame in frames {
+            bus.publish(RacingEvent::TelemetryFrameCollected(Arc::new(frame)))
+                .unwrap();
+        }
+        bus.publish(RacingEvent::SessionFramesEnded(SessionEndPayload {
+            session_id: Uuid::new_v4(),
+        }))
+        .unwrap();
+
+        let mut laps = Vec::new();
+        while let Ok(Ok(event)) = tokio::time::timeout(Duration::from_millis(200), rx.recv()).await
+        {
+            if let RacingEvent::LapComplete(lap) = event {
+                laps.push(lap);
+            }
+        }
+        registry.shutdown();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        laps
+    }
+
+    #[tokio::test]
+    async fn test_session_end_emits_partial_lap() {
+        // Lap 2 starts at the line but the session ends halfway round
+        let mut frames = vec![frame(0, 0.95)];
+        frames.extend(full_lap(1));
+        frames.extend([frame(2, 0.0), frame(2, 0.3), frame(2, 0.5)]);
+
+        let laps = laps_through_session_end(frames).await;
+
+        assert_eq!(laps.len(), 2);
+        assert_eq!(laps[0].lap_type, LapType::Timed);
+        assert_eq!(laps[1].lap_number, 2);
+        assert_eq!(laps[1].lap_type, LapType::Incomplete);
+        assert!(!laps[1].is_valid);
+        assert_eq!(laps[1].frames.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_session_end_drops_negligible_partial_lap() {
+        let mut frames = full_lap(1);
+        frames.extend([frame(2, 0.0), frame(2, 0.1)]);
+
+        let laps = laps_through_session_end(frames).await;
+
+        assert_eq!(laps.len(), 1);
+        assert_eq!(laps[0].lap_number, 1);
+    }
+
+    /// Config under which the 0.3 -> 0.0 drop of [`short_lap`] crosses the
+    /// line.
+    fn short_lap_config() -> LapHandlerConfig {
+        LapHandlerConfig {
+            wraparound_threshold: 0.25,
+            ..Default::default()
+        }
+    }
+
+    /// A lap cut short at 30% of the track, then lap 3 starting.
+    fn short_lap() -> Vec<TelemetryFrame> {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend([0.0, 0.1, 0.2, 0.3].into_iter().map(|pct| frame(2, pct)));
+        frames.push(frame(3, 0.0));
+        frames
+    }
+
+    #[tokio::test]
+    async fn test_short_lap_is_discarded() {
+        let handler = LapHandler::with_config(short_lap_config());
+        let laps = completed_laps_with(handler, short_lap()).await;
+
+        assert!(laps.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_min_coverage_is_configurable() {
+        let handler = LapHandler::with_config(LapHandlerConfig {
+            min_lap_distance_coverage: 0.25,
+            ..short_lap_config()
+        });
+        let laps = completed_laps_with(handler, short_lap()).await;
+
+        assert_eq!(laps.len(), 1);
+        assert_eq!(laps[0].lap_number, 2);
+    }
+
+    #[tokio::test]
+    async fn test_wraparound_threshold_is_configurable() {
+        let lap_numbers = async |wraparound_threshold: f32| {
+            let handler = LapHandler::with_config(LapHandlerConfig {
+                min_lap_distance_coverage: 0.25,
+                wraparound_threshold,
+                ..Default::default()
+            });
+            completed_laps_with(handler, short_lap())
+                .await
+                .iter()
+                .map(|lap| lap.lap_number)
+                .collect::<Vec<_>>()
+        };
+
+        // A 0.3 drop only crosses the line under a threshold below it
+        assert_eq!(lap_numbers(0.25).await, [2]);
+        assert!(lap_numbers(0.5).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reversing_a_little_keeps_the_lap() {
+        let mut frames = vec![frame(1, 0.95), frame(2, 0.0), frame(2, 0.5)];
+        // Backing out of a spin: creeping backwards for longer than glitches
+        // are tolerated, then driving on
+        frames.extend((1..=10).map(|i| frame(2, 0.5 - 0.001 * i as f32)));
+        frames.extend([frame(2, 0.6), frame(2, 0.95), frame(3, 0.0)]);
+
+        let laps = completed_laps(frames).await;
+
+        assert_eq!(laps.len(), 1);
+        assert_eq!(laps[0].lap_number, 2);
+        assert_eq!(laps[0].lap_type, LapType::Timed);
+        assert_eq!(laps[0].frame_count, 14);
+    }
+
+    #[tokio::test]
+    async fn test_isolated_glitches_do_not_split_lap() {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        // Backward distance jump mid-lap without a lap change
+        frames.insert(3, frame(2, 0.01));
+        // Spurious lap number decrement
+        frames.insert(4, frame(0, 0.0));
+        frames.push(frame(3, 0.0));
+
+        let laps = completed_laps(frames).await;
+
+        assert_eq!(laps.len(), 1);
+        assert_eq!(laps[0].lap_number, 2);
+        assert_eq!(laps[0].frame_count, 3);
+        assert_eq!(laps[0].lap_type, LapType::Timed);
+    }
+
+    #[tokio::test]
+    async fn test_lap_change_without_wraparound_is_not_a_boundary() {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        // Lap number briefly jumps mid-lap, distance keeps increasing
+        frames.insert(2, frame(3, 0.3));
+        frames.push(frame(3, 0.0));
+
+        let laps = completed_laps(frames).await;
+
+        assert_eq!(laps.len(), 1);
+        assert_eq!(laps[0].lap_number, 2);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_backward_jump_restarts_lap() {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        // Reset to the pits: distance stays behind the last accepted frame
+        frames.extend((0..10).map(|_| pit_frame(2, 0.05)));
+        frames.push(frame(2, 0.5));
+        frames.push(frame(2, 0.95));
+        frames.push(frame(3, 0.0));
+
+        let laps = completed_laps(frames).await;
+
+        assert_eq!(laps.len(), 1);
+        assert_eq!(laps[0].lap_type, LapType::Incomplete);
+    }
+
+    #[tokio::test]
+    async fn test_lap_frames_are_buffered() {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        frames.push(frame(3, 0.0));
+
+        let laps = completed_laps(frames).await;
+
+        let pcts: Vec<f32> = laps[0].frames.iter().map(|f| f.lap_distance_pct).collect();
+        assert_eq!(pcts, vec![0.0, 0.5, 0.95]);
+    }
+
+    #[tokio::test]
+    async fn test_reused_buffer_holds_only_its_own_lap() {
+        // A long lap sizes the buffer for the next, shorter one
+        let mut frames = vec![frame(1, 0.99)];
+        frames.extend((0..40).map(|i| frame(2, i as f32 / 40.0)));
+        frames.extend(full_lap(3));
+        frames.extend((0..60).map(|i| frame(4, i as f32 / 60.0)));
+        frames.push(frame(5, 0.0));
+
+        let laps = completed_laps(frames).await;
+
+        assert_eq!(laps.len(), 3);
+        let pcts = |lap: &LapCompletePayload| -> Vec<f32> {
+            lap.frames.iter().map(|f| f.lap_distance_pct).collect()
+        };
+        assert_eq!(
+            pcts(&laps[0]),
+            (0..40).map(|i| i as f32 / 40.0).collect::<Vec<_>>()
+        );
+        assert_eq!(pcts(&laps[1]), vec![0.0, 0.5, 0.95]);
+        assert_eq!(
+            pcts(&laps[2]),
+            (0..60).map(|i| i as f32 / 60.0).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_hz_downsamples_buffer() {
+        const FRAMES_PER_LAP: usize = 600; // 10 seconds at 60 Hz
+
+        let mut frames = vec![frame(1, 0.99)];
+        frames.extend((0..FRAMES_PER_LAP).map(|i| TelemetryFrame {
+            session_time: i as f64 / 60.0,
+            ..frame(2, i as f32 / FRAMES_PER_LAP as f32)
+        }));
+        frames.push(frame(3, 0.0));
+
+        let handler = LapHandler::with_config(LapHandlerConfig {
+            upload_hz: Some(10.0),
+            ..Default::default()
+        });
+        let laps = completed_laps_with(handler, frames).await;
+
+        let lap = &laps[0];
+        assert_eq!(lap.frame_count, FRAMES_PER_LAP);
+
+        let expected = FRAMES_PER_LAP / 6;
+        assert!(
+            lap.frames.len().abs_diff(expected) <= 2,
+            "expected ~{} buffered frames, got {}",
+            expected,
+            lap.frames.len()
+        );
+
+        // First and last frames of the lap are always kept
+        assert_eq!(lap.frames.first().unwrap().lap_distance_pct, 0.0);
+        assert_eq!(
+            lap.frames.last().unwrap().lap_distance_pct,
+            (FRAMES_PER_LAP - 1) as f32 / FRAMES_PER_LAP as f32
+        );
+    }
+
+    #[test]
+    fn test_invalid_upload_hz_is_rejected() {
+        for hz in [0.0, -10.0, f64::NAN, f64::INFINITY] {
+            let result = std::panic::catch_unwind(|| {
+                LapHandler::with_config(LapHandlerConfig {
+                    upload_hz: Some(hz),
+                    ..Default::default()
+                })
+            });
+            assert!(result.is_err(), "{hz} Hz was accepted");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_buffer_is_bounded_within_a_lap() {
+        const MAX_FRAMES: usize = 10;
+
+        let mut frames = vec![frame(1, 0.99)];
+        // A lap that runs far past the cap
+        frames.extend((0..50).map(|i| frame(2, i as f32 / 50.0)));
+        frames.extend(full_lap(3));
+        frames.push(frame(4, 0.0));
+
+        let handler = LapHandler::with_config(LapHandlerConfig {
+            max_buffer_frames: Some(MAX_FRAMES),
+            ..Default::default()
+        });
+        let laps = completed_laps_with(handler, frames).await;
+
+        assert_eq!(laps.len(), 2);
+        assert_eq!(laps[0].frame_count, 50);
+        assert_eq!(laps[0].frames.len(), MAX_FRAMES);
+        // The most recent frames are kept
+        assert_eq!(
+            laps[0].frames.first().unwrap().lap_distance_pct,
+            40.0 / 50.0
+        );
+        assert_eq!(laps[0].frames.last().unwrap().lap_distance_pct, 49.0 / 50.0);
+
+        // The next lap buffers normally
+        let pcts: Vec<f32> = laps[1].frames.iter().map(|f| f.lap_distance_pct).collect();
+        assert_eq!(pcts, vec![0.0, 0.5, 0.95]);
+    }
+
+    #[tokio::test]
+    async fn test_drop_newest_keeps_earliest_frames() {
+        let mut frames = vec![frame(1, 0.99)];
+        frames.extend((0..50).map(|i| frame(2, i as f32 / 50.0)));
+        frames.push(frame(3, 0.0));
+
+        let handler = LapHandler::with_config(LapHandlerConfig {
+            max_buffer_frames: Some(10),
+            overflow_policy: BufferOverflow::DropNewest,
+            ..Default::default()
+        });
+        let laps = completed_laps_with(handler, frames).await;
+
+        assert_eq!(laps[0].frames.len(), 10);
+        assert_eq!(laps[0].frames.first().unwrap().lap_distance_pct, 0.0);
+        assert_eq!(laps[0].frames.last().unwrap().lap_distance_pct, 9.0 / 50.0);
+    }
+
+    fn wraparound_handler() -> LapHandler {
+        LapHandler::with_config(LapHandlerConfig {
+            split: LapSplit::DistanceWraparound,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_wraparound_split_ignores_constant_lap_number() {
+        // Lap number is stuck at 1 while the car keeps crossing the line
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(1));
+        frames.extend(full_lap(1));
+        frames.push(frame(1, 0.0));
+
+        let laps = completed_laps_with(wraparound_handler(), frames).await;
+
+        let numbers: Vec<i32> = laps.iter().map(|lap| lap.lap_number).collect();
+        assert_eq!(numbers, vec![2, 3]);
+        assert!(laps.iter().all(|lap| lap.lap_type == LapType::Timed));
+        let pcts: Vec<f32> = laps[0].frames.iter().map(|f| f.lap_distance_pct).collect();
+        assert_eq!(pcts, vec![0.0, 0.5, 0.95]);
+    }
+
+    #[tokio::test]
+    async fn test_wraparound_split_still_ignores_glitches() {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(1));
+        // Small backward jump and a spurious lap number change mid-lap
+        frames.insert(3, frame(1, 0.45));
+        frames.insert(4, frame(7, 0.5));
+        frames.push(frame(1, 0.0));
+
+        let laps = completed_laps_with(wraparound_handler(), frames).await;
+
+        assert_eq!(laps.len(), 1);
+        assert_eq!(laps[0].lap_number, 2);
+        assert_eq!(laps[0].frame_count, 4);
+    }
+
+    #[tokio::test]
+    async fn test_lap_number_split_needs_lap_increment() {
+        // The default strategy treats a wrap without a lap change as a glitch
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(1));
+        frames.push(frame(1, 0.0));
+
+        assert!(completed_laps(frames).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_interval_split_ignores_track_position() {
+        // Two minutes of frames at 1 Hz, distance not advancing consistently
+        let frames: Vec<_> = (0..120)
+            .map(|i| TelemetryFrame {
+                session_time: i as f64,
+                ..frame(0, (i % 7) as f32 / 10.0)
+            })
+            .collect();
+
+        let handler = LapHandler::with_config(LapHandlerConfig {
+            split: LapSplit::Interval(Duration::from_secs(45)),
+            ..Default::default()
+        });
+        let laps = completed_laps_with(handler, frames).await;
+
+        assert_eq!(laps.len(), 2);
+        assert_eq!((laps[0].lap_number, laps[1].lap_number), (0, 1));
+        assert_eq!((laps[0].frame_count, laps[1].frame_count), (45, 45));
+        assert_eq!(laps[1].frames.first().unwrap().session_time, 45.0);
+        assert_eq!(laps[0].lap_type, LapType::Timed);
+    }
+
+    #[tokio::test]
+    async fn test_latest_lap_matches_published_lap() {
+        let mut frames = vec![frame(1, 0.95)];
+        frames.extend(full_lap(2));
+        frames.push(frame(3, 0.0));
+
+        let latest = LatestLap::default();
+        let handler = LapHandler::new().with_latest_lap(latest.clone());
+        assert!(handler.latest_lap().is_none());
+
+        let laps = completed_laps_with(handler, frames).await;
+        let retained = latest
+            .read()
+            .unwrap()
+            .clone()
+            .expect("lap should be retained");
+
+        assert_eq!(laps.len(), 1);
+        assert_eq!(retained.lap_number, laps[0].lap_number);
+        assert_eq!(retained.frame_count, laps[0].frame_count);
+        assert_eq!(retained.lap_type, laps[0].lap_type);
+        assert!(Arc::ptr_eq(&retained.frames, &laps[0].frames));
     }
 }
@@ -0,0 +1,228 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::events::{LapCompletePayload, LiveDeltaPayload, RacingEvent, RacingEventKind};
+use eventbus::{EventHandler, HandlerContext};
+
+/// Compares each telemetry frame against the session's best lap, publishing
+/// a [`RacingEvent::LiveDelta`] with the time gained or lost so far.
+///
+/// The reference lap is taken from [`RacingEvent::NewBestLap`], so deltas
+/// start once a valid lap has been completed. Handlers only receive a single
+/// event kind; use [`LiveDeltaHandler::handlers`] to create one for each kind
+/// it follows. The reference resets on session start and end, i.e. whenever
+/// the track or car changes.
+pub struct LiveDeltaHandler {
+    kind: RacingEventKind,
+    reference: Arc<Mutex<Option<Arc<ReferenceLap>>>>,
+}
+
+impl LiveDeltaHandler {
+    /// Create the handlers sharing one reference lap. Register all of them.
+    pub fn handlers() -> Vec<Self> {
+        let reference = Arc::new(Mutex::new(None));
+        [
+            RacingEventKind::SessionStart,
+            RacingEventKind::SessionEnd,
+            RacingEventKind::NewBestLap,
+            RacingEventKind::TelemetryFrameCollected,
+        ]
+        .into_iter()
+        .map(|kind| Self {
+            kind,
+            reference: reference.clone(),
+        })
+        .collect()
+    }
+}
+
+#[async_trait]
+impl EventHandler<RacingEvent> for LiveDeltaHandler {
+    fn handles(&self) -> RacingEventKind {
+        self.kind
+    }
+
+    fn name(&self) -> &'static str {
+        "LiveDeltaHandler"
+    }
+
+    async fn handle(&self, event: RacingEvent, ctx: &HandlerContext<RacingEvent>) {
+        match event {
+            RacingEvent::SessionStart(_) | RacingEvent::SessionEnd(_) => {
+                *self.reference.lock().unwrap() = None;
+            }
+            RacingEvent::NewBestLap(lap) => {
+                let reference = ReferenceLap::from_lap(&lap);
+                *self.reference.lock().unwrap() = reference.map(Arc::new);
+            }
+            RacingEvent::TelemetryFrameCollected(frame) => {
+                let Some(reference) = self.reference.lock().unwrap().clone() else {
+                    return;
+                };
+                let Some(reference_time) = reference.time_at(frame.lap_distance_pct) else {
+                    return;
+                };
+                ctx.publish(RacingEvent::LiveDelta(LiveDeltaPayload {
+                    distance_pct: frame.lap_distance_pct,
+                    delta_seconds: frame.current_lap_time - reference_time,
+                }));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Elapsed lap time against lap distance for a reference lap.
+struct ReferenceLap {
+    /// `(lap_distance_pct, current_lap_time)`, strictly increasing in distance
+    points: Vec<(f32, f32)>,
+}
+
+impl ReferenceLap {
+    /// The time-vs-distance curve of `lap`, or `None` if it has fewer than
+    /// two usable frames. Frames that don't move further round the lap than
+    /// the ones before them are skipped.
+    fn from_lap(lap: &LapCompletePayload) -> Option<Self> {
+        let mut points: Vec<(f32, f32)> = Vec::with_capacity(lap.frames.len());
+        for frame in lap.frames.iter() {
+            let distance = frame.lap_distance_pct;
+            if points.last().is_none_or(|&(last, _)| distance > last) {
+                points.push((distance, frame.current_lap_time));
+            }
+        }
+        (points.len() >= 2).then_some(Self { points })
+    }
+
+    /// Reference lap time at `distance_pct`, interpolated between the
+    /// nearest frames. `None` outside the distance the reference covers.
+    fn time_at(&self, distance_pct: f32) -> Option<f32> {
+        let idx = self
+            .points
+            .partition_point(|&(distance, _)| distance < distance_pct);
+        let &(d1, t1) = self.points.get(idx)?;
+        if d1 == distance_pct {
+            return Some(t1);
+        }
+        let &(d0, t0) = self.points.get(idx.checked_sub(1)?)?;
+        Some(t0 + (t1 - t0) * (distance_pct - d0) / (d1 - d0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use eventbus::{EventBus, HandlerRegistry};
+    use uuid::Uuid;
+
+    use crate::events::{LapType, SessionEndPayload};
+    use crate::telem::TelemetryFrame;
+    use crate::test_utils::frame;
+
+    /// A lap of 11 frames at even distances, driven at a constant pace that
+    /// takes `lap_time` seconds.
+    fn lap_frames(lap_number: i32, lap_time: f32) -> Vec<TelemetryFrame> {
+        (0..=10)
+            .map(|i| {
+                let pct = i as f32 / 10.0;
+                TelemetryFrame {
+                    current_lap_time: pct * lap_time,
+                    ..frame(lap_number, pct)
+                }
+            })
+            .collect()
+    }
+
+    fn best_lap(lap_time: f32) -> RacingEvent {
+        let frames = lap_frames(1, lap_time).into_iter().map(Arc::new).collect();
+        RacingEvent::NewBestLap(LapCompletePayload {
+            lap_id: Uuid::new_v4(),
+            lap_number: 1,
+            lap_time_ms: Some((lap_time * 1000.0) as u64),
+            frame_count: 11,
+            lap_type: LapType::Timed,
+            is_valid: true,
+            frames: Arc::new(frames),
+        })
+    }
+
+    /// Publish `events` one at a time and return the deltas published.
+    async fn live_deltas(events: Vec<RacingEvent>) -> Vec<LiveDeltaPayload> {
+        let bus = EventBus::new(64);
+        let mut rx = bus.subscribe(RacingEventKind::LiveDelta);
+        let mut registry = HandlerRegistry::new();
+        for handler in LiveDeltaHandler::handlers() {
+            registry.register(handler);
+        }
+        let handles = registry.run(bus.clone());
+
+        for event in events {
+            bus.publish(event).unwrap();
+            // Handlers of different kinds run separately, so let each event
+            // land before the next
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        bus.shutdown_and_drain().await;
+        registry.shutdown();
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        let mut deltas = Vec::new();
+        while let Ok(RacingEvent::LiveDelta(delta)) = rx.try_recv() {
+            deltas.push(delta);
+        }
+        deltas
+    }
+
+    fn frames(lap_number: i32, lap_time: f32) -> impl Iterator<Item = RacingEvent> {
+        lap_frames(lap_number, lap_time)
+            .into_iter()
+            .map(|frame| RacingEvent::TelemetryFrameCollected(Arc::new(frame)))
+    }
+
+    #[tokio::test]
+    async fn test_slower_lap_loses_time_against_best() {
+        let mut events = vec![best_lap(90.0)];
+        events.extend(frames(2, 99.0));
+
+        let deltas = live_deltas(events).await;
+
+        assert_eq!(deltas.len(), 11);
+        assert_eq!(deltas[0].delta_seconds, 0.0);
+        for pair in deltas.windows(2) {
+            assert!(
+                pair[1].delta_seconds > pair[0].delta_seconds,
+                "deltas should grow: {deltas:?}"
+            );
+            assert!(pair[1].distance_pct > pair[0].distance_pct);
+        }
+        assert!((deltas[10].delta_seconds - 9.0).abs() < 1e-3);
+    }
+
+    #[tokio::test]
+    async fn test_no_deltas_before_a_best_lap_or_after_session_end() {
+        let mut events: Vec<RacingEvent> = frames(1, 90.0).collect();
+        events.push(best_lap(90.0));
+        events.push(RacingEvent::SessionEnd(SessionEndPayload {
+            session_id: Uuid::new_v4(),
+        }));
+        events.extend(frames(2, 99.0));
+
+        assert!(live_deltas(events).await.is_empty());
+    }
+
+    #[test]
+    fn test_reference_interpolates_between_frames() {
+        let RacingEvent::NewBestLap(lap) = best_lap(90.0) else {
+            unreachable!();
+        };
+        let reference = ReferenceLap::from_lap(&lap).unwrap();
+
+        assert_eq!(reference.time_at(0.5), Some(45.0));
+        assert!((reference.time_at(0.25).unwrap() - 22.5).abs() < 1e-3);
+        assert_eq!(reference.time_at(1.5), None);
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use async_trait::async_trait;
 use tracing::info;
 
+use crate::config::UnitSystem;
 use crate::events::{RacingEvent, RacingEventKind};
 use eventbus::{EventHandler, HandlerContext};
 
@@ -10,6 +11,7 @@ use eventbus::{EventHandler, HandlerContext};
 pub struct LogHandler {
     log_frequency: usize,
     frame_count: AtomicUsize,
+    units: UnitSystem,
 }
 
 impl LogHandler {
@@ -17,8 +19,15 @@ impl LogHandler {
         Self {
             log_frequency,
             frame_count: AtomicUsize::new(0),
+            units: UnitSystem::default(),
         }
     }
+
+    /// Log speeds in `units` instead of km/h.
+    pub fn with_units(mut self, units: UnitSystem) -> Self {
+        self.units = units;
+        self
+    }
 }
 
 impl Default for LogHandler {
@@ -46,9 +55,10 @@ impl EventHandler<RacingEvent> for LogHandler {
 
         if count.is_multiple_of(self.log_frequency) {
             info!(
-                "Frame {}: Speed={:.1}, RPM={:.0}, Gear={}, Lap={}, DistPct={}, LapTime={}, Surface={}",
+                "Frame {}: Speed={:.1} {}, RPM={:.0}, Gear={}, Lap={}, DistPct={}, LapTime={}, Surface={}",
                 count,
-                frame.speed,
+                self.units.speed(frame.speed.into()),
+                self.units.speed_unit(),
                 frame.rpm,
                 frame.gear,
                 frame.lap_number,
@@ -59,3 +69,68 @@ impl EventHandler<RacingEvent> for LogHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use eventbus::{EventBus, HandlerRegistry};
+
+    use crate::test_utils::frame;
+
+    /// Collects everything logged to it.
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// What a handler logs for one frame at 25 m/s.
+    async fn log_frame(units: UnitSystem) -> String {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        // Handler tasks run on this test's single thread, so they log here
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let bus = EventBus::new(4);
+        let mut registry = HandlerRegistry::new();
+        registry.register(LogHandler::new(1).with_units(units));
+        let handles = registry.run(bus.clone());
+
+        let mut telemetry = frame(1, 0.5);
+        telemetry.speed = 25.0;
+        bus.publish(RacingEvent::TelemetryFrameCollected(telemetry.into()))
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        registry.shutdown();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        String::from_utf8(captured.0.lock().unwrap().clone()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_speed_is_logged_in_configured_units() {
+        let metric = log_frame(UnitSystem::Metric).await;
+        assert!(metric.contains("Speed=90.0 km/h"), "logged: {metric}");
+
+        let imperial = log_frame(UnitSystem::Imperial).await;
+        assert!(imperial.contains("Speed=55.9 mph"), "logged: {imperial}");
+    }
+}
@@ -1,7 +1,28 @@
+mod best_lap;
 mod counter;
+mod ghost;
 mod lap;
+mod live_delta;
 mod log;
+mod recording;
+mod session_state;
+mod stats;
+mod upload;
+#[cfg(feature = "ws")]
+mod ws;
 
+pub use best_lap::BestLapHandler;
 pub use counter::CounterHandler;
-pub use lap::LapHandler;
+pub use ghost::GhostHandler;
+pub use lap::{BufferOverflow, LapHandler, LapHandlerConfig, LapSplit, LatestLap};
+pub use live_delta::LiveDeltaHandler;
 pub use log::LogHandler;
+pub use recording::{RecordedEvent, RecordingHandler, replay_events};
+pub use session_state::SessionStateHandler;
+pub use stats::{RunStats, RunSummary, StatsHandler};
+pub use upload::{
+    JsonLinesUploader, LapUploadHandler, LapUploadResponse, LapUploader, MultiUploader,
+    UploadError, UploadLimit, UploadPolicy,
+};
+#[cfg(feature = "ws")]
+pub use ws::WsHandler;
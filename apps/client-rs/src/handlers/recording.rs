@@ -0,0 +1,322 @@
+//! Event recording and replay.
+//!
+//! [`RecordingHandler`] writes every event it sees to a JSONL file together
+//! with its offset from the start of the recording. [`replay_events`] reads
+//! such a file back and re-publishes the events, which makes captured
+//! sessions usable for debugging and regression tests.
+
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use eventbus::{EventBus, EventHandler, EventLike, HandlerContext};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncWrite, AsyncWriteExt, BufWriter};
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use tracing::warn;
+
+use crate::events::{RacingEvent, RacingEventKind};
+
+/// One line of a recording.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Seconds since the recording started
+    pub offset_secs: f64,
+    pub event: RacingEvent,
+}
+
+/// Longest recorded events stay buffered before being written out, so a
+/// crash loses at most this much of the recording.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Writer shared by all handlers of one recording.
+struct RecordingSink {
+    writer: Mutex<SinkWriter>,
+    started: Instant,
+}
+
+struct SinkWriter {
+    writer: BufWriter<Box<dyn AsyncWrite + Send + Unpin>>,
+    last_flush: Instant,
+}
+
+impl RecordingSink {
+    async fn write(&self, event: RacingEvent) -> io::Result<()> {
+        let record = RecordedEvent {
+            offset_secs: self.started.elapsed().as_secs_f64(),
+            event,
+        };
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+
+        let mut sink = self.writer.lock().await;
+        sink.writer.write_all(&line).await?;
+        if sink.last_flush.elapsed() >= FLUSH_INTERVAL {
+            sink.writer.flush().await?;
+            sink.last_flush = Instant::now();
+        }
+        Ok(())
+    }
+
+    async fn flush(&self) -> io::Result<()> {
+        let mut sink = self.writer.lock().await;
+        sink.writer.flush().await?;
+        sink.last_flush = Instant::now();
+        Ok(())
+    }
+}
+
+/// Writes each event of one kind to a shared recording.
+///
+/// Handlers only receive a single event kind, so a recording is made of one
+/// handler per kind; use [`RecordingHandler::for_all_kinds`] to create them.
+pub struct RecordingHandler {
+    kind: RacingEventKind,
+    sink: Arc<RecordingSink>,
+}
+
+impl RecordingHandler {
+    /// Create handlers recording every event kind to `writer`.
+    ///
+    /// Register all of them; offsets are measured from this call. Events
+    /// are buffered and written out at least every second, and once the
+    /// handlers shut down.
+    pub fn for_all_kinds<W: AsyncWrite + Send + Unpin + 'static>(writer: W) -> Vec<Self> {
+        let started = Instant::now();
+        let sink = Arc::new(RecordingSink {
+            writer: Mutex::new(SinkWriter {
+                writer: BufWriter::new(Box::new(writer)),
+                last_flush: started,
+            }),
+            started,
+        });
+
+        RacingEvent::all_kinds()
+            .map(|kind| Self {
+                kind,
+                sink: sink.clone(),
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EventHandler<RacingEvent> for RecordingHandler {
+    fn handles(&self) -> RacingEventKind {
+        self.kind
+    }
+
+    fn name(&self) -> &'static str {
+        "RecordingHandler"
+    }
+
+    async fn handle(&self, event: RacingEvent, _ctx: &HandlerContext<RacingEvent>) {
+        if let Err(error) = self.sink.write(event).await {
+            warn!(
+                "RecordingHandler: failed to record {:?} event: {}",
+                self.kind, error
+            );
+        }
+    }
+
+    async fn on_shutdown(&self) {
+        if let Err(error) = self.sink.flush().await {
+            warn!("RecordingHandler: failed to flush recording: {}", error);
+        }
+    }
+}
+
+/// Re-publish the events recorded at `path` on `bus`, in offset order.
+///
+/// Events of different kinds are handled concurrently while recording, so the
+/// file may be slightly out of order; replay sorts by offset. With `realtime`
+/// set, events are paced to their recorded offsets, otherwise they are
+/// published as fast as possible.
+///
+/// # Returns
+/// * The number of events replayed
+pub async fn replay_events(
+    path: impl AsRef<Path>,
+    bus: &EventBus<RacingEvent>,
+    realtime: bool,
+) -> io::Result<usize> {
+    let contents = tokio::fs::read_to_string(path).await?;
+
+    let mut records = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str::<RecordedEvent>)
+        .collect::<Result<Vec<_>, _>>()?;
+    records.sort_by(|a, b| a.offset_secs.total_cmp(&b.offset_secs));
+
+    let started = Instant::now();
+    let count = records.len();
+
+    for record in records {
+        if realtime {
+            tokio::time::sleep_until(
+                started + Duration::from_secs_f64(record.offset_secs.max(0.0)),
+            )
+            .await;
+        }
+        let _ = bus.publish(record.event);
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    use crate::events::{SessionEndPayload, SessionStartPayload};
+    use crate::test_utils::frame;
+    use eventbus::HandlerRegistry;
+    use futures::StreamExt;
+    use pitwall::SessionInfo;
+    use uuid::Uuid;
+
+    async fn run_until_idle(
+        registry: &HandlerRegistry<RacingEvent>,
+        handles: Vec<tokio::task::JoinHandle<()>>,
+    ) {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        registry.shutdown();
+        for handle in handles {
+            let _ = handle.await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay_round_trip() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let session_id = Uuid::new_v4();
+
+        // Record a short session
+        let bus = EventBus::new(64);
+        let mut registry = HandlerRegistry::new();
+        let writer = tokio::fs::File::from_std(file.reopen().unwrap());
+        for handler in RecordingHandler::for_all_kinds(writer) {
+            registry.register(handler);
+        }
+        let handles = registry.run(bus.clone());
+
+        bus.publish(RacingEvent::SessionStart(SessionStartPayload {
+            session_id,
+            session: Arc::new(SessionInfo::default()),
+        }))
+        .unwrap();
+        for i in 0..10 {
+            bus.publish(RacingEvent::TelemetryFrameCollected(Arc::new(frame(
+                1,
+                i as f32 / 10.0,
+            ))))
+            .unwrap();
+        }
+        bus.publish(RacingEvent::SessionEnd(SessionEndPayload { session_id }))
+            .unwrap();
+        run_until_idle(&registry, handles).await;
+
+        // Replay it into a fresh bus, counting what arrives
+        let bus = EventBus::new(64);
+        let events = bus.subscribe_stream();
+
+        let replayed = replay_events(file.path(), &bus, false).await.unwrap();
+        let events: Vec<RacingEvent> =
+            tokio::time::timeout(Duration::from_secs(1), events.take(12).collect())
+                .await
+                .expect("all recorded events should be replayed");
+
+        assert_eq!(replayed, 12);
+        assert_eq!(events.len(), 12);
+        let frames = events
+            .iter()
+            .filter(|event| event.kind() == RacingEventKind::TelemetryFrameCollected)
+            .count();
+        assert_eq!(frames, 10);
+
+        // Kinds are delivered on separate channels, so only membership is stable
+        assert!(events.iter().any(
+            |event| matches!(event, RacingEvent::SessionStart(start) if start.session_id == session_id)
+        ));
+        assert!(events.iter().any(
+            |event| matches!(event, RacingEvent::SessionEnd(end) if end.session_id == session_id)
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_realtime_replay_paces_events() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        for offset_secs in [2.0, 0.0, 1.0] {
+            let record = RecordedEvent {
+                offset_secs,
+                event: RacingEvent::TelemetryFrameCollected(Arc::new(frame(1, offset_secs as f32))),
+            };
+            writeln!(
+                file.as_file(),
+                "{}",
+                serde_json::to_string(&record).unwrap()
+            )
+            .unwrap();
+        }
+
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe(RacingEventKind::TelemetryFrameCollected);
+        let started = Instant::now();
+
+        assert_eq!(replay_events(file.path(), &bus, true).await.unwrap(), 3);
+        assert_eq!(started.elapsed(), Duration::from_secs(2));
+
+        for expected in [0.0, 1.0, 2.0] {
+            let RacingEvent::TelemetryFrameCollected(frame) = rx.recv().await.unwrap() else {
+                panic!("expected TelemetryFrameCollected");
+            };
+            assert_eq!(frame.lap_distance_pct, expected);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_recording_is_flushed_periodically() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let bus = EventBus::new(16);
+        let mut registry = HandlerRegistry::new();
+        let writer = tokio::fs::File::from_std(file.reopen().unwrap());
+        for handler in RecordingHandler::for_all_kinds(writer) {
+            registry.register(handler);
+        }
+        let handles = registry.run(bus.clone());
+        let publish_frame = || {
+            bus.publish(RacingEvent::TelemetryFrameCollected(Arc::new(frame(
+                1, 0.5,
+            ))))
+            .unwrap();
+        };
+        let recorded_lines = || {
+            std::fs::read_to_string(file.path())
+                .unwrap()
+                .lines()
+                .count()
+        };
+
+        publish_frame();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(recorded_lines(), 0, "events are buffered between flushes");
+
+        tokio::time::sleep(FLUSH_INTERVAL).await;
+        publish_frame();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(recorded_lines(), 2);
+
+        publish_frame();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        registry.shutdown();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(recorded_lines(), 3, "shutting down flushes the rest");
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::events::{RacingEvent, RacingEventKind};
+use crate::session::SessionState;
+use eventbus::{EventHandler, HandlerContext};
+
+/// Keeps a shared [`SessionState`] up to date with session changes and
+/// completed laps.
+///
+/// Handlers only receive a single event kind; use
+/// [`SessionStateHandler::for_state`] to create one for each kind it follows.
+pub struct SessionStateHandler {
+    kind: RacingEventKind,
+    state: Arc<SessionState>,
+}
+
+impl SessionStateHandler {
+    /// Create the handlers updating `state`. Register all of them.
+    pub fn for_state(state: &Arc<SessionState>) -> Vec<Self> {
+        [
+            RacingEventKind::SessionStart,
+            RacingEventKind::SessionEnd,
+            RacingEventKind::LapComplete,
+        ]
+        .into_iter()
+        .map(|kind| Self {
+            kind,
+            state: state.clone(),
+        })
+        .collect()
+    }
+}
+
+#[async_trait]
+impl EventHandler<RacingEvent> for SessionStateHandler {
+    fn handles(&self) -> RacingEventKind {
+        self.kind
+    }
+
+    fn name(&self) -> &'static str {
+        "SessionStateHandler"
+    }
+
+    async fn handle(&self, event: RacingEvent, _ctx: &HandlerContext<RacingEvent>) {
+        match event {
+            RacingEvent::SessionStart(start) => self.state.start_session(start.session),
+            RacingEvent::SessionEnd(_) => self.state.end_session(),
+            RacingEvent::LapComplete(lap) => self.state.record_lap((&lap).into()),
+            RacingEvent::TelemetryFrameCollected(_)
+            | RacingEvent::SessionFramesEnded(_)
+            | RacingEvent::LapUploaded(_)
+            | RacingEvent::NewBestLap(_)
+            | RacingEvent::ServerStatusChanged(_)
+            | RacingEvent::LiveDelta(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use eventbus::{EventBus, HandlerRegistry};
+    use uuid::Uuid;
+
+    use crate::events::{LapCompletePayload, LapType};
+
+    /// Records the previous lap's time, as read from the context, for each lap.
+    #[derive(Default)]
+    struct PreviousLapReader {
+        seen: Arc<Mutex<Vec<Option<u64>>>>,
+    }
+
+    #[async_trait]
+    impl EventHandler<RacingEvent> for PreviousLapReader {
+        fn handles(&self) -> RacingEventKind {
+            RacingEventKind::LapComplete
+        }
+
+        async fn handle(&self, event: RacingEvent, ctx: &HandlerContext<RacingEvent>) {
+            let RacingEvent::LapComplete(lap) = event else {
+                return;
+            };
+            let previous = ctx
+                .state::<SessionState>()
+                .and_then(|state| state.previous_lap(lap.lap_number))
+                .and_then(|previous| previous.lap_time_ms);
+            self.seen.lock().unwrap().push(previous);
+        }
+    }
+
+    fn lap(lap_number: i32, lap_time_ms: u64) -> RacingEvent {
+        RacingEvent::LapComplete(LapCompletePayload {
+            lap_id: Uuid::new_v4(),
+            lap_number,
+            lap_time_ms: Some(lap_time_ms),
+            frame_count: 0,
+            lap_type: LapType::Timed,
+            is_valid: true,
+            frames: Arc::new(Vec::new()),
+        })
+    }
+
+    /// Wait until `done` returns true.
+    async fn wait_until(done: impl Fn() -> bool) {
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while !done() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("handlers should catch up");
+    }
+
+    #[tokio::test]
+    async fn test_handler_reads_previous_lap_time_from_context() {
+        let bus = EventBus::new(16);
+        let state = SessionState::new(10);
+        let reader = PreviousLapReader::default();
+        let seen = reader.seen.clone();
+
+        let mut registry = HandlerRegistry::new();
+        registry.set_state(state.clone());
+        for handler in SessionStateHandler::for_state(&state) {
+            registry.register(handler);
+        }
+        registry.register(reader);
+        let handles = registry.run(bus.clone());
+
+        bus.publish(lap(2, 91_500)).unwrap();
+        wait_until(|| state.recent_laps().len() == 1).await;
+        bus.publish(lap(3, 90_250)).unwrap();
+        wait_until(|| seen.lock().unwrap().len() == 2).await;
+
+        registry.shutdown();
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        assert_eq!(*seen.lock().unwrap(), [None, Some(91_500)]);
+    }
+}
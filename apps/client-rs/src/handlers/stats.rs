@@ -0,0 +1,126 @@
+//! Run statistics.
+//!
+//! [`RunStats`] holds counters shared by the handlers of one run, and
+//! [`StatsHandler`] fills in the ones that can be read off the bus. At the
+//! end of a run the counters are turned into a [`RunSummary`].
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use eventbus::{EventHandler, HandlerContext};
+
+use crate::events::{RacingEvent, RacingEventKind};
+
+/// Counters for one run, shared between handlers.
+#[derive(Debug, Default)]
+pub struct RunStats {
+    frames: AtomicU64,
+    laps: AtomicUsize,
+    laps_uploaded: AtomicUsize,
+    metrics_extracted: AtomicUsize,
+}
+
+impl RunStats {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    pub fn record_lap_uploaded(&self) {
+        self.laps_uploaded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_metrics_extracted(&self) {
+        self.metrics_extracted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn laps_uploaded(&self) -> usize {
+        self.laps_uploaded.load(Ordering::Relaxed)
+    }
+
+    /// Snapshot the counters for a run that took `elapsed`.
+    pub fn summary(&self, elapsed: Duration) -> RunSummary {
+        RunSummary {
+            frames_processed: self.frames.load(Ordering::Relaxed),
+            laps_detected: self.laps.load(Ordering::Relaxed),
+            laps_uploaded: self.laps_uploaded(),
+            metrics_extracted: self.metrics_extracted.load(Ordering::Relaxed),
+            elapsed,
+        }
+    }
+}
+
+/// What a run processed, reported when it finishes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunSummary {
+    pub frames_processed: u64,
+    pub laps_detected: usize,
+    pub laps_uploaded: usize,
+    pub metrics_extracted: usize,
+    /// Wall-clock duration of the run
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} frames, {} laps detected, {} uploaded, {} metrics extracted in {:.1?}",
+            self.frames_processed,
+            self.laps_detected,
+            self.laps_uploaded,
+            self.metrics_extracted,
+            self.elapsed
+        )
+    }
+}
+
+/// Counts frames and completed laps into a shared [`RunStats`].
+///
+/// Handlers only receive a single event kind; use [`StatsHandler::for_stats`]
+/// to create one for each counted kind.
+pub struct StatsHandler {
+    kind: RacingEventKind,
+    stats: Arc<RunStats>,
+}
+
+impl StatsHandler {
+    /// Create the handlers counting into `stats`. Register all of them.
+    pub fn for_stats(stats: &Arc<RunStats>) -> Vec<Self> {
+        [
+            RacingEventKind::TelemetryFrameCollected,
+            RacingEventKind::LapComplete,
+        ]
+        .into_iter()
+        .map(|kind| Self {
+            kind,
+            stats: stats.clone(),
+        })
+        .collect()
+    }
+}
+
+#[async_trait]
+impl EventHandler<RacingEvent> for StatsHandler {
+    fn handles(&self) -> RacingEventKind {
+        self.kind
+    }
+
+    fn name(&self) -> &'static str {
+        "StatsHandler"
+    }
+
+    async fn handle(&self, event: RacingEvent, _ctx: &HandlerContext<RacingEvent>) {
+        match event {
+            RacingEvent::TelemetryFrameCollected(_) => {
+                self.stats.frames.fetch_add(1, Ordering::Relaxed);
+            }
+            RacingEvent::LapComplete(_) => {
+                self.stats.laps.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {}
+        }
+    }
+}
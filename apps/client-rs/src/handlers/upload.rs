@@ -0,0 +1,642 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::{Mutex, Semaphore};
+use tracing::{Instrument, info, warn};
+use uuid::Uuid;
+
+use crate::events::{
+    LapCompletePayload, LapType, LapUploadedPayload, RacingEvent, RacingEventKind,
+};
+use crate::handlers::RunStats;
+use eventbus::{EventHandler, HandlerContext};
+
+/// A failed lap upload.
+#[derive(Debug, Error)]
+#[error("lap upload failed: {0}")]
+pub struct UploadError(pub String);
+
+/// The server's reply to a lap upload.
+#[derive(Clone, Debug, Deserialize)]
+pub struct LapUploadResponse {
+    /// Id the server stored the lap under
+    pub lap_id: Uuid,
+}
+
+/// Destination for completed laps (e.g. the Racing Coach server).
+#[async_trait]
+pub trait LapUploader: Send + Sync {
+    async fn upload_lap(&self, lap: &LapCompletePayload) -> Result<LapUploadResponse, UploadError>;
+}
+
+/// When a [`MultiUploader`] counts an upload as successful.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UploadPolicy {
+    /// At least one destination accepted the lap
+    #[default]
+    Any,
+    /// Every destination accepted the lap
+    All,
+}
+
+/// Uploads each lap to several destinations at once, e.g. a local and a
+/// hosted server.
+///
+/// The reply is the first successful destination's, in the order the
+/// destinations were given; failures at the others are logged.
+pub struct MultiUploader {
+    uploaders: Vec<Arc<dyn LapUploader>>,
+    policy: UploadPolicy,
+}
+
+impl MultiUploader {
+    pub fn new(uploaders: Vec<Arc<dyn LapUploader>>) -> Self {
+        Self {
+            uploaders,
+            policy: UploadPolicy::default(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: UploadPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+#[async_trait]
+impl LapUploader for MultiUploader {
+    async fn upload_lap(&self, lap: &LapCompletePayload) -> Result<LapUploadResponse, UploadError> {
+        let results = join_all(self.uploaders.iter().map(|u| u.upload_lap(lap))).await;
+
+        let mut accepted = None;
+        let mut errors = Vec::new();
+        for (idx, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(response) => {
+                    accepted.get_or_insert(response);
+                }
+                Err(error) => {
+                    warn!("Lap {} destination {}: {}", lap.lap_number, idx, error);
+                    errors.push(format!("destination {}: {}", idx, error.0));
+                }
+            }
+        }
+
+        match accepted {
+            Some(response) if self.policy == UploadPolicy::Any || errors.is_empty() => Ok(response),
+            None if errors.is_empty() => Err(UploadError("no upload destinations".into())),
+            _ => Err(UploadError(errors.join("; "))),
+        }
+    }
+}
+
+/// One line written by [`JsonLinesUploader`].
+#[derive(Serialize)]
+struct LapRecord {
+    lap_id: Uuid,
+    lap_number: i32,
+    lap_time_ms: Option<u64>,
+    lap_type: LapType,
+    is_valid: bool,
+    frame_count: usize,
+}
+
+/// Writes each lap as a line of JSON instead of uploading it, e.g. to keep
+/// the laps of an offline replay in a local file.
+///
+/// Lines hold the lap's summary without its frames, under the lap's own
+/// `lap_id`, which is also returned as the upload's.
+pub struct JsonLinesUploader {
+    writer: Mutex<Box<dyn AsyncWrite + Send + Unpin>>,
+}
+
+impl JsonLinesUploader {
+    pub fn new(writer: impl AsyncWrite + Send + Unpin + 'static) -> Self {
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+        }
+    }
+
+    /// Append laps to the file at `path`, creating it if needed.
+    pub fn append_to(path: &Path) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self::new(tokio::fs::File::from_std(file)))
+    }
+
+    /// Write laps to stdout.
+    pub fn stdout() -> Self {
+        Self::new(tokio::io::stdout())
+    }
+}
+
+#[async_trait]
+impl LapUploader for JsonLinesUploader {
+    async fn upload_lap(&self, lap: &LapCompletePayload) -> Result<LapUploadResponse, UploadError> {
+        let record = LapRecord {
+            lap_id: lap.lap_id,
+            lap_number: lap.lap_number,
+            lap_time_ms: lap.lap_time_ms,
+            lap_type: lap.lap_type,
+            is_valid: lap.is_valid,
+            frame_count: lap.frame_count,
+        };
+        let mut line =
+            serde_json::to_vec(&record).map_err(|error| UploadError(error.to_string()))?;
+        line.push(b'\n');
+
+        // Held across the write so lines from concurrent uploads don't interleave
+        let mut writer = self.writer.lock().await;
+        writer
+            .write_all(&line)
+            .await
+            .map_err(|error| UploadError(error.to_string()))?;
+        writer
+            .flush()
+            .await
+            .map_err(|error| UploadError(error.to_string()))?;
+        Ok(LapUploadResponse {
+            lap_id: record.lap_id,
+        })
+    }
+}
+
+/// Caps how many lap uploads are in flight at once.
+///
+/// Clones share the cap, so one limit can cover several upload handlers.
+#[derive(Clone, Debug)]
+pub struct UploadLimit {
+    permits: Arc<Semaphore>,
+    max: u32,
+}
+
+impl UploadLimit {
+    /// A limit of `max` uploads at a time.
+    ///
+    /// # Panics
+    /// If `max` is zero.
+    pub fn new(max: u32) -> Self {
+        assert!(max > 0, "upload limit must allow at least one upload");
+        Self {
+            permits: Arc::new(Semaphore::new(max as usize)),
+            max,
+        }
+    }
+
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+
+    /// Wait until no upload under this limit is in flight.
+    async fn wait_idle(&self) {
+        // The semaphore is never closed
+        let _ = self.permits.acquire_many(self.max).await;
+    }
+}
+
+impl Default for UploadLimit {
+    /// One upload at a time.
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+/// Uploads completed laps, or only logs them when disabled (dry run).
+///
+/// Each accepted lap is announced with a [`RacingEvent::LapUploaded`] event
+/// carrying the id the server assigned.
+///
+/// Uploads run in the background, up to the handler's [`UploadLimit`] at a
+/// time; once the limit is reached, the next lap waits for a slot. The
+/// handler waits for uploads still in flight when it shuts down.
+pub struct LapUploadHandler {
+    uploader: Option<Arc<dyn LapUploader>>,
+    stats: Arc<RunStats>,
+    limit: UploadLimit,
+}
+
+impl LapUploadHandler {
+    pub fn new(uploader: Arc<dyn LapUploader>) -> Self {
+        Self {
+            uploader: Some(uploader),
+            stats: RunStats::new(),
+            limit: UploadLimit::default(),
+        }
+    }
+
+    /// A handler that logs what would have been uploaded without uploading.
+    pub fn disabled() -> Self {
+        Self {
+            uploader: None,
+            stats: RunStats::new(),
+            limit: UploadLimit::default(),
+        }
+    }
+
+    /// Count uploads into `stats` instead of the handler's own counters.
+    pub fn with_stats(mut self, stats: Arc<RunStats>) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Keep uploads within `limit`, which may be shared with other handlers.
+    pub fn with_limit(mut self, limit: UploadLimit) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.uploader.is_some()
+    }
+
+    /// Number of laps uploaded successfully.
+    pub fn uploaded(&self) -> usize {
+        self.stats.laps_uploaded()
+    }
+}
+
+async fn upload(
+    uploader: &dyn LapUploader,
+    lap: LapCompletePayload,
+    stats: &RunStats,
+    ctx: &HandlerContext<RacingEvent>,
+) {
+    match uploader.upload_lap(&lap).await {
+        Ok(response) => {
+            stats.record_lap_uploaded();
+            info!("Uploaded lap {} as {}", lap.lap_number, response.lap_id);
+            ctx.publish(RacingEvent::LapUploaded(LapUploadedPayload {
+                lap_number: lap.lap_number,
+                lap_id: response.lap_id,
+            }));
+        }
+        Err(error) => warn!("Lap {}: {}", lap.lap_number, error),
+    }
+}
+
+#[async_trait]
+impl EventHandler<RacingEvent> for LapUploadHandler {
+    fn handles(&self) -> RacingEventKind {
+        RacingEventKind::LapComplete
+    }
+
+    fn name(&self) -> &'static str {
+        "LapUploadHandler"
+    }
+
+    async fn handle(&self, event: RacingEvent, ctx: &HandlerContext<RacingEvent>) {
+        let RacingEvent::LapComplete(lap) = event else {
+            return;
+        };
+
+        let span = lap.span();
+        let Some(uploader) = self.uploader.clone() else {
+            span.in_scope(|| {
+                info!(
+                    "Dry run: would upload lap {} ({:?}, valid: {}, {} frames)",
+                    lap.lap_number,
+                    lap.lap_type,
+                    lap.is_valid,
+                    lap.frames.len()
+                )
+            });
+            return;
+        };
+
+        // The semaphore is never closed
+        let Ok(permit) = self.limit.permits.clone().acquire_owned().await else {
+            return;
+        };
+        let stats = self.stats.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(
+            async move {
+                upload(uploader.as_ref(), lap, &stats, &ctx).await;
+                drop(permit);
+            }
+            .instrument(span),
+        );
+    }
+
+    async fn on_shutdown(&self) {
+        if self.is_enabled() {
+            self.limit.wait_idle().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use eventbus::{EventBus, HandlerRegistry};
+
+    /// Uploader standing in for a server that stores laps under its own id.
+    struct ReassigningUploader {
+        lap_id: Uuid,
+    }
+
+    #[async_trait]
+    impl LapUploader for ReassigningUploader {
+        async fn upload_lap(
+            &self,
+            _lap: &LapCompletePayload,
+        ) -> Result<LapUploadResponse, UploadError> {
+            Ok(LapUploadResponse {
+                lap_id: self.lap_id,
+            })
+        }
+    }
+
+    /// Uploader standing in for a server that rejects every lap.
+    struct FailingUploader;
+
+    #[async_trait]
+    impl LapUploader for FailingUploader {
+        async fn upload_lap(
+            &self,
+            _lap: &LapCompletePayload,
+        ) -> Result<LapUploadResponse, UploadError> {
+            Err(UploadError("503 Service Unavailable".into()))
+        }
+    }
+
+    /// Uploader counting the laps it receives.
+    #[derive(Default)]
+    struct CountingUploader {
+        received: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LapUploader for CountingUploader {
+        async fn upload_lap(
+            &self,
+            _lap: &LapCompletePayload,
+        ) -> Result<LapUploadResponse, UploadError> {
+            self.received.fetch_add(1, Ordering::SeqCst);
+            Ok(LapUploadResponse {
+                lap_id: Uuid::new_v4(),
+            })
+        }
+    }
+
+    /// Uploader standing in for a slow server, recording the most uploads
+    /// it had in progress at once.
+    #[derive(Default)]
+    struct SlowUploader {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+        received: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LapUploader for SlowUploader {
+        async fn upload_lap(
+            &self,
+            _lap: &LapCompletePayload,
+        ) -> Result<LapUploadResponse, UploadError> {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.received.fetch_add(1, Ordering::SeqCst);
+            Ok(LapUploadResponse {
+                lap_id: Uuid::new_v4(),
+            })
+        }
+    }
+
+    fn lap(lap_number: i32) -> LapCompletePayload {
+        LapCompletePayload {
+            lap_id: Uuid::new_v4(),
+            lap_number,
+            lap_time_ms: None,
+            frame_count: 0,
+            lap_type: LapType::Timed,
+            is_valid: true,
+            frames: Arc::new(Vec::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multi_uploader_sends_to_every_destination() {
+        let local = Arc::new(CountingUploader::default());
+        let hosted = Arc::new(CountingUploader::default());
+        let uploader =
+            MultiUploader::new(vec![local.clone(), hosted.clone()]).with_policy(UploadPolicy::All);
+
+        uploader.upload_lap(&lap(1)).await.unwrap();
+        uploader.upload_lap(&lap(2)).await.unwrap();
+
+        for destination in [local, hosted] {
+            assert_eq!(destination.received.load(Ordering::SeqCst), 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multi_uploader_policy_decides_partial_failure() {
+        let server_id = Uuid::new_v4();
+        let destinations = || -> Vec<Arc<dyn LapUploader>> {
+            vec![
+                Arc::new(FailingUploader),
+                Arc::new(ReassigningUploader { lap_id: server_id }),
+            ]
+        };
+
+        let any = MultiUploader::new(destinations()).with_policy(UploadPolicy::Any);
+        assert_eq!(any.upload_lap(&lap(1)).await.unwrap().lap_id, server_id);
+
+        let all = MultiUploader::new(destinations()).with_policy(UploadPolicy::All);
+        let error = all.upload_lap(&lap(1)).await.unwrap_err();
+        assert!(error.0.contains("destination 0"), "{error}");
+
+        let none = MultiUploader::new(vec![Arc::new(FailingUploader)]);
+        assert!(none.upload_lap(&lap(1)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_json_lines_file_gets_one_line_per_lap() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("laps.jsonl");
+        let uploader = JsonLinesUploader::append_to(&path).unwrap();
+
+        let first = uploader.upload_lap(&lap(1)).await.unwrap();
+        uploader
+            .upload_lap(&LapCompletePayload {
+                lap_time_ms: Some(91_250),
+                ..lap(2)
+            })
+            .await
+            .unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["lap_id"], first.lap_id.to_string());
+        assert_eq!(lines[0]["lap_number"], 1);
+        assert_eq!(lines[0]["lap_type"], "Timed");
+        assert_eq!(lines[1]["lap_time_ms"], 91_250);
+
+        // Reopening appends rather than truncating
+        let reopened = JsonLinesUploader::append_to(&path).unwrap();
+        reopened.upload_lap(&lap(3)).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_json_lines_stay_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("laps.jsonl");
+        let uploader = JsonLinesUploader::append_to(&path).unwrap();
+
+        let laps: Vec<_> = (1..=20).map(lap).collect();
+        let results = join_all(laps.iter().map(|lap| uploader.upload_lap(lap))).await;
+        assert!(results.iter().all(Result::is_ok));
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let mut lap_numbers: Vec<i64> = contents
+            .lines()
+            .map(|line| {
+                let record: serde_json::Value = serde_json::from_str(line).unwrap();
+                record["lap_number"].as_i64().unwrap()
+            })
+            .collect();
+        lap_numbers.sort();
+        assert_eq!(lap_numbers, (1..=20).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_json_lines_stdout() {
+        let uploader = JsonLinesUploader::stdout();
+
+        assert!(uploader.upload_lap(&lap(1)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_uploaded_event_carries_server_lap_id() {
+        let server_id = Uuid::new_v4();
+        let bus = EventBus::new(16);
+        let mut uploaded_rx = bus.subscribe(RacingEventKind::LapUploaded);
+
+        let mut registry = HandlerRegistry::new();
+        registry.register(LapUploadHandler::new(Arc::new(ReassigningUploader {
+            lap_id: server_id,
+        })));
+        let handles = registry.run(bus.clone());
+
+        bus.publish(RacingEvent::LapComplete(lap(4))).unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(1), uploaded_rx.recv())
+            .await
+            .expect("lap should be uploaded")
+            .unwrap();
+
+        registry.shutdown();
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        let RacingEvent::LapUploaded(uploaded) = event else {
+            panic!("expected LapUploaded, got {event:?}");
+        };
+        assert_eq!(uploaded.lap_number, 4);
+        assert_eq!(uploaded.lap_id, server_id);
+    }
+
+    #[tokio::test]
+    async fn test_uploads_stay_within_shared_limit() {
+        let uploader = Arc::new(SlowUploader::default());
+        let limit = UploadLimit::new(2);
+        let bus = EventBus::new(16);
+
+        // Two handlers sharing one limit, e.g. for separate destinations
+        let mut registry = HandlerRegistry::new();
+        for _ in 0..2 {
+            registry.register(LapUploadHandler::new(uploader.clone()).with_limit(limit.clone()));
+        }
+        let handles = registry.run(bus.clone());
+
+        for lap_number in 1..=10 {
+            bus.publish(RacingEvent::LapComplete(lap(lap_number)))
+                .unwrap();
+        }
+        bus.shutdown_and_drain().await;
+        registry.shutdown();
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        // Each handler uploads every lap, and waits for them on shutdown
+        assert_eq!(uploader.received.load(Ordering::SeqCst), 20);
+        assert_eq!(uploader.max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    /// Log output, shared with the subscriber writing it.
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_logs_carry_the_lap_id() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        // The test runtime is single-threaded, so the handler's task logs here
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let bus = EventBus::new(16);
+        let mut uploaded_rx = bus.subscribe(RacingEventKind::LapUploaded);
+        let mut registry = HandlerRegistry::new();
+        registry.register(LapUploadHandler::new(Arc::new(CountingUploader::default())));
+        let handles = registry.run(bus.clone());
+
+        let lap = lap(7);
+        bus.publish(RacingEvent::LapComplete(lap.clone())).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), uploaded_rx.recv())
+            .await
+            .expect("lap should be uploaded")
+            .unwrap();
+
+        registry.shutdown();
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let uploaded = logs
+            .lines()
+            .find(|line| line.contains("Uploaded lap 7"))
+            .expect("upload should be logged");
+        assert!(
+            uploaded.contains(&format!("lap{{lap_id={} lap_number=7}}", lap.lap_id)),
+            "{uploaded}"
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{debug, warn};
+
+use crate::events::{RacingEvent, RacingEventKind};
+use crate::ws::{WsError, WsSink};
+use eventbus::{EventHandler, HandlerContext};
+
+/// Streams every telemetry frame to the server over a [`WsSink`].
+///
+/// Frames that can't be sent are dropped; the sink reconnects on its own.
+pub struct WsHandler {
+    sink: Arc<WsSink>,
+}
+
+impl WsHandler {
+    pub fn new(sink: Arc<WsSink>) -> Self {
+        Self { sink }
+    }
+}
+
+#[async_trait]
+impl EventHandler<RacingEvent> for WsHandler {
+    fn handles(&self) -> RacingEventKind {
+        RacingEventKind::TelemetryFrameCollected
+    }
+
+    fn name(&self) -> &'static str {
+        "WsHandler"
+    }
+
+    async fn handle(&self, event: RacingEvent, _ctx: &HandlerContext<RacingEvent>) {
+        let RacingEvent::TelemetryFrameCollected(frame) = event else {
+            return;
+        };
+
+        match self.sink.send_frame(&frame).await {
+            Ok(()) => {}
+            // Logged once by the sink when the connection dropped
+            Err(WsError::Disconnected) => debug!("Dropped frame while disconnected"),
+            Err(error) => warn!("Could not stream frame: {}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::RacingEventKind;
+    use crate::test_utils::{MockWsServer, collect_events, frame};
+
+    #[tokio::test]
+    async fn test_streams_bus_frames_to_server() {
+        let mut server = MockWsServer::start().await;
+        let sink = Arc::new(WsSink::connect(&server.url()).await.unwrap());
+        let frames = vec![frame(1, 0.25), frame(1, 0.5), frame(1, 0.75)];
+
+        collect_events(WsHandler::new(sink), frames, RacingEventKind::LapComplete).await;
+
+        let received: Vec<f32> = server
+            .frames(3)
+            .await
+            .iter()
+            .map(|frame| frame.lap_distance_pct)
+            .collect();
+        assert_eq!(received, vec![0.25, 0.5, 0.75]);
+    }
+}
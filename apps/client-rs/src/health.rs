@@ -0,0 +1,224 @@
+//! Server health monitoring.
+//!
+//! [`HealthMonitor`] polls the server's [`HEALTH_PATH`] endpoint in the
+//! background and publishes [`RacingEvent::ServerStatusChanged`] whenever the
+//! server starts or stops answering, so handlers can react to an outage
+//! during a long session.
+
+use std::time::Duration;
+
+use eventbus::EventBus;
+use thiserror::Error;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+use url::Url;
+
+use crate::config::endpoint_url;
+use crate::events::{RacingEvent, ServerStatusPayload};
+
+/// Server endpoint answering health checks.
+pub const HEALTH_PATH: &str = "/api/v1/health";
+
+/// Longest a single health check may take before the server counts as down.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Why health checks can't be sent to a server.
+#[derive(Debug, Error)]
+pub enum HealthError {
+    #[error("server URL {url:?} is invalid: {source}")]
+    InvalidUrl {
+        url: String,
+        #[source]
+        source: url::ParseError,
+    },
+
+    #[error("health checks need an http or https server URL, got {url:?}")]
+    UnsupportedScheme { url: String },
+
+    #[error("failed to create the HTTP client: {0}")]
+    Client(#[source] reqwest::Error),
+}
+
+/// Polls the server's health endpoint, announcing changes in its status.
+pub struct HealthMonitor {
+    client: reqwest::Client,
+    /// The health endpoint
+    url: Url,
+    interval: Duration,
+}
+
+impl HealthMonitor {
+    /// Check the server at `server_url` every `interval`.
+    pub fn new(server_url: &str, interval: Duration) -> Result<Self, HealthError> {
+        let url = Url::parse(server_url).map_err(|source| HealthError::InvalidUrl {
+            url: server_url.to_string(),
+            source,
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(HealthError::UnsupportedScheme {
+                url: server_url.to_string(),
+            });
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(CHECK_TIMEOUT)
+            .build()
+            .map_err(HealthError::Client)?;
+        Ok(Self {
+            client,
+            url: endpoint_url(&url, HEALTH_PATH),
+            interval,
+        })
+    }
+
+    /// Whether the server answers the health check with a 2xx status, after
+    /// following any redirects.
+    pub async fn check(&self) -> bool {
+        match self.client.get(self.url.clone()).send().await {
+            Ok(response) => response.status().is_success(),
+            Err(_) => false,
+        }
+    }
+
+    /// Check the server every interval until `stop` is cancelled.
+    ///
+    /// The first check always publishes the server's status; after that a
+    /// [`RacingEvent::ServerStatusChanged`] is only published when it flips.
+    pub async fn run(self, bus: EventBus<RacingEvent>, stop: CancellationToken) {
+        let mut ticks = tokio::time::interval(self.interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut healthy = None;
+
+        loop {
+            tokio::select! {
+                _ = stop.cancelled() => return,
+                _ = ticks.tick() => {}
+            }
+
+            let now = self.check().await;
+            if healthy == Some(now) {
+                continue;
+            }
+            if now {
+                info!("Server at {} is healthy", self.url);
+            } else {
+                warn!("Server at {} failed its health check", self.url);
+            }
+            healthy = Some(now);
+            let _ = bus.publish(RacingEvent::ServerStatusChanged(ServerStatusPayload {
+                healthy: now,
+            }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tokio::net::TcpListener;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::events::RacingEventKind;
+
+    /// Answer health checks at `endpoint` with `status`.
+    async fn mount_health(server: &MockServer, endpoint: &str, status: u16) {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(status))
+            .mount(server)
+            .await;
+    }
+
+    #[test]
+    fn test_only_http_urls_are_checked() {
+        assert!(HealthMonitor::new("http://localhost:8000", Duration::from_secs(1)).is_ok());
+        assert!(HealthMonitor::new("https://coach.example", Duration::from_secs(1)).is_ok());
+        assert!(matches!(
+            HealthMonitor::new("ftp://coach.example", Duration::from_secs(1)),
+            Err(HealthError::UnsupportedScheme { .. })
+        ));
+        assert!(matches!(
+            HealthMonitor::new("not a url", Duration::from_secs(1)),
+            Err(HealthError::InvalidUrl { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_status_changes_are_published() {
+        let server = MockServer::start().await;
+        mount_health(&server, HEALTH_PATH, 200).await;
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe(RacingEventKind::ServerStatusChanged);
+        let stop = CancellationToken::new();
+
+        let monitor = HealthMonitor::new(&server.uri(), Duration::from_millis(20)).unwrap();
+        let task = tokio::spawn(monitor.run(bus.clone(), stop.clone()));
+        let mut next_status = async || {
+            let event = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+                .await
+                .expect("status should be published")
+                .unwrap();
+            let RacingEvent::ServerStatusChanged(payload) = event else {
+                panic!("expected ServerStatusChanged, got {event:?}");
+            };
+            payload.healthy
+        };
+
+        assert!(next_status().await);
+        server.reset().await;
+        mount_health(&server, HEALTH_PATH, 500).await;
+        assert!(!next_status().await);
+        server.reset().await;
+        mount_health(&server, HEALTH_PATH, 200).await;
+        assert!(next_status().await);
+
+        stop.cancel();
+        task.await.unwrap();
+        // Steady checks in between published nothing
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_keeps_server_path_prefix() {
+        // A reverse proxy that only forwards requests under /coach
+        let server = MockServer::start().await;
+        mount_health(&server, "/coach/api/v1/health", 200).await;
+
+        let check = async |server_url: String| {
+            let monitor = HealthMonitor::new(&server_url, Duration::from_secs(1)).unwrap();
+            monitor.check().await
+        };
+
+        assert!(check(format!("{}/coach", server.uri())).await);
+        assert!(check(format!("{}/coach/", server.uri())).await);
+        assert!(!check(server.uri()).await);
+    }
+
+    #[tokio::test]
+    async fn test_check_follows_redirects() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(HEALTH_PATH))
+            .respond_with(ResponseTemplate::new(307).insert_header("Location", "/healthz"))
+            .mount(&server)
+            .await;
+        mount_health(&server, "/healthz", 200).await;
+
+        let monitor = HealthMonitor::new(&server.uri(), Duration::from_secs(1)).unwrap();
+        assert!(monitor.check().await);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_server_is_unhealthy() {
+        // Bind and drop a listener for a port nothing listens on
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let monitor = HealthMonitor::new(&url, Duration::from_secs(1)).unwrap();
+        assert!(!monitor.check().await);
+    }
+}
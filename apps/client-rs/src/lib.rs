@@ -1,13 +1,25 @@
+pub mod analyze;
+pub mod app;
 pub mod events;
+pub mod ghost;
 pub mod handlers;
+pub mod health;
 pub mod pitwall_ext;
+pub mod session;
+pub mod source;
 pub mod telem;
+pub mod utils;
+#[cfg(feature = "ws")]
+pub mod ws;
 
 mod config;
 mod pos_service;
+#[cfg(test)]
+mod test_utils;
 
 use crate::pos_service::PositionState;
-pub use config::Config;
+pub use app::RacingCoachApp;
+pub use config::{Cli, Command, Config, ConfigError, LapOutput, TelemetryMode, UnitSystem};
 use eventbus::{EventBus, HandlerRegistry};
 use handlers::{LapHandler, LogHandler};
 pub use pitwall_ext::AcceleratedReplayConnection;
@@ -15,6 +27,7 @@ use pos_service::PositionService;
 use telem::read_telemetry_eventbus;
 use tokio::sync::watch;
 use tokio::time::sleep;
+use tracing::info;
 
 pub async fn run_events() {
     let bus = EventBus::new(10000);
@@ -33,8 +46,7 @@ pub async fn run_events() {
     tokio::spawn(async move {
         let state = pos_service.wait_until_position(0.8).await;
 
-        println!("[POS_SVC_USER] At 80% Lap Percentage!");
-        println!("[POS_SVC_USER] State: {state}");
+        info!("At 80% lap percentage: {state}");
     });
 
     // Run telemetry collection (publisher)
@@ -44,7 +56,7 @@ pub async fn run_events() {
 
     sleep(std::time::Duration::from_secs(15)).await;
 
-    println!("Continuing in run_events()...");
+    info!("Stopping event handlers");
 
     // Signal shutdown
     registry.shutdown();
@@ -54,8 +66,3 @@ pub async fn run_events() {
         let _ = handle.await;
     }
 }
-
-/// Main entry point for the library logic.
-pub fn run(config: &Config) {
-    println!("Server: {}", config.server_url);
-}
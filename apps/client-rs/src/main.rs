@@ -1,5 +1,11 @@
-use client_rs::run_events;
-use tracing::info;
+use std::path::Path;
+
+use clap::Parser;
+use client_rs::analyze::{analyze_ibt, format_summary};
+use client_rs::app::step_on_input;
+use client_rs::ghost::write_ghosts;
+use client_rs::{Cli, Command, Config, RacingCoachApp};
+use tracing::{error, info};
 use tracing_subscriber::{EnvFilter, fmt};
 
 #[tokio::main]
@@ -17,15 +23,85 @@ async fn main() {
 
     info!("Racing Coach Client v{}", env!("CARGO_PKG_VERSION"));
 
-    // let config = Config::new("http://localhost:8000");
-    // client_rs::run(&config);
+    let mut cli = Cli::parse();
+    if let Some(Command::Analyze {
+        file,
+        ghost_dir,
+        ghost_points,
+    }) = cli.command.take()
+    {
+        analyze(&file, ghost_dir.as_deref(), ghost_points, cli).await;
+        return;
+    }
+
+    let config = match Config::load_from(cli) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("{e}");
+            std::process::exit(1);
+        }
+    };
+
+    let app = match RacingCoachApp::from_config(config) {
+        Ok(app) => app,
+        Err(e) => {
+            error!("Failed to open lap output: {e}");
+            std::process::exit(1);
+        }
+    };
 
-    println!("Hello World!");
+    // Stop cleanly on Ctrl+C so queued laps are still uploaded
+    let handle = app.handle();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            info!("Stopping...");
+            handle.stop().await;
+        }
+    });
+
+    if app.config().start_paused {
+        info!("Paused: press Enter to step one frame, or type a frame count, p or r");
+        let input = tokio::io::BufReader::new(tokio::io::stdin());
+        tokio::spawn(step_on_input(app.handle(), input));
+    }
+
+    match app.run().await {
+        Ok(summary) => info!("Done: {}", summary),
+        Err(e) => {
+            error!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
 
-    run_events().await;
+/// Print a summary of the laps in the IBT file at `path`, writing a ghost
+/// of each with `ghost_points` points to `ghost_dir` if given.
+async fn analyze(path: &Path, ghost_dir: Option<&Path>, ghost_points: usize, cli: Cli) {
+    // Only the display settings apply; the replay itself isn't configurable
+    let units = match Config::from_cli(cli) {
+        Ok(config) => config.units,
+        Err(e) => {
+            error!("{e}");
+            std::process::exit(1);
+        }
+    };
 
-    // read_telemetry().await;
+    let laps = match analyze_ibt(path).await {
+        Ok(laps) => laps,
+        Err(e) => {
+            error!("{e}");
+            std::process::exit(1);
+        }
+    };
+    print!("{}", format_summary(&laps, units));
 
-    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-    println!("Main Done.");
+    if let Some(dir) = ghost_dir {
+        match write_ghosts(&laps, dir, ghost_points) {
+            Ok(paths) => info!("Wrote {} ghosts to {}", paths.len(), dir.display()),
+            Err(e) => {
+                error!("Failed to write ghosts to {}: {e}", dir.display());
+                std::process::exit(1);
+            }
+        }
+    }
 }
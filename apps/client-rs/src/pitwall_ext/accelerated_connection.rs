@@ -9,7 +9,7 @@ use std::time::Duration;
 use futures::{Stream, StreamExt};
 use pitwall::driver::{Driver, SyncState};
 use pitwall::provider::Provider;
-use pitwall::stream::ThrottleExt;
+use pitwall::stream::{DedupExt, ThrottleExt};
 use pitwall::{FrameAdapter, FramePacket, Result, SessionInfo, UpdateRate, VariableSchema};
 use tokio::sync::broadcast;
 use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
@@ -58,6 +58,19 @@ impl AcceleratedReplayConnection {
     /// Waits for the first frame to be available before returning to ensure
     /// the connection is fully initialized and ready for subscriptions.
     pub async fn open<P: AsRef<Path>>(path: P, speed: f64) -> Result<Self> {
+        Self::open_with_loop(path, speed, false).await
+    }
+
+    /// Open an IBT file for accelerated replay, optionally looping playback.
+    ///
+    /// When `loop_playback` is true the replay restarts from the first frame
+    /// after reaching the end of the file instead of ending the stream, and
+    /// session info is re-published at the start of every loop.
+    pub async fn open_with_loop<P: AsRef<Path>>(
+        path: P,
+        speed: f64,
+        loop_playback: bool,
+    ) -> Result<Self> {
         let path = path.as_ref();
         info!(
             "Opening IBT file for accelerated replay: {}",
@@ -65,11 +78,22 @@ impl AcceleratedReplayConnection {
         );
 
         // Create provider and extract metadata
-        let provider = AcceleratedReplayProvider::new(path, speed)?;
+        let mut provider = AcceleratedReplayProvider::new(path, speed)?;
+        provider.set_loop_playback(loop_playback);
         let schema = provider.schema();
-        let source_hz = provider.tick_rate();
         let actual_speed = provider.speed();
 
+        Ok(Self::from_provider(provider, schema, actual_speed).await)
+    }
+
+    /// Run `provider` behind a connection, once its first frame is available.
+    async fn from_provider<P: Provider>(
+        provider: P,
+        schema: Arc<VariableSchema>,
+        speed: f64,
+    ) -> Self {
+        let source_hz = provider.tick_rate();
+
         // Spawn driver tasks
         let channels = Driver::spawn(provider);
 
@@ -88,18 +112,18 @@ impl AcceleratedReplayConnection {
 
         info!(
             "Accelerated replay connection opened ({}Hz, {}x speed)",
-            source_hz, actual_speed
+            source_hz, speed
         );
 
-        Ok(Self {
+        Self {
             frame_tx: channels.frame_tx,
             session_tx: channels.session_tx,
             sync_state: channels.sync_state,
             schema,
             source_hz,
-            speed: actual_speed,
+            speed,
             cancel: channels.cancel,
-        })
+        }
     }
 
     /// Subscribe to telemetry frames
@@ -116,6 +140,17 @@ impl AcceleratedReplayConnection {
         // Validate schema once at subscription time
         let validation = T::validate_schema(&self.schema).expect("Schema validation failed");
 
+        self.raw_frames(rate)
+            .map(move |packet| T::adapt(&packet, &validation))
+    }
+
+    /// Subscribe to undecoded frame packets
+    ///
+    /// For running an adapter of your own over the replay: validate it once
+    /// against [`AcceleratedReplayConnection::schema`], then adapt each packet.
+    /// Packets are deduplicated and throttled exactly as for
+    /// [`AcceleratedReplayConnection::subscribe`].
+    pub fn raw_frames(&self, rate: UpdateRate) -> impl Stream<Item = Arc<FramePacket>> + 'static {
         // Create base frame stream from broadcast channel
         let frames =
             BroadcastStream::new(self.frame_tx.subscribe()).filter_map(|result| async move {
@@ -127,25 +162,13 @@ impl AcceleratedReplayConnection {
                     }
                 }
             });
+        // Drop frames the sim republished without advancing (e.g. while paused)
+        let frames = frames.skip_duplicates();
 
-        // Apply rate control and adaptation
-        let effective_rate = rate.normalize(self.source_hz);
-
-        match effective_rate {
-            UpdateRate::Native => {
-                // Direct adaptation, no throttling
-                frames
-                    .map(move |packet| T::adapt(&packet, &validation))
-                    .boxed()
-            }
-            UpdateRate::Max(hz) => {
-                // Throttle then adapt
-                let interval = Duration::from_secs_f64(1.0 / hz as f64);
-                frames
-                    .throttle(interval)
-                    .map(move |packet| T::adapt(&packet, &validation))
-                    .boxed()
-            }
+        // Apply rate control
+        match rate.throttle_interval(self.source_hz) {
+            None => frames.boxed(),
+            Some(interval) => frames.throttle(interval).boxed(),
         }
     }
 
@@ -195,3 +218,142 @@ impl Drop for AcceleratedReplayConnection {
         self.cancel.cancel();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{RacingEvent, RacingEventKind};
+    use crate::source::{CollectorControl, collect_with_control};
+    use async_trait::async_trait;
+    use eventbus::EventBus;
+    use pitwall::adapters::{AdapterValidation, FieldExtraction};
+    use pitwall::{VariableInfo, VariableType};
+    use std::collections::{HashMap, VecDeque};
+    use tokio_util::sync::CancellationToken;
+
+    /// Provider that plays back scripted ticks every 10ms, then ends. Each
+    /// frame's `Speed` is ten times its tick.
+    struct TickProvider {
+        ticks: VecDeque<u32>,
+        schema: Arc<VariableSchema>,
+    }
+
+    impl TickProvider {
+        fn new(ticks: &[u32]) -> Self {
+            let speed = VariableInfo {
+                name: "Speed".to_string(),
+                data_type: VariableType::Float32,
+                offset: 0,
+                count: 1,
+                count_as_time: false,
+                units: "m/s".to_string(),
+                description: String::new(),
+            };
+            Self {
+                ticks: ticks.iter().copied().collect(),
+                schema: Arc::new(VariableSchema {
+                    variables: HashMap::from([("Speed".to_string(), speed)]),
+                    frame_size: 4,
+                    version: None,
+                }),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for TickProvider {
+        async fn next_frame(&mut self) -> Result<Option<FramePacket>> {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Ok(self.ticks.pop_front().map(|tick| {
+                let speed = tick as f32 * 10.0;
+                FramePacket::new(speed.to_le_bytes().to_vec(), tick, 0, self.schema.clone())
+            }))
+        }
+
+        async fn session_yaml(&mut self, _version: u32) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        fn tick_rate(&self) -> f64 {
+            100.0
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_collector_skips_repeated_ticks() {
+        let provider = TickProvider::new(&[1, 2, 2, 3, 3, 3, 4, 5, 5]);
+        let schema = provider.schema.clone();
+        // Returns once tick 1 has been read, so collection starts at tick 2
+        let connection = AcceleratedReplayConnection::from_provider(provider, schema, 1.0).await;
+
+        let bus = EventBus::new(64);
+        let mut frames = bus.subscribe(RacingEventKind::TelemetryFrameCollected);
+        let stop = CancellationToken::new();
+        let control = CollectorControl::new(stop.clone(), tokio::sync::watch::channel(false).1);
+
+        let (published, ()) = tokio::join!(
+            collect_with_control(&connection, UpdateRate::Native, &bus, &control),
+            async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                stop.cancel();
+            }
+        );
+
+        assert_eq!(published, 4);
+        let mut count = 0;
+        while let Ok(RacingEvent::TelemetryFrameCollected(_)) = frames.try_recv() {
+            count += 1;
+        }
+        assert_eq!(count, 4);
+    }
+
+    /// Hand-written adapter reading only `Speed`.
+    #[derive(Debug, PartialEq)]
+    struct SimpleFrame {
+        speed: f32,
+    }
+
+    impl FrameAdapter for SimpleFrame {
+        fn validate_schema(schema: &VariableSchema) -> Result<AdapterValidation> {
+            let extraction_plan = schema
+                .variables
+                .get("Speed")
+                .map(|var_info| FieldExtraction::Required {
+                    name: "Speed".to_string(),
+                    var_info: var_info.clone(),
+                })
+                .into_iter()
+                .collect();
+            Ok(AdapterValidation::new(extraction_plan))
+        }
+
+        fn adapt(packet: &FramePacket, validation: &AdapterValidation) -> Self {
+            Self {
+                speed: validation.fetch_or_default(packet, "Speed"),
+            }
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_raw_frames_adapt_like_subscribe() {
+        let provider = TickProvider::new(&[1, 2, 2, 3, 4, 5]);
+        let schema = provider.schema.clone();
+        let connection = AcceleratedReplayConnection::from_provider(provider, schema, 1.0).await;
+
+        // Subscribed together, both streams start at the same frame
+        let raw = connection.raw_frames(UpdateRate::Native);
+        let typed = connection.subscribe::<SimpleFrame>(UpdateRate::Native);
+
+        let validation = SimpleFrame::validate_schema(connection.schema()).unwrap();
+        let adapted: Vec<SimpleFrame> = raw
+            .take(4)
+            .map(|packet| SimpleFrame::adapt(&packet, &validation))
+            .collect()
+            .await;
+        let subscribed: Vec<SimpleFrame> = typed.take(4).collect().await;
+
+        let speeds: Vec<f32> = adapted.iter().map(|frame| frame.speed).collect();
+        assert_eq!(speeds, vec![20.0, 30.0, 40.0, 50.0]);
+        assert_eq!(adapted, subscribed);
+    }
+}
@@ -27,6 +27,12 @@ pub struct AcceleratedReplayProvider {
 
     /// Configured playback speed
     speed: f64,
+
+    /// Rewind to the first frame instead of ending at the end of the file
+    loop_playback: bool,
+
+    /// Number of times playback has wrapped back to the start
+    loop_count: u32,
 }
 
 impl AcceleratedReplayProvider {
@@ -65,9 +71,19 @@ impl AcceleratedReplayProvider {
             schema,
             tick_rate,
             speed,
+            loop_playback: false,
+            loop_count: 0,
         })
     }
 
+    /// Enable or disable looping playback
+    ///
+    /// When enabled, the reader is rewound to the first frame once the end of
+    /// the file is reached, so the frame stream never terminates.
+    pub fn set_loop_playback(&mut self, loop_playback: bool) {
+        self.loop_playback = loop_playback;
+    }
+
     /// Get the variable schema
     pub fn schema(&self) -> Arc<VariableSchema> {
         Arc::clone(&self.schema)
@@ -85,8 +101,17 @@ impl Provider for AcceleratedReplayProvider {
         // Check if we've reached the end
         let total_frames = self.reader.total_frames();
         if self.reader.current_frame() >= total_frames {
-            debug!("Reached end of replay");
-            return Ok(None);
+            if !self.loop_playback || total_frames == 0 {
+                debug!("Reached end of replay");
+                return Ok(None);
+            }
+
+            self.reader.seek_to_frame(0)?;
+            self.loop_count += 1;
+            info!(
+                "Replay reached end of file, looping (loop {})",
+                self.loop_count
+            );
         }
 
         // Wait for next frame timing (pacing)
@@ -109,6 +134,10 @@ impl Provider for AcceleratedReplayProvider {
             session_version
         );
 
+        // Offset the session version by the loop count so the driver sees a
+        // version change and re-publishes session info on every loop.
+        let session_version = session_version.wrapping_add(self.loop_count);
+
         let packet = FramePacket::new(frame_data, tick, session_version, Arc::clone(&self.schema));
 
         Ok(Some(packet))
@@ -124,3 +153,54 @@ impl Provider for AcceleratedReplayProvider {
         self.tick_rate
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_IBT: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../sample_data/ligierjsp320_bathurst 2025-11-17 18-15-16.ibt"
+    );
+
+    #[tokio::test(start_paused = true)]
+    async fn test_loop_playback_continues_past_end_of_file() {
+        let mut provider = AcceleratedReplayProvider::new(SAMPLE_IBT, 100.0).unwrap();
+        provider.set_loop_playback(true);
+
+        let total_frames = provider.reader.total_frames();
+        assert!(total_frames > 0);
+
+        let mut first_version = None;
+        let mut last_version = 0;
+        for _ in 0..total_frames + 10 {
+            let packet = provider
+                .next_frame()
+                .await
+                .unwrap()
+                .expect("looping stream should not terminate");
+            first_version.get_or_insert(packet.session_version);
+            last_version = packet.session_version;
+        }
+
+        assert_eq!(provider.loop_count, 1);
+        assert_ne!(
+            Some(last_version),
+            first_version,
+            "session version should change per loop"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_playback_ends_without_loop() {
+        let mut provider = AcceleratedReplayProvider::new(SAMPLE_IBT, 100.0).unwrap();
+        let total_frames = provider.reader.total_frames();
+
+        for _ in 0..total_frames {
+            assert!(provider.next_frame().await.unwrap().is_some());
+        }
+
+        assert!(provider.next_frame().await.unwrap().is_none());
+        assert_eq!(provider.loop_count, 0);
+    }
+}
@@ -0,0 +1,386 @@
+//! Session change detection and shared session state.
+//!
+//! Watches session info updates and publishes `SessionStart`/`SessionEnd`
+//! events whenever the track, car, or session number changes. The current
+//! session, its most recent laps and its best lap are kept in a
+//! [`SessionState`] that handlers read through their context.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, RwLock};
+
+use eventbus::EventBus;
+use futures::{Stream, StreamExt};
+use pitwall::SessionInfo;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::events::{
+    LapCompletePayload, LapType, RacingEvent, SessionEndPayload, SessionStartPayload,
+};
+
+/// The parts of `SessionInfo` that identify a session.
+///
+/// Session info is re-published for many reasons (weather, results, etc.),
+/// so only a change in this key starts a new session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionKey {
+    pub track_id: Option<i32>,
+    pub car_id: Option<i32>,
+    pub session_num: i32,
+}
+
+impl SessionKey {
+    pub fn from_session_info(session: &SessionInfo) -> Self {
+        let car_id = session.driver_info.as_ref().and_then(|info| {
+            let idx = info.driver_car_idx?;
+            info.drivers
+                .as_ref()?
+                .iter()
+                .find(|driver| driver.car_idx == idx)?
+                .car_id
+        });
+
+        Self {
+            track_id: session.weekend_info.track_id,
+            car_id,
+            session_num: session.session_info.current_session_num,
+        }
+    }
+}
+
+/// Tracks the current session and produces start/end events on changes.
+#[derive(Default)]
+pub struct SessionTracker {
+    current: Option<(Uuid, SessionKey)>,
+}
+
+impl SessionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of the current session, if one has started.
+    pub fn session_id(&self) -> Option<Uuid> {
+        self.current.as_ref().map(|(id, _)| *id)
+    }
+
+    /// Process a session info update, returning the events it causes.
+    pub fn update(&mut self, session: Arc<SessionInfo>) -> Vec<RacingEvent> {
+        let key = SessionKey::from_session_info(&session);
+
+        if matches!(&self.current, Some((_, current)) if *current == key) {
+            return Vec::new();
+        }
+
+        let mut events = Vec::with_capacity(2);
+        events.extend(self.finish());
+
+        let session_id = Uuid::new_v4();
+        info!("Session {} started: {:?}", session_id, key);
+        self.current = Some((session_id, key));
+        events.push(RacingEvent::SessionStart(SessionStartPayload {
+            session_id,
+            session,
+        }));
+
+        events
+    }
+
+    /// End the current session, if any: `SessionFramesEnded` followed by
+    /// `SessionEnd`.
+    pub fn finish(&mut self) -> Vec<RacingEvent> {
+        let Some((session_id, _)) = self.current.take() else {
+            return Vec::new();
+        };
+        info!("Session {} ended", session_id);
+        let ended = SessionEndPayload { session_id };
+        vec![
+            RacingEvent::SessionFramesEnded(ended.clone()),
+            RacingEvent::SessionEnd(ended),
+        ]
+    }
+}
+
+/// Publish session start/end events for a stream of session info updates.
+///
+/// Runs until the stream ends, then ends the last session.
+pub async fn track_sessions<S>(sessions: S, bus: EventBus<RacingEvent>)
+where
+    S: Stream<Item = Arc<SessionInfo>>,
+{
+    let mut tracker = SessionTracker::new();
+    let mut sessions = std::pin::pin!(sessions);
+
+    while let Some(session) = sessions.next().await {
+        for event in tracker.update(session) {
+            let _ = bus.publish(event);
+        }
+    }
+
+    for event in tracker.finish() {
+        let _ = bus.publish(event);
+    }
+}
+
+/// Summary of a completed lap kept in [`SessionState`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LapRecord {
+    pub lap_number: i32,
+    pub lap_time_ms: Option<u64>,
+    pub lap_type: LapType,
+    pub is_valid: bool,
+}
+
+impl LapRecord {
+    /// Whether the lap counts towards the session best: valid and timed.
+    fn counts_for_best(&self) -> bool {
+        self.is_valid && self.lap_time_ms.is_some()
+    }
+}
+
+impl From<&LapCompletePayload> for LapRecord {
+    fn from(lap: &LapCompletePayload) -> Self {
+        Self {
+            lap_number: lap.lap_number,
+            lap_time_ms: lap.lap_time_ms,
+            lap_type: lap.lap_type,
+            is_valid: lap.is_valid,
+        }
+    }
+}
+
+/// The current session and its most recent laps.
+///
+/// Only the last `lap_capacity` laps are kept, so memory stays bounded over
+/// long practice sessions, but the session best is remembered after its lap
+/// is dropped.
+///
+/// Registered as handler state, so any handler can read it with
+/// `ctx.state::<SessionState>()`. Handlers receive events concurrently, so a
+/// handler processing a lap can't rely on that lap already being recorded;
+/// the lookups take the lap number to look before.
+#[derive(Debug)]
+pub struct SessionState {
+    session: RwLock<Option<Arc<SessionInfo>>>,
+    laps: RwLock<VecDeque<LapRecord>>,
+    lap_capacity: usize,
+    /// Fastest valid lap of the session, including dropped laps
+    session_best: RwLock<Option<LapRecord>>,
+}
+
+impl SessionState {
+    /// Create an empty state keeping the last `lap_capacity` laps.
+    pub fn new(lap_capacity: usize) -> Arc<Self> {
+        Arc::new(Self {
+            session: RwLock::new(None),
+            laps: RwLock::new(VecDeque::with_capacity(lap_capacity)),
+            lap_capacity,
+            session_best: RwLock::new(None),
+        })
+    }
+
+    /// Info for the current session, if one has started.
+    pub fn session(&self) -> Option<Arc<SessionInfo>> {
+        self.session.read().unwrap().clone()
+    }
+
+    /// Start a new session, forgetting the laps of the previous one.
+    pub fn start_session(&self, session: Arc<SessionInfo>) {
+        *self.session.write().unwrap() = Some(session);
+        self.laps.write().unwrap().clear();
+        *self.session_best.write().unwrap() = None;
+    }
+
+    /// End the current session. Its laps stay readable until the next starts.
+    pub fn end_session(&self) {
+        *self.session.write().unwrap() = None;
+    }
+
+    /// Record a completed lap, dropping the oldest once at capacity.
+    pub fn record_lap(&self, lap: LapRecord) {
+        if lap.counts_for_best() {
+            let mut best = self.session_best.write().unwrap();
+            if best
+                .as_ref()
+                .is_none_or(|best| lap.lap_time_ms < best.lap_time_ms)
+            {
+                *best = Some(lap.clone());
+            }
+        }
+
+        if self.lap_capacity == 0 {
+            return;
+        }
+        let mut laps = self.laps.write().unwrap();
+        if laps.len() == self.lap_capacity {
+            laps.pop_front();
+        }
+        laps.push_back(lap);
+    }
+
+    /// Recorded laps, oldest first.
+    pub fn recent_laps(&self) -> Vec<LapRecord> {
+        self.laps.read().unwrap().iter().cloned().collect()
+    }
+
+    /// The latest recorded lap numbered before `lap_number`.
+    pub fn previous_lap(&self, lap_number: i32) -> Option<LapRecord> {
+        self.laps
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|lap| lap.lap_number < lap_number)
+            .cloned()
+    }
+
+    /// The fastest valid recorded lap numbered before `lap_number`.
+    pub fn best_lap_before(&self, lap_number: i32) -> Option<LapRecord> {
+        self.laps
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|lap| lap.counts_for_best() && lap.lap_number < lap_number)
+            .min_by_key(|lap| lap.lap_time_ms)
+            .cloned()
+    }
+
+    /// The fastest valid lap among the recorded laps.
+    pub fn best_recent_lap(&self) -> Option<LapRecord> {
+        self.best_lap_before(i32::MAX)
+    }
+
+    /// The fastest valid lap of the session, even if it has since been
+    /// dropped from the recorded laps.
+    pub fn session_best_lap(&self) -> Option<LapRecord> {
+        self.session_best.read().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::RacingEventKind;
+    use pitwall::schema::session::{Driver, DriverInfoData};
+
+    fn session(track_id: i32, car_id: i32, session_num: i32) -> Arc<SessionInfo> {
+        let mut info = SessionInfo::default();
+        info.weekend_info.track_id = Some(track_id);
+        info.session_info.current_session_num = session_num;
+        info.driver_info = Some(DriverInfoData {
+            driver_car_idx: Some(0),
+            drivers: Some(vec![Driver {
+                car_idx: 0,
+                car_id: Some(car_id),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        Arc::new(info)
+    }
+
+    #[test]
+    fn test_session_key_uses_player_car() {
+        let key = SessionKey::from_session_info(&session(10, 20, 1));
+        assert_eq!(
+            key,
+            SessionKey {
+                track_id: Some(10),
+                car_id: Some(20),
+                session_num: 1,
+            }
+        );
+    }
+
+    fn lap(lap_number: i32, lap_time_ms: u64, is_valid: bool) -> LapRecord {
+        LapRecord {
+            lap_number,
+            lap_time_ms: Some(lap_time_ms),
+            lap_type: LapType::Timed,
+            is_valid,
+        }
+    }
+
+    #[test]
+    fn test_session_state_keeps_recent_laps() {
+        let state = SessionState::new(2);
+        state.start_session(session(1, 1, 0));
+
+        state.record_lap(lap(1, 91_000, true));
+        state.record_lap(lap(2, 89_000, false));
+        state.record_lap(lap(3, 90_000, true));
+
+        assert_eq!(
+            state.recent_laps(),
+            [lap(2, 89_000, false), lap(3, 90_000, true)]
+        );
+        assert_eq!(state.previous_lap(3), Some(lap(2, 89_000, false)));
+        assert_eq!(state.previous_lap(2), None);
+        // Invalid laps don't count towards the session best
+        assert_eq!(state.best_lap_before(4), Some(lap(3, 90_000, true)));
+
+        state.start_session(session(2, 1, 0));
+        assert!(state.recent_laps().is_empty());
+        assert_eq!(state.session().unwrap().weekend_info.track_id, Some(2));
+    }
+
+    #[test]
+    fn test_session_best_outlives_dropped_laps() {
+        let state = SessionState::new(3);
+        state.start_session(session(1, 1, 0));
+
+        state.record_lap(lap(1, 88_000, true));
+        state.record_lap(lap(2, 87_000, false));
+        for (lap_number, lap_time_ms) in [(3, 91_000), (4, 90_000), (5, 92_000)] {
+            state.record_lap(lap(lap_number, lap_time_ms, true));
+        }
+
+        let recorded: Vec<i32> = state.recent_laps().iter().map(|l| l.lap_number).collect();
+        assert_eq!(recorded, [3, 4, 5]);
+        assert_eq!(state.best_recent_lap(), Some(lap(4, 90_000, true)));
+        // Lap 1 was dropped but is still the session best; lap 2 was invalid
+        assert_eq!(state.session_best_lap(), Some(lap(1, 88_000, true)));
+
+        state.start_session(session(1, 1, 1));
+        assert_eq!(state.session_best_lap(), None);
+    }
+
+    #[test]
+    fn test_repeated_session_info_is_ignored() {
+        let mut tracker = SessionTracker::new();
+
+        assert_eq!(tracker.update(session(1, 1, 0)).len(), 1);
+        assert!(tracker.update(session(1, 1, 0)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_track_change_ends_and_starts_session() {
+        let bus = EventBus::new(16);
+        let mut start_rx = bus.subscribe(RacingEventKind::SessionStart);
+        let mut end_rx = bus.subscribe(RacingEventKind::SessionEnd);
+
+        let sessions =
+            futures::stream::iter(vec![session(1, 1, 0), session(1, 1, 0), session(2, 1, 0)]);
+        track_sessions(sessions, bus.clone()).await;
+
+        let RacingEvent::SessionStart(first) = start_rx.recv().await.unwrap() else {
+            panic!("expected SessionStart");
+        };
+        let RacingEvent::SessionStart(second) = start_rx.recv().await.unwrap() else {
+            panic!("expected SessionStart");
+        };
+        assert!(start_rx.try_recv().is_err());
+        assert_eq!(first.session.weekend_info.track_id, Some(1));
+        assert_eq!(second.session.weekend_info.track_id, Some(2));
+        assert_ne!(first.session_id, second.session_id);
+
+        let RacingEvent::SessionEnd(first_end) = end_rx.recv().await.unwrap() else {
+            panic!("expected SessionEnd");
+        };
+        let RacingEvent::SessionEnd(second_end) = end_rx.recv().await.unwrap() else {
+            panic!("expected SessionEnd");
+        };
+        assert_eq!(first_end.session_id, first.session_id);
+        assert_eq!(second_end.session_id, second.session_id);
+    }
+}
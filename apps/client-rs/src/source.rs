@@ -0,0 +1,591 @@
+//! Telemetry sources and collection.
+//!
+//! A [`TelemetryProvider`] abstracts over live and replay connections so the
+//! collector can be driven by any source, including scripted ones in tests.
+//! The collector sanitizes frames (see [`TelemetryFrame::sanitize`]),
+//! publishes them and session changes to the bus and, when the frame stream
+//! ends (e.g. iRacing was closed), reconnects with exponential backoff. A
+//! [`CollectorControl`] lets the caller pause, step or stop collection from
+//! outside.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use eventbus::EventBus;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use pitwall::{SessionInfo, UpdateRate};
+use tokio::sync::{Semaphore, watch};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::events::RacingEvent;
+use crate::pitwall_ext::AcceleratedReplayConnection;
+use crate::session::SessionTracker;
+use crate::telem::TelemetryFrame;
+
+/// Stream of telemetry frames from a provider.
+pub type FrameStream = BoxStream<'static, TelemetryFrame>;
+
+/// Stream of session info updates from a provider.
+pub type SessionStream = BoxStream<'static, Arc<SessionInfo>>;
+
+/// An open connection to a telemetry source.
+///
+/// Streams end when the provider is dropped or the source goes away.
+pub trait TelemetryProvider: Send + Sync {
+    /// Subscribe to telemetry frames at the given rate.
+    fn subscribe(&self, rate: UpdateRate) -> FrameStream;
+
+    /// Most recent session info, if any has been parsed.
+    fn current_session(&self) -> Option<Arc<SessionInfo>>;
+
+    /// Subsequent session info updates.
+    fn session_updates(&self) -> SessionStream;
+}
+
+impl TelemetryProvider for AcceleratedReplayConnection {
+    fn subscribe(&self, rate: UpdateRate) -> FrameStream {
+        AcceleratedReplayConnection::subscribe(self, rate).boxed()
+    }
+
+    fn current_session(&self) -> Option<Arc<SessionInfo>> {
+        AcceleratedReplayConnection::current_session(self)
+    }
+
+    fn session_updates(&self) -> SessionStream {
+        AcceleratedReplayConnection::session_updates(self).boxed()
+    }
+}
+
+#[cfg(windows)]
+impl TelemetryProvider for pitwall::LiveConnection {
+    fn subscribe(&self, rate: UpdateRate) -> FrameStream {
+        pitwall::LiveConnection::subscribe(self, rate).boxed()
+    }
+
+    fn current_session(&self) -> Option<Arc<SessionInfo>> {
+        pitwall::LiveConnection::current_session(self)
+    }
+
+    fn session_updates(&self) -> SessionStream {
+        pitwall::LiveConnection::session_updates(self).boxed()
+    }
+}
+
+/// Where telemetry comes from.
+#[derive(Clone, Debug)]
+pub enum SourceMode {
+    /// A running iRacing instance (Windows only)
+    Live,
+    /// An IBT file played back at `speed` times real time
+    Replay {
+        path: PathBuf,
+        speed: f64,
+        loop_playback: bool,
+    },
+}
+
+/// Factory for telemetry providers.
+pub struct TelemetrySource;
+
+impl TelemetrySource {
+    /// Open a provider for `mode`.
+    pub async fn create(mode: &SourceMode) -> pitwall::Result<Box<dyn TelemetryProvider>> {
+        match mode {
+            SourceMode::Live => Self::create_live().await,
+            SourceMode::Replay {
+                path,
+                speed,
+                loop_playback,
+            } => {
+                let connection =
+                    AcceleratedReplayConnection::open_with_loop(path, *speed, *loop_playback)
+                        .await?;
+                Ok(Box::new(connection))
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    async fn create_live() -> pitwall::Result<Box<dyn TelemetryProvider>> {
+        Ok(Box::new(pitwall::LiveConnection::connect().await?))
+    }
+
+    #[cfg(not(windows))]
+    async fn create_live() -> pitwall::Result<Box<dyn TelemetryProvider>> {
+        Err(pitwall::TelemetryError::unsupported_platform(
+            "Live telemetry",
+            "Windows",
+        ))
+    }
+}
+
+/// Opens connections to a telemetry source.
+#[async_trait]
+pub trait Connector: Send {
+    async fn connect(&mut self) -> pitwall::Result<Box<dyn TelemetryProvider>>;
+}
+
+#[async_trait]
+impl Connector for SourceMode {
+    async fn connect(&mut self) -> pitwall::Result<Box<dyn TelemetryProvider>> {
+        TelemetrySource::create(self).await
+    }
+}
+
+/// How to retry when a connection attempt fails or a stream ends.
+#[derive(Clone, Debug)]
+pub struct ReconnectPolicy {
+    /// Consecutive failed connection attempts before giving up
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound for the doubling retry delay
+    pub max_backoff: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay before retry number `attempt` (1-based).
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Pause, step and stop signals for a collector.
+///
+/// The default control is never paused or stopped.
+#[derive(Clone, Debug)]
+pub struct CollectorControl {
+    stop: CancellationToken,
+    paused: watch::Receiver<bool>,
+    steps: Arc<Semaphore>,
+}
+
+impl CollectorControl {
+    /// Stop collecting once `stop` is cancelled, and publish nothing while
+    /// `paused` is true.
+    pub fn new(stop: CancellationToken, paused: watch::Receiver<bool>) -> Self {
+        Self {
+            stop,
+            paused,
+            steps: Arc::new(Semaphore::new(0)),
+        }
+    }
+
+    /// While paused, publish one frame for each permit added to `steps`.
+    pub fn with_steps(mut self, steps: Arc<Semaphore>) -> Self {
+        self.steps = steps;
+        self
+    }
+}
+
+impl Default for CollectorControl {
+    fn default() -> Self {
+        Self::new(CancellationToken::new(), watch::channel(false).1)
+    }
+}
+
+/// Publish telemetry from `provider` to the bus until its frame stream ends.
+///
+/// Session changes are published as `SessionStart`/`SessionEnd`, and the last
+/// session is ended once the stream finishes. Returns the number of frames
+/// published.
+pub async fn collect(
+    provider: &dyn TelemetryProvider,
+    rate: UpdateRate,
+    bus: &EventBus<RacingEvent>,
+) -> u64 {
+    collect_with_control(provider, rate, bus, &CollectorControl::default()).await
+}
+
+/// Like [`collect`], but also returns early once `control` is stopped and
+/// holds off reading frames while it is paused.
+pub async fn collect_with_control(
+    provider: &dyn TelemetryProvider,
+    rate: UpdateRate,
+    bus: &EventBus<RacingEvent>,
+    control: &CollectorControl,
+) -> u64 {
+    let mut tracker = SessionTracker::new();
+    let published = forward(provider, rate, &mut tracker, bus, control).await;
+
+    for event in tracker.finish() {
+        let _ = bus.publish(event);
+    }
+
+    published
+}
+
+/// Publish telemetry from `connector` to the bus, reconnecting when the
+/// source goes away, until `control` is stopped.
+///
+/// Each connection gap ends the current session (`SessionEnd`) and the next
+/// connection starts a new one. Returns the number of frames published once
+/// stopped, or the last connection error once `policy.max_attempts`
+/// consecutive attempts have failed.
+pub async fn collect_with_reconnect<C: Connector>(
+    mut connector: C,
+    policy: ReconnectPolicy,
+    rate: UpdateRate,
+    bus: &EventBus<RacingEvent>,
+    control: &CollectorControl,
+) -> pitwall::Result<u64> {
+    let mut tracker = SessionTracker::new();
+    let mut published = 0;
+    let mut failures = 0;
+
+    while !control.stop.is_cancelled() {
+        let provider = match connector.connect().await {
+            Ok(provider) => {
+                failures = 0;
+                provider
+            }
+            Err(error) => {
+                failures += 1;
+                if failures >= policy.max_attempts {
+                    warn!(
+                        "Giving up after {} connection attempts: {}",
+                        failures, error
+                    );
+                    return Err(error);
+                }
+
+                let delay = policy.backoff(failures);
+                warn!(
+                    "Connection attempt {} failed: {}. Retrying in {:?}",
+                    failures, error, delay
+                );
+                tokio::select! {
+                    _ = control.stop.cancelled() => break,
+                    _ = tokio::time::sleep(delay) => continue,
+                }
+            }
+        };
+
+        info!("Telemetry source connected");
+        let frames = forward(provider.as_ref(), rate, &mut tracker, bus, control).await;
+        published += frames;
+
+        for event in tracker.finish() {
+            let _ = bus.publish(event);
+        }
+        if !control.stop.is_cancelled() {
+            info!(
+                "Telemetry stream ended after {} frames, reconnecting",
+                frames
+            );
+        }
+    }
+
+    Ok(published)
+}
+
+/// Forward frames and session changes until the frame stream ends or
+/// `control` is stopped.
+async fn forward(
+    provider: &dyn TelemetryProvider,
+    rate: UpdateRate,
+    tracker: &mut SessionTracker,
+    bus: &EventBus<RacingEvent>,
+    control: &CollectorControl,
+) -> u64 {
+    // Seed with the session parsed so far; duplicates are ignored by the tracker
+    let mut sessions = futures::stream::iter(provider.current_session())
+        .chain(provider.session_updates())
+        .fuse();
+    let mut frames = provider.subscribe(rate);
+    let mut published = 0;
+    let mut paused = control.paused.clone();
+    let mut previous: Option<Arc<TelemetryFrame>> = None;
+    let mut repaired: u64 = 0;
+    // Whether a step allows one frame through while paused
+    let mut stepping = false;
+
+    loop {
+        if *paused.borrow_and_update() && !stepping {
+            tokio::select! {
+                _ = control.stop.cancelled() => return published,
+                changed = paused.changed() => {
+                    if changed.is_err() {
+                        // Nobody left to resume us
+                        return published;
+                    }
+                }
+                Ok(permit) = control.steps.acquire() => {
+                    permit.forget();
+                    stepping = true;
+                }
+            }
+            continue;
+        }
+
+        tokio::select! {
+            biased;
+            _ = control.stop.cancelled() => return published,
+            Some(session) = sessions.next() => {
+                for event in tracker.update(session) {
+                    let _ = bus.publish(event);
+                }
+            }
+            frame = frames.next() => {
+                let Some(mut frame) = frame else {
+                    if repaired > 0 {
+                        warn!("Repaired {} of {} frames", repaired, published);
+                    }
+                    return published;
+                };
+
+                let warnings = frame.sanitize(previous.as_deref());
+                if !warnings.is_empty() {
+                    repaired += 1;
+                    let fixed: Vec<String> =
+                        warnings.iter().map(ToString::to_string).collect();
+                    let fixed = fixed.join(", ");
+                    // A broken channel usually stays broken; warn only once
+                    if repaired == 1 {
+                        warn!("Repaired frame at {:.3}s: {}", frame.session_time, fixed);
+                    } else {
+                        debug!("Repaired frame at {:.3}s: {}", frame.session_time, fixed);
+                    }
+                }
+
+                let frame = Arc::new(frame);
+                previous = Some(frame.clone());
+                let _ = bus.publish(RacingEvent::TelemetryFrameCollected(frame));
+                published += 1;
+                stepping = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::RacingEventKind;
+    use crate::test_utils::{FrameProvider, frame};
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Provider that yields a fixed number of scripted frames, then ends.
+    struct MockProvider {
+        frame_count: usize,
+        session: Option<Arc<SessionInfo>>,
+    }
+
+    impl MockProvider {
+        fn new(frame_count: usize) -> Self {
+            Self {
+                frame_count,
+                session: Some(Arc::new(SessionInfo::default())),
+            }
+        }
+    }
+
+    impl TelemetryProvider for MockProvider {
+        fn subscribe(&self, _rate: UpdateRate) -> FrameStream {
+            let frames: Vec<_> = (0..self.frame_count)
+                .map(|i| frame(1, i as f32 / self.frame_count as f32))
+                .collect();
+            futures::stream::iter(frames).boxed()
+        }
+
+        fn current_session(&self) -> Option<Arc<SessionInfo>> {
+            self.session.clone()
+        }
+
+        fn session_updates(&self) -> SessionStream {
+            futures::stream::empty().boxed()
+        }
+    }
+
+    /// Connector that replays a scripted sequence of connection results.
+    struct MockConnector {
+        /// Frame count per successful connection; `None` fails the attempt
+        script: VecDeque<Option<usize>>,
+        attempts: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl Connector for MockConnector {
+        async fn connect(&mut self) -> pitwall::Result<Box<dyn TelemetryProvider>> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+
+            match self.script.pop_front().flatten() {
+                Some(frame_count) => Ok(Box::new(MockProvider::new(frame_count))),
+                None => Err(pitwall::TelemetryError::connection_failed(
+                    "sim not running",
+                )),
+            }
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = ReconnectPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_collect_publishes_session_and_frames() {
+        let bus = EventBus::new(64);
+        let mut frame_rx = bus.subscribe(RacingEventKind::TelemetryFrameCollected);
+        let mut start_rx = bus.subscribe(RacingEventKind::SessionStart);
+        let mut end_rx = bus.subscribe(RacingEventKind::SessionEnd);
+
+        let provider: Box<dyn TelemetryProvider> = Box::new(MockProvider::new(4));
+        let published = collect(provider.as_ref(), UpdateRate::Native, &bus).await;
+        assert_eq!(published, 4);
+
+        let RacingEvent::SessionStart(start) = start_rx.try_recv().unwrap() else {
+            panic!("expected SessionStart");
+        };
+        for _ in 0..4 {
+            let event = frame_rx.try_recv().unwrap();
+            assert!(matches!(event, RacingEvent::TelemetryFrameCollected(_)));
+        }
+        // The session's frames end in order with the frames themselves
+        let RacingEvent::SessionFramesEnded(frames_ended) = frame_rx.try_recv().unwrap() else {
+            panic!("expected SessionFramesEnded");
+        };
+        let RacingEvent::SessionEnd(end) = end_rx.try_recv().unwrap() else {
+            panic!("expected SessionEnd");
+        };
+        assert_eq!(start.session_id, end.session_id);
+        assert_eq!(frames_ended.session_id, end.session_id);
+    }
+
+    #[tokio::test]
+    async fn test_collect_publishes_sanitized_frames() {
+        let bus = EventBus::new(64);
+        let mut frame_rx = bus.subscribe(RacingEventKind::TelemetryFrameCollected);
+        let broken = TelemetryFrame {
+            speed: f32::NAN,
+            throttle: 1.5,
+            ..frame(1, 0.2)
+        };
+        let provider = FrameProvider::new(vec![
+            TelemetryFrame {
+                speed: 30.0,
+                ..frame(1, 0.1)
+            },
+            broken,
+        ]);
+
+        collect(&provider, UpdateRate::Native, &bus).await;
+
+        frame_rx.try_recv().unwrap();
+        let RacingEvent::TelemetryFrameCollected(repaired) = frame_rx.try_recv().unwrap() else {
+            panic!("expected TelemetryFrameCollected");
+        };
+        assert_eq!(repaired.speed, 30.0);
+        assert_eq!(repaired.throttle, 1.0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reconnects_after_stream_ends() {
+        let bus = EventBus::new(64);
+        let mut frame_rx = bus.subscribe(RacingEventKind::TelemetryFrameCollected);
+        let mut start_rx = bus.subscribe(RacingEventKind::SessionStart);
+        let mut end_rx = bus.subscribe(RacingEventKind::SessionEnd);
+
+        let attempts = Arc::new(AtomicU32::new(0));
+        let connector = MockConnector {
+            // connect, fail once, connect again, then fail until giving up
+            script: VecDeque::from([Some(3), None, Some(2)]),
+            attempts: Arc::clone(&attempts),
+        };
+        let policy = ReconnectPolicy {
+            max_attempts: 2,
+            ..Default::default()
+        };
+
+        let result = collect_with_reconnect(
+            connector,
+            policy,
+            UpdateRate::Native,
+            &bus,
+            &CollectorControl::default(),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 5);
+
+        let mut frames = 0;
+        while let Ok(event) = frame_rx.try_recv() {
+            if let RacingEvent::TelemetryFrameCollected(_) = event {
+                frames += 1;
+            }
+        }
+        assert_eq!(frames, 5);
+
+        let mut start_ids = Vec::new();
+        while let Ok(RacingEvent::SessionStart(payload)) = start_rx.try_recv() {
+            start_ids.push(payload.session_id);
+        }
+        let mut end_ids = Vec::new();
+        while let Ok(RacingEvent::SessionEnd(payload)) = end_rx.try_recv() {
+            end_ids.push(payload.session_id);
+        }
+
+        assert_eq!(start_ids.len(), 2, "each connection starts a new session");
+        assert_ne!(start_ids[0], start_ids[1]);
+        assert_eq!(end_ids, start_ids, "each gap ends the previous session");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reconnect_stops_while_waiting_to_retry() {
+        let bus = EventBus::new(64);
+        let mut end_rx = bus.subscribe(RacingEventKind::SessionEnd);
+        let attempts = Arc::new(AtomicU32::new(0));
+        let connector = MockConnector {
+            // connected once, then never again
+            script: VecDeque::from([Some(3)]),
+            attempts: Arc::clone(&attempts),
+        };
+        let stop = CancellationToken::new();
+        let control = CollectorControl::new(stop.clone(), watch::channel(false).1);
+
+        let collecting = tokio::spawn(async move {
+            collect_with_reconnect(
+                connector,
+                ReconnectPolicy::default(),
+                UpdateRate::Native,
+                &bus,
+                &control,
+            )
+            .await
+        });
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        stop.cancel();
+
+        assert_eq!(collecting.await.unwrap().unwrap(), 3);
+        assert!(attempts.load(Ordering::SeqCst) > 1, "retried while waiting");
+        assert!(matches!(end_rx.try_recv(), Ok(RacingEvent::SessionEnd(_))));
+    }
+}
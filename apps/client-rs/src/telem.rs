@@ -1,15 +1,23 @@
+use std::collections::VecDeque;
+use std::collections::vec_deque;
+use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
 use eventbus::EventBus;
 use futures::StreamExt;
-use pitwall::{PitwallFrame, UpdateRate};
+use pitwall::irsdk_flags::session_flags;
+use pitwall::{BitField, DynamicFrame, PitwallFrame, UpdateRate};
+use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
 
 use crate::events::RacingEvent;
 use crate::pitwall_ext::AcceleratedReplayConnection;
 use crate::pos_service::PositionState;
+use crate::session::track_sessions;
+use crate::source::{SourceMode, TelemetrySource};
 
-#[derive(Debug, PitwallFrame)]
+#[derive(Debug, Default, PitwallFrame, Serialize, Deserialize)]
 pub struct TelemetryFrame {
     #[field_name = "SessionTime"]
     pub session_time: f64,
@@ -38,9 +46,292 @@ pub struct TelemetryFrame {
     pub brake: f32,
     #[field_name = "Clutch"]
     pub clutch: f32,
+    /// Steering wheel angle in radians, positive left
+    #[field_name = "SteeringWheelAngle"]
+    pub steering_angle: f32,
+    /// Steering input as a fraction of full lock, positive left (0.0 when the
+    /// car doesn't report its lock)
+    #[calculated = "if SteeringWheelAngleMax > 0.0 { SteeringWheelAngle / SteeringWheelAngleMax } else { 0.0 }"]
+    pub steering_pct: f32,
 
     #[field_name = "PlayerTrackSurface"]
     pub track_surface: i32,
+    #[field_name = "OnPitRoad"]
+    pub on_pit_road: bool,
+    /// Raw `irsdk_Flags` bits, see [`pitwall::irsdk_flags::session_flags`]
+    #[bitfield_map(name = "SessionFlags", decoder = "|flags: BitField| flags.value()")]
+    pub session_flags: u32,
+}
+
+/// Flags shown to a driver being penalized: the black flag, the meatball
+/// (repair) flag, or disqualification.
+const PENALTY_FLAGS: u32 = session_flags::BLACK | session_flags::REPAIR | session_flags::DISQUALIFY;
+
+/// A value [`TelemetryFrame::sanitize`] repaired.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FrameWarning {
+    /// The channel was NaN or infinite and now holds the previous frame's value
+    NonFinite {
+        channel: &'static str,
+        replacement: f64,
+    },
+    /// The channel was out of range and was clamped into it
+    Clamped {
+        channel: &'static str,
+        value: f64,
+        clamped: f64,
+    },
+}
+
+impl fmt::Display for FrameWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameWarning::NonFinite {
+                channel,
+                replacement,
+            } => write!(f, "{channel} was not finite, using {replacement}"),
+            FrameWarning::Clamped {
+                channel,
+                value,
+                clamped,
+            } => write!(f, "{channel} was {value}, clamped to {clamped}"),
+        }
+    }
+}
+
+impl TelemetryFrame {
+    /// Whether the car is anywhere but on the racing surface (`irsdk_TrkLoc`
+    /// other than on track, e.g. off track or in the pit stall).
+    pub fn is_off_track(&self) -> bool {
+        self.track_surface != 3
+    }
+
+    /// Whether the driver is shown a black, meatball or disqualified flag.
+    pub fn has_penalty_flag(&self) -> bool {
+        self.session_flags & PENALTY_FLAGS != 0
+    }
+
+    /// Repair values that would silently corrupt metrics, returning what was
+    /// fixed.
+    ///
+    /// NaN or infinite channels take their value from `previous` (which should
+    /// itself be sanitized), or zero without one. Pedals are then clamped to
+    /// 0..=1 and RPM to non-negative.
+    pub fn sanitize(&mut self, previous: Option<&TelemetryFrame>) -> Vec<FrameWarning> {
+        let mut warnings = Vec::new();
+        let zero = TelemetryFrame::default();
+        let previous = previous.unwrap_or(&zero);
+
+        if !self.session_time.is_finite() {
+            warnings.push(FrameWarning::NonFinite {
+                channel: "session_time",
+                replacement: previous.session_time,
+            });
+            self.session_time = previous.session_time;
+        }
+
+        let channels = [
+            (
+                "lap_distance_pct",
+                &mut self.lap_distance_pct,
+                previous.lap_distance_pct,
+            ),
+            (
+                "lap_distance",
+                &mut self.lap_distance,
+                previous.lap_distance,
+            ),
+            (
+                "current_lap_time",
+                &mut self.current_lap_time,
+                previous.current_lap_time,
+            ),
+            (
+                "last_lap_time",
+                &mut self.last_lap_time,
+                previous.last_lap_time,
+            ),
+            (
+                "best_lap_time",
+                &mut self.best_lap_time,
+                previous.best_lap_time,
+            ),
+            ("speed", &mut self.speed, previous.speed),
+            ("rpm", &mut self.rpm, previous.rpm),
+            ("throttle", &mut self.throttle, previous.throttle),
+            ("brake", &mut self.brake, previous.brake),
+            ("clutch", &mut self.clutch, previous.clutch),
+            (
+                "steering_angle",
+                &mut self.steering_angle,
+                previous.steering_angle,
+            ),
+            (
+                "steering_pct",
+                &mut self.steering_pct,
+                previous.steering_pct,
+            ),
+        ];
+        for (channel, value, replacement) in channels {
+            if !value.is_finite() {
+                warnings.push(FrameWarning::NonFinite {
+                    channel,
+                    replacement: replacement.into(),
+                });
+                *value = replacement;
+            }
+        }
+
+        let ranges = [
+            ("rpm", &mut self.rpm, 0.0, f32::MAX),
+            ("throttle", &mut self.throttle, 0.0, 1.0),
+            ("brake", &mut self.brake, 0.0, 1.0),
+            ("clutch", &mut self.clutch, 0.0, 1.0),
+            ("steering_pct", &mut self.steering_pct, -1.0, 1.0),
+        ];
+        for (channel, value, min, max) in ranges {
+            let clamped = value.clamp(min, max);
+            if clamped != *value {
+                warnings.push(FrameWarning::Clamped {
+                    channel,
+                    value: (*value).into(),
+                    clamped: clamped.into(),
+                });
+                *value = clamped;
+            }
+        }
+
+        warnings
+    }
+}
+
+/// Explicit by-name mapping from a dynamic pitwall frame, for tooling that
+/// reads frames without the derived adapter.
+///
+/// Every channel uses the same iRacing variables as the `#[field_name]` or
+/// `#[calculated]` expression on the field it fills. A variable that is
+/// missing from the frame, or has a type that can't be read as the field's
+/// type, leaves the field at its default (zero, or `false` for
+/// `on_pit_road`). When adding a channel, add it to the
+/// struct and here; the tests check both read the same variables.
+impl From<&DynamicFrame> for TelemetryFrame {
+    fn from(frame: &DynamicFrame) -> Self {
+        Self {
+            // Seconds since the session started
+            session_time: frame.get("SessionTime").unwrap_or_default(),
+            lap_number: frame.i32("Lap").unwrap_or_default(),
+            // 0.0..1.0 around the lap
+            lap_distance_pct: frame.f32("LapDistPct").unwrap_or_default(),
+            // Meters from the start/finish line
+            lap_distance: frame.f32("LapDist").unwrap_or_default(),
+            current_lap_time: frame.f32("LapCurrentLapTime").unwrap_or_default(),
+            last_lap_time: frame.f32("LapLastLapTime").unwrap_or_default(),
+            best_lap_time: frame.f32("LapBestLapTime").unwrap_or_default(),
+
+            // Meters per second
+            speed: frame.f32("Speed").unwrap_or_default(),
+            rpm: frame.f32("RPM").unwrap_or_default(),
+            // -1 is reverse, 0 neutral
+            gear: frame.i32("Gear").unwrap_or_default(),
+            throttle: frame.f32("Throttle").unwrap_or_default(),
+            brake: frame.f32("Brake").unwrap_or_default(),
+            clutch: frame.f32("Clutch").unwrap_or_default(),
+            // Radians, positive left
+            steering_angle: frame.f32("SteeringWheelAngle").unwrap_or_default(),
+            // Fraction of full lock; the maximum is the car's lock in radians
+            steering_pct: match (
+                frame.f32("SteeringWheelAngle"),
+                frame.f32("SteeringWheelAngleMax"),
+            ) {
+                (Some(angle), Some(max)) if max > 0.0 => angle / max,
+                _ => 0.0,
+            },
+
+            // irsdk_TrkLoc; 3 is on track
+            track_surface: frame.i32("PlayerTrackSurface").unwrap_or_default(),
+            on_pit_road: frame.bool("OnPitRoad").unwrap_or_default(),
+            // irsdk_Flags
+            session_flags: frame
+                .get::<BitField>("SessionFlags")
+                .map_or(0, |flags| flags.value()),
+        }
+    }
+}
+
+/// The most recent frames, up to a fixed count, for sliding-window analysis
+/// such as live balance or smoothness overlays.
+///
+/// Unlike the lap buffer it knows nothing about laps: every pushed frame is
+/// kept until `capacity` newer ones arrive. Storage is allocated up front, so
+/// pushing never allocates.
+#[derive(Clone, Debug)]
+pub struct FrameRingBuffer {
+    frames: VecDeque<Arc<TelemetryFrame>>,
+    capacity: usize,
+}
+
+impl FrameRingBuffer {
+    /// An empty buffer holding at most `capacity` frames.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            frames: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Add the newest frame, evicting the oldest once full.
+    pub fn push(&mut self, frame: Arc<TelemetryFrame>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Every buffered frame, oldest first.
+    pub fn iter(&self) -> vec_deque::Iter<'_, Arc<TelemetryFrame>> {
+        self.frames.iter()
+    }
+
+    /// The frames from the last `duration` of `session_time`, oldest first,
+    /// ending with the newest frame.
+    ///
+    /// Frames are assumed to be pushed in `session_time` order; call
+    /// [`FrameRingBuffer::clear`] when the session restarts.
+    pub fn window(&self, duration: Duration) -> vec_deque::Iter<'_, Arc<TelemetryFrame>> {
+        let Some(newest) = self.frames.back() else {
+            return self.frames.range(..);
+        };
+        let start_time = newest.session_time - duration.as_secs_f64();
+        let start = self
+            .frames
+            .partition_point(|frame| frame.session_time < start_time);
+        self.frames.range(start..)
+    }
+
+    /// The newest frame, if any.
+    pub fn latest(&self) -> Option<&Arc<TelemetryFrame>> {
+        self.frames.back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Drop every frame, keeping the storage.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
 }
 
 pub async fn read_telemetry_print() {
@@ -66,14 +357,19 @@ pub async fn read_telemetry_eventbus(
     speed: f64,
     pos_tx: watch::Sender<PositionState>,
 ) {
-    let connection = AcceleratedReplayConnection::open(
-        "../../../sample_data/ligierjsp320_bathurst 2025-11-17 18-15-16.ibt",
+    let mode = SourceMode::Replay {
+        path: "../../../sample_data/ligierjsp320_bathurst 2025-11-17 18-15-16.ibt".into(),
         speed,
-    )
-    .await
-    .unwrap();
+        loop_playback: false,
+    };
+    let connection = TelemetrySource::create(&mode).await.unwrap();
 
-    let mut stream = connection.subscribe::<TelemetryFrame>(UpdateRate::Max(60));
+    // Seed with the session parsed so far; duplicates are ignored by the tracker
+    let sessions =
+        futures::stream::iter(connection.current_session()).chain(connection.session_updates());
+    let session_task = tokio::spawn(track_sessions(sessions, bus.clone()));
+
+    let mut stream = connection.subscribe(UpdateRate::Max(60));
     let mut published_count: u64 = 0;
 
     while let Some(frame) = stream.next().await {
@@ -101,4 +397,267 @@ pub async fn read_telemetry_eventbus(
         "[Telemetry Publisher] Finished - total frames published: {}",
         published_count
     );
+
+    // Dropping the connection ends the session stream, which ends the session
+    drop(connection);
+    let _ = session_task.await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pitwall::adapters::{AdapterValidation, FrameAdapter};
+    use pitwall::types::{FramePacket, VariableType};
+    use pitwall::{VariableInfo, VariableSchema};
+    use std::collections::HashMap;
+
+    /// A variable and its little-endian value.
+    type Var = (&'static str, VariableType, Vec<u8>);
+
+    fn all_vars() -> Vec<Var> {
+        use VariableType::*;
+        vec![
+            ("SessionTime", Float64, 1234.5f64.to_le_bytes().to_vec()),
+            ("Lap", Int32, 7i32.to_le_bytes().to_vec()),
+            ("LapDistPct", Float32, 0.25f32.to_le_bytes().to_vec()),
+            ("LapDist", Float32, 1500.0f32.to_le_bytes().to_vec()),
+            ("LapCurrentLapTime", Float32, 31.5f32.to_le_bytes().to_vec()),
+            ("LapLastLapTime", Float32, 92.25f32.to_le_bytes().to_vec()),
+            ("LapBestLapTime", Float32, 91.75f32.to_le_bytes().to_vec()),
+            ("Speed", Float32, 55.5f32.to_le_bytes().to_vec()),
+            ("RPM", Float32, 7200.0f32.to_le_bytes().to_vec()),
+            ("Gear", Int32, 4i32.to_le_bytes().to_vec()),
+            ("Throttle", Float32, 0.75f32.to_le_bytes().to_vec()),
+            ("Brake", Float32, 0.125f32.to_le_bytes().to_vec()),
+            ("Clutch", Float32, 1.0f32.to_le_bytes().to_vec()),
+            (
+                "SteeringWheelAngle",
+                Float32,
+                (-2.0f32).to_le_bytes().to_vec(),
+            ),
+            (
+                "SteeringWheelAngleMax",
+                Float32,
+                8.0f32.to_le_bytes().to_vec(),
+            ),
+            ("PlayerTrackSurface", Int32, 3i32.to_le_bytes().to_vec()),
+            ("OnPitRoad", Bool, vec![1]),
+            (
+                "SessionFlags",
+                BitField,
+                0x0010_0004u32.to_le_bytes().to_vec(),
+            ),
+        ]
+    }
+
+    fn packet(vars: &[Var]) -> FramePacket {
+        let mut variables = HashMap::new();
+        let mut data = Vec::new();
+        for (name, data_type, bytes) in vars {
+            variables.insert(
+                name.to_string(),
+                VariableInfo {
+                    name: name.to_string(),
+                    data_type: *data_type,
+                    offset: data.len(),
+                    count: 1,
+                    count_as_time: false,
+                    units: String::new(),
+                    description: String::new(),
+                },
+            );
+            data.extend_from_slice(bytes);
+        }
+        let schema = VariableSchema {
+            variables,
+            frame_size: data.len(),
+            version: None,
+        };
+        FramePacket::new(data, 1, 0, Arc::new(schema))
+    }
+
+    fn convert(vars: &[Var]) -> TelemetryFrame {
+        let frame = DynamicFrame::adapt(&packet(vars), &AdapterValidation::new(vec![]));
+        TelemetryFrame::from(&frame)
+    }
+
+    #[test]
+    fn test_from_dynamic_frame_maps_every_field() {
+        let frame = convert(&all_vars());
+
+        assert_eq!(frame.session_time, 1234.5);
+        assert_eq!(frame.lap_number, 7);
+        assert_eq!(frame.lap_distance_pct, 0.25);
+        assert_eq!(frame.lap_distance, 1500.0);
+        assert_eq!(frame.current_lap_time, 31.5);
+        assert_eq!(frame.last_lap_time, 92.25);
+        assert_eq!(frame.best_lap_time, 91.75);
+        assert_eq!(frame.speed, 55.5);
+        assert_eq!(frame.rpm, 7200.0);
+        assert_eq!(frame.gear, 4);
+        assert_eq!(frame.throttle, 0.75);
+        assert_eq!(frame.brake, 0.125);
+        assert_eq!(frame.clutch, 1.0);
+        assert_eq!(frame.steering_angle, -2.0);
+        assert_eq!(frame.steering_pct, -0.25);
+        assert_eq!(frame.track_surface, 3);
+        assert!(frame.on_pit_road);
+        assert_eq!(frame.session_flags, 0x0010_0004);
+        assert!(frame.has_penalty_flag());
+    }
+
+    #[test]
+    fn test_from_dynamic_frame_matches_derived_adapter() {
+        let packet = packet(&all_vars());
+        let validation = TelemetryFrame::validate_schema(&packet.schema).unwrap();
+        let derived = TelemetryFrame::adapt(&packet, &validation);
+
+        let dynamic = DynamicFrame::adapt(&packet, &AdapterValidation::new(vec![]));
+        let converted = TelemetryFrame::from(&dynamic);
+
+        assert_eq!(
+            serde_json::to_value(&converted).unwrap(),
+            serde_json::to_value(&derived).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_missing_variables_use_defaults() {
+        let vars: Vec<Var> = all_vars()
+            .into_iter()
+            .filter(|(name, ..)| !matches!(*name, "Speed" | "Gear" | "OnPitRoad"))
+            .collect();
+
+        let frame = convert(&vars);
+
+        assert_eq!(frame.speed, 0.0);
+        assert_eq!(frame.gear, 0);
+        assert!(!frame.on_pit_road);
+        // Present variables are unaffected
+        assert_eq!(frame.lap_number, 7);
+        assert_eq!(frame.rpm, 7200.0);
+    }
+
+    #[test]
+    fn test_mistyped_variable_uses_default() {
+        let mut vars = all_vars();
+        // Lap reported as a float can't be read as the i32 lap number
+        vars[1] = ("Lap", VariableType::Float32, 7.0f32.to_le_bytes().to_vec());
+
+        let frame = convert(&vars);
+
+        assert_eq!(frame.lap_number, 0);
+        assert_eq!(frame.speed, 55.5);
+    }
+
+    fn driving_frame() -> TelemetryFrame {
+        TelemetryFrame {
+            session_time: 100.0,
+            speed: 42.0,
+            rpm: 6500.0,
+            throttle: 0.8,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sanitize_repairs_nan_speed_and_throttle_overshoot() {
+        let previous = driving_frame();
+        let mut frame = TelemetryFrame {
+            session_time: 100.016,
+            speed: f32::NAN,
+            throttle: 1.5,
+            ..driving_frame()
+        };
+
+        let warnings = frame.sanitize(Some(&previous));
+
+        assert_eq!(frame.speed, 42.0);
+        assert_eq!(frame.throttle, 1.0);
+        assert_eq!(
+            warnings,
+            vec![
+                FrameWarning::NonFinite {
+                    channel: "speed",
+                    replacement: 42.0,
+                },
+                FrameWarning::Clamped {
+                    channel: "throttle",
+                    value: 1.5,
+                    clamped: 1.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sanitize_without_previous_uses_zero() {
+        let mut frame = TelemetryFrame {
+            rpm: f32::INFINITY,
+            brake: -0.1,
+            ..driving_frame()
+        };
+
+        let warnings = frame.sanitize(None);
+
+        assert_eq!(frame.rpm, 0.0);
+        assert_eq!(frame.brake, 0.0);
+        assert_eq!(warnings.len(), 2);
+    }
+
+    fn timed_frame(session_time: f64) -> Arc<TelemetryFrame> {
+        Arc::new(TelemetryFrame {
+            session_time,
+            ..Default::default()
+        })
+    }
+
+    fn session_times<'a>(frames: impl Iterator<Item = &'a Arc<TelemetryFrame>>) -> Vec<f64> {
+        frames.map(|frame| frame.session_time).collect()
+    }
+
+    #[test]
+    fn test_ring_buffer_evicts_oldest_frames() {
+        let mut buffer = FrameRingBuffer::new(3);
+        let storage = buffer.frames.capacity();
+        for time in 0..5 {
+            buffer.push(timed_frame(time as f64));
+        }
+
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(session_times(buffer.iter()), vec![2.0, 3.0, 4.0]);
+        assert_eq!(buffer.latest().unwrap().session_time, 4.0);
+        // Evicting made room without growing the storage
+        assert_eq!(buffer.frames.capacity(), storage);
+
+        let mut empty = FrameRingBuffer::new(0);
+        empty.push(timed_frame(0.0));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_ring_buffer_window_covers_requested_span() {
+        let mut buffer = FrameRingBuffer::new(100);
+        assert_eq!(buffer.window(Duration::from_secs(1)).count(), 0);
+        // Ten seconds at 4 Hz
+        for tick in 0..40 {
+            buffer.push(timed_frame(tick as f64 * 0.25));
+        }
+
+        assert_eq!(
+            session_times(buffer.window(Duration::from_secs(1))),
+            vec![8.75, 9.0, 9.25, 9.5, 9.75]
+        );
+        assert_eq!(session_times(buffer.window(Duration::ZERO)), vec![9.75]);
+        assert_eq!(buffer.window(Duration::from_secs(60)).count(), 40);
+    }
+
+    #[test]
+    fn test_sanitize_leaves_valid_frame_alone() {
+        let mut frame = driving_frame();
+
+        assert!(frame.sanitize(None).is_empty());
+        assert_eq!(frame.speed, 42.0);
+        assert_eq!(frame.throttle, 0.8);
+    }
 }
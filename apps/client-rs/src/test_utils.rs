@@ -0,0 +1,184 @@
+//! Shared helpers for handler tests.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use eventbus::{EventBus, EventHandler, HandlerRegistry};
+use futures::StreamExt;
+use pitwall::{SessionInfo, UpdateRate};
+
+use crate::events::{RacingEvent, RacingEventKind};
+use crate::source::{FrameStream, SessionStream, TelemetryProvider};
+use crate::telem::TelemetryFrame;
+
+/// Build an on-track frame at the given lap position.
+pub fn frame(lap_number: i32, lap_distance_pct: f32) -> TelemetryFrame {
+    TelemetryFrame {
+        lap_number,
+        lap_distance_pct,
+        track_surface: 3,
+        ..Default::default()
+    }
+}
+
+/// Run `handler` on a fresh bus, publish `frames`, and collect every event
+/// of `kind` it emits until the bus goes quiet.
+pub async fn collect_events<H>(
+    handler: H,
+    frames: Vec<TelemetryFrame>,
+    kind: RacingEventKind,
+) -> Vec<RacingEvent>
+where
+    H: EventHandler<RacingEvent> + 'static,
+{
+    let bus = EventBus::new(frames.len().max(16));
+    let mut rx = bus.subscribe(kind);
+
+    let mut registry = HandlerRegistry::new();
+    registry.register(handler);
+    let handles = registry.run(bus.clone());
+
+    for frame in frames {
+        bus.publish(RacingEvent::TelemetryFrameCollected(Arc::new(frame)))
+            .expect("handler should be subscribed");
+    }
+
+    let mut events = Vec::new();
+    while let Ok(Ok(event)) = tokio::time::timeout(Duration::from_millis(200), rx.recv()).await {
+        events.push(event);
+    }
+
+    registry.shutdown();
+    for handle in handles {
+        let _ = handle.await;
+    }
+
+    events
+}
+
+/// Provider that yields scripted frames to its first subscriber, then ends.
+pub struct FrameProvider {
+    frames: Mutex<Vec<TelemetryFrame>>,
+}
+
+impl FrameProvider {
+    pub fn new(frames: Vec<TelemetryFrame>) -> Self {
+        Self {
+            frames: Mutex::new(frames),
+        }
+    }
+}
+
+impl TelemetryProvider for FrameProvider {
+    fn subscribe(&self, _rate: UpdateRate) -> FrameStream {
+        let frames = std::mem::take(&mut *self.frames.lock().unwrap());
+        futures::stream::iter(frames).boxed()
+    }
+
+    fn current_session(&self) -> Option<Arc<SessionInfo>> {
+        Some(Arc::new(SessionInfo::default()))
+    }
+
+    fn session_updates(&self) -> SessionStream {
+        futures::stream::empty().boxed()
+    }
+}
+
+/// Local WebSocket server collecting the telemetry frames sent to it.
+#[cfg(feature = "ws")]
+pub struct MockWsServer {
+    addr: std::net::SocketAddr,
+    frames: tokio::sync::mpsc::UnboundedReceiver<TelemetryFrame>,
+    connections: Arc<std::sync::atomic::AtomicUsize>,
+    drop_tx: tokio::sync::watch::Sender<()>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(feature = "ws")]
+impl MockWsServer {
+    /// Start listening on a free local port. The server shuts down when dropped.
+    pub async fn start() -> Self {
+        use std::sync::atomic::Ordering;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (frames_tx, frames) = tokio::sync::mpsc::unbounded_channel();
+        let connections = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let (drop_tx, drop_rx) = tokio::sync::watch::channel(());
+
+        let accepted = connections.clone();
+        let task = tokio::spawn(async move {
+            // Dropping the set with this task closes every connection
+            let mut tasks = tokio::task::JoinSet::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                let Ok(mut socket) = tokio_tungstenite::accept_async(stream).await else {
+                    continue;
+                };
+                accepted.fetch_add(1, Ordering::SeqCst);
+                let frames_tx = frames_tx.clone();
+                let mut drop_rx = drop_rx.clone();
+                drop_rx.mark_unchanged();
+
+                tasks.spawn(async move {
+                    loop {
+                        tokio::select! {
+                            _ = drop_rx.changed() => return,
+                            message = socket.next() => {
+                                let Some(Ok(message)) = message else { return };
+                                if let Ok(text) = message.to_text()
+                                    && let Ok(frame) = serde_json::from_str(text)
+                                {
+                                    let _ = frames_tx.send(frame);
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        Self {
+            addr,
+            frames,
+            connections,
+            drop_tx,
+            task,
+        }
+    }
+
+    /// Base URL to connect to, as configured for the Racing Coach server.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Number of connections accepted so far.
+    pub fn connections(&self) -> usize {
+        self.connections.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Close every open connection without a close handshake, as a network
+    /// failure would.
+    pub fn drop_connections(&self) {
+        self.drop_tx.send_replace(());
+    }
+
+    /// Wait for the next `count` frames to arrive.
+    pub async fn frames(&mut self, count: usize) -> Vec<TelemetryFrame> {
+        let mut frames = Vec::with_capacity(count);
+        while frames.len() < count {
+            let frame = tokio::time::timeout(Duration::from_secs(2), self.frames.recv())
+                .await
+                .expect("frames should arrive")
+                .expect("server should be running");
+            frames.push(frame);
+        }
+        frames
+    }
+}
+
+#[cfg(feature = "ws")]
+impl Drop for MockWsServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
@@ -0,0 +1,60 @@
+//! Unit conversions for presenting telemetry.
+//!
+//! Telemetry and everything stored or sent to the server stays in SI units;
+//! these helpers only convert values for display.
+
+/// Kilometers per hour in one meter per second.
+const KMH_PER_MPS: f64 = 3.6;
+
+/// Miles per hour in one meter per second (a mile is exactly 1609.344 m).
+const MPH_PER_MPS: f64 = 3600.0 / 1609.344;
+
+/// Convert a speed in m/s to km/h.
+pub fn mps_to_kmh(mps: f64) -> f64 {
+    mps * KMH_PER_MPS
+}
+
+/// Convert a speed in km/h to m/s.
+pub fn kmh_to_mps(kmh: f64) -> f64 {
+    kmh / KMH_PER_MPS
+}
+
+/// Convert a speed in m/s to mph.
+pub fn mps_to_mph(mps: f64) -> f64 {
+    mps * MPH_PER_MPS
+}
+
+/// Convert a speed in mph to m/s.
+pub fn mph_to_mps(mph: f64) -> f64 {
+    mph / MPH_PER_MPS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_speed_conversions() {
+        assert_close(mps_to_kmh(10.0), 36.0);
+        assert_close(kmh_to_mps(36.0), 10.0);
+        assert_close(mps_to_mph(44.704), 100.0);
+        assert_close(mph_to_mps(100.0), 44.704);
+        // km/h to mph by way of m/s
+        assert_close(mps_to_mph(kmh_to_mps(160.9344)), 100.0);
+    }
+
+    #[test]
+    fn test_conversions_round_trip() {
+        for mps in [0.0, 1.5, 83.3] {
+            assert_close(kmh_to_mps(mps_to_kmh(mps)), mps);
+            assert_close(mph_to_mps(mps_to_mph(mps)), mps);
+        }
+    }
+}
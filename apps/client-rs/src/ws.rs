@@ -0,0 +1,252 @@
+//! Live telemetry streaming over WebSocket.
+//!
+//! [`WsSink`] sends frames to the server's [`TELEMETRY_WS_PATH`] endpoint for
+//! real-time dashboards, reconnecting when the connection drops. Use
+//! [`crate::handlers::WsHandler`] to stream every frame published on the bus.
+//!
+//! Only available with the `ws` feature.
+
+use std::time::Duration;
+
+use futures::SinkExt;
+use thiserror::Error;
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::{self, Message, Utf8Bytes};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tracing::{info, warn};
+use url::Url;
+
+use crate::config::endpoint_url;
+use crate::telem::TelemetryFrame;
+
+/// Server endpoint receiving live telemetry.
+pub const TELEMETRY_WS_PATH: &str = "/api/v1/ws/telemetry";
+
+/// Time to wait after a failed connection attempt before trying again.
+/// Frames sent in the meantime are dropped rather than queued; a live
+/// dashboard only cares about the latest ones.
+const RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+/// A failure to stream telemetry.
+#[derive(Debug, Error)]
+pub enum WsError {
+    #[error("server URL {url:?} is invalid: {source}")]
+    InvalidUrl {
+        url: String,
+        #[source]
+        source: url::ParseError,
+    },
+
+    #[error("server URL {url:?} must use http or https")]
+    UnsupportedScheme { url: String },
+
+    #[error("WebSocket connection failed: {0}")]
+    Connection(#[from] tungstenite::Error),
+
+    #[error("not connected, waiting to reconnect")]
+    Disconnected,
+
+    #[error("could not serialize frame: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+struct SinkState {
+    socket: Option<Socket>,
+    /// Earliest time of the next connection attempt after a failed one
+    retry_at: Option<Instant>,
+}
+
+/// Sends telemetry frames to the server over a WebSocket.
+///
+/// A failed send drops the connection; the next send reconnects, at most once
+/// per [`RECONNECT_DELAY`].
+pub struct WsSink {
+    url: Url,
+    state: Mutex<SinkState>,
+}
+
+impl WsSink {
+    /// The telemetry endpoint for a server at `server_url` (e.g.
+    /// `http://localhost:8000` becomes `ws://localhost:8000/api/v1/ws/telemetry`),
+    /// keeping any path prefix the server is mounted under.
+    pub fn telemetry_url(server_url: &str) -> Result<Url, WsError> {
+        let mut url = Url::parse(server_url).map_err(|source| WsError::InvalidUrl {
+            url: server_url.to_string(),
+            source,
+        })?;
+        let scheme = match url.scheme() {
+            "http" => "ws",
+            "https" => "wss",
+            _ => {
+                return Err(WsError::UnsupportedScheme {
+                    url: server_url.to_string(),
+                });
+            }
+        };
+        url.set_scheme(scheme)
+            .expect("ws and wss are valid replacements for http and https");
+        Ok(endpoint_url(&url, TELEMETRY_WS_PATH))
+    }
+
+    /// Connect to the telemetry endpoint of the server at `server_url`.
+    pub async fn connect(server_url: &str) -> Result<Self, WsError> {
+        let url = Self::telemetry_url(server_url)?;
+        let socket = open(&url).await?;
+        Ok(Self {
+            url,
+            state: Mutex::new(SinkState {
+                socket: Some(socket),
+                retry_at: None,
+            }),
+        })
+    }
+
+    /// The endpoint frames are sent to.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Send one frame as JSON, reconnecting first if the connection dropped.
+    ///
+    /// Returns [`WsError::Disconnected`] without sending while waiting to
+    /// retry a failed connection.
+    pub async fn send_frame(&self, frame: &TelemetryFrame) -> Result<(), WsError> {
+        let text = Utf8Bytes::from(serde_json::to_string(frame)?);
+        let mut state = self.state.lock().await;
+
+        let socket = match state.socket.take() {
+            Some(socket) => socket,
+            None => {
+                if state.retry_at.is_some_and(|at| Instant::now() < at) {
+                    return Err(WsError::Disconnected);
+                }
+                match open(&self.url).await {
+                    Ok(socket) => {
+                        info!("Reconnected to {}", self.url);
+                        state.retry_at = None;
+                        socket
+                    }
+                    Err(error) => {
+                        state.retry_at = Some(Instant::now() + RECONNECT_DELAY);
+                        return Err(error);
+                    }
+                }
+            }
+        };
+
+        let socket = state.socket.insert(socket);
+        if let Err(error) = socket.send(Message::Text(text)).await {
+            warn!("Lost connection to {}: {}", self.url, error);
+            state.socket = None;
+            return Err(error.into());
+        }
+        Ok(())
+    }
+
+    /// Close the connection, if open.
+    pub async fn close(&self) {
+        if let Some(mut socket) = self.state.lock().await.socket.take() {
+            let _ = socket.close(None).await;
+        }
+    }
+}
+
+async fn open(url: &Url) -> Result<Socket, WsError> {
+    let (socket, _response) = tokio_tungstenite::connect_async(url.as_str()).await?;
+    Ok(socket)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockWsServer, frame};
+
+    #[test]
+    fn test_telemetry_url_from_server_url() {
+        assert_eq!(
+            WsSink::telemetry_url("http://localhost:8000")
+                .unwrap()
+                .as_str(),
+            "ws://localhost:8000/api/v1/ws/telemetry"
+        );
+        assert_eq!(
+            WsSink::telemetry_url("https://coach.example")
+                .unwrap()
+                .as_str(),
+            "wss://coach.example/api/v1/ws/telemetry"
+        );
+        assert_eq!(
+            WsSink::telemetry_url("https://host/coach/")
+                .unwrap()
+                .as_str(),
+            "wss://host/coach/api/v1/ws/telemetry"
+        );
+        assert!(matches!(
+            WsSink::telemetry_url("ftp://coach.example"),
+            Err(WsError::UnsupportedScheme { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_server_receives_frames() {
+        let mut server = MockWsServer::start().await;
+        let sink = WsSink::connect(&server.url()).await.unwrap();
+
+        for lap_number in 1..=3 {
+            sink.send_frame(&frame(lap_number, 0.5)).await.unwrap();
+        }
+
+        let received: Vec<i32> = server
+            .frames(3)
+            .await
+            .iter()
+            .map(|frame| frame.lap_number)
+            .collect();
+        assert_eq!(received, vec![1, 2, 3]);
+        sink.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_connection_drops() {
+        let mut server = MockWsServer::start().await;
+        let sink = WsSink::connect(&server.url()).await.unwrap();
+
+        sink.send_frame(&frame(1, 0.1)).await.unwrap();
+        server.frames(1).await;
+        server.drop_connections();
+
+        // Sends fail until the dropped connection is noticed, then reconnect
+        let reconnected = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let _ = sink.send_frame(&frame(2, 0.2)).await;
+                if server.connections() == 2 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await;
+        assert!(reconnected.is_ok(), "sink should reconnect");
+
+        sink.send_frame(&frame(3, 0.3)).await.unwrap();
+        let last = server.frames(1).await;
+        assert!(last[0].lap_number >= 2);
+    }
+
+    #[tokio::test]
+    async fn test_connect_fails_without_server() {
+        // Reserve a port, then free it so nothing is listening there
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        assert!(matches!(
+            WsSink::connect(&url).await,
+            Err(WsError::Connection(_))
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+//! Benchmarks for publishing bursts faster than handlers keep up
+//!
+//! Each iteration publishes a burst of events in quick chunks to a bus with a
+//! small channel, then waits for the handler to drain it. Under `Overwrite`
+//! the handler lags and skips most of the burst; under `Spill` the overflow
+//! queue absorbs it and every event is handled. Events lost per burst are
+//! printed before the timings.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use criterion::{Criterion, criterion_group, criterion_main};
+use eventbus::{
+    BusOptions, EventBus, EventHandler, EventLike, HandlerContext, HandlerRegistry, OverflowPolicy,
+};
+use tokio::runtime::Runtime;
+
+const CAPACITY: usize = 64;
+const BURST: u64 = 10_000;
+const CHUNK: u64 = 1_000;
+
+#[derive(Clone)]
+struct Sample(u64);
+
+impl EventLike for Sample {
+    type Kind = ();
+
+    fn kind(&self) -> Self::Kind {}
+
+    fn all_kinds() -> impl Iterator<Item = Self::Kind> {
+        std::iter::once(())
+    }
+}
+
+/// Counts samples, yielding after each like a handler doing async work.
+struct Counter(Arc<AtomicU64>);
+
+#[async_trait]
+impl EventHandler<Sample> for Counter {
+    fn handles(&self) {}
+
+    async fn handle(&self, event: Sample, _ctx: &HandlerContext<Sample>) {
+        std::hint::black_box(event.0);
+        self.0.fetch_add(1, Ordering::Relaxed);
+        tokio::task::yield_now().await;
+    }
+}
+
+/// Publish one burst and drain it, returning how long that took and how
+/// many events the handler never saw.
+fn run_burst(runtime: &Runtime, policy: OverflowPolicy) -> (Duration, u64) {
+    runtime.block_on(async {
+        let bus = EventBus::with_options(BusOptions {
+            overflow_policy: policy,
+            ..BusOptions::new(CAPACITY)
+        });
+        let handled = Arc::new(AtomicU64::new(0));
+        let mut registry = HandlerRegistry::new();
+        registry.register(Counter(handled.clone()));
+        let handles = registry.run(bus.clone());
+
+        let started = Instant::now();
+        for i in 0..BURST {
+            let _ = bus.publish(Sample(i));
+            if (i + 1).is_multiple_of(CHUNK) {
+                tokio::task::yield_now().await;
+            }
+        }
+        bus.shutdown_and_drain().await;
+        let elapsed = started.elapsed();
+
+        for handle in handles {
+            let _ = handle.await;
+        }
+        (elapsed, BURST - handled.load(Ordering::Relaxed))
+    })
+}
+
+fn bench_bursty_publish(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let policies = [OverflowPolicy::Overwrite, OverflowPolicy::Spill];
+
+    for policy in policies {
+        let (_, lost) = run_burst(&runtime, policy);
+        eprintln!("{policy:?}: {lost} of {BURST} events lost per burst");
+    }
+
+    let mut group = c.benchmark_group("bursty_publish");
+    for policy in policies {
+        group.bench_function(format!("{policy:?}"), |b| {
+            b.iter_custom(|iters| (0..iters).map(|_| run_burst(&runtime, policy).0).sum())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_bursty_publish);
+criterion_main!(benches);
@@ -1,8 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::hash::Hash;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use tokio::sync::broadcast;
+use futures::{Stream, StreamExt};
+use tokio::sync::{broadcast, watch};
+use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
 
 pub use broadcast::error::SendError;
 pub use broadcast::Receiver;
@@ -24,6 +30,108 @@ pub trait EventLike: Clone + Send + Sync + 'static {
     fn all_kinds() -> impl Iterator<Item = Self::Kind>;
 }
 
+/// Weight of each new sample in a handler's moving average processing time.
+const HANDLER_TIME_SMOOTHING: f64 = 0.1;
+
+/// Default for [`BusOptions::overflow_warning_interval`].
+const OVERFLOW_WARNING_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default for [`BusOptions::spill_warning_depth`].
+const SPILL_WARNING_DEPTH: usize = 4096;
+
+/// What an [`EventBus`] does with an event published to a full channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Accept the event, evicting the oldest one; handlers that hadn't read
+    /// it yet skip it as lagged
+    #[default]
+    Overwrite,
+    /// Refuse the event, returning it from [`EventBus::publish`]
+    DropNewest,
+    /// Accept the event into an unbounded overflow queue for its kind,
+    /// moved into the channel in order as handlers free up space. Nothing
+    /// is lost to a burst; the queue's memory is released once it empties.
+    ///
+    /// Queued events move on when events are published or handled, so
+    /// subscribers that aren't handlers may see them late.
+    Spill,
+}
+
+/// Settings for [`EventBus::with_options`].
+#[derive(Clone, Debug)]
+pub struct BusOptions {
+    /// Channel capacity per event kind
+    pub capacity: usize,
+    pub overflow_policy: OverflowPolicy,
+    /// Minimum time between warnings about full channels
+    pub overflow_warning_interval: Duration,
+    /// Overflow queue length that logs a warning under
+    /// [`OverflowPolicy::Spill`], and again each time it doubles
+    pub spill_warning_depth: usize,
+}
+
+impl BusOptions {
+    /// Default options with the given channel capacity per event kind.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            overflow_policy: OverflowPolicy::default(),
+            overflow_warning_interval: OVERFLOW_WARNING_INTERVAL,
+            spill_warning_depth: SPILL_WARNING_DEPTH,
+        }
+    }
+}
+
+/// Snapshot of an [`EventBus`]'s counters, see [`EventBus::metrics`].
+#[derive(Clone, Debug)]
+pub struct BusMetrics<K> {
+    /// Events published per kind, including follow-ups from handlers
+    pub published: HashMap<K, u64>,
+    /// Events handled per kind, counted once per handler that handled them
+    pub processed: HashMap<K, u64>,
+    /// Events lost to full channels per kind: refused under
+    /// [`OverflowPolicy::DropNewest`], or evicted before a handler read them
+    /// (counted once per handler)
+    pub dropped: HashMap<K, u64>,
+    /// Events published but not yet handled, counted once per handler
+    pub queue_depth: usize,
+    /// Events waiting in the overflow queue per kind, see
+    /// [`OverflowPolicy::Spill`]
+    pub spilled: HashMap<K, usize>,
+    /// One entry per handler task, in the order they were started
+    pub handlers: Vec<HandlerMetrics<K>>,
+}
+
+/// Counters for one handler task, see [`BusMetrics::handlers`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HandlerMetrics<K> {
+    pub name: &'static str,
+    pub kind: K,
+    /// Events handled so far
+    pub processed: u64,
+    /// Exponential moving average of the time spent handling an event
+    pub average_time: Duration,
+}
+
+/// Live counters for one handler task, updated only by that task.
+pub(crate) struct HandlerStats<K> {
+    name: &'static str,
+    kind: K,
+    processed: AtomicU64,
+    average_nanos: AtomicU64,
+}
+
+impl<K: Copy> HandlerStats<K> {
+    fn snapshot(&self) -> HandlerMetrics<K> {
+        HandlerMetrics {
+            name: self.name,
+            kind: self.kind,
+            processed: self.processed.load(Ordering::Relaxed),
+            average_time: Duration::from_nanos(self.average_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
 /// Event bus with discriminant-keyed channels for efficient routing.
 ///
 /// # Type Parameters
@@ -35,50 +143,593 @@ pub struct EventBus<E: EventLike> {
 
 struct EventBusInner<E: EventLike> {
     channels: HashMap<E::Kind, broadcast::Sender<E>>,
-    capacity: usize,
+    /// Number of handler tasks subscribed to each kind
+    handlers: HashMap<E::Kind, AtomicUsize>,
+    /// Events published but not yet handled, counted once per handler
+    pending: watch::Sender<usize>,
+    published: HashMap<E::Kind, AtomicU64>,
+    processed: HashMap<E::Kind, AtomicU64>,
+    dropped: HashMap<E::Kind, AtomicU64>,
+    /// Events waiting for space in each channel under
+    /// [`OverflowPolicy::Spill`]
+    spills: HashMap<E::Kind, Mutex<VecDeque<E>>>,
+    /// When a full channel was last warned about, if ever
+    last_overflow_warning: Mutex<Option<Instant>>,
+    /// Publishes to a full channel since the last warning
+    overflows_since_warning: AtomicU64,
+    handler_stats: Mutex<Vec<Arc<HandlerStats<E::Kind>>>>,
+    /// Set once the bus stops accepting events from outside handlers
+    closed: AtomicBool,
+    options: BusOptions,
+    shutdown: CancellationToken,
 }
 
 impl<E: EventLike> EventBus<E> {
     /// Create a new event bus with the given channel capacity per event kind.
     pub fn new(capacity: usize) -> Self {
+        Self::with_options(BusOptions::new(capacity))
+    }
+
+    /// Create a new event bus configured by `options`.
+    pub fn with_options(options: BusOptions) -> Self {
         let channels = E::all_kinds()
             .map(|kind| {
-                let (tx, _) = broadcast::channel(capacity);
+                let (tx, _) = broadcast::channel(options.capacity);
                 (kind, tx)
             })
             .collect();
+        let handlers = E::all_kinds()
+            .map(|kind| (kind, AtomicUsize::new(0)))
+            .collect();
+        let counters = || {
+            E::all_kinds()
+                .map(|kind| (kind, AtomicU64::new(0)))
+                .collect()
+        };
+
+        let spills = E::all_kinds()
+            .map(|kind| (kind, Mutex::new(VecDeque::new())))
+            .collect();
 
         Self {
-            inner: Arc::new(EventBusInner { channels, capacity }),
+            inner: Arc::new(EventBusInner {
+                channels,
+                handlers,
+                pending: watch::channel(0).0,
+                published: counters(),
+                processed: counters(),
+                dropped: counters(),
+                spills,
+                last_overflow_warning: Mutex::new(None),
+                overflows_since_warning: AtomicU64::new(0),
+                handler_stats: Mutex::new(Vec::new()),
+                closed: AtomicBool::new(false),
+                options,
+                shutdown: CancellationToken::new(),
+            }),
         }
     }
 
     /// Publish an event to the appropriate channel (sync - no await needed).
     ///
     /// Returns the number of receivers that received the event,
-    /// or an error if the channel has no subscribers.
+    /// or an error if the channel has no subscribers, the bus is draining
+    /// (see [`EventBus::shutdown_and_drain`]) or the channel is full under
+    /// [`OverflowPolicy::DropNewest`].
     pub fn publish(&self, event: E) -> Result<usize, SendError<E>> {
+        if self.inner.closed.load(Ordering::Acquire) {
+            return Err(SendError(event));
+        }
+        self.send(event)
+    }
+
+    /// Publish an event even while draining, for follow-up events from
+    /// handlers.
+    pub(crate) fn send(&self, event: E) -> Result<usize, SendError<E>> {
         let kind = event.kind();
+        let channel = self.channel(kind);
+        // Under Spill, publishes of a kind take turns so queued events go first
+        let mut spill = (self.inner.options.overflow_policy == OverflowPolicy::Spill)
+            .then(|| self.spill(kind).lock().unwrap());
+        if let Some(spill) = spill.as_deref_mut() {
+            self.refill_from(channel, spill);
+        }
+        let spilling = spill.as_ref().is_some_and(|spill| !spill.is_empty());
+
+        if spilling || channel.len() >= self.inner.options.capacity {
+            self.warn_overflow(kind);
+            match (self.inner.options.overflow_policy, spill.as_deref_mut()) {
+                (OverflowPolicy::DropNewest, _) => {
+                    self.record_dropped(kind, 1);
+                    return Err(SendError(event));
+                }
+                (OverflowPolicy::Spill, Some(spill)) if channel.receiver_count() > 0 => {
+                    let handlers = self.handler_count(kind).load(Ordering::Acquire);
+                    if handlers > 0 {
+                        self.inner
+                            .pending
+                            .send_modify(|pending| *pending += handlers);
+                    }
+                    spill.push_back(event);
+                    counter(&self.inner.published, kind).fetch_add(1, Ordering::Relaxed);
+                    self.warn_spill_depth(kind, spill.len());
+                    return Ok(channel.receiver_count());
+                }
+                _ => {}
+            }
+        }
+
+        // Count the event before sending so a handler can't finish it first
+        let handlers = self.handler_count(kind).load(Ordering::Acquire);
+        if handlers > 0 {
+            self.inner
+                .pending
+                .send_modify(|pending| *pending += handlers);
+        }
+        let result = channel.send(event);
+        match &result {
+            Ok(_) => {
+                counter(&self.inner.published, kind).fetch_add(1, Ordering::Relaxed);
+            }
+            Err(_) if handlers > 0 => self.complete(handlers),
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Warn that `kind`'s channel is full, at most once per
+    /// [`BusOptions::overflow_warning_interval`].
+    fn warn_overflow(&self, kind: E::Kind) {
+        let overflows = self
+            .inner
+            .overflows_since_warning
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        let mut last_warning = self.inner.last_overflow_warning.lock().unwrap();
+        let interval = self.inner.options.overflow_warning_interval;
+        if last_warning.is_some_and(|at| at.elapsed() < interval) {
+            return;
+        }
+
+        *last_warning = Some(Instant::now());
+        self.inner
+            .overflows_since_warning
+            .fetch_sub(overflows, Ordering::Relaxed);
+        warn!(
+            "Channel for {:?} is full ({} events, {:?}); {} publishes to full channels since the last warning",
+            kind, self.inner.options.capacity, self.inner.options.overflow_policy, overflows
+        );
+    }
+
+    /// Warn when `kind`'s overflow queue reaches
+    /// [`BusOptions::spill_warning_depth`] `depth` events, or double that,
+    /// and so on.
+    fn warn_spill_depth(&self, kind: E::Kind, depth: usize) {
+        let threshold = self.inner.options.spill_warning_depth.max(1);
+        if depth.is_multiple_of(threshold) && (depth / threshold).is_power_of_two() {
+            warn!(
+                "Overflow queue for {:?} has grown to {} events; handlers are falling behind",
+                kind, depth
+            );
+        }
+    }
+
+    /// Move events waiting in `kind`'s overflow queue into its channel, as
+    /// far as space allows. Does nothing unless the policy is
+    /// [`OverflowPolicy::Spill`].
+    pub(crate) fn refill(&self, kind: E::Kind) {
+        if self.inner.options.overflow_policy == OverflowPolicy::Spill {
+            self.refill_from(self.channel(kind), &mut self.spill(kind).lock().unwrap());
+        }
+    }
+
+    fn refill_from(&self, channel: &broadcast::Sender<E>, spill: &mut VecDeque<E>) {
+        if spill.is_empty() {
+            return;
+        }
+        while channel.len() < self.inner.options.capacity {
+            let Some(event) = spill.pop_front() else {
+                break;
+            };
+            // With no receivers left, each handler already gave up its
+            // share of the queue when it unsubscribed
+            let _ = channel.send(event);
+        }
+        // Give back what a burst grew the queue to
+        if spill.is_empty() && spill.capacity() > self.inner.options.capacity {
+            spill.shrink_to(self.inner.options.capacity);
+        }
+    }
+
+    fn channel(&self, kind: E::Kind) -> &broadcast::Sender<E> {
         self.inner
             .channels
             .get(&kind)
             .expect("all event kinds should have channels initialized")
-            .send(event)
+    }
+
+    fn spill(&self, kind: E::Kind) -> &Mutex<VecDeque<E>> {
+        self.inner
+            .spills
+            .get(&kind)
+            .expect("all event kinds should have overflow queues initialized")
+    }
+
+    /// Count `count` events of `kind` lost to a full channel.
+    pub(crate) fn record_dropped(&self, kind: E::Kind, count: u64) {
+        counter(&self.inner.dropped, kind).fetch_add(count, Ordering::Relaxed);
+    }
+
+    fn handler_count(&self, kind: E::Kind) -> &AtomicUsize {
+        self.inner
+            .handlers
+            .get(&kind)
+            .expect("all event kinds should have handler counts initialized")
+    }
+
+    /// Subscribe a handler task, whose events are tracked for draining.
+    ///
+    /// The handler must call [`EventBus::complete`] for every event it
+    /// receives (or skips) and [`EventBus::unsubscribe_handler`] when it stops.
+    pub(crate) fn subscribe_handler(&self, kind: E::Kind) -> Receiver<E> {
+        let rx = self.subscribe(kind);
+        self.handler_count(kind).fetch_add(1, Ordering::AcqRel);
+        rx
+    }
+
+    /// Remove a handler task, dropping the count of events it never handled.
+    pub(crate) fn unsubscribe_handler(&self, kind: E::Kind, rx: Receiver<E>) {
+        self.handler_count(kind).fetch_sub(1, Ordering::AcqRel);
+        let spilled = self.spill(kind).lock().unwrap().len();
+        self.complete(rx.len() + spilled);
+        drop(rx);
+        // Events it hadn't read may have been all that was holding up the queue
+        self.refill(kind);
+    }
+
+    /// Start tracking processing times for a handler task.
+    pub(crate) fn handler_stats(
+        &self,
+        name: &'static str,
+        kind: E::Kind,
+    ) -> Arc<HandlerStats<E::Kind>> {
+        let stats = Arc::new(HandlerStats {
+            name,
+            kind,
+            processed: AtomicU64::new(0),
+            average_nanos: AtomicU64::new(0),
+        });
+        self.inner.handler_stats.lock().unwrap().push(stats.clone());
+        stats
+    }
+
+    /// Record that a handler took `elapsed` to handle one event.
+    ///
+    /// Call before [`EventBus::complete`], so an event never counts as
+    /// drained before it counts as processed.
+    pub(crate) fn record_processed(&self, stats: &HandlerStats<E::Kind>, elapsed: Duration) {
+        let sample = elapsed.as_nanos().min(u64::MAX as u128) as u64;
+        // Only the handler's own task writes its stats
+        let average = match stats.processed.load(Ordering::Relaxed) {
+            0 => sample,
+            _ => {
+                let previous = stats.average_nanos.load(Ordering::Relaxed) as f64;
+                (previous + HANDLER_TIME_SMOOTHING * (sample as f64 - previous)) as u64
+            }
+        };
+        stats.average_nanos.store(average, Ordering::Relaxed);
+        stats.processed.fetch_add(1, Ordering::Relaxed);
+        counter(&self.inner.processed, stats.kind).fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Mark `count` events as handled (or skipped) by a handler.
+    pub(crate) fn complete(&self, count: usize) {
+        if count > 0 {
+            self.inner
+                .pending
+                .send_modify(|pending| *pending = pending.saturating_sub(count));
+        }
     }
 
     /// Subscribe to a specific event kind.
     ///
     /// Returns a `Receiver` that will receive all events of the given kind.
     pub fn subscribe(&self, kind: E::Kind) -> Receiver<E> {
-        self.inner
-            .channels
-            .get(&kind)
-            .expect("all event kinds should have channels initialized")
-            .subscribe()
+        self.channel(kind).subscribe()
+    }
+
+    /// Subscribe to every event kind as a single stream.
+    ///
+    /// Useful for observers that don't implement `EventHandler` (UI layers,
+    /// tests). Events of one kind arrive in publish order; ordering across
+    /// kinds is not guaranteed. Lagged events are skipped with a warning.
+    /// The stream ends when [`EventBus::shutdown`] is called.
+    pub fn subscribe_stream(&self) -> impl Stream<Item = E> + Send + Unpin + 'static {
+        let streams = self.inner.channels.values().map(|tx| {
+            BroadcastStream::new(tx.subscribe())
+                .filter_map(|result| async move {
+                    match result {
+                        Ok(event) => Some(event),
+                        Err(BroadcastStreamRecvError::Lagged(n)) => {
+                            warn!("Event stream lagged, dropped {} events", n);
+                            None
+                        }
+                    }
+                })
+                .boxed()
+        });
+
+        let shutdown = self.inner.shutdown.clone();
+        futures::stream::select_all(streams).take_until(Box::pin(shutdown.cancelled_owned()))
+    }
+
+    /// Signal shutdown, ending all streams from [`EventBus::subscribe_stream`]
+    /// and stopping handler tasks.
+    pub fn shutdown(&self) {
+        self.inner.shutdown.cancel();
+    }
+
+    /// Stop accepting new events, wait until handlers have processed every
+    /// event already published, then [`EventBus::shutdown`].
+    ///
+    /// Follow-up events that handlers publish while draining are still
+    /// delivered and waited for. Events handlers skipped because they lagged
+    /// are not.
+    pub async fn shutdown_and_drain(&self) {
+        self.inner.closed.store(true, Ordering::Release);
+        let mut pending = self.inner.pending.subscribe();
+        // The sender lives as long as the bus, so this can't fail
+        let _ = pending.wait_for(|pending| *pending == 0).await;
+        self.shutdown();
+    }
+
+    /// Resolves once [`EventBus::shutdown`] is called.
+    pub(crate) async fn shutdown_requested(&self) {
+        self.inner.shutdown.cancelled().await
     }
 
     /// Get the configured channel capacity.
     pub fn capacity(&self) -> usize {
-        self.inner.capacity
+        self.inner.options.capacity
+    }
+
+    /// Current counters, for monitoring throughput and handler latency.
+    pub fn metrics(&self) -> BusMetrics<E::Kind> {
+        let snapshot = |counters: &HashMap<E::Kind, AtomicU64>| {
+            counters
+                .iter()
+                .map(|(kind, count)| (*kind, count.load(Ordering::Relaxed)))
+                .collect()
+        };
+
+        BusMetrics {
+            published: snapshot(&self.inner.published),
+            processed: snapshot(&self.inner.processed),
+            dropped: snapshot(&self.inner.dropped),
+            queue_depth: *self.inner.pending.borrow(),
+            spilled: self
+                .inner
+                .spills
+                .iter()
+                .map(|(kind, spill)| (*kind, spill.lock().unwrap().len()))
+                .collect(),
+            handlers: self
+                .inner
+                .handler_stats
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|stats| stats.snapshot())
+                .collect(),
+        }
+    }
+}
+
+fn counter<K: Hash + Eq>(counters: &HashMap<K, AtomicU64>, kind: K) -> &AtomicU64 {
+    counters
+        .get(&kind)
+        .expect("all event kinds should have counters initialized")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum TestEvent {
+        Ping(u32),
+        Pong,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    enum TestEventKind {
+        Ping,
+        Pong,
+    }
+
+    impl EventLike for TestEvent {
+        type Kind = TestEventKind;
+
+        fn kind(&self) -> Self::Kind {
+            match self {
+                TestEvent::Ping(_) => TestEventKind::Ping,
+                TestEvent::Pong => TestEventKind::Pong,
+            }
+        }
+
+        fn all_kinds() -> impl Iterator<Item = Self::Kind> {
+            [TestEventKind::Ping, TestEventKind::Pong].into_iter()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_stream_collects_events() {
+        let bus = EventBus::new(16);
+        let stream = bus.subscribe_stream();
+
+        bus.publish(TestEvent::Ping(1)).unwrap();
+        bus.publish(TestEvent::Pong).unwrap();
+        bus.publish(TestEvent::Ping(2)).unwrap();
+
+        let events: Vec<_> = tokio::time::timeout(Duration::from_secs(1), stream.take(3).collect())
+            .await
+            .expect("stream should yield three events");
+
+        assert_eq!(events.len(), 3);
+        let pings: Vec<_> = events
+            .iter()
+            .filter(|e| matches!(e, TestEvent::Ping(_)))
+            .collect();
+        assert_eq!(pings, [&TestEvent::Ping(1), &TestEvent::Ping(2)]);
+        assert!(events.contains(&TestEvent::Pong));
+    }
+
+    /// Handles one kind of event, taking a little while over each.
+    struct SlowHandler(TestEventKind);
+
+    #[async_trait::async_trait]
+    impl crate::EventHandler<TestEvent> for SlowHandler {
+        fn handles(&self) -> TestEventKind {
+            self.0
+        }
+
+        fn name(&self) -> &'static str {
+            "SlowHandler"
+        }
+
+        async fn handle(&self, _event: TestEvent, _ctx: &crate::HandlerContext<TestEvent>) {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_published_and_processed_events() {
+        let bus = EventBus::new(16);
+        let mut registry = crate::HandlerRegistry::new();
+        registry.register(SlowHandler(TestEventKind::Ping));
+        registry.register(SlowHandler(TestEventKind::Pong));
+        registry.run(bus.clone());
+
+        for i in 0..3 {
+            bus.publish(TestEvent::Ping(i)).unwrap();
+        }
+        for _ in 0..2 {
+            bus.publish(TestEvent::Pong).unwrap();
+        }
+        assert_eq!(bus.metrics().published[&TestEventKind::Ping], 3);
+        assert!(bus.metrics().queue_depth > 0);
+
+        tokio::time::timeout(Duration::from_secs(5), bus.shutdown_and_drain())
+            .await
+            .expect("drain should finish");
+
+        let metrics = bus.metrics();
+        assert_eq!(metrics.published[&TestEventKind::Ping], 3);
+        assert_eq!(metrics.published[&TestEventKind::Pong], 2);
+        assert_eq!(metrics.processed[&TestEventKind::Ping], 3);
+        assert_eq!(metrics.processed[&TestEventKind::Pong], 2);
+        assert_eq!(metrics.queue_depth, 0);
+
+        let handled: Vec<_> = metrics
+            .handlers
+            .iter()
+            .map(|handler| (handler.name, handler.kind, handler.processed))
+            .collect();
+        assert_eq!(
+            handled,
+            [
+                ("SlowHandler", TestEventKind::Ping, 3),
+                ("SlowHandler", TestEventKind::Pong, 2)
+            ]
+        );
+        assert!(
+            metrics
+                .handlers
+                .iter()
+                .all(|handler| handler.average_time >= Duration::from_millis(5))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_drop_policy_counts_refused_events() {
+        let bus = EventBus::with_options(BusOptions {
+            overflow_policy: OverflowPolicy::DropNewest,
+            ..BusOptions::new(2)
+        });
+        // Never reads, so the channel fills up
+        let mut rx = bus.subscribe(TestEventKind::Ping);
+
+        let results: Vec<_> = (0..5).map(|i| bus.publish(TestEvent::Ping(i))).collect();
+
+        assert!(results[..2].iter().all(Result::is_ok));
+        assert!(matches!(results[2], Err(SendError(TestEvent::Ping(2)))));
+        let metrics = bus.metrics();
+        assert_eq!(metrics.published[&TestEventKind::Ping], 2);
+        assert_eq!(metrics.dropped[&TestEventKind::Ping], 3);
+        assert_eq!(metrics.dropped[&TestEventKind::Pong], 0);
+
+        // The oldest events were kept, and space frees up once read
+        assert_eq!(rx.recv().await.unwrap(), TestEvent::Ping(0));
+        assert!(bus.publish(TestEvent::Ping(5)).is_ok());
+        assert_eq!(bus.metrics().dropped[&TestEventKind::Ping], 3);
+    }
+
+    /// Records the value of every ping it handles.
+    struct PingRecorder(Arc<Mutex<Vec<u32>>>);
+
+    #[async_trait::async_trait]
+    impl crate::EventHandler<TestEvent> for PingRecorder {
+        fn handles(&self) -> TestEventKind {
+            TestEventKind::Ping
+        }
+
+        async fn handle(&self, event: TestEvent, _ctx: &crate::HandlerContext<TestEvent>) {
+            if let TestEvent::Ping(i) = event {
+                self.0.lock().unwrap().push(i);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spill_policy_keeps_every_event_of_a_burst() {
+        let bus = EventBus::with_options(BusOptions {
+            overflow_policy: OverflowPolicy::Spill,
+            ..BusOptions::new(16)
+        });
+        let handled = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = crate::HandlerRegistry::new();
+        registry.register(PingRecorder(handled.clone()));
+        registry.run(bus.clone());
+
+        // Far more than the channel holds, before the handler gets to run
+        for i in 0..10_000 {
+            bus.publish(TestEvent::Ping(i)).unwrap();
+        }
+        assert_eq!(bus.metrics().spilled[&TestEventKind::Ping], 10_000 - 16);
+
+        tokio::time::timeout(Duration::from_secs(5), bus.shutdown_and_drain())
+            .await
+            .expect("drain should finish");
+
+        assert_eq!(*handled.lock().unwrap(), (0..10_000).collect::<Vec<_>>());
+        let metrics = bus.metrics();
+        assert_eq!(metrics.published[&TestEventKind::Ping], 10_000);
+        assert_eq!(metrics.processed[&TestEventKind::Ping], 10_000);
+        assert_eq!(metrics.dropped[&TestEventKind::Ping], 0);
+        assert_eq!(metrics.spilled[&TestEventKind::Ping], 0);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_stream_ends_on_shutdown() {
+        let bus = EventBus::<TestEvent>::new(16);
+        let mut stream = bus.subscribe_stream();
+
+        bus.shutdown();
+
+        let next = tokio::time::timeout(Duration::from_secs(1), stream.next())
+            .await
+            .expect("stream should end after shutdown");
+        assert!(next.is_none());
     }
 }
@@ -1,4 +1,6 @@
+use std::any::Any;
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use tokio::sync::broadcast;
@@ -8,33 +10,49 @@ use tracing::{info, warn};
 
 use crate::bus::{EventBus, EventLike};
 
-/// Context passed to handlers - allows publishing follow-up events.
+/// Shared state handed to every handler, see [`HandlerRegistry::set_state`].
+type SharedState = Arc<dyn Any + Send + Sync>;
+
+/// Context passed to handlers - allows publishing follow-up events and
+/// reading state shared by the application.
 #[derive(Clone)]
 pub struct HandlerContext<E: EventLike> {
     bus: EventBus<E>,
+    state: Option<SharedState>,
 }
 
 impl<E: EventLike> HandlerContext<E> {
-    pub(crate) fn new(bus: EventBus<E>) -> Self {
-        Self { bus }
+    pub(crate) fn new(bus: EventBus<E>, state: Option<SharedState>) -> Self {
+        Self { bus, state }
+    }
+
+    /// The state registered with [`HandlerRegistry::set_state`].
+    ///
+    /// Returns `None` if no state was registered or it isn't a `T`.
+    pub fn state<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.state.as_deref()?.downcast_ref()
     }
 
     /// Publish a follow-up event.
     ///
-    /// Errors are ignored (only happens if channel has no subscribers).
+    /// Follow-up events are accepted while the bus drains, so they are
+    /// handled before [`EventBus::shutdown_and_drain`] returns. Errors are
+    /// ignored (only happens if channel has no subscribers).
     pub fn publish(&self, event: E) {
-        let _ = self.bus.publish(event);
+        let _ = self.bus.send(event);
     }
 
     /// Publish with error handling if caller needs to know about failures.
     pub fn try_publish(&self, event: E) -> Result<usize, broadcast::error::SendError<E>> {
-        self.bus.publish(event)
+        self.bus.send(event)
     }
 }
 
 /// Trait for event handlers.
 ///
 /// Handlers are spawned as tokio tasks and receive events from a single channel.
+/// Each handler has its own task, so a slow handler never holds up the
+/// others; its own events are handled one at a time, in publish order.
 #[async_trait]
 pub trait EventHandler<E: EventLike>: Send + Sync {
     /// Which event kind this handler processes.
@@ -45,6 +63,15 @@ pub trait EventHandler<E: EventLike>: Send + Sync {
     /// The handler can emit follow-up events via the context.
     async fn handle(&self, event: E, ctx: &HandlerContext<E>);
 
+    /// Called once when the handler's task starts, before its first event.
+    ///
+    /// Events published meanwhile are queued for the handler, not missed.
+    async fn on_start(&self, _ctx: &HandlerContext<E>) {}
+
+    /// Called once after the handler stops receiving events, whether from
+    /// [`HandlerRegistry::shutdown`] or the bus shutting down.
+    async fn on_shutdown(&self) {}
+
     /// Handler name for logging and debugging.
     fn name(&self) -> &'static str {
         std::any::type_name::<Self>()
@@ -54,6 +81,7 @@ pub trait EventHandler<E: EventLike>: Send + Sync {
 /// Registry for managing handler lifecycle.
 pub struct HandlerRegistry<E: EventLike> {
     handlers: Vec<Arc<dyn EventHandler<E>>>,
+    state: Option<SharedState>,
     cancel_token: CancellationToken,
 }
 
@@ -61,6 +89,7 @@ impl<E: EventLike> HandlerRegistry<E> {
     pub fn new() -> Self {
         Self {
             handlers: Vec::new(),
+            state: None,
             cancel_token: CancellationToken::new(),
         }
     }
@@ -70,19 +99,30 @@ impl<E: EventLike> HandlerRegistry<E> {
         self.handlers.push(Arc::new(handler));
     }
 
+    /// Share `state` with every handler through [`HandlerContext::state`].
+    ///
+    /// Replaces any state set before. Handlers spawned by an earlier
+    /// [`HandlerRegistry::run`] keep the state they started with.
+    pub fn set_state<T: Any + Send + Sync>(&mut self, state: Arc<T>) {
+        self.state = Some(state);
+    }
+
     /// Spawn all handler tasks, returns join handles.
     pub fn run(&self, bus: EventBus<E>) -> Vec<JoinHandle<()>> {
         self.handlers
             .iter()
             .map(|handler| {
                 let kind = handler.handles();
-                let mut rx = bus.subscribe(kind);
+                let mut rx = bus.subscribe_handler(kind);
                 let handler = Arc::clone(handler);
-                let bus_clone = bus.clone();
+                let bus = bus.clone();
+                let ctx = HandlerContext::new(bus.clone(), self.state.clone());
                 let token = self.cancel_token.clone();
                 let handler_name = handler.name();
+                let stats = bus.handler_stats(handler_name, kind);
 
                 tokio::spawn(async move {
+                    handler.on_start(&ctx).await;
                     info!("Handler {} started, listening for {:?}", handler_name, kind);
 
                     let mut events_received: u64 = 0;
@@ -97,15 +137,27 @@ impl<E: EventLike> HandlerRegistry<E> {
                                 );
                                 break;
                             }
+                            _ = bus.shutdown_requested() => {
+                                info!(
+                                    "Handler {} stopped with the bus - received: {}, lagged: {}",
+                                    handler_name, events_received, events_lagged
+                                );
+                                break;
+                            }
                             result = rx.recv() => {
                                 match result {
                                     Ok(event) => {
                                         events_received += 1;
-                                        let ctx = HandlerContext::new(bus_clone.clone());
+                                        let started = Instant::now();
                                         handler.handle(event, &ctx).await;
+                                        bus.record_processed(&stats, started.elapsed());
+                                        bus.complete(1);
+                                        bus.refill(kind);
                                     }
                                     Err(broadcast::error::RecvError::Lagged(n)) => {
                                         events_lagged += n;
+                                        bus.record_dropped(kind, n);
+                                        bus.complete(n as usize);
                                         warn!(
                                             "Handler {} lagged, dropped {} events (total lagged: {})",
                                             handler_name, n, events_lagged
@@ -122,6 +174,9 @@ impl<E: EventLike> HandlerRegistry<E> {
                             }
                         }
                     }
+
+                    bus.unsubscribe_handler(kind, rx);
+                    handler.on_shutdown().await;
                 })
             })
             .collect()
@@ -139,3 +194,258 @@ impl<E: EventLike> Default for HandlerRegistry<E> {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[derive(Clone, Debug)]
+    struct Ping;
+
+    impl EventLike for Ping {
+        type Kind = ();
+
+        fn kind(&self) -> Self::Kind {}
+
+        fn all_kinds() -> impl Iterator<Item = Self::Kind> {
+            std::iter::once(())
+        }
+    }
+
+    /// Records the shared greeting each time it handles a ping.
+    struct GreetingReader {
+        seen: Arc<Mutex<Vec<Option<String>>>>,
+    }
+
+    #[async_trait]
+    impl EventHandler<Ping> for GreetingReader {
+        fn handles(&self) {}
+
+        async fn handle(&self, _event: Ping, ctx: &HandlerContext<Ping>) {
+            let greeting = ctx
+                .state::<Mutex<String>>()
+                .map(|s| s.lock().unwrap().clone());
+            self.seen.lock().unwrap().push(greeting);
+            // State of another type isn't visible
+            assert!(ctx.state::<u32>().is_none());
+        }
+    }
+
+    async fn run_with_state(state: Option<Arc<Mutex<String>>>) -> Vec<Option<String>> {
+        let bus = EventBus::new(4);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = HandlerRegistry::new();
+        registry.register(GreetingReader { seen: seen.clone() });
+        if let Some(state) = state.clone() {
+            registry.set_state(state);
+        }
+        let handles = registry.run(bus.clone());
+
+        bus.publish(Ping).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        if let Some(state) = state {
+            *state.lock().unwrap() = "updated".to_string();
+        }
+        bus.publish(Ping).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        registry.shutdown();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        seen.lock().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn test_handlers_read_shared_state() {
+        let state = Arc::new(Mutex::new("hello".to_string()));
+
+        let seen = run_with_state(Some(state)).await;
+
+        assert_eq!(
+            seen,
+            [Some("hello".to_string()), Some("updated".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_state_is_none_when_unset() {
+        assert_eq!(run_with_state(None).await, [None, None]);
+    }
+
+    /// Records its lifecycle hooks alongside the pings it handles.
+    struct Lifecycle {
+        log: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait]
+    impl EventHandler<Ping> for Lifecycle {
+        fn handles(&self) {}
+
+        async fn on_start(&self, _ctx: &HandlerContext<Ping>) {
+            self.log.lock().unwrap().push("start");
+        }
+
+        async fn handle(&self, _event: Ping, _ctx: &HandlerContext<Ping>) {
+            self.log.lock().unwrap().push("ping");
+        }
+
+        async fn on_shutdown(&self) {
+            self.log.lock().unwrap().push("shutdown");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_hooks_run_once_around_events() {
+        let bus = EventBus::new(4);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = HandlerRegistry::new();
+        registry.register(Lifecycle { log: log.clone() });
+        let handles = registry.run(bus.clone());
+
+        bus.publish(Ping).unwrap();
+        bus.publish(Ping).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        registry.shutdown();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(*log.lock().unwrap(), ["start", "ping", "ping", "shutdown"]);
+    }
+
+    /// Records its name for every ping after sleeping for `delay`.
+    struct Sleeper {
+        name: &'static str,
+        delay: Duration,
+        log: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait]
+    impl EventHandler<Ping> for Sleeper {
+        fn handles(&self) {}
+
+        async fn handle(&self, _event: Ping, _ctx: &HandlerContext<Ping>) {
+            tokio::time::sleep(self.delay).await;
+            self.log.lock().unwrap().push(self.name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_handler_does_not_block_others() {
+        let bus = EventBus::new(16);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = HandlerRegistry::new();
+        registry.register(Sleeper {
+            name: "slow",
+            delay: Duration::from_millis(500),
+            log: log.clone(),
+        });
+        registry.register(Sleeper {
+            name: "fast",
+            delay: Duration::ZERO,
+            log: log.clone(),
+        });
+        let handles = registry.run(bus.clone());
+
+        for _ in 0..3 {
+            bus.publish(Ping).unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // The fast handler got through every ping while the slow one is
+        // still on its first
+        assert_eq!(*log.lock().unwrap(), ["fast", "fast", "fast"]);
+
+        registry.shutdown();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    }
+
+    /// Counts down: handling `Countdown(n)` publishes `Countdown(n - 1)`.
+    #[derive(Clone, Debug)]
+    struct Countdown(u32);
+
+    impl EventLike for Countdown {
+        type Kind = ();
+
+        fn kind(&self) -> Self::Kind {}
+
+        fn all_kinds() -> impl Iterator<Item = Self::Kind> {
+            std::iter::once(())
+        }
+    }
+
+    /// Slowly handles countdowns, recording each value.
+    struct SlowCountdown {
+        handled: Arc<Mutex<Vec<u32>>>,
+    }
+
+    #[async_trait]
+    impl EventHandler<Countdown> for SlowCountdown {
+        fn handles(&self) {}
+
+        async fn handle(&self, event: Countdown, ctx: &HandlerContext<Countdown>) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.handled.lock().unwrap().push(event.0);
+            if event.0 > 0 {
+                ctx.publish(Countdown(event.0 - 1));
+            }
+        }
+    }
+
+    fn run_countdown(bus: &EventBus<Countdown>) -> Arc<Mutex<Vec<u32>>> {
+        let handled = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = HandlerRegistry::new();
+        registry.register(SlowCountdown {
+            handled: handled.clone(),
+        });
+        registry.run(bus.clone());
+        handled
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_and_drain_handles_queued_events() {
+        let bus = EventBus::new(16);
+        let handled = run_countdown(&bus);
+
+        for _ in 0..5 {
+            bus.publish(Countdown(0)).unwrap();
+        }
+        tokio::time::timeout(Duration::from_secs(5), bus.shutdown_and_drain())
+            .await
+            .expect("drain should finish");
+
+        assert_eq!(handled.lock().unwrap().len(), 5);
+        // New events are refused once draining starts
+        assert!(bus.publish(Countdown(0)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_and_drain_waits_for_follow_up_events() {
+        let bus = EventBus::new(16);
+        let handled = run_countdown(&bus);
+
+        bus.publish(Countdown(3)).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), bus.shutdown_and_drain())
+            .await
+            .expect("drain should finish");
+
+        assert_eq!(*handled.lock().unwrap(), [3, 2, 1, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_and_drain_without_handlers() {
+        let bus = EventBus::<Countdown>::new(16);
+        let _rx = bus.subscribe(());
+        bus.publish(Countdown(1)).unwrap();
+
+        // Plain subscribers aren't waited for
+        tokio::time::timeout(Duration::from_secs(1), bus.shutdown_and_drain())
+            .await
+            .expect("drain should finish");
+    }
+}
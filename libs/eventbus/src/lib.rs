@@ -1,5 +1,8 @@
 mod bus;
 mod handler;
 
-pub use bus::{EventBus, EventLike, Receiver, SendError};
+pub use bus::{
+    BusMetrics, BusOptions, EventBus, EventLike, HandlerMetrics, OverflowPolicy, Receiver,
+    SendError,
+};
 pub use handler::{EventHandler, HandlerContext, HandlerRegistry};
@@ -8,7 +8,17 @@
 //! - **Default values**: `#[field_name = "Fuel"] #[missing = "50.0"]`
 //! - **Critical fields**: `#[field_name = "Temp"] #[fail_if_missing]`
 //! - **Calculated fields**: `#[calculated = "42"]` - computed at runtime
-//! - **Skipped fields**: `#[skip]` - application-managed, not from telemetry
+//! - **Derived fields**: `#[derived = "combined_g"]` - built-in calculated channels
+//! - **Skipped fields**: `#[skip]` - application-managed, not from telemetry;
+//!   `#[skip = "1"]` sets the initial value instead of `Default::default()`
+//! - **Frame tick**: `#[pitwall(with_tick)]` on the struct fills its
+//!   `frame_tick: u32` field with the packet's tick, for correlating with sim time
+//! - **Schema version gate**: `#[pitwall(min_schema_version = 2)]` on the struct
+//!   makes `validate_schema` reject telemetry from older header versions
+//! - **Type mismatches**: `#[pitwall(on_type_mismatch = "...")]` on the struct
+//!   picks how fields that can't be decoded are reported: `"warn_once"` (the
+//!   default) logs once per field, `"warn_always"` on every frame, and
+//!   `"error"` makes `validate_schema` reject schemas whose types don't match
 //!
 //! # Example Usage
 //!
@@ -51,7 +61,17 @@ use syn::{
 /// 2. Runtime field extraction with zero HashMap lookups
 #[proc_macro_derive(
     PitwallFrame,
-    attributes(field_name, missing, fail_if_missing, calculated, skip, bitfield, bitfield_map)
+    attributes(
+        field_name,
+        missing,
+        fail_if_missing,
+        calculated,
+        derived,
+        skip,
+        bitfield,
+        bitfield_map,
+        pitwall
+    )
 )]
 pub fn derive_from_raw_frame(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -75,12 +95,27 @@ fn generate_frame_adapter(input: &DeriveInput) -> syn::Result<TokenStream> {
         _ => return Err(syn::Error::new_spanned(input, "Only structs are supported")),
     };
 
+    let options = parse_struct_options(&input.attrs)?;
+
     // Parse each field into strategies
     let mut field_strategies = Vec::new();
     for field in fields.iter() {
-        let strategy = parse_field_strategy(field)?;
+        let strategy = match &field.ident {
+            Some(ident) if options.with_tick && ident == "frame_tick" => {
+                FieldStrategy::Tick { field_ident: ident.clone() }
+            }
+            _ => parse_field_strategy(field)?,
+        };
         field_strategies.push(strategy);
     }
+    if options.with_tick
+        && !field_strategies.iter().any(|strategy| matches!(strategy, FieldStrategy::Tick { .. }))
+    {
+        return Err(syn::Error::new_spanned(
+            input,
+            "#[pitwall(with_tick)] requires a `frame_tick: u32` field",
+        ));
+    }
 
     // Build lookup map for calculated expressions
     let mut telemetry_map: HashMap<String, (usize, syn::Type)> = HashMap::new();
@@ -98,26 +133,55 @@ fn generate_frame_adapter(input: &DeriveInput) -> syn::Result<TokenStream> {
             | FieldStrategy::BitfieldMap { field_name: _, .. } => {
                 // Bitfield variables have u32 underlying type (BitField). Calculated expressions rarely reference them directly; skip mapping.
             }
-            FieldStrategy::Calculated { .. } | FieldStrategy::Skipped { .. } => {}
+            FieldStrategy::Calculated { .. }
+            | FieldStrategy::Derived { .. }
+            | FieldStrategy::Skipped { .. }
+            | FieldStrategy::Tick { .. } => {}
         }
     }
 
     // Generate validation phase code
     let (validation_checks, extraction_plan_items) = generate_validation_phase(&field_strategies);
+    let type_checks = generate_type_checks(&field_strategies);
+    let type_check_count = type_checks.len();
 
     // Generate extraction phase code
-    let extraction_assignments = generate_extraction_phase(&field_strategies, &telemetry_map)?;
+    let extraction_assignments =
+        generate_extraction_phase(&field_strategies, &telemetry_map, options.on_type_mismatch)?;
+
+    let reject_mismatches = if options.on_type_mismatch == MismatchMode::Error {
+        quote! {
+            if let ::core::option::Option::Some(warning) = type_checks.iter().flatten().next() {
+                return Err(::pitwall::TelemetryError::from(warning.clone()));
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let min_schema_version = match options.min_schema_version {
+        Some(version) => quote! { ::core::option::Option::Some(#version) },
+        None => quote! { ::core::option::Option::None },
+    };
 
     // Generate the complete implementation
     let expanded = quote! {
         impl ::pitwall::adapters::FrameAdapter for #struct_name {
+            const MIN_SCHEMA_VERSION: ::core::option::Option<u32> = #min_schema_version;
+
             fn validate_schema(schema: &::pitwall::VariableSchema) -> ::pitwall::Result<::pitwall::adapters::AdapterValidation> {
                 use ::pitwall::adapters::FieldExtraction;
 
+                <Self as ::pitwall::adapters::FrameAdapter>::check_schema_version(schema)?;
+
                 #(#validation_checks)*
 
                 let extraction_plan = vec![#(#extraction_plan_items),*];
-                Ok(::pitwall::adapters::AdapterValidation::new(extraction_plan))
+                let type_checks: [::core::option::Option<::pitwall::adapters::SchemaWarning>; #type_check_count] =
+                    [#(#type_checks),*];
+                #reject_mismatches
+                let warnings = type_checks.into_iter().flatten().collect();
+                Ok(::pitwall::adapters::AdapterValidation::new(extraction_plan).with_warnings(warnings))
             }
 
             fn adapt(packet: &::pitwall::types::FramePacket, validation: &::pitwall::adapters::AdapterValidation) -> Self {
@@ -157,7 +221,14 @@ enum FieldStrategy {
         expression: Expr,
         expression_str: String,
     },
-    /// Bitfield single-bit extraction to bool/Option<bool> using mask
+    /// Built-in calculated channel selected by name from [`DERIVATIONS`].
+    Derived {
+        field_ident: syn::Ident,
+        field_type: syn::Type,
+        expression: Expr,
+        expression_str: String,
+    },
+    /// Bitfield mask test to bool/Option<bool> (`has_flag`, `has_any` or `has_all`)
     BitfieldHas {
         field_name: String,
         field_ident: syn::Ident,
@@ -165,8 +236,10 @@ enum FieldStrategy {
         default_expr: Option<Expr>,
         fail_if_missing: bool,
         mask_expr: Expr,
+        mask_method: syn::Ident,
     },
-    /// Bitfield decode using a user-provided decoder: fn(BitField) -> T
+    /// Bitfield decode using a user-provided decoder: fn(BitField) -> T.
+    /// `Option<T>` targets also accept fn(BitField) -> Option<T>, flattened.
     BitfieldMap {
         field_name: String,
         field_ident: syn::Ident,
@@ -175,8 +248,71 @@ enum FieldStrategy {
         fail_if_missing: bool,
         decoder_expr: Expr,
     },
-    /// Field managed entirely by application code.
-    Skipped { field_ident: syn::Ident, field_type: syn::Type },
+    /// Field managed entirely by application code, starting from the
+    /// `#[skip = "..."]` initializer or `<T as Default>::default()`.
+    Skipped { field_ident: syn::Ident, field_type: syn::Type, initializer: Option<Expr> },
+    /// `frame_tick` field filled from `FramePacket::tick` under `#[pitwall(with_tick)]`.
+    Tick { field_ident: syn::Ident },
+}
+
+/// Struct-level `#[pitwall(...)]` options.
+#[derive(Default)]
+struct StructOptions {
+    /// Fill the `frame_tick` field from the packet's tick.
+    with_tick: bool,
+    /// Oldest telemetry header version the adapter accepts.
+    min_schema_version: Option<u32>,
+    /// How fields that fail to decode are reported.
+    on_type_mismatch: MismatchMode,
+}
+
+/// Struct-level `#[pitwall(on_type_mismatch = "...")]` setting.
+#[derive(Clone, Copy, Default, PartialEq)]
+enum MismatchMode {
+    /// Warn the first time each field fails to decode.
+    #[default]
+    WarnOnce,
+    /// Warn every time a field fails to decode.
+    WarnAlways,
+    /// Reject schemas with mismatched types in `validate_schema`; anything
+    /// failing to decode later (a malformed packet) is warned about once.
+    Error,
+}
+
+/// Parse `#[pitwall(...)]` attributes on the struct itself
+fn parse_struct_options(attrs: &[Attribute]) -> syn::Result<StructOptions> {
+    let mut options = StructOptions::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("pitwall")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("with_tick") {
+                options.with_tick = true;
+                Ok(())
+            } else if meta.path.is_ident("min_schema_version") {
+                let version: LitInt = meta.value()?.parse()?;
+                options.min_schema_version = Some(version.base10_parse()?);
+                Ok(())
+            } else if meta.path.is_ident("on_type_mismatch") {
+                let mode: LitStr = meta.value()?.parse()?;
+                options.on_type_mismatch = match mode.value().as_str() {
+                    "warn_once" => MismatchMode::WarnOnce,
+                    "warn_always" => MismatchMode::WarnAlways,
+                    "error" => MismatchMode::Error,
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            mode,
+                            "expected `\"warn_once\"`, `\"warn_always\"` or `\"error\"`",
+                        ));
+                    }
+                };
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "unknown pitwall option, expected `with_tick`, `min_schema_version` or `on_type_mismatch`",
+                ))
+            }
+        })?;
+    }
+    Ok(options)
 }
 
 /// Parse a single field into its strategy
@@ -209,7 +345,7 @@ fn parse_field_strategy(field: &Field) -> syn::Result<FieldStrategy> {
         };
 
         match bit_attr {
-            BitfieldAttr::Has { name, mask } => {
+            BitfieldAttr::Has { name, mask, mask_method } => {
                 // Validate target type: bool or Option<bool>
                 let is_bool = if target_is_option {
                     extract_option_type(&field_type).map(|t| quote::quote!(#t).to_string())
@@ -220,7 +356,7 @@ fn parse_field_strategy(field: &Field) -> syn::Result<FieldStrategy> {
                 if !is_bool {
                     return Err(syn::Error::new_spanned(
                         &field.ty,
-                        "#[bitfield(..., has = ...)] requires field type bool or Option<bool>",
+                        "#[bitfield(..., has/any/all = ...)] requires field type bool or Option<bool>",
                     ));
                 }
                 let mask_expr: Expr = syn::parse_str(&mask)?;
@@ -233,6 +369,7 @@ fn parse_field_strategy(field: &Field) -> syn::Result<FieldStrategy> {
                     default_expr,
                     fail_if_missing,
                     mask_expr,
+                    mask_method,
                 });
             }
             BitfieldAttr::Map { name, decoder } => {
@@ -257,7 +394,8 @@ fn parse_field_strategy(field: &Field) -> syn::Result<FieldStrategy> {
     let mut default_value: Option<String> = None;
     let mut fail_if_missing = false;
     let mut calculated: Option<String> = None;
-    let mut skip = false;
+    let mut derived: Option<String> = None;
+    let mut skip: Option<Option<String>> = None;
 
     for attr in &field.attrs {
         if let Ok(attr_value) = parse_attribute(attr) {
@@ -266,13 +404,15 @@ fn parse_field_strategy(field: &Field) -> syn::Result<FieldStrategy> {
                 AttributeValue::Missing(value) => default_value = Some(value),
                 AttributeValue::FailIfMissing => fail_if_missing = true,
                 AttributeValue::Calculated(expr) => calculated = Some(expr),
-                AttributeValue::Skip => skip = true,
+                AttributeValue::Derived(name) => derived = Some(name),
+                AttributeValue::Skip(initializer) => skip = Some(initializer),
             }
         }
     }
 
-    if skip {
-        return Ok(FieldStrategy::Skipped { field_ident, field_type });
+    if let Some(initializer) = skip {
+        let initializer = initializer.map(|expr_str| syn::parse_str(&expr_str)).transpose()?;
+        return Ok(FieldStrategy::Skipped { field_ident, field_type, initializer });
     }
 
     if let Some(expr_str) = calculated {
@@ -285,6 +425,23 @@ fn parse_field_strategy(field: &Field) -> syn::Result<FieldStrategy> {
         });
     }
 
+    if let Some(name) = derived {
+        let expr_str = derived_expression(&name).ok_or_else(|| {
+            let known: Vec<&str> = DERIVATIONS.iter().map(|(name, _)| *name).collect();
+            syn::Error::new_spanned(
+                field,
+                format!("Unknown derivation \"{}\". Known derivations: {}", name, known.join(", ")),
+            )
+        })?;
+        let expression: Expr = syn::parse_str(expr_str)?;
+        return Ok(FieldStrategy::Derived {
+            field_ident,
+            field_type,
+            expression,
+            expression_str: expr_str.to_string(),
+        });
+    }
+
     let field_name = field_name.ok_or_else(|| {
         syn::Error::new_spanned(
             field,
@@ -313,6 +470,20 @@ fn parse_field_strategy(field: &Field) -> syn::Result<FieldStrategy> {
     Ok(FieldStrategy::TypeDefault { field_name, field_ident, field_type })
 }
 
+/// Built-in derivations for `#[derived = "..."]`, as calculated expressions
+/// over telemetry variable names.
+const DERIVATIONS: &[(&str, &str)] = &[
+    // Combined lateral and longitudinal acceleration in g
+    ("combined_g", "(LatAccel * LatAccel + LongAccel * LongAccel).sqrt() / 9.80665"),
+    ("speed_kmh", "Speed * 3.6"),
+    ("speed_mph", "Speed * 2.236_936"),
+];
+
+/// Look up the calculated expression for a built-in derivation.
+fn derived_expression(name: &str) -> Option<&'static str> {
+    DERIVATIONS.iter().find(|(known, _)| *known == name).map(|(_, expr)| *expr)
+}
+
 /// Parsed attribute values
 #[derive(Debug)]
 enum AttributeValue {
@@ -320,13 +491,14 @@ enum AttributeValue {
     Missing(String),
     FailIfMissing,
     Calculated(String),
-    Skip,
+    Derived(String),
+    Skip(Option<String>),
 }
 
 /// Parsed bitfield attributes
 #[derive(Debug)]
 enum BitfieldAttr {
-    Has { name: String, mask: String },
+    Has { name: String, mask: String, mask_method: syn::Ident },
     Map { name: String, decoder: String },
 }
 
@@ -338,7 +510,7 @@ fn parse_bitfield_attr(field: &Field) -> syn::Result<Option<BitfieldAttr>> {
         if let Meta::List(list) = &attr.meta {
             if list.path.is_ident("bitfield") {
                 let mut name: Option<String> = None;
-                let mut mask: Option<String> = None;
+                let mut mask: Option<(String, &str)> = None;
                 let pairs: Punctuated<MetaNameValue, Token![,]> =
                     Punctuated::parse_terminated.parse2(list.tokens.clone())?;
                 for nv in pairs {
@@ -352,14 +524,27 @@ fn parse_bitfield_attr(field: &Field) -> syn::Result<Option<BitfieldAttr>> {
                                 "bitfield name must be a string literal",
                             ));
                         }
-                    } else if nv.path.is_ident("has") {
+                    } else if let Some((key, method)) = [
+                        ("has", "has_flag"),
+                        ("any", "has_any"),
+                        ("all", "has_all"),
+                    ]
+                    .into_iter()
+                    .find(|(key, _)| nv.path.is_ident(key))
+                    {
+                        if mask.is_some() {
+                            return Err(syn::Error::new_spanned(
+                                &nv.path,
+                                "bitfield accepts only one of has, any or all",
+                            ));
+                        }
                         if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) = nv.value
                         {
-                            mask = Some(s.value());
+                            mask = Some((s.value(), method));
                         } else {
                             return Err(syn::Error::new_spanned(
                                 &nv.value,
-                                "bitfield has must be a string literal expression path",
+                                format!("bitfield {key} must be a string literal mask expression"),
                             ));
                         }
                     }
@@ -367,10 +552,14 @@ fn parse_bitfield_attr(field: &Field) -> syn::Result<Option<BitfieldAttr>> {
                 let name = name.ok_or_else(|| {
                     syn::Error::new_spanned(attr, "bitfield requires name = \"...\"")
                 })?;
-                let mask = mask.ok_or_else(|| {
-                    syn::Error::new_spanned(attr, "bitfield requires has = \"...\"")
+                let (mask, method) = mask.ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        attr,
+                        "bitfield requires one of has, any or all = \"...\"",
+                    )
                 })?;
-                return Ok(Some(BitfieldAttr::Has { name, mask }));
+                let mask_method = format_ident!("{}", method);
+                return Ok(Some(BitfieldAttr::Has { name, mask, mask_method }));
             } else if list.path.is_ident("bitfield_map") {
                 let mut name: Option<String> = None;
                 let mut decoder: Option<String> = None;
@@ -469,8 +658,22 @@ fn parse_attribute(attr: &Attribute) -> syn::Result<AttributeValue> {
                 ))
             }
         }
+        Meta::NameValue(name_value) if name_value.path.is_ident("derived") => {
+            if let Expr::Lit(syn::ExprLit { lit: Lit::Str(lit_str), .. }) = &name_value.value {
+                Ok(AttributeValue::Derived(lit_str.value()))
+            } else {
+                Err(syn::Error::new_spanned(&name_value.value, "derived must be a string literal"))
+            }
+        }
         Meta::Path(path) if path.is_ident("fail_if_missing") => Ok(AttributeValue::FailIfMissing),
-        Meta::Path(path) if path.is_ident("skip") => Ok(AttributeValue::Skip),
+        Meta::Path(path) if path.is_ident("skip") => Ok(AttributeValue::Skip(None)),
+        Meta::NameValue(name_value) if name_value.path.is_ident("skip") => {
+            if let Expr::Lit(syn::ExprLit { lit: Lit::Str(lit_str), .. }) = &name_value.value {
+                Ok(AttributeValue::Skip(Some(lit_str.value())))
+            } else {
+                Err(syn::Error::new_spanned(&name_value.value, "skip must be a string literal"))
+            }
+        }
         _ => Err(syn::Error::new_spanned(attr, "Unknown attribute")),
     }
 }
@@ -552,11 +755,10 @@ fn generate_validation_phase(
                 let field_name_lit = field_name;
                 validation_checks.push(quote! {
                     if !schema.variables.contains_key(#field_name_lit) {
-                        let available_fields: Vec<String> = schema.variables.keys().cloned().collect();
-                        return Err(::pitwall::TelemetryError::Parse {
-                            context: "Frame adapter validation".to_string(),
-                            details: format!("Critical field '{}' is missing from schema. Connection aborted. Available fields: {}",
-                                #field_name_lit, available_fields.join(", ")),
+                        let diagnosis = ::pitwall::adapters::AdapterValidation::diagnose(schema, &[#field_name_lit]);
+                        return Err(::pitwall::TelemetryError::FieldMissing {
+                            name: #field_name_lit.to_string(),
+                            suggestion: diagnosis.suggestion_for(#field_name_lit).map(str::to_string),
                         });
                     }
                 });
@@ -595,11 +797,10 @@ fn generate_validation_phase(
                     // Override to Required path (strongest semantics)
                     validation_checks.push(quote! {
                         if !schema.variables.contains_key(#field_name_lit) {
-                            let available_fields: Vec<String> = schema.variables.keys().cloned().collect();
-                            return Err(::pitwall::TelemetryError::Parse {
-                                context: "Frame adapter validation".to_string(),
-                                details: format!("Critical field '{}' is missing from schema. Connection aborted. Available fields: {}",
-                                    #field_name_lit, available_fields.join(", ")),
+                            let diagnosis = ::pitwall::adapters::AdapterValidation::diagnose(schema, &[#field_name_lit]);
+                            return Err(::pitwall::TelemetryError::FieldMissing {
+                                name: #field_name_lit.to_string(),
+                                suggestion: diagnosis.suggestion_for(#field_name_lit).map(str::to_string),
                             });
                         }
                     });
@@ -634,7 +835,8 @@ fn generate_validation_phase(
                     });
                 }
             }
-            FieldStrategy::Calculated { expression_str, .. } => {
+            FieldStrategy::Calculated { expression_str, .. }
+            | FieldStrategy::Derived { expression_str, .. } => {
                 extraction_plan_items.push(quote! {
                     FieldExtraction::Calculated {
                         expression: #expression_str.to_string(),
@@ -646,12 +848,57 @@ fn generate_validation_phase(
                     FieldExtraction::Skipped
                 });
             }
+            FieldStrategy::Tick { .. } => {
+                extraction_plan_items.push(quote! {
+                    FieldExtraction::Calculated {
+                        expression: "packet.tick".to_string(),
+                    }
+                });
+            }
         }
     }
 
     (validation_checks, extraction_plan_items)
 }
 
+/// Generate a check per telemetry field that its schema type can be read as
+/// the Rust type the field is extracted as, yielding a warning if not.
+fn generate_type_checks(strategies: &[FieldStrategy]) -> Vec<proc_macro2::TokenStream> {
+    let bitfield_type: syn::Type = syn::parse_quote!(::pitwall::BitField);
+
+    strategies
+        .iter()
+        .filter_map(|strategy| match strategy {
+            FieldStrategy::TypeDefault { field_name, field_type, .. }
+            | FieldStrategy::WithDefault { field_name, field_type, .. }
+            | FieldStrategy::Critical { field_name, field_type, .. } => {
+                Some((field_name, field_type))
+            }
+            FieldStrategy::Optional { field_name, inner_type, .. } => {
+                Some((field_name, inner_type))
+            }
+            FieldStrategy::BitfieldHas { field_name, .. }
+            | FieldStrategy::BitfieldMap { field_name, .. } => Some((field_name, &bitfield_type)),
+            FieldStrategy::Calculated { .. }
+            | FieldStrategy::Derived { .. }
+            | FieldStrategy::Skipped { .. }
+            | FieldStrategy::Tick { .. } => None,
+        })
+        .map(|(field_name, read_type)| {
+            quote! {
+                schema
+                    .get_variable(#field_name)
+                    .filter(|info| !<#read_type as ::pitwall::VarData>::accepts(info))
+                    .map(|info| ::pitwall::adapters::SchemaWarning::TypeMismatch {
+                        field: #field_name.to_string(),
+                        expected: ::std::any::type_name::<#read_type>(),
+                        actual: info.data_type,
+                    })
+            }
+        })
+        .collect()
+}
+
 /// Process calculated field expressions to replace telemetry field names with extraction calls
 fn process_calculated_expression(
     expr: &Expr,
@@ -694,15 +941,44 @@ impl<'a> Fold for CalculatedExprFolder<'a> {
     }
 }
 
+/// A decode failure warning for a generated field assignment, logged as
+/// `mode` asks. `fields` are the `tracing` fields, each followed by a comma.
+fn decode_warning(
+    mode: MismatchMode,
+    fields: proc_macro2::TokenStream,
+    message: &str,
+) -> proc_macro2::TokenStream {
+    match mode {
+        MismatchMode::WarnAlways => quote! {
+            ::tracing::warn!(#fields #message);
+        },
+        MismatchMode::WarnOnce | MismatchMode::Error => {
+            let message = format!("{} (warning shown once)", message);
+            quote! {
+                static WARNED: ::std::sync::Once = ::std::sync::Once::new();
+                WARNED.call_once(|| {
+                    ::tracing::warn!(#fields #message);
+                });
+            }
+        }
+    }
+}
+
 /// Generate field assignment for TypeDefault strategy
 fn generate_type_default_assignment(
     index: usize,
     field_ident: &syn::Ident,
     field_type: &syn::Type,
     field_name: &str,
+    mode: MismatchMode,
 ) -> proc_macro2::TokenStream {
     let index_lit = LitInt::new(&index.to_string(), proc_macro2::Span::call_site());
     let field_name_lit = field_name;
+    let mismatch_warning = decode_warning(
+        mode,
+        quote! { field = #field_name_lit, expected_type = ::std::any::type_name::<#field_type>(), actual_type = ?var_info.data_type, error = ?_e, },
+        "Type mismatch: failed to convert field, using default value",
+    );
     quote! {
         #field_ident: {
             match validation.extraction_plan.get(#index_lit) {
@@ -711,16 +987,7 @@ fn generate_type_default_assignment(
                         match <#field_type as ::pitwall::VarData>::from_bytes(&data, var_info) {
                             Ok(value) => value,
                             Err(_e) => {
-                                static WARNED: ::std::sync::Once = ::std::sync::Once::new();
-                                WARNED.call_once(|| {
-                                    ::tracing::warn!(
-                                        field = #field_name_lit,
-                                        expected_type = ::std::any::type_name::<#field_type>(),
-                                        actual_type = ?var_info.data_type,
-                                        error = ?_e,
-                                        "Type mismatch: failed to convert field, using default value (warning shown once)"
-                                    );
-                                });
+                                #mismatch_warning
                                 <#field_type as ::core::default::Default>::default()
                             }
                         }
@@ -741,9 +1008,15 @@ fn generate_with_default_assignment(
     field_type: &syn::Type,
     default_expr: &Expr,
     field_name: &str,
+    mode: MismatchMode,
 ) -> proc_macro2::TokenStream {
     let index_lit = LitInt::new(&index.to_string(), proc_macro2::Span::call_site());
     let field_name_lit = field_name;
+    let mismatch_warning = decode_warning(
+        mode,
+        quote! { field = #field_name_lit, expected_type = ::std::any::type_name::<#field_type>(), actual_type = ?var_info.data_type, error = ?_e, },
+        "Type mismatch: failed to convert field, using default value",
+    );
     quote! {
         #field_ident: {
             let fallback = || -> #field_type { #default_expr };
@@ -753,16 +1026,7 @@ fn generate_with_default_assignment(
                         match <#field_type as ::pitwall::VarData>::from_bytes(&data, var_info) {
                             Ok(value) => value,
                             Err(_e) => {
-                                static WARNED: ::std::sync::Once = ::std::sync::Once::new();
-                                WARNED.call_once(|| {
-                                    ::tracing::warn!(
-                                        field = #field_name_lit,
-                                        expected_type = ::std::any::type_name::<#field_type>(),
-                                        actual_type = ?var_info.data_type,
-                                        error = ?_e,
-                                        "Type mismatch: failed to convert field, using default value (warning shown once)"
-                                    );
-                                });
+                                #mismatch_warning
                                 fallback()
                             }
                         }
@@ -782,9 +1046,15 @@ fn generate_optional_assignment(
     field_ident: &syn::Ident,
     inner_type: &syn::Type,
     field_name: &str,
+    mode: MismatchMode,
 ) -> proc_macro2::TokenStream {
     let index_lit = LitInt::new(&index.to_string(), proc_macro2::Span::call_site());
     let field_name_lit = field_name;
+    let mismatch_warning = decode_warning(
+        mode,
+        quote! { field = #field_name_lit, expected_type = ::std::any::type_name::<#inner_type>(), actual_type = ?var_info.data_type, error = ?_e, },
+        "Type mismatch: failed to convert optional field, using None",
+    );
     quote! {
         #field_ident: {
             match validation.extraction_plan.get(#index_lit) {
@@ -793,16 +1063,7 @@ fn generate_optional_assignment(
                         match <#inner_type as ::pitwall::VarData>::from_bytes(&data, var_info) {
                             Ok(value) => Some(value),
                             Err(_e) => {
-                                static WARNED: ::std::sync::Once = ::std::sync::Once::new();
-                                WARNED.call_once(|| {
-                                    ::tracing::warn!(
-                                        field = #field_name_lit,
-                                        expected_type = ::std::any::type_name::<#inner_type>(),
-                                        actual_type = ?var_info.data_type,
-                                        error = ?_e,
-                                        "Type mismatch: failed to convert optional field, using None (warning shown once)"
-                                    );
-                                });
+                                #mismatch_warning
                                 None
                             }
                         }
@@ -822,16 +1083,26 @@ fn generate_critical_assignment(
     field_ident: &syn::Ident,
     field_type: &syn::Type,
     field_name: &str,
+    mode: MismatchMode,
 ) -> proc_macro2::TokenStream {
     let index_lit = LitInt::new(&index.to_string(), proc_macro2::Span::call_site());
     let field_name_lit = field_name;
+    let mismatch_warning = decode_warning(
+        mode,
+        quote! { field = %name, error = ?_e, },
+        "Failed to decode critical field, using default value",
+    );
     quote! {
         #field_ident: {
             match validation.extraction_plan.get(#index_lit) {
                 Some(::pitwall::adapters::FieldExtraction::Required { name, var_info }) => {
                     match <#field_type as ::pitwall::VarData>::from_bytes(&data, var_info) {
                         Ok(value) => value,
-                        Err(err) => panic!("Failed to decode critical field '{}' during adapt: {err:?}", name),
+                        Err(_e) => {
+                            // Validated at connection, so only a malformed packet gets here
+                            #mismatch_warning
+                            <#field_type as ::core::default::Default>::default()
+                        }
                     }
                 }
                 Some(other) => panic!("Validation plan entry for '{}' is {:?}, expected Required", #field_name_lit, other),
@@ -842,6 +1113,7 @@ fn generate_critical_assignment(
 }
 
 /// Generate field assignment for BitfieldHas strategy
+#[allow(clippy::too_many_arguments)]
 fn generate_bitfield_has_assignment(
     index: usize,
     field_ident: &syn::Ident,
@@ -849,9 +1121,26 @@ fn generate_bitfield_has_assignment(
     target_is_option: bool,
     default_expr: &Option<Expr>,
     mask_expr: &Expr,
+    mask_method: &syn::Ident,
+    mode: MismatchMode,
 ) -> proc_macro2::TokenStream {
     let index_lit = LitInt::new(&index.to_string(), proc_macro2::Span::call_site());
     let field_name_lit = field_name;
+    let mismatch_warning = decode_warning(
+        mode,
+        quote! { field = #field_name_lit, expected_type = "BitField", actual_type = ?var_info.data_type, error = ?_e, },
+        "Type mismatch: failed to convert bitfield, using None",
+    );
+    let mismatch_warning_2 = decode_warning(
+        mode,
+        quote! { field = #field_name_lit, error = ?_e, },
+        "Failed to decode critical bitfield, using default value",
+    );
+    let mismatch_warning_3 = decode_warning(
+        mode,
+        quote! { field = #field_name_lit, expected_type = "BitField", actual_type = ?var_info.data_type, error = ?_e, },
+        "Type mismatch: failed to convert bitfield, using default value",
+    );
 
     if target_is_option {
         quote! {
@@ -860,18 +1149,9 @@ fn generate_bitfield_has_assignment(
                     Some(::pitwall::adapters::FieldExtraction::Optional { var_info, .. }) => {
                         if let Some(var_info) = var_info {
                             match <::pitwall::BitField as ::pitwall::VarData>::from_bytes(&data, var_info) {
-                                Ok(bits) => Some(bits.has_flag(#mask_expr)),
+                                Ok(bits) => Some(bits.#mask_method(#mask_expr)),
                                 Err(_e) => {
-                                    static WARNED: ::std::sync::Once = ::std::sync::Once::new();
-                                    WARNED.call_once(|| {
-                                        ::tracing::warn!(
-                                            field = #field_name_lit,
-                                            expected_type = "BitField",
-                                            actual_type = ?var_info.data_type,
-                                            error = ?_e,
-                                            "Type mismatch: failed to convert bitfield, using None (warning shown once)"
-                                        );
-                                    });
+                                    #mismatch_warning
                                     None
                                 }
                             }
@@ -892,25 +1172,19 @@ fn generate_bitfield_has_assignment(
                 match validation.extraction_plan.get(#index_lit) {
                     Some(::pitwall::adapters::FieldExtraction::Required { var_info, .. }) => {
                         match <::pitwall::BitField as ::pitwall::VarData>::from_bytes(&data, var_info) {
-                            Ok(bits) => bits.has_flag(#mask_expr),
-                            Err(err) => panic!("Failed to decode critical bitfield during adapt: {err:?}"),
+                            Ok(bits) => bits.#mask_method(#mask_expr),
+                            Err(_e) => {
+                                #mismatch_warning_2
+                                #fallback_bool
+                            }
                         }
                     }
                     Some(::pitwall::adapters::FieldExtraction::WithDefault { var_info, .. }) => {
                         if let Some(var_info) = var_info {
                             match <::pitwall::BitField as ::pitwall::VarData>::from_bytes(&data, var_info) {
-                                Ok(bits) => bits.has_flag(#mask_expr),
+                                Ok(bits) => bits.#mask_method(#mask_expr),
                                 Err(_e) => {
-                                    static WARNED: ::std::sync::Once = ::std::sync::Once::new();
-                                    WARNED.call_once(|| {
-                                        ::tracing::warn!(
-                                            field = #field_name_lit,
-                                            expected_type = "BitField",
-                                            actual_type = ?var_info.data_type,
-                                            error = ?_e,
-                                            "Type mismatch: failed to convert bitfield, using default value (warning shown once)"
-                                        );
-                                    });
+                                    #mismatch_warning_3
                                     #fallback_bool
                                 }
                             }
@@ -931,9 +1205,25 @@ fn generate_bitfield_map_assignment(
     target_is_option: bool,
     default_expr: &Option<Expr>,
     decoder_expr: &Expr,
+    mode: MismatchMode,
 ) -> proc_macro2::TokenStream {
     let index_lit = LitInt::new(&index.to_string(), proc_macro2::Span::call_site());
     let field_name_lit = field_name;
+    let mismatch_warning = decode_warning(
+        mode,
+        quote! { field = #field_name_lit, expected_type = "BitField", actual_type = ?var_info.data_type, error = ?_e, },
+        "Type mismatch: failed to convert bitfield, using None",
+    );
+    let mismatch_warning_2 = decode_warning(
+        mode,
+        quote! { field = #field_name_lit, error = ?_e, },
+        "Failed to decode critical bitfield, using default value",
+    );
+    let mismatch_warning_3 = decode_warning(
+        mode,
+        quote! { field = #field_name_lit, expected_type = "BitField", actual_type = ?var_info.data_type, error = ?_e, },
+        "Type mismatch: failed to convert bitfield, using default value",
+    );
 
     if target_is_option {
         quote! {
@@ -942,18 +1232,10 @@ fn generate_bitfield_map_assignment(
                     Some(::pitwall::adapters::FieldExtraction::Optional { var_info, .. }) => {
                         if let Some(var_info) = var_info {
                             match <::pitwall::BitField as ::pitwall::VarData>::from_bytes(&data, var_info) {
-                                Ok(bits) => Some((#decoder_expr)(bits)),
+                                // `From` wraps a `T` in `Some` and passes an `Option<T>` through
+                                Ok(bits) => ::core::option::Option::from((#decoder_expr)(bits)),
                                 Err(_e) => {
-                                    static WARNED: ::std::sync::Once = ::std::sync::Once::new();
-                                    WARNED.call_once(|| {
-                                        ::tracing::warn!(
-                                            field = #field_name_lit,
-                                            expected_type = "BitField",
-                                            actual_type = ?var_info.data_type,
-                                            error = ?_e,
-                                            "Type mismatch: failed to convert bitfield, using None (warning shown once)"
-                                        );
-                                    });
+                                    #mismatch_warning
                                     None
                                 }
                             }
@@ -975,7 +1257,10 @@ fn generate_bitfield_map_assignment(
                     Some(::pitwall::adapters::FieldExtraction::Required { var_info, .. }) => {
                         match <::pitwall::BitField as ::pitwall::VarData>::from_bytes(&data, var_info) {
                             Ok(bits) => (#decoder_expr)(bits),
-                            Err(err) => panic!("Failed to decode critical bitfield during adapt: {err:?}"),
+                            Err(_e) => {
+                                #mismatch_warning_2
+                                #fallback_expr
+                            }
                         }
                     }
                     Some(::pitwall::adapters::FieldExtraction::WithDefault { var_info, .. }) => {
@@ -983,16 +1268,7 @@ fn generate_bitfield_map_assignment(
                             match <::pitwall::BitField as ::pitwall::VarData>::from_bytes(&data, var_info) {
                                 Ok(bits) => (#decoder_expr)(bits),
                                 Err(_e) => {
-                                    static WARNED: ::std::sync::Once = ::std::sync::Once::new();
-                                    WARNED.call_once(|| {
-                                        ::tracing::warn!(
-                                            field = #field_name_lit,
-                                            expected_type = "BitField",
-                                            actual_type = ?var_info.data_type,
-                                            error = ?_e,
-                                            "Type mismatch: failed to convert bitfield, using default value (warning shown once)"
-                                        );
-                                    });
+                                    #mismatch_warning_3
                                     #fallback_expr
                                 }
                             }
@@ -1009,13 +1285,14 @@ fn generate_bitfield_map_assignment(
 fn generate_extraction_phase(
     strategies: &[FieldStrategy],
     telemetry_map: &HashMap<String, (usize, syn::Type)>,
+    mode: MismatchMode,
 ) -> syn::Result<Vec<proc_macro2::TokenStream>> {
     let mut assignments = Vec::new();
 
     for (index, strategy) in strategies.iter().enumerate() {
         let assignment = match strategy {
             FieldStrategy::TypeDefault { field_ident, field_type, field_name } => {
-                generate_type_default_assignment(index, field_ident, field_type, field_name)
+                generate_type_default_assignment(index, field_ident, field_type, field_name, mode)
             }
             FieldStrategy::WithDefault { field_ident, field_type, default_expr, field_name } => {
                 generate_with_default_assignment(
@@ -1024,13 +1301,14 @@ fn generate_extraction_phase(
                     field_type,
                     default_expr,
                     field_name,
+                    mode,
                 )
             }
             FieldStrategy::Optional { field_ident, inner_type, field_name } => {
-                generate_optional_assignment(index, field_ident, inner_type, field_name)
+                generate_optional_assignment(index, field_ident, inner_type, field_name, mode)
             }
             FieldStrategy::Critical { field_ident, field_type, field_name } => {
-                generate_critical_assignment(index, field_ident, field_type, field_name)
+                generate_critical_assignment(index, field_ident, field_type, field_name, mode)
             }
             FieldStrategy::BitfieldHas {
                 field_ident,
@@ -1038,6 +1316,7 @@ fn generate_extraction_phase(
                 target_is_option,
                 default_expr,
                 mask_expr,
+                mask_method,
                 ..
             } => generate_bitfield_has_assignment(
                 index,
@@ -1046,6 +1325,8 @@ fn generate_extraction_phase(
                 *target_is_option,
                 default_expr,
                 mask_expr,
+                mask_method,
+                mode,
             ),
             FieldStrategy::BitfieldMap {
                 field_ident,
@@ -1061,20 +1342,32 @@ fn generate_extraction_phase(
                 *target_is_option,
                 default_expr,
                 decoder_expr,
+                mode,
             ),
 
-            FieldStrategy::Calculated { field_ident, field_type, expression, .. } => {
+            FieldStrategy::Calculated { field_ident, field_type, expression, .. }
+            | FieldStrategy::Derived { field_ident, field_type, expression, .. } => {
                 let rewritten =
                     process_calculated_expression(expression, telemetry_map, field_type)?;
                 quote! {
                     #field_ident: { #rewritten }
                 }
             }
-            FieldStrategy::Skipped { field_ident, field_type } => {
+            FieldStrategy::Skipped { field_ident, initializer: Some(initializer), .. } => {
+                quote! {
+                    #field_ident: { #initializer }
+                }
+            }
+            FieldStrategy::Skipped { field_ident, field_type, initializer: None } => {
                 quote! {
                     #field_ident: <#field_type as ::core::default::Default>::default()
                 }
             }
+            FieldStrategy::Tick { field_ident } => {
+                quote! {
+                    #field_ident: packet.tick
+                }
+            }
         };
 
         assignments.push(assignment);
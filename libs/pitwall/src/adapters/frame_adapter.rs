@@ -9,6 +9,35 @@ use super::AdapterValidation;
 /// `validate_schema()` runs once at connection time, `adapt()` runs at 60Hz using
 /// pre-computed extraction plans. This separation minimizes runtime overhead.
 pub trait FrameAdapter: Sized {
+    /// Oldest telemetry header version this adapter can read, if it has a
+    /// minimum.
+    ///
+    /// Set with `#[pitwall(min_schema_version = N)]` when deriving.
+    const MIN_SCHEMA_VERSION: Option<u32> = None;
+
+    /// Fail if `schema` comes from a header version older than
+    /// [`FrameAdapter::MIN_SCHEMA_VERSION`].
+    ///
+    /// Schemas of unknown version are accepted. Derived adapters call this
+    /// first thing in `validate_schema()`.
+    fn check_schema_version(schema: &VariableSchema) -> crate::Result<()> {
+        match (Self::MIN_SCHEMA_VERSION, schema.version) {
+            (Some(minimum), Some(version)) if version < minimum => {
+                Err(crate::TelemetryError::schema_validation_error(
+                    format!(
+                        "{} needs telemetry schema version {} or newer, found {}",
+                        std::any::type_name::<Self>(),
+                        minimum,
+                        version
+                    ),
+                    Some(minimum),
+                    Some(version),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Validate adapter against telemetry schema at connection time.
     ///
     /// This method:
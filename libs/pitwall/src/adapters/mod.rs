@@ -30,9 +30,9 @@
 //!
 //!         // Validate required fields exist
 //!         let speed_info = schema.get_variable("Speed")
-//!             .ok_or_else(|| TelemetryError::Parse {
-//!                 context: "Field validation".to_string(),
-//!                 details: "Missing required field 'Speed'".to_string(),
+//!             .ok_or_else(|| TelemetryError::FieldMissing {
+//!                 name: "Speed".to_string(),
+//!                 suggestion: None,
 //!             })?;
 //!
 //!         extraction_plan.push(FieldExtraction::Required {
@@ -67,7 +67,9 @@ mod validation;
 // Re-export all public types
 pub use frame_adapter::FrameAdapter;
 pub use schema_provider::SchemaProvider;
-pub use validation::{AdapterValidation, DefaultValue, FieldExtraction};
+pub use validation::{
+    AdapterValidation, DefaultValue, FieldExtraction, SchemaDiagnosis, SchemaWarning,
+};
 
 #[cfg(test)]
 mod tests {
@@ -154,6 +156,35 @@ mod tests {
         assert!(skipped_field.var_info().is_none());
     }
 
+    #[test]
+    fn diagnose_suggests_similar_field() {
+        let mut variables = create_test_schema().variables;
+        let mut speed = variables.remove("Speed").unwrap();
+        speed.name = "SpeedMS".to_string();
+        variables.insert("SpeedMS".to_string(), speed);
+        let schema = VariableSchema::new(variables, 8).unwrap();
+
+        let diagnosis = AdapterValidation::diagnose(&schema, &["Speed", "RPM", "Throttle"]);
+
+        assert_eq!(diagnosis.present, vec!["RPM"]);
+        assert_eq!(diagnosis.missing, vec!["Speed", "Throttle"]);
+        assert_eq!(diagnosis.suggestion_for("Speed"), Some("SpeedMS"));
+        assert_eq!(diagnosis.suggestion_for("Throttle"), None);
+        assert!(!diagnosis.is_complete());
+        assert_eq!(
+            diagnosis.to_string(),
+            "missing 'Speed'; did you mean 'SpeedMS'?, missing 'Throttle'"
+        );
+    }
+
+    #[test]
+    fn diagnose_complete_schema() {
+        let diagnosis = AdapterValidation::diagnose(&create_test_schema(), &["Speed", "RPM"]);
+
+        assert!(diagnosis.is_complete());
+        assert!(diagnosis.suggestions.is_empty());
+    }
+
     #[test]
     fn schema_provider_basic_usage() {
         struct TestProvider {
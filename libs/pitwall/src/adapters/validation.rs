@@ -1,9 +1,11 @@
 //! Validation types and field extraction strategies for adapters
 
+use crate::VariableType;
 #[allow(unused_imports)] // Used by generated derive macro code
 use crate::{TelemetryError, VariableInfo, VariableSchema};
 #[allow(unused_imports)] // Used by generated derive macro code and tests
 use std::collections::HashMap;
+use std::fmt;
 
 /// Pre-computed extraction plan built during connection-time validation.
 ///
@@ -18,6 +20,8 @@ pub struct AdapterValidation {
     pub extraction_plan: Vec<FieldExtraction>,
     /// Fast lookup from telemetry field name to extraction index
     index_map: HashMap<String, usize>,
+    /// Problems found in the schema that don't prevent extraction
+    warnings: Vec<SchemaWarning>,
 }
 
 impl AdapterValidation {
@@ -31,7 +35,21 @@ impl AdapterValidation {
             })
             .collect();
 
-        Self { extraction_plan, index_map }
+        Self { extraction_plan, index_map, warnings: Vec::new() }
+    }
+
+    /// Attach warnings found while validating the schema.
+    pub fn with_warnings(mut self, warnings: Vec<SchemaWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Problems found at validation time that will surface while adapting
+    /// frames, such as fields whose schema type the adapter can't read.
+    ///
+    /// Log these when subscribing rather than waiting for the first frame.
+    pub fn warnings(&self) -> &[SchemaWarning] {
+        &self.warnings
     }
 
     /// Get the number of fields that will be extracted.
@@ -49,6 +67,29 @@ impl AdapterValidation {
         self.index_map.get(name).copied()
     }
 
+    /// Compare the `requested` field names against `schema`.
+    ///
+    /// Each missing field is paired with the most similar schema field, if one
+    /// is close enough to be a likely typo or naming variant (e.g. `Speed` vs
+    /// `SpeedMS`). Used by derived adapters to explain validation failures.
+    pub fn diagnose(schema: &VariableSchema, requested: &[&str]) -> SchemaDiagnosis {
+        let mut diagnosis = SchemaDiagnosis::default();
+
+        for &name in requested {
+            if schema.variables.contains_key(name) {
+                diagnosis.present.push(name.to_string());
+                continue;
+            }
+
+            diagnosis.missing.push(name.to_string());
+            if let Some(suggestion) = closest_field(schema, name) {
+                diagnosis.suggestions.push((name.to_string(), suggestion));
+            }
+        }
+
+        diagnosis
+    }
+
     /// Fetch a telemetry value by name using the precomputed extraction plan.
     pub fn fetch_or_default<T>(&self, packet: &crate::types::FramePacket, name: &str) -> T
     where
@@ -76,6 +117,131 @@ impl AdapterValidation {
     }
 }
 
+/// A schema problem that doesn't abort validation, see [`AdapterValidation::warnings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaWarning {
+    /// The schema's type for a field can't be read as the adapter's Rust
+    /// type, so the field will fall back to its default on every frame.
+    TypeMismatch {
+        /// Field name in telemetry schema
+        field: String,
+        /// Rust type the adapter reads the field as
+        expected: &'static str,
+        /// Type the schema declares for the field
+        actual: VariableType,
+    },
+}
+
+impl fmt::Display for SchemaWarning {
+    /// Formats as e.g. `'Gear' is Float32 in the schema but read as i32`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaWarning::TypeMismatch { field, expected, actual } => {
+                write!(f, "'{}' is {:?} in the schema but read as {}", field, actual, expected)
+            }
+        }
+    }
+}
+
+impl From<SchemaWarning> for TelemetryError {
+    /// The error for adapters that treat the warning as fatal.
+    fn from(warning: SchemaWarning) -> Self {
+        match warning {
+            SchemaWarning::TypeMismatch { field, expected, actual } => {
+                TelemetryError::TypeMismatch { field, expected, actual }
+            }
+        }
+    }
+}
+
+/// Which requested fields a schema provides, see [`AdapterValidation::diagnose`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDiagnosis {
+    /// Requested fields found in the schema
+    pub present: Vec<String>,
+    /// Requested fields absent from the schema
+    pub missing: Vec<String>,
+    /// `(missing field, closest schema field)` for missing fields with a close match
+    pub suggestions: Vec<(String, String)>,
+}
+
+impl SchemaDiagnosis {
+    /// Check if every requested field is present.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Get the suggested replacement for a missing field.
+    pub fn suggestion_for(&self, field: &str) -> Option<&str> {
+        self.suggestions
+            .iter()
+            .find(|(missing, _)| missing == field)
+            .map(|(_, suggestion)| suggestion.as_str())
+    }
+}
+
+impl fmt::Display for SchemaDiagnosis {
+    /// Formats as e.g. `missing 'Speed'; did you mean 'SpeedMS'?`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_complete() {
+            return write!(f, "all {} fields present", self.present.len());
+        }
+
+        for (index, field) in self.missing.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "missing '{}'", field)?;
+            if let Some(suggestion) = self.suggestion_for(field) {
+                write!(f, "; did you mean '{}'?", suggestion)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Find the schema field most similar to `name`, ignoring case.
+///
+/// A field matches if its edit distance is at most a third of the longer
+/// name (minimum 2), or if one name contains the other. Ties are broken
+/// alphabetically so suggestions are stable.
+fn closest_field(schema: &VariableSchema, name: &str) -> Option<String> {
+    let name_lower = name.to_lowercase();
+
+    schema
+        .variables
+        .keys()
+        .filter_map(|candidate| {
+            let candidate_lower = candidate.to_lowercase();
+            let distance = levenshtein(&name_lower, &candidate_lower);
+            let longest = name_lower.chars().count().max(candidate_lower.chars().count());
+            let close = distance <= (longest / 3).max(2)
+                || candidate_lower.contains(&name_lower)
+                || name_lower.contains(&candidate_lower);
+            close.then_some((distance, candidate))
+        })
+        .min()
+        .map(|(_, candidate)| candidate.clone())
+}
+
+/// Levenshtein edit distance between two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
 /// Extraction strategy for a single adapter field.
 ///
 /// Strategy is determined at connection time based on field annotations,
@@ -7,12 +7,11 @@ use {
     crate::driver::{Driver, SyncState},
     crate::provider::Provider,
     crate::providers::live::LiveProvider,
-    crate::stream::ThrottleExt,
+    crate::stream::{DedupExt, ThrottleExt},
     crate::types::{FramePacket, UpdateRate},
     crate::{FrameAdapter, SessionInfo, VariableSchema},
     futures::{Stream, StreamExt},
     std::sync::Arc,
-    std::time::Duration,
     tokio::sync::broadcast,
     tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError},
     tokio_util::sync::CancellationToken,
@@ -82,6 +81,9 @@ impl LiveConnection {
     {
         // Validate schema once at subscription time
         let validation = T::validate_schema(&self.schema).expect("Schema validation failed");
+        for warning in validation.warnings() {
+            warn!("{}: {}", std::any::type_name::<T>(), warning);
+        }
 
         // Create base frame stream from broadcast channel
         // BroadcastStream waits for the next message, which is perfect for live
@@ -95,18 +97,17 @@ impl LiveConnection {
                 }
             }
         });
+        // Drop frames the sim republished without advancing (e.g. while paused)
+        let frames = frames.skip_duplicates();
 
         // Apply rate control and adaptation
-        let effective_rate = rate.normalize(self.source_hz);
-
-        match effective_rate {
-            UpdateRate::Native => {
+        match rate.throttle_interval(self.source_hz) {
+            None => {
                 // Direct adaptation, no throttling
                 frames.map(move |packet| T::adapt(&packet, &validation)).boxed()
             }
-            UpdateRate::Max(hz) => {
+            Some(interval) => {
                 // Throttle then adapt
-                let interval = Duration::from_secs_f64(1.0 / hz as f64);
                 frames.throttle(interval).map(move |packet| T::adapt(&packet, &validation)).boxed()
             }
         }
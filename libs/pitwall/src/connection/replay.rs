@@ -3,7 +3,6 @@
 use futures::{Stream, StreamExt};
 use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
 use tokio::sync::broadcast;
 use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
 use tokio_util::sync::CancellationToken;
@@ -12,7 +11,7 @@ use tracing::{debug, info, warn};
 use crate::driver::{Driver, SyncState};
 use crate::provider::Provider;
 use crate::providers::replay::ReplayProvider;
-use crate::stream::ThrottleExt;
+use crate::stream::{DedupExt, ThrottleExt};
 use crate::types::{FramePacket, UpdateRate};
 use crate::{FrameAdapter, Result, SessionInfo, VariableSchema};
 
@@ -86,7 +85,19 @@ impl ReplayConnection {
     {
         // Validate schema once at subscription time
         let validation = T::validate_schema(&self.schema).expect("Schema validation failed");
+        for warning in validation.warnings() {
+            warn!("{}: {}", std::any::type_name::<T>(), warning);
+        }
+
+        self.raw_frames(rate).map(move |packet| T::adapt(&packet, &validation))
+    }
 
+    /// Subscribe to undecoded frame packets
+    ///
+    /// For running an adapter of your own over the replay: validate it once
+    /// against [`ReplayConnection::schema`], then adapt each packet. Packets
+    /// are deduplicated and throttled exactly as for [`ReplayConnection::subscribe`].
+    pub fn raw_frames(&self, rate: UpdateRate) -> impl Stream<Item = Arc<FramePacket>> + 'static {
         // Create base frame stream from broadcast channel
         let frames = BroadcastStream::new(self.frame_tx.subscribe()).filter_map(|result| async move {
             match result {
@@ -97,20 +108,13 @@ impl ReplayConnection {
                 }
             }
         });
+        // Drop frames the sim republished without advancing (e.g. while paused)
+        let frames = frames.skip_duplicates();
 
-        // Apply rate control and adaptation
-        let effective_rate = rate.normalize(self.source_hz);
-
-        match effective_rate {
-            UpdateRate::Native => {
-                // Direct adaptation, no throttling
-                frames.map(move |packet| T::adapt(&packet, &validation)).boxed()
-            }
-            UpdateRate::Max(hz) => {
-                // Throttle then adapt
-                let interval = Duration::from_secs_f64(1.0 / hz as f64);
-                frames.throttle(interval).map(move |packet| T::adapt(&packet, &validation)).boxed()
-            }
+        // Apply rate control
+        match rate.throttle_interval(self.source_hz) {
+            None => frames.boxed(),
+            Some(interval) => frames.throttle(interval).boxed(),
         }
     }
 
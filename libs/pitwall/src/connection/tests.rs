@@ -102,9 +102,8 @@ impl FrameAdapter for BasicTelemetry {
 
 // Simple frame for throttle testing
 #[cfg(test)]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct SimpleFrame {
-    #[allow(dead_code)]
     speed: f32,
 }
 
@@ -473,6 +472,77 @@ async fn replay_telemetry_stream_throttling() {
     info!("Received {} frames over {:?}", frames.len(), start.elapsed());
 }
 
+#[tokio::test]
+async fn replay_telemetry_stream_interval_throttling() {
+    use crate::test_utils;
+    use std::time::Instant;
+
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let ibt_file = test_utils::get_smallest_ibt_test_file().expect("No IBT test files found");
+
+    let connection =
+        replay::ReplayConnection::open(ibt_file).await.expect("Failed to open IBT file");
+
+    // Subscribe with one frame every 300ms (~3.33 Hz, not expressible with Max)
+    let mut stream = Box::pin(
+        connection.subscribe::<SimpleFrame>(UpdateRate::Interval(Duration::from_millis(300))),
+    );
+
+    let mut timestamps = Vec::new();
+    let start = Instant::now();
+
+    // Collect frames for 2 seconds
+    while start.elapsed() < Duration::from_secs(2) {
+        match tokio::time::timeout(Duration::from_millis(400), stream.next()).await {
+            Ok(Some(_)) => timestamps.push(Instant::now()),
+            Ok(None) => break,
+            Err(_) => continue,
+        }
+    }
+
+    assert!(timestamps.len() > 2, "Should receive several frames, got {}", timestamps.len());
+
+    let intervals: Vec<Duration> = timestamps.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let avg_interval = intervals.iter().sum::<Duration>() / intervals.len() as u32;
+    let expected_interval = Duration::from_millis(300);
+
+    // Allow 50ms tolerance
+    let diff = avg_interval.abs_diff(expected_interval);
+
+    assert!(
+        diff < Duration::from_millis(50),
+        "Interval throttling not working correctly. Expected ~300ms, got {:?}",
+        avg_interval
+    );
+
+    info!("Interval throttling working: avg interval = {:?}", avg_interval);
+}
+
+#[tokio::test]
+async fn replay_raw_frames_adapt_like_subscribe() {
+    use crate::test_utils;
+
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let ibt_file = test_utils::get_smallest_ibt_test_file().expect("No IBT test files found");
+
+    let connection =
+        replay::ReplayConnection::open(ibt_file).await.expect("Failed to open IBT file");
+
+    // Subscribed together, both streams start at the same frame
+    let raw = connection.raw_frames(UpdateRate::Native);
+    let typed = connection.subscribe::<SimpleFrame>(UpdateRate::Native);
+
+    let validation = SimpleFrame::validate_schema(connection.schema()).unwrap();
+    let adapted: Vec<SimpleFrame> =
+        raw.take(20).map(|packet| SimpleFrame::adapt(&packet, &validation)).collect().await;
+    let subscribed: Vec<SimpleFrame> = typed.take(20).collect().await;
+
+    assert_eq!(adapted.len(), 20);
+    assert_eq!(adapted, subscribed);
+}
+
 #[tokio::test]
 async fn replay_current_frame_access() {
     use crate::test_utils;
@@ -490,5 +560,34 @@ async fn replay_current_frame_access() {
     info!("Current frame tick: {}", frame.tick);
     info!("Current frame session version: {}", frame.session_version);
 
-    assert!(frame.tick >= 0, "Frame tick should be non-negative");
+    assert!(!frame.data.is_empty(), "Frame should carry telemetry data");
+}
+
+#[tokio::test]
+async fn replay_empty_recording_is_rejected() {
+    use std::io::Write;
+
+    /// An IBT header for a file with no variables, session info or frames.
+    fn header_only() -> Vec<u8> {
+        let mut data = vec![0u8; 144 + 32];
+        data[0..4].copy_from_slice(&2i32.to_le_bytes()); // version
+        data[8..12].copy_from_slice(&60i32.to_le_bytes()); // tick rate
+        data[28..32].copy_from_slice(&176i32.to_le_bytes()); // var header offset
+        data
+    }
+
+    let truncated = header_only()[..100].to_vec();
+    for (name, contents) in
+        [("empty", Vec::new()), ("truncated", truncated), ("header only", header_only())]
+    {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&contents).unwrap();
+
+        let error = replay::ReplayConnection::open(file.path()).await.err();
+
+        assert!(
+            matches!(&error, Some(crate::TelemetryError::EmptyRecording { path }) if path == file.path()),
+            "{name} file: expected EmptyRecording, got {error:?}"
+        );
+    }
 }
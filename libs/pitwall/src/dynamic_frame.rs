@@ -114,7 +114,7 @@ mod tests {
                 description: "Per-car lap distance percentage".into(),
             },
         );
-        let schema = VariableSchema { variables: vars, frame_size: 24 };
+        let schema = VariableSchema { variables: vars, frame_size: 24, version: None };
 
         // Build frame bytes (Int32 + Float32 + four Float32 array elements)
         let mut data = vec![0u8; 24];
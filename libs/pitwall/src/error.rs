@@ -52,6 +52,8 @@ use std::path::PathBuf;
 use std::time::Duration;
 use thiserror::Error;
 
+use crate::VariableType;
+
 #[cfg(windows)]
 use windows_core as core;
 
@@ -76,6 +78,9 @@ pub enum TelemetryError {
         source: std::io::Error,
     },
 
+    #[error("IBT file {} contains no telemetry frames", path.display())]
+    EmptyRecording { path: PathBuf },
+
     #[error("SDK version mismatch: expected {expected}, found {found}")]
     Version { expected: u32, found: u32 },
 
@@ -95,9 +100,21 @@ pub enum TelemetryError {
     #[error("Field '{field}' not found in telemetry data")]
     FieldNotFound { field: String },
 
+    #[error(
+        "Connection aborted, critical field missing '{name}'{}",
+        suggestion.as_ref().map(|s| format!("; did you mean '{s}'?")).unwrap_or_default()
+    )]
+    FieldMissing { name: String, suggestion: Option<String> },
+
     #[error("Type conversion error: {details}")]
     TypeConversion { details: String },
 
+    #[error("'{field}' is {actual:?} in the schema but read as {expected}")]
+    TypeMismatch { field: String, expected: &'static str, actual: VariableType },
+
+    #[error("'{field}' needs {needed} bytes but the frame is {got} bytes")]
+    BufferTooSmall { field: String, needed: usize, got: usize },
+
     #[error("{feature} is only available on {required_platform}")]
     UnsupportedPlatform { feature: String, required_platform: String },
 
@@ -130,10 +147,14 @@ impl TelemetryError {
             TelemetryError::Buffer { .. } => true,
             TelemetryError::Memory { .. } => false,
             TelemetryError::File { .. } => false,
+            TelemetryError::EmptyRecording { .. } => false,
             TelemetryError::Version { .. } => false,
             TelemetryError::Parse { .. } => false,
             TelemetryError::FieldNotFound { .. } => false,
+            TelemetryError::FieldMissing { .. } => false,
             TelemetryError::TypeConversion { .. } => false,
+            TelemetryError::TypeMismatch { .. } => false,
+            TelemetryError::BufferTooSmall { .. } => false,
             TelemetryError::UnsupportedPlatform { .. } => false,
             #[cfg(windows)]
             TelemetryError::WindowsApi { .. } => true,
@@ -156,6 +177,10 @@ impl TelemetryError {
                 "Ensure sufficient disk space",
                 "Check file permissions",
             ],
+            TelemetryError::EmptyRecording { .. } => vec![
+                "Check the recording finished writing",
+                "Record at least a few seconds on track",
+            ],
             TelemetryError::Memory { .. } => vec![
                 "Check memory access bounds",
                 "Verify shared memory is still valid",
@@ -181,11 +206,20 @@ impl TelemetryError {
                 "Verify field exists in current iRacing version",
                 "Use optional field access patterns",
             ],
-            TelemetryError::TypeConversion { .. } => vec![
+            TelemetryError::FieldMissing { .. } => vec![
+                "Check field name spelling",
+                "Verify field exists in current iRacing version",
+                "Make the field optional if it isn't always recorded",
+            ],
+            TelemetryError::TypeConversion { .. } | TelemetryError::TypeMismatch { .. } => vec![
                 "Check data type compatibility",
                 "Verify expected vs actual data types",
                 "Use appropriate conversion methods",
             ],
+            TelemetryError::BufferTooSmall { .. } => vec![
+                "Check the frame matches the schema it was read with",
+                "Verify source data integrity",
+            ],
             TelemetryError::UnsupportedPlatform { .. } => vec![
                 "Use platform-appropriate features",
                 "Consider IBT file replay for cross-platform testing",
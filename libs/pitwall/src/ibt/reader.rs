@@ -70,6 +70,12 @@ impl IbtReader {
 
     /// Create IbtReader from bytes with path context
     fn from_bytes_with_path(data: &[u8], path: PathBuf) -> Result<Self> {
+        // Too short to even hold the headers, e.g. a recording that was
+        // never written to
+        if data.len() < IbtHeader::HEADER_SIZE + IbtDiskSubHeader::DISK_HEADER_SIZE {
+            return Err(TelemetryError::EmptyRecording { path });
+        }
+
         let mut cursor = std::io::Cursor::new(data);
 
         // Parse IBT header
@@ -80,7 +86,8 @@ impl IbtReader {
         let disk_header = IbtDiskSubHeader::parse_from_reader(&mut cursor)?;
 
         // Extract variable schema
-        let variable_schema = extract_variable_schema(&mut cursor, &header)?;
+        let mut variable_schema = extract_variable_schema(&mut cursor, &header)?;
+        variable_schema.version = u32::try_from(header.version).ok();
 
         // Calculate frame data start position correctly with checked arithmetic
         // Frame data starts AFTER both variable headers AND session info
@@ -117,11 +124,10 @@ impl IbtReader {
         let frame_data_start = session_info_end.max(var_headers_end) as usize;
 
         // Calculate total frames based on remaining file data with bounds checking
-        let remaining_bytes =
-            data.len().checked_sub(frame_data_start).ok_or_else(|| TelemetryError::Parse {
-                context: "Frame data calculation".to_string(),
-                details: "Frame data start position exceeds file size".to_string(),
-            })?;
+        let remaining_bytes = data
+            .len()
+            .checked_sub(frame_data_start)
+            .ok_or_else(|| TelemetryError::EmptyRecording { path: path.clone() })?;
 
         let total_frames = if header.buf_len > 0 {
             remaining_bytes / header.buf_len as usize
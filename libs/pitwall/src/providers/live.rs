@@ -51,7 +51,9 @@ impl LiveProvider {
         }
 
         let frame_size = header.buf_len as usize;
-        let schema = Arc::new(VariableSchema::new(variable_map, frame_size)?);
+        let mut schema = VariableSchema::new(variable_map, frame_size)?;
+        schema.version = u32::try_from(header.ver).ok();
+        let schema = Arc::new(schema);
 
         Ok(Self { connection, schema })
     }
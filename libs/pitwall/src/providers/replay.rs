@@ -31,10 +31,14 @@ pub struct ReplayProvider {
 impl ReplayProvider {
     /// Create a new replay provider from an IBT file
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let reader = IbtReader::open(path)?;
+        let reader = IbtReader::open(&path)?;
 
         // Get metadata
         let total_frames = reader.total_frames();
+        if total_frames == 0 {
+            // Nothing to replay, e.g. a file holding only headers and session info
+            return Err(TelemetryError::EmptyRecording { path: path.as_ref().to_path_buf() });
+        }
         let tick_rate = reader.tick_rate();
 
         // Get the variable schema from the reader
@@ -0,0 +1,104 @@
+//! Stale frame filtering
+
+use futures::{Stream, ready};
+use pin_project_lite::pin_project;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::types::FramePacket;
+
+/// Extension trait to drop repeated frames from a packet stream
+pub trait DedupExt: Stream<Item = Arc<FramePacket>> {
+    /// Skip packets that repeat the previous packet's tick
+    ///
+    /// The sim republishes the same frame while paused, which would otherwise
+    /// show up as zero-motion samples. See [`FramePacket::is_duplicate_of`].
+    fn skip_duplicates(self) -> SkipDuplicates<Self>
+    where
+        Self: Sized,
+    {
+        SkipDuplicates::new(self)
+    }
+}
+
+impl<T: Stream<Item = Arc<FramePacket>>> DedupExt for T {}
+
+pin_project! {
+    /// A stream combinator that drops packets whose tick hasn't advanced
+    pub struct SkipDuplicates<S> {
+        #[pin]
+        stream: S,
+        last: Option<Arc<FramePacket>>,
+    }
+}
+
+impl<S> SkipDuplicates<S> {
+    /// Create a new deduplicating stream
+    pub fn new(stream: S) -> Self {
+        Self { stream, last: None }
+    }
+}
+
+impl<S: Stream<Item = Arc<FramePacket>>> Stream for SkipDuplicates<S> {
+    type Item = Arc<FramePacket>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            let Some(packet) = ready!(this.stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            if this.last.as_ref().is_some_and(|last| packet.is_duplicate_of(last)) {
+                continue;
+            }
+
+            *this.last = Some(Arc::clone(&packet));
+            return Poll::Ready(Some(packet));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VariableSchema;
+    use futures::StreamExt;
+    use std::collections::HashMap;
+
+    fn packets(ticks: &[(u32, u32)]) -> Vec<Arc<FramePacket>> {
+        let schema =
+            Arc::new(VariableSchema { variables: HashMap::new(), frame_size: 0, version: None });
+        ticks
+            .iter()
+            .map(|&(tick, version)| {
+                Arc::new(FramePacket::new(vec![], tick, version, schema.clone()))
+            })
+            .collect()
+    }
+
+    async fn ticks_after_dedup(ticks: &[(u32, u32)]) -> Vec<u32> {
+        futures::stream::iter(packets(ticks)).skip_duplicates().map(|p| p.tick).collect().await
+    }
+
+    #[tokio::test]
+    async fn repeated_ticks_are_skipped() {
+        let ticks = ticks_after_dedup(&[(1, 0), (2, 0), (2, 0), (2, 0), (3, 0), (3, 0)]).await;
+        assert_eq!(ticks, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn tick_reset_is_not_a_duplicate() {
+        // A looping replay or a new sim session starts counting again
+        let ticks = ticks_after_dedup(&[(5, 0), (6, 0), (0, 0), (1, 0)]).await;
+        assert_eq!(ticks, vec![5, 6, 0, 1]);
+    }
+
+    #[tokio::test]
+    async fn session_change_on_same_tick_is_kept() {
+        let ticks = ticks_after_dedup(&[(7, 1), (7, 2)]).await;
+        assert_eq!(ticks, vec![7, 7]);
+    }
+}
@@ -1,5 +1,7 @@
 //! Stream utilities for telemetry processing
 
+pub mod dedup;
 pub mod throttle;
 
+pub use dedup::DedupExt;
 pub use throttle::ThrottleExt;
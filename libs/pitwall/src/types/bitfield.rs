@@ -23,6 +23,18 @@ impl BitField {
         (self.0 & flag) != 0
     }
 
+    /// Check if any of the flags in a combined mask are set.
+    pub fn has_any(&self, mask: u32) -> bool {
+        (self.0 & mask) != 0
+    }
+
+    /// Check if all of the flags in a combined mask are set.
+    ///
+    /// An empty mask is trivially satisfied.
+    pub fn has_all(&self, mask: u32) -> bool {
+        (self.0 & mask) == mask
+    }
+
     /// Get the raw u32 value.
     pub fn value(&self) -> u32 {
         self.0
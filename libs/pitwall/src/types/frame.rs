@@ -33,4 +33,13 @@ impl FramePacket {
     ) -> Self {
         Self { data: data.into(), tick, session_version, schema }
     }
+
+    /// Whether this packet repeats `prev` rather than carrying new data
+    ///
+    /// The tick only stops advancing when the sim republishes a frame (e.g.
+    /// while paused). A tick that goes backwards is new data from a restarted
+    /// source, and a session change always counts as new.
+    pub fn is_duplicate_of(&self, prev: &FramePacket) -> bool {
+        self.tick == prev.tick && self.session_version == prev.session_version
+    }
 }
@@ -9,8 +9,33 @@ pub mod engine_warnings {
     pub const OPT_REP_NEEDED: u32 = 0x0100; // irsdk_optRepNeeded
 }
 
-// Global session flags additions (1.19)
+// Global session flags
 pub mod session_flags {
+    pub const CHECKERED: u32 = 0x0000_0001; // irsdk_checkered
+    pub const WHITE: u32 = 0x0000_0002; // irsdk_white
+    pub const GREEN: u32 = 0x0000_0004; // irsdk_green
+    pub const YELLOW: u32 = 0x0000_0008; // irsdk_yellow
+    pub const RED: u32 = 0x0000_0010; // irsdk_red
+    pub const BLUE: u32 = 0x0000_0020; // irsdk_blue
+    pub const DEBRIS: u32 = 0x0000_0040; // irsdk_debris
+    pub const CROSSED: u32 = 0x0000_0080; // irsdk_crossed
+    pub const YELLOW_WAVING: u32 = 0x0000_0100; // irsdk_yellowWaving
+    pub const ONE_LAP_TO_GREEN: u32 = 0x0000_0200; // irsdk_oneLapToGreen
+    pub const GREEN_HELD: u32 = 0x0000_0400; // irsdk_greenHeld
+    pub const TEN_TO_GO: u32 = 0x0000_0800; // irsdk_tenToGo
+    pub const FIVE_TO_GO: u32 = 0x0000_1000; // irsdk_fiveToGo
+    pub const RANDOM_WAVING: u32 = 0x0000_2000; // irsdk_randomWaving
+    pub const CAUTION: u32 = 0x0000_4000; // irsdk_caution
+    pub const CAUTION_WAVING: u32 = 0x0000_8000; // irsdk_cautionWaving
+
+    // Drivers black flags
+    pub const BLACK: u32 = 0x0001_0000; // irsdk_black
+    pub const DISQUALIFY: u32 = 0x0002_0000; // irsdk_disqualify
+    pub const SERVICEABLE: u32 = 0x0004_0000; // irsdk_servicible
+    pub const FURLED: u32 = 0x0008_0000; // irsdk_furled
+    pub const REPAIR: u32 = 0x0010_0000; // irsdk_repair
+
+    // Added in 1.19
     pub const DQ_SCORING_INVALID: u32 = 0x0020_0000; // irsdk_dqScoringInvalid
 }
 
@@ -38,7 +38,7 @@
 //!     description: "Engine RPM".to_string(),
 //! });
 //!
-//! let schema = Arc::new(VariableSchema { variables, frame_size: 4 });
+//! let schema = Arc::new(VariableSchema { variables, frame_size: 4, version: None });
 //! let data = vec![0x00, 0xA0, 0x8C, 0x45]; // 4500.0 as little-endian f32
 //!
 //! let packet = FramePacket::new(
@@ -146,6 +146,7 @@ mod tests {
             let schema = VariableSchema {
                 variables: adjusted_variables,
                 frame_size,
+                version: None,
             };
 
             // Schema should be consistent
@@ -344,6 +345,18 @@ mod tests {
         assert!(!bitfield.has_flag(0b0100));
     }
 
+    #[test]
+    fn bitfield_any_and_all_masks() {
+        use crate::irsdk_flags::session_flags as sf;
+        let flags = BitField::new(sf::YELLOW | sf::CAUTION);
+
+        assert!(flags.has_any(sf::CAUTION | sf::CAUTION_WAVING));
+        assert!(!flags.has_any(sf::GREEN | sf::CHECKERED));
+        assert!(flags.has_all(sf::YELLOW | sf::CAUTION));
+        assert!(!flags.has_all(sf::YELLOW | sf::CAUTION | sf::RED));
+        assert!(flags.has_all(0));
+    }
+
     #[test]
     fn test_incident_decoding_rep_only() {
         use crate::irsdk_flags::incident as inc;
@@ -389,4 +402,79 @@ mod tests {
         let none = BitField::new(0);
         assert!(!session_dq_scoring_invalid(none));
     }
+
+    #[test]
+    fn test_var_data_accepts_matching_types() {
+        let info = |data_type, offset, count| VariableInfo {
+            name: "CarIdxLapDistPct".to_string(),
+            data_type,
+            offset,
+            count,
+            count_as_time: false,
+            units: String::new(),
+            description: String::new(),
+        };
+
+        assert!(f32::accepts(&info(VariableType::Float32, 512, 1)));
+        assert!(!i32::accepts(&info(VariableType::Float32, 512, 1)));
+        assert!(u8::accepts(&info(VariableType::Char, 0, 1)));
+        assert!(Vec::<f32>::accepts(&info(VariableType::Float32, 512, 64)));
+        assert!(!Vec::<f64>::accepts(&info(VariableType::Float32, 512, 64)));
+    }
+
+    #[test]
+    fn test_var_data_reports_type_mismatch() {
+        let info = VariableInfo {
+            name: "Gear".to_string(),
+            data_type: VariableType::Float32,
+            offset: 0,
+            count: 1,
+            count_as_time: false,
+            units: String::new(),
+            description: String::new(),
+        };
+
+        let error = i32::from_bytes(&[0u8; 4], &info).unwrap_err();
+
+        assert!(
+            matches!(
+                &error,
+                crate::TelemetryError::TypeMismatch {
+                    field,
+                    expected: "i32",
+                    actual: VariableType::Float32,
+                } if field == "Gear"
+            ),
+            "unexpected error: {error}"
+        );
+        assert_eq!(error.to_string(), "'Gear' is Float32 in the schema but read as i32");
+    }
+
+    #[test]
+    fn test_var_data_rejects_short_buffers() {
+        let info = |data_type, offset, count| VariableInfo {
+            name: "Speed".to_string(),
+            data_type,
+            offset,
+            count,
+            count_as_time: false,
+            units: String::new(),
+            description: String::new(),
+        };
+        let data = [0u8; 6];
+
+        let error = f32::from_bytes(&data, &info(VariableType::Float32, 4, 1)).unwrap_err();
+        assert!(
+            matches!(error, crate::TelemetryError::BufferTooSmall { needed: 8, got: 6, .. }),
+            "unexpected error: {error}"
+        );
+        assert_eq!(error.to_string(), "'Speed' needs 8 bytes but the frame is 6 bytes");
+
+        assert!(f64::from_bytes(&data, &info(VariableType::Float64, 0, 1)).is_err());
+        assert!(bool::from_bytes(&data, &info(VariableType::Bool, 6, 1)).is_err());
+        assert!(Vec::<u8>::from_bytes(&data, &info(VariableType::UInt8, 2, 8)).is_err());
+        // Offsets and counts large enough to overflow are errors, not panics
+        assert!(u32::from_bytes(&data, &info(VariableType::UInt32, usize::MAX - 1, 1)).is_err());
+        assert!(Vec::<u8>::from_bytes(&data, &info(VariableType::UInt8, 0, usize::MAX)).is_err());
+    }
 }
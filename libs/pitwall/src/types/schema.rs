@@ -13,16 +13,25 @@ pub struct VariableSchema {
     pub variables: HashMap<String, VariableInfo>,
     /// Total size of a telemetry frame in bytes
     pub frame_size: usize,
+    /// Telemetry header version reported by the source (`None` when unknown)
+    #[serde(default)]
+    pub version: Option<u32>,
 }
 
 impl VariableSchema {
     /// Create a new VariableSchema with validation.
     pub fn new(variables: HashMap<String, VariableInfo>, frame_size: usize) -> crate::Result<Self> {
-        let schema = Self { variables, frame_size };
+        let schema = Self { variables, frame_size, version: None };
         schema.validate()?;
         Ok(schema)
     }
 
+    /// Record the telemetry header version the schema was read from.
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
     /// Validate the schema for consistency.
     pub fn validate(&self) -> crate::Result<()> {
         for (name, var_info) in &self.variables {
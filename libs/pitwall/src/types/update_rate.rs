@@ -1,6 +1,7 @@
 //! Update rate control for telemetry streams
 
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Update rate for telemetry streams
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -12,6 +13,11 @@ pub enum UpdateRate {
     /// Throttled to maximum Hz
     /// If the requested rate exceeds source rate, Native is used
     Max(u32),
+
+    /// Throttled to at most one frame per interval (the latest one)
+    /// Allows sub-Hz and non-integer rates; if the interval is shorter than
+    /// the source frame period, Native is used
+    Interval(Duration),
 }
 
 impl UpdateRate {
@@ -22,6 +28,10 @@ impl UpdateRate {
             UpdateRate::Native => UpdateRate::Native,
             UpdateRate::Max(hz) if hz as f64 >= source_hz => UpdateRate::Native,
             UpdateRate::Max(hz) => UpdateRate::Max(hz),
+            UpdateRate::Interval(interval) if interval.as_secs_f64() * source_hz <= 1.0 => {
+                UpdateRate::Native
+            }
+            UpdateRate::Interval(interval) => UpdateRate::Interval(interval),
         }
     }
 
@@ -29,15 +39,37 @@ impl UpdateRate {
     pub fn needs_throttle(self, source_hz: f64) -> bool {
         match self.normalize(source_hz) {
             UpdateRate::Native => false,
-            UpdateRate::Max(_) => true,
+            UpdateRate::Max(_) | UpdateRate::Interval(_) => true,
         }
     }
 
     /// Get throttle interval if needed
-    pub fn throttle_interval(self, source_hz: f64) -> Option<std::time::Duration> {
+    pub fn throttle_interval(self, source_hz: f64) -> Option<Duration> {
         match self.normalize(source_hz) {
             UpdateRate::Native => None,
-            UpdateRate::Max(hz) => Some(std::time::Duration::from_secs_f64(1.0 / hz as f64)),
+            UpdateRate::Max(hz) => Some(Duration::from_secs_f64(1.0 / hz as f64)),
+            UpdateRate::Interval(interval) => Some(interval),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_throttles_below_source_rate() {
+        let rate = UpdateRate::Interval(Duration::from_millis(250));
+
+        assert!(rate.needs_throttle(60.0));
+        assert_eq!(rate.throttle_interval(60.0), Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn interval_shorter_than_source_period_is_native() {
+        let rate = UpdateRate::Interval(Duration::from_millis(10));
+
+        assert_eq!(rate.normalize(60.0), UpdateRate::Native);
+        assert_eq!(rate.throttle_interval(60.0), None);
+    }
+}
@@ -6,20 +6,51 @@ use super::{BitField, VariableInfo, VariableType};
 pub trait VarData: Sized {
     /// Parse this type from binary data at the given offset.
     fn from_bytes(data: &[u8], info: &VariableInfo) -> crate::Result<Self>;
+
+    /// Check whether variables described by `info` can be parsed as this type.
+    ///
+    /// Parses a zeroed buffer laid out like `info`, so only the type check
+    /// in [`VarData::from_bytes`] can fail.
+    fn accepts(info: &VariableInfo) -> bool {
+        let probe = VariableInfo { offset: 0, ..info.clone() };
+        let data = vec![0u8; info.data_type.size() * info.count.max(1)];
+        !matches!(Self::from_bytes(&data, &probe), Err(crate::TelemetryError::TypeMismatch { .. }))
+    }
+}
+
+/// The `len` bytes at `info.offset`, or [`crate::TelemetryError::BufferTooSmall`]
+/// if `data` is too short to hold them (e.g. a truncated or malformed packet).
+///
+/// Values are little-endian, as iRacing writes them; the bytes are copied out
+/// before decoding, so `data` needs no particular alignment.
+fn var_bytes<'a>(data: &'a [u8], info: &VariableInfo, len: usize) -> crate::Result<&'a [u8]> {
+    let end = info.offset.checked_add(len);
+    end.and_then(|end| data.get(info.offset..end)).ok_or_else(|| {
+        crate::TelemetryError::BufferTooSmall {
+            field: info.name.clone(),
+            needed: info.offset.saturating_add(len),
+            got: data.len(),
+        }
+    })
+}
+
+/// The error for reading the variable described by `info` as `T`.
+fn type_mismatch<T>(info: &VariableInfo) -> crate::TelemetryError {
+    crate::TelemetryError::TypeMismatch {
+        field: info.name.clone(),
+        expected: std::any::type_name::<T>(),
+        actual: info.data_type,
+    }
 }
 
 // Implement VarData for basic types
 impl VarData for f32 {
     fn from_bytes(data: &[u8], info: &VariableInfo) -> crate::Result<Self> {
         if info.data_type != VariableType::Float32 {
-            return Err(crate::TelemetryError::TypeConversion {
-                details: format!("Expected Float32, got {:?}", info.data_type),
-            });
+            return Err(type_mismatch::<Self>(info));
         }
 
-        let bytes = data
-            .get(info.offset..info.offset + 4)
-            .ok_or(crate::TelemetryError::Memory { offset: info.offset, source: None })?;
+        let bytes = var_bytes(data, info, 4)?;
 
         Ok(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
     }
@@ -28,14 +59,10 @@ impl VarData for f32 {
 impl VarData for i32 {
     fn from_bytes(data: &[u8], info: &VariableInfo) -> crate::Result<Self> {
         if info.data_type != VariableType::Int32 {
-            return Err(crate::TelemetryError::TypeConversion {
-                details: format!("Expected Int32, got {:?}", info.data_type),
-            });
+            return Err(type_mismatch::<Self>(info));
         }
 
-        let bytes = data
-            .get(info.offset..info.offset + 4)
-            .ok_or(crate::TelemetryError::Memory { offset: info.offset, source: None })?;
+        let bytes = var_bytes(data, info, 4)?;
 
         Ok(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
     }
@@ -44,30 +71,22 @@ impl VarData for i32 {
 impl VarData for bool {
     fn from_bytes(data: &[u8], info: &VariableInfo) -> crate::Result<Self> {
         if info.data_type != VariableType::Bool {
-            return Err(crate::TelemetryError::TypeConversion {
-                details: format!("Expected Bool, got {:?}", info.data_type),
-            });
+            return Err(type_mismatch::<Self>(info));
         }
 
-        let byte = data
-            .get(info.offset)
-            .ok_or(crate::TelemetryError::Memory { offset: info.offset, source: None })?;
+        let byte = var_bytes(data, info, 1)?[0];
 
-        Ok(*byte != 0)
+        Ok(byte != 0)
     }
 }
 
 impl VarData for BitField {
     fn from_bytes(data: &[u8], info: &VariableInfo) -> crate::Result<Self> {
         if info.data_type != VariableType::BitField {
-            return Err(crate::TelemetryError::TypeConversion {
-                details: format!("Expected BitField, got {:?}", info.data_type),
-            });
+            return Err(type_mismatch::<Self>(info));
         }
 
-        let bytes = data
-            .get(info.offset..info.offset + 4)
-            .ok_or(crate::TelemetryError::Memory { offset: info.offset, source: None })?;
+        let bytes = var_bytes(data, info, 4)?;
 
         Ok(BitField(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])))
     }
@@ -77,46 +96,34 @@ impl VarData for BitField {
 impl VarData for u8 {
     fn from_bytes(data: &[u8], info: &VariableInfo) -> crate::Result<Self> {
         if !matches!(info.data_type, VariableType::UInt8 | VariableType::Char) {
-            return Err(crate::TelemetryError::TypeConversion {
-                details: format!("Expected UInt8 or Char, got {:?}", info.data_type),
-            });
+            return Err(type_mismatch::<Self>(info));
         }
 
-        let byte = data
-            .get(info.offset)
-            .ok_or(crate::TelemetryError::Memory { offset: info.offset, source: None })?;
+        let byte = var_bytes(data, info, 1)?[0];
 
-        Ok(*byte)
+        Ok(byte)
     }
 }
 
 impl VarData for i8 {
     fn from_bytes(data: &[u8], info: &VariableInfo) -> crate::Result<Self> {
         if info.data_type != VariableType::Int8 {
-            return Err(crate::TelemetryError::TypeConversion {
-                details: format!("Expected Int8, got {:?}", info.data_type),
-            });
+            return Err(type_mismatch::<Self>(info));
         }
 
-        let byte = data
-            .get(info.offset)
-            .ok_or(crate::TelemetryError::Memory { offset: info.offset, source: None })?;
+        let byte = var_bytes(data, info, 1)?[0];
 
-        Ok(*byte as i8)
+        Ok(byte as i8)
     }
 }
 
 impl VarData for u16 {
     fn from_bytes(data: &[u8], info: &VariableInfo) -> crate::Result<Self> {
         if info.data_type != VariableType::UInt16 {
-            return Err(crate::TelemetryError::TypeConversion {
-                details: format!("Expected UInt16, got {:?}", info.data_type),
-            });
+            return Err(type_mismatch::<Self>(info));
         }
 
-        let bytes = data
-            .get(info.offset..info.offset + 2)
-            .ok_or(crate::TelemetryError::Memory { offset: info.offset, source: None })?;
+        let bytes = var_bytes(data, info, 2)?;
 
         Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
     }
@@ -125,14 +132,10 @@ impl VarData for u16 {
 impl VarData for i16 {
     fn from_bytes(data: &[u8], info: &VariableInfo) -> crate::Result<Self> {
         if info.data_type != VariableType::Int16 {
-            return Err(crate::TelemetryError::TypeConversion {
-                details: format!("Expected Int16, got {:?}", info.data_type),
-            });
+            return Err(type_mismatch::<Self>(info));
         }
 
-        let bytes = data
-            .get(info.offset..info.offset + 2)
-            .ok_or(crate::TelemetryError::Memory { offset: info.offset, source: None })?;
+        let bytes = var_bytes(data, info, 2)?;
 
         Ok(i16::from_le_bytes([bytes[0], bytes[1]]))
     }
@@ -141,14 +144,10 @@ impl VarData for i16 {
 impl VarData for u32 {
     fn from_bytes(data: &[u8], info: &VariableInfo) -> crate::Result<Self> {
         if info.data_type != VariableType::UInt32 {
-            return Err(crate::TelemetryError::TypeConversion {
-                details: format!("Expected UInt32, got {:?}", info.data_type),
-            });
+            return Err(type_mismatch::<Self>(info));
         }
 
-        let bytes = data
-            .get(info.offset..info.offset + 4)
-            .ok_or(crate::TelemetryError::Memory { offset: info.offset, source: None })?;
+        let bytes = var_bytes(data, info, 4)?;
 
         Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
     }
@@ -157,14 +156,10 @@ impl VarData for u32 {
 impl VarData for f64 {
     fn from_bytes(data: &[u8], info: &VariableInfo) -> crate::Result<Self> {
         if info.data_type != VariableType::Float64 {
-            return Err(crate::TelemetryError::TypeConversion {
-                details: format!("Expected Float64, got {:?}", info.data_type),
-            });
+            return Err(type_mismatch::<Self>(info));
         }
 
-        let bytes = data
-            .get(info.offset..info.offset + 8)
-            .ok_or(crate::TelemetryError::Memory { offset: info.offset, source: None })?;
+        let bytes = var_bytes(data, info, 8)?;
 
         Ok(f64::from_le_bytes([
             bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
@@ -179,7 +174,11 @@ impl<T: VarData> VarData for Vec<T> {
             return Ok(Vec::new());
         }
 
+        // Check the whole array up front so a bad count can't allocate or
+        // overflow before the first element fails
         let element_size = info.data_type.size();
+        let total = element_size.saturating_mul(info.count);
+        var_bytes(data, info, total)?;
         let mut result = Vec::with_capacity(info.count);
 
         for i in 0..info.count {
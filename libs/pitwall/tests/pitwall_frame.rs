@@ -1,3 +1,12 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use pitwall::adapters::{FrameAdapter, SchemaWarning};
+use pitwall::irsdk_flags::session_flags;
+use pitwall::types::{FramePacket, VariableInfo, VariableSchema, VariableType};
+use pitwall::{BitField, PitwallFrame, TelemetryError};
+
 #[test]
 fn trybuild_tests() {
     let t = trybuild::TestCases::new();
@@ -8,3 +17,404 @@ fn trybuild_tests() {
     // Test expected failures
     t.compile_fail("tests/pitwall_frame/fail/*.rs");
 }
+
+#[derive(PitwallFrame, Debug)]
+struct DerivedFrame {
+    #[derived = "combined_g"]
+    combined_g: f32,
+
+    #[derived = "speed_kmh"]
+    speed_kmh: f32,
+}
+
+fn var(name: &str, data_type: VariableType, offset: usize) -> (String, VariableInfo) {
+    let info = VariableInfo {
+        name: name.to_string(),
+        data_type,
+        offset,
+        count: 1,
+        count_as_time: false,
+        units: String::new(),
+        description: String::new(),
+    };
+    (name.to_string(), info)
+}
+
+#[test]
+fn derived_fields_fetch_source_variables() {
+    let variables: HashMap<_, _> = [
+        var("LatAccel", VariableType::Float32, 0),
+        var("LongAccel", VariableType::Float32, 4),
+        var("Speed", VariableType::Float32, 8),
+    ]
+    .into_iter()
+    .collect();
+    let schema = Arc::new(VariableSchema { variables, frame_size: 12, version: None });
+
+    let mut data = vec![0u8; 12];
+    data[0..4].copy_from_slice(&(3.0f32 * 9.80665).to_le_bytes());
+    data[4..8].copy_from_slice(&(4.0f32 * 9.80665).to_le_bytes());
+    data[8..12].copy_from_slice(&10.0f32.to_le_bytes());
+    let packet = FramePacket::new(data, 1, 0, schema.clone());
+
+    let validation = DerivedFrame::validate_schema(&schema).unwrap();
+    let frame = DerivedFrame::adapt(&packet, &validation);
+
+    assert!((frame.combined_g - 5.0).abs() < 1e-4);
+    assert!((frame.speed_kmh - 36.0).abs() < 1e-4);
+}
+
+#[derive(PitwallFrame, Debug)]
+struct FlagFrame {
+    #[bitfield(name = "SessionFlags", any = "session_flags::CAUTION | session_flags::CAUTION_WAVING")]
+    caution: bool,
+
+    #[bitfield(name = "SessionFlags", all = "session_flags::GREEN | session_flags::WHITE")]
+    green_white: Option<bool>,
+
+    #[bitfield(name = "SessionFlags", all = "session_flags::YELLOW | session_flags::CAUTION")]
+    yellow_caution: bool,
+}
+
+#[test]
+fn bitfield_any_and_all_masks() {
+    let variables: HashMap<_, _> =
+        [var("SessionFlags", VariableType::BitField, 0)].into_iter().collect();
+    let schema = Arc::new(VariableSchema { variables, frame_size: 4, version: None });
+    let validation = FlagFrame::validate_schema(&schema).unwrap();
+
+    let flags = session_flags::CAUTION | session_flags::GREEN | session_flags::WHITE;
+    let packet = FramePacket::new(flags.to_le_bytes().to_vec(), 1, 0, schema.clone());
+    let frame = FlagFrame::adapt(&packet, &validation);
+
+    assert!(frame.caution);
+    assert_eq!(frame.green_white, Some(true));
+    assert!(!frame.yellow_caution, "YELLOW is not set");
+
+    let packet = FramePacket::new(session_flags::GREEN.to_le_bytes().to_vec(), 2, 0, schema);
+    let frame = FlagFrame::adapt(&packet, &validation);
+
+    assert!(!frame.caution);
+    assert_eq!(frame.green_white, Some(false));
+}
+
+#[derive(Debug, PartialEq)]
+enum Spotter {
+    CarLeft,
+    CarRight,
+}
+
+/// No car alongside has no `Spotter` value.
+fn decode_spotter(bits: BitField) -> Option<Spotter> {
+    match bits.value() {
+        0 => None,
+        1 => Some(Spotter::CarLeft),
+        _ => Some(Spotter::CarRight),
+    }
+}
+
+#[derive(PitwallFrame, Debug)]
+struct SpotterFrame {
+    #[bitfield_map(name = "CarLeftRight", decoder = "decode_spotter")]
+    spotter: Option<Spotter>,
+}
+
+#[test]
+fn option_decoder_is_flattened() {
+    let variables: HashMap<_, _> =
+        [var("CarLeftRight", VariableType::BitField, 0)].into_iter().collect();
+    let schema = Arc::new(VariableSchema { variables, frame_size: 4, version: None });
+    let validation = SpotterFrame::validate_schema(&schema).unwrap();
+    let adapt = |value: u32| {
+        let packet = FramePacket::new(value.to_le_bytes().to_vec(), 1, 0, schema.clone());
+        SpotterFrame::adapt(&packet, &validation).spotter
+    };
+
+    assert_eq!(adapt(0), None);
+    assert_eq!(adapt(1), Some(Spotter::CarLeft));
+    assert_eq!(adapt(2), Some(Spotter::CarRight));
+}
+
+#[derive(PitwallFrame, Debug)]
+struct CriticalFrame {
+    #[field_name = "Speed"]
+    #[fail_if_missing]
+    speed: f32,
+}
+
+#[test]
+fn missing_critical_field_suggests_similar_name() {
+    let variables: HashMap<_, _> =
+        [var("SpeedMS", VariableType::Float32, 0), var("RPM", VariableType::Float32, 4)]
+            .into_iter()
+            .collect();
+    let schema = VariableSchema { variables, frame_size: 8, version: None };
+
+    let error = CriticalFrame::validate_schema(&schema).unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            TelemetryError::FieldMissing { name, suggestion: Some(suggestion) }
+                if name == "Speed" && suggestion == "SpeedMS"
+        ),
+        "unexpected error: {error}"
+    );
+    assert!(
+        error.to_string().contains("missing 'Speed'; did you mean 'SpeedMS'?"),
+        "unexpected error: {error}"
+    );
+
+    // The same adapter works once the field is present
+    let variables: HashMap<_, _> = [var("Speed", VariableType::Float32, 0)].into_iter().collect();
+    let schema = Arc::new(VariableSchema { variables, frame_size: 4, version: None });
+    let validation = CriticalFrame::validate_schema(&schema).unwrap();
+    let packet = FramePacket::new(12.5f32.to_le_bytes().to_vec(), 1, 0, schema);
+
+    assert_eq!(CriticalFrame::adapt(&packet, &validation).speed, 12.5);
+}
+
+#[test]
+fn truncated_packet_falls_back_to_defaults() {
+    let variables: HashMap<_, _> =
+        [var("Speed", VariableType::Float32, 0), var("Gear", VariableType::Int32, 4)]
+            .into_iter()
+            .collect();
+    let schema = Arc::new(VariableSchema { variables, frame_size: 8, version: None });
+    // Only the first half of Speed made it
+    let packet = FramePacket::new(vec![0; 2], 1, 0, schema.clone());
+
+    let critical = CriticalFrame::validate_schema(&schema).unwrap();
+    assert_eq!(CriticalFrame::adapt(&packet, &critical).speed, 0.0);
+
+    let optional = GearFrame::validate_schema(&schema).unwrap();
+    let frame = GearFrame::adapt(&packet, &optional);
+    assert_eq!(frame.speed, 0.0);
+    assert_eq!(frame.gear, None);
+}
+
+#[derive(PitwallFrame, Debug)]
+struct CounterFrame {
+    #[field_name = "Speed"]
+    speed: f32,
+
+    #[skip = "1"]
+    lap_counter: u32,
+
+    #[skip]
+    notes: Vec<String>,
+}
+
+#[test]
+fn skipped_field_starts_at_its_initializer() {
+    let variables: HashMap<_, _> = [var("Speed", VariableType::Float32, 0)].into_iter().collect();
+    let schema = Arc::new(VariableSchema { variables, frame_size: 4, version: None });
+    let validation = CounterFrame::validate_schema(&schema).unwrap();
+    let packet = FramePacket::new(30.0f32.to_le_bytes().to_vec(), 1, 0, schema);
+
+    let frame = CounterFrame::adapt(&packet, &validation);
+
+    assert_eq!(frame.speed, 30.0);
+    assert_eq!(frame.lap_counter, 1);
+    assert!(frame.notes.is_empty());
+}
+
+#[derive(PitwallFrame, Debug)]
+#[pitwall(with_tick)]
+struct TickedFrame {
+    #[field_name = "Speed"]
+    speed: f32,
+
+    frame_tick: u32,
+}
+
+#[test]
+fn frame_tick_comes_from_the_packet() {
+    let variables: HashMap<_, _> = [var("Speed", VariableType::Float32, 0)].into_iter().collect();
+    let schema = Arc::new(VariableSchema { variables, frame_size: 4, version: None });
+    let validation = TickedFrame::validate_schema(&schema).unwrap();
+    let packet = FramePacket::new(30.0f32.to_le_bytes().to_vec(), 4321, 0, schema);
+
+    let frame = TickedFrame::adapt(&packet, &validation);
+
+    assert_eq!(frame.speed, 30.0);
+    assert_eq!(frame.frame_tick, 4321);
+}
+
+#[derive(PitwallFrame, Debug)]
+struct GearFrame {
+    #[field_name = "Speed"]
+    speed: f32,
+
+    #[field_name = "Gear"]
+    gear: Option<i32>,
+}
+
+#[test]
+fn type_mismatch_is_reported_at_validation() {
+    let variables: HashMap<_, _> = [
+        var("Speed", VariableType::Float32, 0),
+        // The adapter reads Gear as i32
+        var("Gear", VariableType::Float32, 4),
+    ]
+    .into_iter()
+    .collect();
+    let schema = Arc::new(VariableSchema { variables, frame_size: 8, version: None });
+
+    let validation = GearFrame::validate_schema(&schema).unwrap();
+
+    assert_eq!(
+        validation.warnings(),
+        [SchemaWarning::TypeMismatch {
+            field: "Gear".to_string(),
+            expected: "i32",
+            actual: VariableType::Float32,
+        }]
+    );
+    assert_eq!(
+        validation.warnings()[0].to_string(),
+        "'Gear' is Float32 in the schema but read as i32"
+    );
+
+    // As predicted, the field can't be read from frames
+    let mut data = 42.0f32.to_le_bytes().to_vec();
+    data.extend_from_slice(&3.0f32.to_le_bytes());
+    let frame = GearFrame::adapt(&FramePacket::new(data, 1, 0, schema), &validation);
+    assert_eq!(frame.speed, 42.0);
+    assert_eq!(frame.gear, None);
+
+    // Matching types and absent fields produce no warnings
+    let variables: HashMap<_, _> = [var("Speed", VariableType::Float32, 0)].into_iter().collect();
+    let schema = VariableSchema { variables, frame_size: 4, version: None };
+    assert!(GearFrame::validate_schema(&schema).unwrap().warnings().is_empty());
+}
+
+#[derive(PitwallFrame, Debug)]
+#[pitwall(min_schema_version = 2)]
+struct VersionedFrame {
+    #[field_name = "Speed"]
+    speed: f32,
+}
+
+#[test]
+fn older_schema_version_is_rejected() {
+    let variables: HashMap<_, _> = [var("Speed", VariableType::Float32, 0)].into_iter().collect();
+    let schema = VariableSchema { variables, frame_size: 4, version: None };
+
+    let error = VersionedFrame::validate_schema(&schema.clone().with_version(1)).unwrap_err();
+
+    assert!(
+        matches!(
+            error,
+            TelemetryError::SchemaValidation {
+                expected_version: Some(2),
+                actual_version: Some(1),
+                ..
+            }
+        ),
+        "unexpected error: {error}"
+    );
+    assert!(error.to_string().contains("version 2 or newer, found 1"), "unexpected error: {error}");
+
+    // The minimum itself, newer versions and unknown versions are accepted
+    let schema_v2 = Arc::new(schema.clone().with_version(2));
+    let validation = VersionedFrame::validate_schema(&schema_v2).unwrap();
+    let packet = FramePacket::new(30.0f32.to_le_bytes().to_vec(), 1, 0, schema_v2);
+    assert_eq!(VersionedFrame::adapt(&packet, &validation).speed, 30.0);
+    assert!(VersionedFrame::validate_schema(&schema.clone().with_version(3)).is_ok());
+    assert!(VersionedFrame::validate_schema(&schema).is_ok());
+    // Adapters without a minimum accept any version
+    assert!(GearFrame::validate_schema(&schema.with_version(1)).is_ok());
+}
+
+#[derive(PitwallFrame, Debug)]
+#[pitwall(on_type_mismatch = "warn_always")]
+struct NoisyGearFrame {
+    #[field_name = "Speed"]
+    speed: f32,
+
+    #[field_name = "Gear"]
+    gear: Option<i32>,
+}
+
+#[derive(PitwallFrame, Debug)]
+#[pitwall(on_type_mismatch = "error")]
+struct StrictGearFrame {
+    #[field_name = "Speed"]
+    speed: f32,
+
+    #[field_name = "Gear"]
+    gear: Option<i32>,
+}
+
+/// `Speed` as Float32 and `Gear` as Float32, which the gear frames read as i32.
+fn mismatched_gear_schema() -> Arc<VariableSchema> {
+    let variables: HashMap<_, _> =
+        [var("Speed", VariableType::Float32, 0), var("Gear", VariableType::Float32, 4)]
+            .into_iter()
+            .collect();
+    Arc::new(VariableSchema { variables, frame_size: 8, version: None })
+}
+
+/// Collects everything logged through it.
+#[derive(Clone, Default)]
+struct LogBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn warn_always_warns_on_every_frame() {
+    let schema = mismatched_gear_schema();
+    let mut data = 42.0f32.to_le_bytes().to_vec();
+    data.extend_from_slice(&3.0f32.to_le_bytes());
+    let packet = FramePacket::new(data, 1, 0, schema.clone());
+
+    let logs = LogBuffer::default();
+    let writer = logs.clone();
+    let subscriber =
+        tracing_subscriber::fmt().with_writer(move || writer.clone()).with_ansi(false).finish();
+    tracing::subscriber::with_default(subscriber, || {
+        let validation = NoisyGearFrame::validate_schema(&schema).unwrap();
+        for _ in 0..3 {
+            let frame = NoisyGearFrame::adapt(&packet, &validation);
+            assert_eq!(frame.speed, 42.0);
+            assert_eq!(frame.gear, None);
+        }
+    });
+
+    let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    let warnings: Vec<_> = logs.lines().filter(|line| line.contains("Type mismatch")).collect();
+    assert_eq!(warnings.len(), 3, "unexpected logs: {logs}");
+    assert!(warnings.iter().all(|line| line.contains("Gear") && !line.contains("shown once")));
+}
+
+#[test]
+fn error_mode_rejects_mismatched_schema() {
+    let error = StrictGearFrame::validate_schema(&mismatched_gear_schema()).unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            TelemetryError::TypeMismatch { field, .. } if field == "Gear"
+        ),
+        "unexpected error: {error}"
+    );
+
+    // Matching types and absent fields are still fine
+    let variables: HashMap<_, _> = [var("Speed", VariableType::Float32, 0)].into_iter().collect();
+    let schema = Arc::new(VariableSchema { variables, frame_size: 4, version: None });
+    let validation = StrictGearFrame::validate_schema(&schema).unwrap();
+    let packet = FramePacket::new(30.0f32.to_le_bytes().to_vec(), 1, 0, schema);
+    let frame = StrictGearFrame::adapt(&packet, &validation);
+    assert_eq!(frame.speed, 30.0);
+    assert_eq!(frame.gear, None);
+}
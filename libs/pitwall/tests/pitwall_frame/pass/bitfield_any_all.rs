@@ -0,0 +1,18 @@
+use pitwall::PitwallFrame;
+
+#[derive(PitwallFrame, Debug)]
+struct FlagView {
+    #[bitfield(
+        name = "SessionFlags",
+        any = "pitwall::irsdk_flags::session_flags::CAUTION | pitwall::irsdk_flags::session_flags::CAUTION_WAVING"
+    )]
+    caution: bool,
+
+    #[bitfield(
+        name = "SessionFlags",
+        all = "pitwall::irsdk_flags::session_flags::GREEN | pitwall::irsdk_flags::session_flags::WHITE"
+    )]
+    green_white: Option<bool>,
+}
+
+fn main() {}
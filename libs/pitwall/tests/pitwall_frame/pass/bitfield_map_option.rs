@@ -0,0 +1,23 @@
+use pitwall::{BitField, PitwallFrame};
+
+#[derive(Debug)]
+enum Direction {
+    Left,
+    Right,
+}
+
+fn decode_direction(bits: BitField) -> Option<Direction> {
+    match bits.value() {
+        0 => None,
+        1 => Some(Direction::Left),
+        _ => Some(Direction::Right),
+    }
+}
+
+#[derive(PitwallFrame, Debug)]
+struct DirectionView {
+    #[bitfield_map(name = "CarLeftRight", decoder = "decode_direction")]
+    direction: Option<Direction>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pitwall::PitwallFrame;
+
+#[derive(PitwallFrame, Debug)]
+struct DerivedChannels {
+    #[derived = "combined_g"]
+    combined_g: f32,
+
+    #[derived = "speed_kmh"]
+    speed_kmh: f32,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use pitwall::PitwallFrame;
+
+#[derive(PitwallFrame, Debug)]
+#[pitwall(on_type_mismatch = "warn_once")]
+struct GearFrame {
+    #[field_name = "Speed"]
+    speed: f32,
+
+    #[field_name = "Gear"]
+    gear: Option<i32>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pitwall::PitwallFrame;
+
+#[derive(PitwallFrame, Debug)]
+struct SkippedWithInitializer {
+    #[skip = "1"]
+    lap_counter: u32,
+
+    #[skip = "String::from(\"unset\")"]
+    label: String,
+}
+
+fn main() {}
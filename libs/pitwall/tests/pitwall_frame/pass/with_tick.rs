@@ -0,0 +1,12 @@
+use pitwall::PitwallFrame;
+
+#[derive(PitwallFrame, Debug)]
+#[pitwall(with_tick)]
+struct TickedFrame {
+    #[field_name = "Speed"]
+    speed: f32,
+
+    frame_tick: u32,
+}
+
+fn main() {}
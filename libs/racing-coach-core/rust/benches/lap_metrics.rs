@@ -0,0 +1,79 @@
+//! Benchmarks for lap metrics extraction
+//!
+//! Compares `extract_lap_metrics`, which feeds each frame to every detector
+//! in a single loop, with running the braking, corner and shift extractors
+//! one after another over the same frames, on a long synthetic lap.
+//!
+//! Like the tests, linking needs libpython; see the README.
+
+use std::hint::black_box;
+
+use _rs::{
+    extract_braking_zones, extract_corners, extract_lap_metrics, extract_shifts, AnalysisConfig,
+    TelemetryFrame,
+};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+const FRAMES: usize = 100_000;
+
+/// Frames per corner sequence: straight, braking, corner, exit
+const SEQUENCE_FRAMES: usize = 400;
+
+/// A lap of repeating corner sequences, each with a braking zone, a
+/// downshift, a corner and an upshift.
+fn synthetic_lap(frame_count: usize) -> Vec<TelemetryFrame> {
+    (0..frame_count)
+        .map(|idx| {
+            let phase = idx % SEQUENCE_FRAMES;
+            let (brake, throttle, steering, lateral_g, gear) = match phase {
+                0..=199 => (0.0, 1.0, 0.0, 0.0, 5),
+                200..=259 => (0.9, 0.0, 0.0, 0.0, 4),
+                260..=339 => (0.0, 0.3, 0.4, 2.5, 3),
+                _ => (0.0, 1.0, 0.05, 0.5, 4),
+            };
+            let speed = 70.0 - 25.0 * (brake + steering);
+
+            TelemetryFrame::new(
+                brake,
+                throttle,
+                speed,
+                idx as f64 / frame_count as f64,
+                steering,
+                lateral_g,
+                0.0,
+                idx as f64 / 60.0,
+                false,
+            )
+            .with_gear(gear)
+            .with_rpm(4000.0 + 1000.0 * gear as f64)
+        })
+        .collect()
+}
+
+fn bench_lap_metrics(c: &mut Criterion) {
+    let frames = synthetic_lap(FRAMES);
+    let config = AnalysisConfig::default();
+
+    let mut group = c.benchmark_group("lap_metrics");
+    group.throughput(Throughput::Elements(FRAMES as u64));
+
+    group.bench_function("single_pass", |b| {
+        b.iter(|| extract_lap_metrics(black_box(&frames), &config, 1, None))
+    });
+
+    group.bench_function("multi_pass", |b| {
+        b.iter(|| {
+            let frames = black_box(&frames);
+            (
+                extract_braking_zones(frames, &config),
+                extract_corners(frames, &config),
+                extract_shifts(frames),
+            )
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_lap_metrics);
+criterion_main!(benches);
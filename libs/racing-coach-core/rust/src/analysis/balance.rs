@@ -0,0 +1,187 @@
+//! Oversteer/understeer balance estimation.
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::detection::extract_corners;
+use crate::types::{AnalysisConfig, TelemetryFrame};
+
+/// Steering wheel angle per unit of road wheel angle, typical of race cars.
+pub const STEERING_RATIO: f64 = 12.0;
+
+/// Wheelbase in meters, typical of GT and sports prototype cars.
+pub const WHEELBASE_METERS: f64 = 2.7;
+
+/// Frames slower than this (m/s) are ignored; yaw at parking speeds says
+/// nothing about balance.
+const MIN_BALANCE_SPEED: f64 = 5.0;
+
+/// Handling balance of one corner.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[pyclass]
+pub struct CornerBalance {
+    /// Lap distance where steering input begins (turn-in point)
+    #[pyo3(get)]
+    pub turn_in_distance: f64,
+
+    /// Lap distance at corner apex
+    #[pyo3(get)]
+    pub apex_distance: f64,
+
+    /// Signed balance index: positive when the car rotated more than the
+    /// steering asked for (oversteer), negative when less (understeer)
+    #[pyo3(get)]
+    pub balance: f64,
+}
+
+#[pymethods]
+impl CornerBalance {
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "CornerBalance(apex={:.3}, balance={:+.2})",
+            self.apex_distance, self.balance
+        )
+    }
+}
+
+/// Yaw rate (rad/s) a neutral car would have at `speed` with `steering_angle`
+/// at the wheel, from the kinematic bicycle model.
+fn expected_yaw_rate(speed: f64, steering_angle: f64) -> f64 {
+    speed * (steering_angle / STEERING_RATIO) / WHEELBASE_METERS
+}
+
+/// Balance index over the frames of one corner.
+///
+/// Compares the measured yaw rate in the direction of the steering input
+/// with the yaw rate a neutral car would have: `measured / expected - 1`.
+/// Yaw against the steering (counter-steer) counts as extra oversteer.
+/// Returns 0.0 when no yaw rate was recorded or the car barely moved.
+pub fn corner_balance(frames: &[TelemetryFrame]) -> f64 {
+    let mut measured = 0.0;
+    let mut expected = 0.0;
+    let mut has_yaw = false;
+
+    for frame in frames
+        .iter()
+        .filter(|frame| frame.speed >= MIN_BALANCE_SPEED)
+    {
+        let expected_yaw = expected_yaw_rate(frame.speed, frame.steering_angle);
+        if expected_yaw == 0.0 {
+            continue;
+        }
+        has_yaw |= frame.yaw_rate != 0.0;
+        measured += frame.yaw_rate * expected_yaw.signum();
+        expected += expected_yaw.abs();
+    }
+
+    if !has_yaw || expected == 0.0 {
+        return 0.0;
+    }
+    measured / expected - 1.0
+}
+
+/// Estimate the handling balance of each corner in `frames`.
+///
+/// Corners are detected as in [`extract_corners`]; the balance index is the
+/// one attached to each corner's `CornerMetrics`.
+pub fn estimate_balance(frames: &[TelemetryFrame], config: &AnalysisConfig) -> Vec<CornerBalance> {
+    extract_corners(frames, config)
+        .into_iter()
+        .map(|corner| CornerBalance {
+            turn_in_distance: corner.turn_in_distance,
+            apex_distance: corner.apex_distance,
+            balance: corner.balance,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEED: f64 = 40.0;
+    const STEERING: f64 = 0.6;
+
+    /// A corner at constant speed and steering with `yaw_scale` times the
+    /// neutral yaw rate.
+    fn corner_frames(yaw_scale: f64) -> Vec<TelemetryFrame> {
+        let yaw_rate = expected_yaw_rate(SPEED, STEERING) * yaw_scale;
+        let mut frames = vec![TelemetryFrame::new(
+            0.0, 0.5, SPEED, 0.10, 0.0, 0.0, 0.0, 0.0, false,
+        )];
+        frames.extend((1..10).map(|i| {
+            TelemetryFrame::new(
+                0.0,
+                0.3,
+                SPEED,
+                0.10 + i as f64 * 0.01,
+                STEERING,
+                10.0,
+                0.0,
+                i as f64 * 0.1,
+                false,
+            )
+            .with_yaw_rate(yaw_rate)
+        }));
+        frames.push(TelemetryFrame::new(
+            0.0, 0.8, SPEED, 0.20, 0.0, 0.0, 0.0, 1.0, false,
+        ));
+        frames
+    }
+
+    #[test]
+    fn test_oversteer_is_positive() {
+        let balance = estimate_balance(&corner_frames(1.5), &AnalysisConfig::default());
+
+        assert_eq!(balance.len(), 1);
+        assert!((balance[0].balance - 0.5).abs() < 1e-9, "{:?}", balance[0]);
+    }
+
+    #[test]
+    fn test_understeer_is_negative() {
+        let balance = estimate_balance(&corner_frames(0.6), &AnalysisConfig::default());
+
+        assert_eq!(balance.len(), 1);
+        assert!((balance[0].balance + 0.4).abs() < 1e-9, "{:?}", balance[0]);
+    }
+
+    #[test]
+    fn test_neutral_and_mirrored_corner() {
+        assert!(corner_balance(&corner_frames(1.0)).abs() < 1e-9);
+
+        // A right-hander: steering and yaw both negative
+        let mirrored: Vec<_> = corner_frames(1.5)
+            .into_iter()
+            .map(|mut frame| {
+                frame.steering_angle = -frame.steering_angle;
+                frame.yaw_rate = -frame.yaw_rate;
+                frame
+            })
+            .collect();
+        assert!((corner_balance(&mirrored) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_counter_steer_is_oversteer() {
+        let frames: Vec<_> = corner_frames(1.0)
+            .into_iter()
+            .map(|mut frame| {
+                frame.yaw_rate = -frame.yaw_rate;
+                frame
+            })
+            .collect();
+
+        assert!((corner_balance(&frames) + 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unknown_yaw_rate_is_neutral() {
+        let frames = vec![TelemetryFrame::new(
+            0.0, 0.3, SPEED, 0.1, STEERING, 10.0, 0.0, 0.0, false,
+        )];
+
+        assert_eq!(corner_balance(&frames), 0.0);
+        assert_eq!(corner_balance(&[]), 0.0);
+    }
+}
@@ -0,0 +1,147 @@
+//! Adaptive threshold calibration.
+
+use crate::types::{AnalysisConfig, TelemetryFrame};
+use crate::utils::percentile;
+
+/// Percentile of an input taken as its typical peak; high enough to ignore
+/// most of the straights, low enough to ignore the odd spike.
+const PEAK_PERCENTILE: f64 = 95.0;
+
+/// Fraction of the typical steering peak that counts as turning. A car
+/// peaking around 0.43 rad gets the default threshold of 0.15 rad.
+const STEERING_FRACTION: f64 = 0.35;
+
+/// Fraction of the typical brake peak that counts as braking. A driver
+/// peaking around 85% pressure gets the default threshold of 5%.
+const BRAKE_FRACTION: f64 = 0.06;
+
+/// Floor for the calibrated steering threshold (radians), so sensor noise on
+/// the straights never reads as a corner.
+const MIN_STEERING_THRESHOLD: f64 = 0.02;
+
+/// Floor for the calibrated brake threshold (0-1).
+const MIN_BRAKE_THRESHOLD: f64 = 0.01;
+
+/// Derive detection thresholds from the input distributions of a lap.
+///
+/// Fixed thresholds suit a typical car on a dry track. Low-grip conditions,
+/// quick steering racks or light braking shift those distributions, and
+/// corners or braking zones can go undetected. This scales the steering and
+/// brake thresholds to the lap's own typical peaks instead. Other settings
+/// keep their defaults, as does any threshold whose input never moved.
+///
+/// # Arguments
+/// * `frames` - Slice of telemetry frames, ideally a full lap
+///
+/// # Returns
+/// * AnalysisConfig with calibrated thresholds
+pub fn calibrate_thresholds(frames: &[TelemetryFrame]) -> AnalysisConfig {
+    let mut config = AnalysisConfig::default();
+
+    let steering: Vec<f64> = frames
+        .iter()
+        .map(|frame| frame.steering_angle.abs())
+        .collect();
+    let steering_peak = percentile(&steering, PEAK_PERCENTILE);
+    if steering_peak > 0.0 {
+        config.steering_threshold = (steering_peak * STEERING_FRACTION).max(MIN_STEERING_THRESHOLD);
+    }
+
+    // Only frames on the brakes; most of a lap is spent off them
+    let brake: Vec<f64> = frames
+        .iter()
+        .map(|frame| frame.brake)
+        .filter(|&brake| brake > 0.0)
+        .collect();
+    let brake_peak = percentile(&brake, PEAK_PERCENTILE);
+    if brake_peak > 0.0 {
+        config.brake_threshold = (brake_peak * BRAKE_FRACTION).max(MIN_BRAKE_THRESHOLD);
+    }
+
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{extract_braking_zones, extract_corners};
+
+    fn make_frame(brake: f64, steering: f64, idx: usize) -> TelemetryFrame {
+        TelemetryFrame::new(
+            brake,
+            1.0 - brake,
+            40.0,
+            idx as f64 * 0.005,
+            steering,
+            0.0,
+            0.0,
+            idx as f64 / 60.0,
+            false,
+        )
+    }
+
+    /// A lap with two corners, the steering peaking at `peak_steering` and
+    /// the brakes at `peak_brake` before each.
+    fn lap(peak_steering: f64, peak_brake: f64) -> Vec<TelemetryFrame> {
+        (0..200)
+            .map(|idx| {
+                let phase = idx % 100;
+                let brake = if (30..40).contains(&phase) {
+                    peak_brake
+                } else {
+                    0.0
+                };
+                let steering = if (40..60).contains(&phase) {
+                    peak_steering
+                } else {
+                    0.0
+                };
+                make_frame(brake, steering, idx)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_low_grip_corners_found_after_calibration() {
+        // Wet lap: barely any steering is needed before the car slides
+        let frames = lap(0.1, 0.8);
+
+        assert!(extract_corners(&frames, &AnalysisConfig::default()).is_empty());
+
+        let config = calibrate_thresholds(&frames);
+        assert!((config.steering_threshold - 0.035).abs() < 1e-9);
+        assert_eq!(extract_corners(&frames, &config).len(), 2);
+    }
+
+    #[test]
+    fn test_light_braking_found_after_calibration() {
+        let frames = lap(0.4, 0.04);
+
+        assert!(extract_braking_zones(&frames, &AnalysisConfig::default()).is_empty());
+
+        let config = calibrate_thresholds(&frames);
+        assert!(config.brake_threshold < 0.04);
+        assert_eq!(extract_braking_zones(&frames, &config).len(), 2);
+    }
+
+    #[test]
+    fn test_thresholds_are_floored() {
+        let config = calibrate_thresholds(&lap(0.01, 0.05));
+
+        assert_eq!(config.steering_threshold, MIN_STEERING_THRESHOLD);
+        assert_eq!(config.brake_threshold, MIN_BRAKE_THRESHOLD);
+    }
+
+    #[test]
+    fn test_idle_inputs_keep_defaults() {
+        let defaults = AnalysisConfig::default();
+
+        for config in [
+            calibrate_thresholds(&[]),
+            calibrate_thresholds(&lap(0.0, 0.0)),
+        ] {
+            assert_eq!(config.steering_threshold, defaults.steering_threshold);
+            assert_eq!(config.brake_threshold, defaults.brake_threshold);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Track condition tracking over a session.
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::TelemetryFrame;
+
+/// A change in track wetness.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[pyclass]
+pub struct WetnessTransition {
+    /// Timestamp of the first frame at the new level, in seconds
+    #[pyo3(get)]
+    pub timestamp: f64,
+
+    /// Wetness level before the change
+    #[pyo3(get)]
+    pub from_level: i32,
+
+    /// Wetness level after the change
+    #[pyo3(get)]
+    pub to_level: i32,
+}
+
+#[pymethods]
+impl WetnessTransition {
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "WetnessTransition(t={:.1}, {} -> {})",
+            self.timestamp, self.from_level, self.to_level
+        )
+    }
+}
+
+/// Min, max and sum of a temperature channel.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct TemperatureRange {
+    min: f64,
+    max: f64,
+    sum: f64,
+}
+
+impl TemperatureRange {
+    fn new(value: f64) -> Self {
+        Self {
+            min: value,
+            max: value,
+            sum: value,
+        }
+    }
+
+    fn update(&mut self, value: f64) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value;
+    }
+}
+
+/// Accumulator for how track and weather conditions evolve over a session.
+///
+/// Tracks the range and mean of track and air temperature and every change
+/// in track wetness, so laps driven in different conditions can be told
+/// apart. Temperatures read 0.0 when no frames have been added.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[pyclass]
+pub struct TrackConditions {
+    count: usize,
+    track_temp: Option<TemperatureRange>,
+    air_temp: Option<TemperatureRange>,
+    wetness: Option<i32>,
+    initial_wetness: Option<i32>,
+    transitions: Vec<WetnessTransition>,
+}
+
+impl TrackConditions {
+    /// Create a new empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an accumulator pre-filled with `frames`.
+    pub fn from_frames(frames: &[TelemetryFrame]) -> Self {
+        let mut conditions = Self::new();
+        for frame in frames {
+            conditions.update(frame);
+        }
+        conditions
+    }
+
+    /// Record the conditions of one frame.
+    pub fn update(&mut self, frame: &TelemetryFrame) {
+        self.count += 1;
+
+        match &mut self.track_temp {
+            Some(range) => range.update(frame.track_temp),
+            None => self.track_temp = Some(TemperatureRange::new(frame.track_temp)),
+        }
+        match &mut self.air_temp {
+            Some(range) => range.update(frame.air_temp),
+            None => self.air_temp = Some(TemperatureRange::new(frame.air_temp)),
+        }
+
+        match self.wetness {
+            Some(level) if level != frame.track_wetness => {
+                self.transitions.push(WetnessTransition {
+                    timestamp: frame.timestamp,
+                    from_level: level,
+                    to_level: frame.track_wetness,
+                });
+            }
+            Some(_) => {}
+            None => self.initial_wetness = Some(frame.track_wetness),
+        }
+        self.wetness = Some(frame.track_wetness);
+    }
+
+    fn mean(&self, range: Option<TemperatureRange>) -> f64 {
+        range.map_or(0.0, |range| range.sum / self.count as f64)
+    }
+}
+
+#[pymethods]
+impl TrackConditions {
+    /// Create an accumulator from a list of frames (Python constructor).
+    #[new]
+    #[pyo3(signature = (frames=Vec::new()))]
+    fn py_new(frames: Vec<TelemetryFrame>) -> Self {
+        Self::from_frames(&frames)
+    }
+
+    /// Lowest track temperature in Celsius.
+    pub fn track_temp_min(&self) -> f64 {
+        self.track_temp.map_or(0.0, |range| range.min)
+    }
+
+    /// Highest track temperature in Celsius.
+    pub fn track_temp_max(&self) -> f64 {
+        self.track_temp.map_or(0.0, |range| range.max)
+    }
+
+    /// Average track temperature in Celsius.
+    pub fn track_temp_mean(&self) -> f64 {
+        self.mean(self.track_temp)
+    }
+
+    /// Lowest air temperature in Celsius.
+    pub fn air_temp_min(&self) -> f64 {
+        self.air_temp.map_or(0.0, |range| range.min)
+    }
+
+    /// Highest air temperature in Celsius.
+    pub fn air_temp_max(&self) -> f64 {
+        self.air_temp.map_or(0.0, |range| range.max)
+    }
+
+    /// Average air temperature in Celsius.
+    pub fn air_temp_mean(&self) -> f64 {
+        self.mean(self.air_temp)
+    }
+
+    /// Wetness level of the first frame, if any.
+    pub fn initial_wetness(&self) -> Option<i32> {
+        self.initial_wetness
+    }
+
+    /// Wetness level of the latest frame, if any.
+    pub fn final_wetness(&self) -> Option<i32> {
+        self.wetness
+    }
+
+    /// Every change in wetness level, in order.
+    pub fn wetness_transitions(&self) -> Vec<WetnessTransition> {
+        self.transitions.clone()
+    }
+
+    /// Number of frames recorded.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "TrackConditions(count={}, track_temp={:.1}-{:.1}, air_temp={:.1}-{:.1}, wetness_changes={})",
+            self.count,
+            self.track_temp_min(),
+            self.track_temp_max(),
+            self.air_temp_min(),
+            self.air_temp_max(),
+            self.transitions.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_frame(timestamp: f64, track_temp: f64, air_temp: f64, wetness: i32) -> TelemetryFrame {
+        TelemetryFrame::new(0.0, 1.0, 50.0, 0.5, 0.0, 0.0, 0.0, timestamp, false)
+            .with_conditions(track_temp, air_temp, wetness)
+    }
+
+    #[test]
+    fn test_empty_conditions() {
+        let conditions = TrackConditions::new();
+
+        assert_eq!(conditions.count(), 0);
+        assert_eq!(conditions.track_temp_min(), 0.0);
+        assert_eq!(conditions.track_temp_mean(), 0.0);
+        assert_eq!(conditions.air_temp_max(), 0.0);
+        assert_eq!(conditions.initial_wetness(), None);
+        assert!(conditions.wetness_transitions().is_empty());
+    }
+
+    #[test]
+    fn test_rising_track_temp() {
+        let frames: Vec<_> = (0..5)
+            .map(|i| make_frame(i as f64 * 60.0, 30.0 + i as f64, 20.0, 1))
+            .collect();
+
+        let conditions = TrackConditions::from_frames(&frames);
+
+        assert_eq!(conditions.count(), 5);
+        assert_eq!(conditions.track_temp_min(), 30.0);
+        assert_eq!(conditions.track_temp_max(), 34.0);
+        assert_eq!(conditions.track_temp_mean(), 32.0);
+        assert_eq!(conditions.air_temp_min(), 20.0);
+        assert_eq!(conditions.air_temp_mean(), 20.0);
+        assert!(conditions.wetness_transitions().is_empty());
+    }
+
+    #[test]
+    fn test_wetness_transitions() {
+        let frames = [
+            make_frame(0.0, 25.0, 18.0, 1),
+            make_frame(10.0, 25.0, 18.0, 1),
+            make_frame(20.0, 24.0, 17.0, 3),
+            make_frame(30.0, 23.0, 17.0, 3),
+            make_frame(40.0, 23.0, 17.0, 2),
+        ];
+
+        let conditions = TrackConditions::from_frames(&frames);
+
+        assert_eq!(conditions.initial_wetness(), Some(1));
+        assert_eq!(conditions.final_wetness(), Some(2));
+        assert_eq!(
+            conditions.wetness_transitions(),
+            vec![
+                WetnessTransition {
+                    timestamp: 20.0,
+                    from_level: 1,
+                    to_level: 3,
+                },
+                WetnessTransition {
+                    timestamp: 40.0,
+                    from_level: 3,
+                    to_level: 2,
+                },
+            ]
+        );
+        assert_eq!(conditions.track_temp_min(), 23.0);
+        assert_eq!(conditions.track_temp_max(), 25.0);
+    }
+}
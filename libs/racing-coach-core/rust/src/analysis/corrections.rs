@@ -0,0 +1,183 @@
+//! Steering correction (counter-steer) detection.
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{AnalysisConfig, TelemetryFrame};
+
+/// A rapid steering reversal made while the car was rotating, typically the
+/// driver catching a slide.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
+pub struct CorrectionEvent {
+    /// Track distance (0-1) where the reversal started
+    #[pyo3(get)]
+    pub distance: f64,
+
+    /// Steering angle swept through in radians
+    #[pyo3(get)]
+    pub magnitude: f64,
+
+    /// Time from the start to the end of the reversal in seconds
+    #[pyo3(get)]
+    pub duration: f64,
+
+    /// Highest absolute yaw rate during the reversal in rad/s
+    #[pyo3(get)]
+    pub peak_yaw_rate: f64,
+}
+
+#[pymethods]
+impl CorrectionEvent {
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "CorrectionEvent(distance={:.3}, magnitude={:.2}, duration={:.2}s, yaw_rate={:.2})",
+            self.distance, self.magnitude, self.duration, self.peak_yaw_rate
+        )
+    }
+}
+
+/// Find steering corrections in `frames`.
+///
+/// A correction is a swing of the wheel at `correction_steering_rate` or
+/// faster, in one direction without pause, that carries the steering angle
+/// across zero while the yaw rate reaches `correction_yaw_rate`. Turning in
+/// and unwinding out of a corner are too slow to count, and a quick flick on
+/// a straight doesn't rotate the car. Intervals whose timestamps don't
+/// increase end the current swing.
+pub fn detect_corrections(
+    frames: &[TelemetryFrame],
+    config: &AnalysisConfig,
+) -> Vec<CorrectionEvent> {
+    let mut corrections = Vec::new();
+    // Start index and direction of the swing in progress
+    let mut swing: Option<(usize, f64)> = None;
+
+    for (idx, pair) in frames.windows(2).enumerate() {
+        let dt = pair[1].timestamp - pair[0].timestamp;
+        let rate = (pair[1].steering_angle - pair[0].steering_angle) / dt;
+        let direction = if dt > 0.0 && rate.abs() >= config.correction_steering_rate {
+            rate.signum()
+        } else {
+            0.0
+        };
+
+        match swing {
+            Some((_, current)) if current == direction => continue,
+            Some((start, _)) => {
+                corrections.extend(finish_swing(&frames[start..=idx], config));
+            }
+            None => {}
+        }
+        swing = (direction != 0.0).then_some((idx, direction));
+    }
+
+    if let Some((start, _)) = swing {
+        corrections.extend(finish_swing(&frames[start..], config));
+    }
+    corrections
+}
+
+/// The correction made by a fast steering swing over `frames`, if any.
+fn finish_swing(frames: &[TelemetryFrame], config: &AnalysisConfig) -> Option<CorrectionEvent> {
+    let (first, last) = (frames.first()?, frames.last()?);
+    if first.steering_angle * last.steering_angle >= 0.0 {
+        return None;
+    }
+
+    let peak_yaw_rate = frames.iter().map(|f| f.yaw_rate.abs()).fold(0.0, f64::max);
+    (peak_yaw_rate >= config.correction_yaw_rate).then(|| CorrectionEvent {
+        distance: first.lap_distance,
+        magnitude: (last.steering_angle - first.steering_angle).abs(),
+        duration: last.timestamp - first.timestamp,
+        peak_yaw_rate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f64 = 1.0 / 60.0;
+
+    /// Frames 1/60 s apart with the given steering angles and a constant yaw rate.
+    fn frames(steering: &[f64], yaw_rate: f64) -> Vec<TelemetryFrame> {
+        steering
+            .iter()
+            .enumerate()
+            .map(|(idx, &angle)| {
+                let t = idx as f64 * DT;
+                TelemetryFrame::new(0.0, 0.5, 40.0, t / 90.0, angle, 0.0, 0.0, t, false)
+                    .with_yaw_rate(yaw_rate)
+            })
+            .collect()
+    }
+
+    /// Steering that turns in to `lock` over `secs`, holds it, and unwinds
+    /// over the same time.
+    fn smooth_corner(lock: f64, secs: f64) -> Vec<f64> {
+        let ramp = (secs / DT) as usize;
+        let turn_in = (0..ramp).map(|i| lock * i as f64 / ramp as f64);
+        let unwind = (0..=ramp).map(|i| lock * (1.0 - i as f64 / ramp as f64));
+        turn_in
+            .chain(std::iter::repeat_n(lock, 60))
+            .chain(unwind)
+            .collect()
+    }
+
+    #[test]
+    fn test_counter_steer_is_detected() {
+        // Holding 0.4 rad of lock, then flicking to -0.8 rad over 0.2 s
+        let mut steering = vec![0.4; 30];
+        steering.extend((1..=12).map(|i| 0.4 - 0.1 * i as f64));
+        steering.extend([-0.8; 30]);
+
+        let corrections = detect_corrections(&frames(&steering, 0.8), &AnalysisConfig::default());
+
+        assert_eq!(corrections.len(), 1);
+        let correction = corrections[0];
+        assert!((correction.magnitude - 1.2).abs() < 1e-9);
+        assert!((correction.duration - 12.0 * DT).abs() < 1e-9);
+        assert!((correction.distance - 29.0 * DT / 90.0).abs() < 1e-9);
+        assert_eq!(correction.peak_yaw_rate, 0.8);
+    }
+
+    #[test]
+    fn test_smooth_corner_has_no_corrections() {
+        let steering = smooth_corner(0.6, 1.0);
+        assert!(detect_corrections(&frames(&steering, 0.5), &AnalysisConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_flick_without_rotation_is_ignored() {
+        let mut steering = vec![0.4; 10];
+        steering.extend((1..=12).map(|i| 0.4 - 0.1 * i as f64));
+        let config = AnalysisConfig::default();
+
+        assert!(detect_corrections(&frames(&steering, 0.0), &config).is_empty());
+
+        let config = AnalysisConfig {
+            correction_yaw_rate: 0.0,
+            ..config
+        };
+        assert_eq!(
+            detect_corrections(&frames(&steering, 0.0), &config).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_steering_rate_is_configurable() {
+        // Unwinding at 1.2 rad/s, through zero into the next corner
+        let steering: Vec<f64> = (0..60).map(|i| 0.6 - 0.02 * i as f64).collect();
+        let lap = frames(&steering, 0.5);
+        assert!(detect_corrections(&lap, &AnalysisConfig::default()).is_empty());
+
+        let config = AnalysisConfig {
+            correction_steering_rate: 1.0,
+            ..AnalysisConfig::default()
+        };
+        assert_eq!(detect_corrections(&lap, &config).len(), 1);
+    }
+}
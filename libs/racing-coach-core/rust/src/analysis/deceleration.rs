@@ -7,6 +7,10 @@ use crate::types::TelemetryFrame;
 /// Returns the rate of speed change in m/s². Negative values indicate
 /// deceleration (slowing down), positive values indicate acceleration.
 ///
+/// Some replays repeat a frame's timestamp. When the end frame shares the
+/// start frame's timestamp, the measurement runs on to the first later frame
+/// with a different one, so duplicates never divide by zero.
+///
 /// # Arguments
 /// * `frames` - Slice of telemetry frames
 /// * `start_idx` - Starting frame index
@@ -14,22 +18,72 @@ use crate::types::TelemetryFrame;
 ///
 /// # Returns
 /// * Deceleration in m/s² (negative for slowing down)
-/// * Returns 0.0 if indices are invalid or time delta is zero
+/// * Returns 0.0 if indices are invalid, no later frame has a different
+///   timestamp, or time runs backwards
 pub fn calculate_deceleration(frames: &[TelemetryFrame], start_idx: usize, end_idx: usize) -> f64 {
     if start_idx >= end_idx || end_idx >= frames.len() {
         return 0.0;
     }
 
     let start_frame = &frames[start_idx];
-    let end_frame = &frames[end_idx];
+    let Some(end_frame) = frames[end_idx..]
+        .iter()
+        .find(|frame| frame.timestamp != start_frame.timestamp)
+    else {
+        return 0.0;
+    };
 
     let speed_delta = end_frame.speed - start_frame.speed;
     let time_delta = end_frame.timestamp - start_frame.timestamp;
 
-    if time_delta <= 0.0 {
+    if time_delta > 0.0 {
+        speed_delta / time_delta
+    } else {
         0.0
+    }
+}
+
+/// Least-squares slope of speed against time between two frame indices.
+///
+/// Like [`calculate_deceleration`] but fitted through every frame in the
+/// window, so a single noisy sample at either end barely moves it. Repeated
+/// timestamps at the end of the window are handled the same way.
+///
+/// # Returns
+/// * Deceleration in m/s² (negative for slowing down)
+/// * Returns 0.0 if indices are invalid, no later frame has a different
+///   timestamp, or time runs backwards
+pub fn deceleration_slope(frames: &[TelemetryFrame], start_idx: usize, end_idx: usize) -> f64 {
+    if start_idx >= end_idx || end_idx >= frames.len() {
+        return 0.0;
+    }
+
+    let start_time = frames[start_idx].timestamp;
+    let Some(extra) = frames[end_idx..]
+        .iter()
+        .position(|frame| frame.timestamp != start_time)
+    else {
+        return 0.0;
+    };
+    let end_idx = end_idx + extra;
+    if frames[end_idx].timestamp < start_time {
+        return 0.0;
+    }
+
+    // Times relative to the first frame keep the sums well conditioned
+    let window = &frames[start_idx..=end_idx];
+    let n = window.len() as f64;
+    let mean_t = window.iter().map(|f| f.timestamp - start_time).sum::<f64>() / n;
+    let mean_v = window.iter().map(|f| f.speed).sum::<f64>() / n;
+    let (sxy, sxx) = window.iter().fold((0.0, 0.0), |(sxy, sxx), frame| {
+        let dt = frame.timestamp - start_time - mean_t;
+        (sxy + dt * (frame.speed - mean_v), sxx + dt * dt)
+    });
+
+    if sxx > 0.0 {
+        sxy / sxx
     } else {
-        speed_delta / time_delta
+        0.0
     }
 }
 
@@ -38,7 +92,7 @@ mod tests {
     use super::*;
 
     fn make_frame(speed: f64, timestamp: f64) -> TelemetryFrame {
-        TelemetryFrame::new(0.0, 0.0, speed, 0.0, 0.0, 0.0, 0.0, timestamp)
+        TelemetryFrame::new(0.0, 0.0, speed, 0.0, 0.0, 0.0, 0.0, timestamp, false)
     }
 
     #[test]
@@ -88,4 +142,77 @@ mod tests {
 
         assert_eq!(calculate_deceleration(&frames, 0, 1), 0.0);
     }
+
+    #[test]
+    fn test_duplicate_timestamps_use_next_distinct_frame() {
+        let frames = vec![
+            make_frame(100.0, 0.0),
+            make_frame(100.0, 0.0), // Repeated frame
+            make_frame(99.0, 0.0),  // Repeated timestamp, new speed
+            make_frame(98.0, 0.1),
+            make_frame(96.0, 0.2),
+        ];
+
+        // Runs on from frame 2 to frame 3: -2 m/s over 0.1 s
+        let decel = calculate_deceleration(&frames, 0, 2);
+        assert!((decel - (-20.0)).abs() < 1e-10, "got {decel}");
+
+        // Duplicates between the endpoints don't matter
+        let decel = calculate_deceleration(&frames, 0, 4);
+        assert!((decel - (-20.0)).abs() < 1e-10, "got {decel}");
+    }
+
+    #[test]
+    fn test_backwards_time_is_zero() {
+        let frames = vec![make_frame(100.0, 5.0), make_frame(80.0, 4.0)];
+
+        assert_eq!(calculate_deceleration(&frames, 0, 1), 0.0);
+        assert_eq!(deceleration_slope(&frames, 0, 1), 0.0);
+    }
+
+    #[test]
+    fn test_slope_matches_linear_deceleration() {
+        let frames: Vec<_> = (0..10)
+            .map(|i| make_frame(80.0 - 1.5 * i as f64, i as f64 / 60.0))
+            .collect();
+
+        // 1.5 m/s per 1/60 s = -90 m/s²
+        let slope = deceleration_slope(&frames, 0, 9);
+        assert!((slope - (-90.0)).abs() < 1e-9, "got {slope}");
+        assert!((slope - calculate_deceleration(&frames, 0, 9)).abs() < 1e-9);
+
+        // Invalid indices and a window without time both give 0.0
+        assert_eq!(deceleration_slope(&frames, 4, 4), 0.0);
+        assert_eq!(deceleration_slope(&frames, 0, 10), 0.0);
+        assert_eq!(
+            deceleration_slope(&[make_frame(80.0, 0.0), make_frame(70.0, 0.0)], 0, 1),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_slope_is_stable_under_noise() {
+        // Alternating +/-0.5 m/s noise on a -90 m/s² ramp
+        let noise = |i: usize| if i.is_multiple_of(2) { 0.5 } else { -0.5 };
+        let frames: Vec<_> = (0..10)
+            .map(|i| make_frame(80.0 - 1.5 * i as f64 + noise(i), i as f64 / 60.0))
+            .collect();
+
+        let slope = deceleration_slope(&frames, 0, 9);
+        let endpoints = calculate_deceleration(&frames, 0, 9);
+        assert!((slope - (-90.0)).abs() < 10.0, "got {slope}");
+        assert!((slope - (-90.0)).abs() < (endpoints - (-90.0)).abs());
+    }
+
+    #[test]
+    fn test_slope_runs_past_repeated_end_timestamps() {
+        let frames = vec![
+            make_frame(100.0, 0.0),
+            make_frame(99.0, 0.0), // Repeated timestamp
+            make_frame(98.0, 0.1),
+        ];
+
+        let slope = deceleration_slope(&frames, 0, 1);
+        assert!(slope < 0.0, "got {slope}");
+    }
 }
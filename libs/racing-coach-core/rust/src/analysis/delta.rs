@@ -0,0 +1,205 @@
+//! Lap-to-lap time delta analysis.
+
+use crate::types::TelemetryFrame;
+
+/// Number of evenly spaced distance samples in a time delta trace.
+pub const DEFAULT_DELTA_SAMPLES: usize = 200;
+
+/// Cumulative time delta at a point on track.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeltaPoint {
+    /// Lap distance of the sample (normalized 0-1)
+    pub distance: f64,
+    /// Time lost versus the reference lap up to this distance in seconds
+    /// (positive = comparison lap is slower)
+    pub delta: f64,
+}
+
+/// Compute where the comparison lap gained or lost time versus a reference lap.
+///
+/// Elapsed time is interpolated against lap distance for both laps and sampled
+/// at [`DEFAULT_DELTA_SAMPLES`] evenly spaced distances. See
+/// [`compute_time_delta_with_samples`] for details.
+pub fn compute_time_delta(
+    reference: &[TelemetryFrame],
+    comparison: &[TelemetryFrame],
+) -> Vec<DeltaPoint> {
+    compute_time_delta_with_samples(reference, comparison, DEFAULT_DELTA_SAMPLES)
+}
+
+/// Compute a time delta trace with `samples` evenly spaced distance points.
+///
+/// Laps may have different frame counts and slightly different start and end
+/// distances: the trace only covers the distance range both laps share, and
+/// the delta is zero at its start. Frames that don't advance lap distance
+/// (e.g. when stationary) are ignored.
+///
+/// # Returns
+/// * Delta points ordered by distance, or an empty vec if either lap has fewer
+///   than two usable frames or the laps don't overlap
+pub fn compute_time_delta_with_samples(
+    reference: &[TelemetryFrame],
+    comparison: &[TelemetryFrame],
+    samples: usize,
+) -> Vec<DeltaPoint> {
+    let reference = distance_time_trace(reference);
+    let comparison = distance_time_trace(comparison);
+    if reference.len() < 2 || comparison.len() < 2 || samples < 2 {
+        return Vec::new();
+    }
+
+    let start = reference[0].0.max(comparison[0].0);
+    let end = reference[reference.len() - 1]
+        .0
+        .min(comparison[comparison.len() - 1].0);
+    if end <= start {
+        return Vec::new();
+    }
+
+    let reference_start = interpolate_time(&reference, start);
+    let comparison_start = interpolate_time(&comparison, start);
+    let step = (end - start) / (samples - 1) as f64;
+
+    (0..samples)
+        .map(|i| {
+            let distance = start + step * i as f64;
+            let reference_elapsed = interpolate_time(&reference, distance) - reference_start;
+            let comparison_elapsed = interpolate_time(&comparison, distance) - comparison_start;
+            DeltaPoint {
+                distance,
+                delta: comparison_elapsed - reference_elapsed,
+            }
+        })
+        .collect()
+}
+
+/// Build a strictly increasing (distance, timestamp) trace for a lap.
+///
+/// A lap whose first frames sit just before the start/finish line (e.g.
+/// 0.995, then 0.0, 0.01, ...) has those frames placed before 0.0, at
+/// 0.995 - 1.0, so they lead into the lap instead of hiding the rest of it.
+pub(super) fn distance_time_trace(frames: &[TelemetryFrame]) -> Vec<(f64, f64)> {
+    let mut trace: Vec<(f64, f64)> = Vec::with_capacity(frames.len());
+
+    // Frames before the line are on the far half of the track until it wraps
+    let pre_line = frames
+        .iter()
+        .take_while(|frame| frame.lap_distance > 0.5)
+        .count();
+    let pre_line = if pre_line < frames.len() { pre_line } else { 0 };
+
+    for (i, frame) in frames.iter().enumerate() {
+        let lap_distance = if i < pre_line {
+            frame.lap_distance - 1.0
+        } else {
+            frame.lap_distance
+        };
+        match trace.last() {
+            Some(&(distance, _)) if lap_distance <= distance => {}
+            _ => trace.push((lap_distance, frame.timestamp)),
+        }
+    }
+
+    trace
+}
+
+/// Linearly interpolate the timestamp at `distance` within `trace`.
+///
+/// `distance` must lie within the trace's distance range.
+pub(super) fn interpolate_time(trace: &[(f64, f64)], distance: f64) -> f64 {
+    // Index of the first point at or beyond `distance`
+    let upper = trace
+        .partition_point(|&(d, _)| d < distance)
+        .clamp(1, trace.len() - 1);
+    let (d0, t0) = trace[upper - 1];
+    let (d1, t1) = trace[upper];
+
+    t0 + (t1 - t0) * (distance - d0) / (d1 - d0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Frames evenly spaced in distance, at constant speed over `lap_time`.
+    fn make_lap(frame_count: usize, start_distance: f64, lap_time: f64) -> Vec<TelemetryFrame> {
+        (0..frame_count)
+            .map(|i| {
+                let distance =
+                    start_distance + (1.0 - start_distance) * i as f64 / (frame_count - 1) as f64;
+                TelemetryFrame::new(
+                    0.0,
+                    1.0,
+                    50.0,
+                    distance,
+                    0.0,
+                    0.0,
+                    0.0,
+                    distance * lap_time,
+                    false,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_uniformly_slower_lap() {
+        let reference = make_lap(101, 0.0, 100.0);
+        // 1% slower, different frame count, slightly later start
+        let comparison = make_lap(77, 0.005, 101.0);
+
+        let delta = compute_time_delta(&reference, &comparison);
+
+        assert_eq!(delta.len(), DEFAULT_DELTA_SAMPLES);
+        assert!((delta[0].distance - 0.005).abs() < 1e-10);
+        assert!(delta[0].delta.abs() < 1e-10);
+        for pair in delta.windows(2) {
+            assert!(pair[1].delta > pair[0].delta);
+        }
+
+        // 1% of the 99.5s covered by the reference over the shared range
+        let last = delta.last().unwrap();
+        assert!((last.distance - 1.0).abs() < 1e-10);
+        assert!((last.delta - 0.995).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_lap_starting_before_the_line() {
+        let reference = make_lap(101, 0.0, 100.0);
+        // Recorded from just before the line, 1% slower
+        let mut comparison = vec![TelemetryFrame::new(
+            0.0, 1.0, 50.0, 0.99, 0.0, 0.0, 0.0, -1.01, false,
+        )];
+        comparison.extend(make_lap(77, 0.0, 101.0));
+
+        let delta = compute_time_delta(&reference, &comparison);
+
+        assert_eq!(delta.len(), DEFAULT_DELTA_SAMPLES);
+        assert!(delta[0].distance.abs() < 1e-10);
+        assert!(delta[0].delta.abs() < 1e-10);
+        let last = delta.last().unwrap();
+        assert!((last.distance - 1.0).abs() < 1e-10);
+        assert!((last.delta - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_identical_laps_have_zero_delta() {
+        let lap = make_lap(50, 0.0, 90.0);
+        let delta = compute_time_delta_with_samples(&lap, &lap, 10);
+
+        assert_eq!(delta.len(), 10);
+        assert!(delta.iter().all(|point| point.delta.abs() < 1e-10));
+    }
+
+    #[test]
+    fn test_insufficient_data() {
+        let lap = make_lap(50, 0.0, 90.0);
+
+        assert!(compute_time_delta(&[], &lap).is_empty());
+        assert!(compute_time_delta(&lap, &lap[..1]).is_empty());
+
+        // Stationary frames collapse to a single point
+        let stationary = vec![lap[0]; 10];
+        assert!(compute_time_delta(&lap, &stationary).is_empty());
+    }
+}
@@ -0,0 +1,132 @@
+//! Fuel usage and projected range.
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::TelemetryFrame;
+
+/// Smallest rise in fuel level between two frames (liters) treated as
+/// refueling. Smaller rises are fuel sloshing around the sender.
+const REFUEL_THRESHOLD: f64 = 0.1;
+
+/// Fuel used over a run of frames, typically one lap, and how far the
+/// remaining fuel would go at the same rate.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
+pub struct FuelUsage {
+    /// Fuel burned in liters, not counting any fuel added
+    #[pyo3(get)]
+    pub fuel_used: f64,
+
+    /// Fuel level on the last frame in liters
+    #[pyo3(get)]
+    pub fuel_remaining: f64,
+
+    /// Whether fuel was added along the way
+    #[pyo3(get)]
+    pub refueled: bool,
+
+    /// Laps the remaining fuel lasts at this burn rate (None if no fuel was burned)
+    #[pyo3(get)]
+    pub laps_remaining: Option<f64>,
+}
+
+#[pymethods]
+impl FuelUsage {
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "FuelUsage(used={:.2}, remaining={:.2}, laps_remaining={:?}, refueled={})",
+            self.fuel_used, self.fuel_remaining, self.laps_remaining, self.refueled
+        )
+    }
+}
+
+/// Measure the fuel burned over `frames`.
+///
+/// The frames are split wherever the fuel level jumps up by more than
+/// [`REFUEL_THRESHOLD`], and the burn is the drop over each stretch between
+/// refuels, so a pit stop never counts as negative burn. A stretch whose level
+/// ends higher than it started, from sensor noise, counts as no burn.
+///
+/// # Returns
+/// * `None` if there are no frames or the fuel level is never reported
+///   (zero throughout)
+pub fn compute_fuel_usage(frames: &[TelemetryFrame]) -> Option<FuelUsage> {
+    let last = frames.last()?;
+    if frames.iter().all(|frame| frame.fuel_level == 0.0) {
+        return None;
+    }
+
+    let mut fuel_used = 0.0;
+    let mut refueled = false;
+    let mut stint_start = frames[0].fuel_level;
+    for pair in frames.windows(2) {
+        let (before, after) = (pair[0].fuel_level, pair[1].fuel_level);
+        if after - before > REFUEL_THRESHOLD {
+            fuel_used += (stint_start - before).max(0.0);
+            stint_start = after;
+            refueled = true;
+        }
+    }
+    fuel_used += (stint_start - last.fuel_level).max(0.0);
+
+    Some(FuelUsage {
+        fuel_used,
+        fuel_remaining: last.fuel_level,
+        refueled,
+        laps_remaining: (fuel_used > 0.0).then(|| last.fuel_level / fuel_used),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lap(fuel_levels: &[f64]) -> Vec<TelemetryFrame> {
+        fuel_levels
+            .iter()
+            .enumerate()
+            .map(|(i, &fuel_level)| {
+                TelemetryFrame::new(0.0, 1.0, 50.0, 0.0, 0.0, 0.0, 0.0, i as f64, false)
+                    .with_fuel_level(fuel_level)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fuel_burned_over_a_lap() {
+        let usage = compute_fuel_usage(&lap(&[40.0, 39.2, 38.5, 37.5])).unwrap();
+
+        assert!((usage.fuel_used - 2.5).abs() < 1e-10);
+        assert_eq!(usage.fuel_remaining, 37.5);
+        assert!(!usage.refueled);
+        assert!((usage.laps_remaining.unwrap() - 15.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_refuel_is_not_negative_burn() {
+        // 2 liters burned on the way in, 40 added, 1 burned on the way out
+        let usage = compute_fuel_usage(&lap(&[5.0, 3.0, 3.0, 43.0, 42.5, 42.0])).unwrap();
+
+        assert!((usage.fuel_used - 3.0).abs() < 1e-10);
+        assert_eq!(usage.fuel_remaining, 42.0);
+        assert!(usage.refueled);
+        assert!((usage.laps_remaining.unwrap() - 14.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_sloshing_and_missing_fuel() {
+        // Small rises are noise, not refueling
+        let usage = compute_fuel_usage(&lap(&[20.0, 19.9, 19.95, 19.5])).unwrap();
+        assert!(!usage.refueled);
+        assert!((usage.fuel_used - 0.5).abs() < 1e-10);
+
+        // No burn, no projection
+        let usage = compute_fuel_usage(&lap(&[20.0, 20.0])).unwrap();
+        assert_eq!(usage.laps_remaining, None);
+
+        assert_eq!(compute_fuel_usage(&lap(&[0.0, 0.0])), None);
+        assert_eq!(compute_fuel_usage(&[]), None);
+    }
+}
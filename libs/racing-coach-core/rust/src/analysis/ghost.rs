@@ -0,0 +1,133 @@
+//! Compact "ghost" laps for overlaying in other tools.
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::resample_by_distance;
+use crate::types::TelemetryFrame;
+
+/// A lap's driver inputs and speed on an evenly spaced distance axis.
+///
+/// Each array holds one value per point, in distance order, so the lap can be
+/// shared as JSON and lined up against another lap point by point.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
+pub struct GhostLap {
+    /// Lap distance (normalized 0-1) of each point
+    #[pyo3(get)]
+    pub distance: Vec<f64>,
+
+    /// Speed in m/s
+    #[pyo3(get)]
+    pub speed: Vec<f64>,
+
+    /// Throttle position (0-1)
+    #[pyo3(get)]
+    pub throttle: Vec<f64>,
+
+    /// Brake pressure (0-1)
+    #[pyo3(get)]
+    pub brake: Vec<f64>,
+
+    /// Steering angle in radians
+    #[pyo3(get)]
+    pub steering: Vec<f64>,
+}
+
+#[pymethods]
+impl GhostLap {
+    /// Serialize the ghost to a JSON string.
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("GhostLap should always serialize")
+    }
+
+    /// Number of points.
+    fn __len__(&self) -> usize {
+        self.distance.len()
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!("GhostLap(points={})", self.distance.len())
+    }
+}
+
+/// Resample a lap into a [`GhostLap`] of `num_points` evenly spaced lap
+/// distances from 0.0 to 1.0.
+///
+/// Channels are interpolated as in [`resample_by_distance`].
+///
+/// # Returns
+/// * A ghost with `num_points` points, or an empty ghost if there are no frames
+pub fn export_ghost(frames: &[TelemetryFrame], num_points: usize) -> GhostLap {
+    let resampled = resample_by_distance(frames, num_points);
+    let channel = |value: fn(&TelemetryFrame) -> f64| resampled.iter().map(value).collect();
+
+    GhostLap {
+        distance: channel(|frame| frame.lap_distance),
+        speed: channel(|frame| frame.speed),
+        throttle: channel(|frame| frame.throttle),
+        brake: channel(|frame| frame.brake),
+        steering: channel(|frame| frame.steering_angle),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{FrameSequenceBuilder, BRAKE_PRESSURE};
+
+    /// Most of a 1 km lap: a straight, a stop for a hairpin and the hairpin.
+    fn lap() -> Vec<TelemetryFrame> {
+        FrameSequenceBuilder::new()
+            .track_length(1000.0)
+            .straight(10.0, 60.0)
+            .braking(2.0, 60.0, 20.0)
+            .corner(5.0, 15.0, 0.4)
+            .build()
+    }
+
+    #[test]
+    fn test_ghost_arrays_share_a_distance_axis() {
+        let ghost = export_ghost(&lap(), 101);
+
+        for channel in [
+            &ghost.distance,
+            &ghost.speed,
+            &ghost.throttle,
+            &ghost.brake,
+            &ghost.steering,
+        ] {
+            assert_eq!(channel.len(), 101);
+        }
+        assert_eq!(ghost.distance[0], 0.0);
+        assert_eq!(ghost.distance[100], 1.0);
+        assert!(ghost.distance.windows(2).all(|pair| pair[1] > pair[0]));
+
+        // The straight covers the first 60% of the lap
+        assert_eq!(ghost.speed[30], 60.0);
+        assert_eq!(ghost.throttle[30], 1.0);
+        assert!(ghost.brake.contains(&BRAKE_PRESSURE));
+        assert_eq!(ghost.steering[100], 0.4);
+    }
+
+    #[test]
+    fn test_ghost_round_trips_through_json() {
+        let ghost = export_ghost(&lap(), 20);
+        let json = ghost.to_json();
+
+        assert!(json.starts_with(r#"{"distance":[0.0,"#), "{json}");
+        let parsed: GhostLap = serde_json::from_str(&json).unwrap();
+        let close = |a: &[f64], b: &[f64]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9)
+        };
+        assert!(close(&parsed.distance, &ghost.distance));
+        assert!(close(&parsed.speed, &ghost.speed));
+        assert!(close(&parsed.brake, &ghost.brake));
+    }
+
+    #[test]
+    fn test_empty_lap_has_an_empty_ghost() {
+        assert_eq!(export_ghost(&[], 50), GhostLap::default());
+    }
+}
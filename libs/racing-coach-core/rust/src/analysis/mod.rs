@@ -1,9 +1,35 @@
 //! Analysis functions for telemetry data.
 
+mod balance;
+mod calibration;
+mod conditions;
+mod corrections;
 mod deceleration;
+mod delta;
+mod fuel;
+mod ghost;
+mod pedals;
+mod resample;
+mod sectors;
+mod smoothness;
 mod statistics;
+mod tires;
 mod trail_braking;
 
-pub use deceleration::calculate_deceleration;
+pub use balance::{corner_balance, estimate_balance, CornerBalance};
+pub use calibration::calibrate_thresholds;
+pub use conditions::{TrackConditions, WetnessTransition};
+pub use corrections::{detect_corrections, CorrectionEvent};
+pub use deceleration::{calculate_deceleration, deceleration_slope};
+pub use delta::{
+    compute_time_delta, compute_time_delta_with_samples, DeltaPoint, DEFAULT_DELTA_SAMPLES,
+};
+pub use fuel::{compute_fuel_usage, FuelUsage};
+pub use ghost::{export_ghost, GhostLap};
+pub use pedals::{compute_pedal_usage, PedalUsage};
+pub use resample::resample_by_distance;
+pub use sectors::{compute_sector_times, MiniSectorTimer, DEFAULT_SECTOR_COUNT};
+pub use smoothness::{compute_smoothness, SmoothnessScore};
 pub use statistics::SpeedStatistics;
+pub use tires::{compute_tire_analysis, TireAnalysis, TireCornerTemps, DEFAULT_MAX_TIRE_SPREAD};
 pub use trail_braking::{detect_trail_braking, TrailBrakingInfo};
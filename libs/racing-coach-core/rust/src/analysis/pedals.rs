@@ -0,0 +1,152 @@
+//! Share of a lap spent flat out, hard on the brakes, or coasting.
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{AnalysisConfig, TelemetryFrame};
+
+/// How a lap's time divides between pedal states, each as a percentage of
+/// the lap time (0-100).
+///
+/// The three states don't overlap, so the percentages sum to at most 100;
+/// the rest is time on part throttle or part brake.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
+pub struct PedalUsage {
+    /// Time at or above `full_throttle_threshold` without braking
+    #[pyo3(get)]
+    pub full_throttle_pct: f64,
+
+    /// Time at or above `full_brake_threshold`
+    #[pyo3(get)]
+    pub full_brake_pct: f64,
+
+    /// Time below both the throttle and brake thresholds
+    #[pyo3(get)]
+    pub coasting_pct: f64,
+}
+
+#[pymethods]
+impl PedalUsage {
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "PedalUsage(full_throttle={:.1}%, full_brake={:.1}%, coasting={:.1}%)",
+            self.full_throttle_pct, self.full_brake_pct, self.coasting_pct
+        )
+    }
+}
+
+/// Measure the share of time in `frames` spent at full throttle, at full
+/// brake and coasting.
+///
+/// Each frame counts for the time until the next one, so uneven frame
+/// spacing doesn't skew the result; the last frame and intervals whose
+/// timestamps don't increase count for nothing.
+///
+/// # Returns
+/// * `None` if the frames span no time
+pub fn compute_pedal_usage(
+    frames: &[TelemetryFrame],
+    config: &AnalysisConfig,
+) -> Option<PedalUsage> {
+    let mut total = 0.0;
+    let mut full_throttle = 0.0;
+    let mut full_brake = 0.0;
+    let mut coasting = 0.0;
+
+    for pair in frames.windows(2) {
+        let (frame, dt) = (&pair[0], pair[1].timestamp - pair[0].timestamp);
+        if !(dt > 0.0 && dt.is_finite()) {
+            continue;
+        }
+        total += dt;
+
+        if frame.brake >= config.full_brake_threshold {
+            full_brake += dt;
+        } else if frame.throttle >= config.full_throttle_threshold
+            && frame.brake < config.brake_threshold
+        {
+            full_throttle += dt;
+        } else if frame.throttle < config.throttle_threshold && frame.brake < config.brake_threshold
+        {
+            coasting += dt;
+        }
+    }
+
+    (total > 0.0).then(|| PedalUsage {
+        full_throttle_pct: full_throttle / total * 100.0,
+        full_brake_pct: full_brake / total * 100.0,
+        coasting_pct: coasting / total * 100.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(throttle: f64, brake: f64, timestamp: f64) -> TelemetryFrame {
+        TelemetryFrame::new(brake, throttle, 50.0, 0.0, 0.0, 0.0, 0.0, timestamp, false)
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_time_weighted_split() {
+        // 1.5 s flat out, 0.5 s braking, 2 s coasting and 1 s on part
+        // throttle, sampled unevenly
+        let frames = vec![
+            frame(1.0, 0.0, 0.0),
+            frame(0.99, 0.0, 1.0),
+            frame(0.0, 1.0, 1.5),
+            frame(0.0, 0.0, 2.0),
+            frame(0.5, 0.0, 4.0),
+            frame(1.0, 0.0, 5.0),
+        ];
+
+        let usage = compute_pedal_usage(&frames, &AnalysisConfig::default()).unwrap();
+
+        assert_close(usage.full_throttle_pct, 30.0);
+        assert_close(usage.full_brake_pct, 10.0);
+        assert_close(usage.coasting_pct, 40.0);
+    }
+
+    #[test]
+    fn test_thresholds_are_configurable() {
+        let frames = vec![
+            frame(0.9, 0.0, 0.0),
+            frame(0.0, 0.7, 1.0),
+            frame(0.0, 0.0, 2.0),
+        ];
+        let default = compute_pedal_usage(&frames, &AnalysisConfig::default()).unwrap();
+        assert_eq!(
+            (default.full_throttle_pct, default.full_brake_pct),
+            (0.0, 0.0)
+        );
+
+        let config = AnalysisConfig {
+            full_throttle_threshold: 0.85,
+            full_brake_threshold: 0.6,
+            ..AnalysisConfig::default()
+        };
+        let usage = compute_pedal_usage(&frames, &config).unwrap();
+        assert_close(usage.full_throttle_pct, 50.0);
+        assert_close(usage.full_brake_pct, 50.0);
+    }
+
+    #[test]
+    fn test_no_elapsed_time() {
+        let config = AnalysisConfig::default();
+        assert_eq!(compute_pedal_usage(&[], &config), None);
+        assert_eq!(compute_pedal_usage(&[frame(1.0, 0.0, 3.0)], &config), None);
+        assert_eq!(
+            compute_pedal_usage(&[frame(1.0, 0.0, 3.0), frame(1.0, 0.0, 3.0)], &config),
+            None
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Resampling telemetry onto a lap distance axis.
+
+use crate::types::{TelemetryFrame, TireTemps};
+
+/// Resample a lap onto `num_points` evenly spaced lap distances from 0.0 to 1.0.
+///
+/// Frames are time-sampled, so two laps rarely have frames at the same
+/// distances; resampling both onto the same grid lets them be compared point
+/// by point. Continuous channels are linearly interpolated between the two
+/// frames surrounding each grid distance, while gear, pit road and track
+/// wetness take the value of the nearer frame.
+///
+/// Frames are ordered by lap distance first, so distance glitches that jump
+/// backwards don't fold the trace over itself, and of several frames at the
+/// same distance only the first is used. Distances are clamped to 0.0-1.0.
+/// Grid points before the first or after the last frame hold that frame's
+/// values.
+///
+/// # Returns
+/// * `num_points` frames (a single frame at distance 0.0 for one point), or
+///   an empty vec if there are no frames
+pub fn resample_by_distance(frames: &[TelemetryFrame], num_points: usize) -> Vec<TelemetryFrame> {
+    let mut trace: Vec<TelemetryFrame> = frames
+        .iter()
+        .map(|frame| TelemetryFrame {
+            lap_distance: frame.lap_distance.clamp(0.0, 1.0),
+            ..*frame
+        })
+        .collect();
+    // Stable, so the first of several frames at one distance stays first
+    trace.sort_by(|a, b| a.lap_distance.total_cmp(&b.lap_distance));
+    trace.dedup_by(|later, earlier| later.lap_distance == earlier.lap_distance);
+    if trace.is_empty() {
+        return Vec::new();
+    }
+
+    let step = if num_points > 1 {
+        1.0 / (num_points - 1) as f64
+    } else {
+        0.0
+    };
+
+    (0..num_points)
+        .map(|i| {
+            let distance = step * i as f64;
+            // Index of the first frame at or beyond `distance`
+            let upper = trace.partition_point(|frame| frame.lap_distance < distance);
+            if upper == 0 {
+                return TelemetryFrame {
+                    lap_distance: distance,
+                    ..trace[0]
+                };
+            }
+            if upper == trace.len() {
+                return TelemetryFrame {
+                    lap_distance: distance,
+                    ..trace[trace.len() - 1]
+                };
+            }
+
+            let (a, b) = (&trace[upper - 1], &trace[upper]);
+            let fraction = (distance - a.lap_distance) / (b.lap_distance - a.lap_distance);
+            interpolate(a, b, fraction, distance)
+        })
+        .collect()
+}
+
+/// The frame `fraction` of the way from `a` to `b`, placed at `distance`.
+fn interpolate(
+    a: &TelemetryFrame,
+    b: &TelemetryFrame,
+    fraction: f64,
+    distance: f64,
+) -> TelemetryFrame {
+    let lerp = |from: f64, to: f64| from + (to - from) * fraction;
+    let nearer = if fraction < 0.5 { a } else { b };
+    let corner = |from: [f64; 3], to: [f64; 3]| [0, 1, 2].map(|i| lerp(from[i], to[i]));
+
+    TelemetryFrame {
+        brake: lerp(a.brake, b.brake),
+        steering_angle: lerp(a.steering_angle, b.steering_angle),
+        steering_pct: lerp(a.steering_pct, b.steering_pct),
+        throttle: lerp(a.throttle, b.throttle),
+        speed: lerp(a.speed, b.speed),
+        lap_distance: distance,
+        lap_distance_meters: lerp(a.lap_distance_meters, b.lap_distance_meters),
+        timestamp: lerp(a.timestamp, b.timestamp),
+        lateral_acceleration: lerp(a.lateral_acceleration, b.lateral_acceleration),
+        longitudinal_acceleration: lerp(a.longitudinal_acceleration, b.longitudinal_acceleration),
+        yaw_rate: lerp(a.yaw_rate, b.yaw_rate),
+        on_pit_road: nearer.on_pit_road,
+        gear: nearer.gear,
+        rpm: lerp(a.rpm, b.rpm),
+        track_temp: lerp(a.track_temp, b.track_temp),
+        air_temp: lerp(a.air_temp, b.air_temp),
+        track_wetness: nearer.track_wetness,
+        tire_temps: TireTemps {
+            lf: corner(a.tire_temps.lf, b.tire_temps.lf),
+            rf: corner(a.tire_temps.rf, b.tire_temps.rf),
+            lr: corner(a.tire_temps.lr, b.tire_temps.lr),
+            rr: corner(a.tire_temps.rr, b.tire_temps.rr),
+        },
+        fuel_level: lerp(a.fuel_level, b.fuel_level),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A frame at `distance`, `timestamp` seconds in, with speed and throttle
+    /// set so midpoints are easy to check.
+    fn frame(distance: f64, timestamp: f64, speed: f64, gear: i32) -> TelemetryFrame {
+        TelemetryFrame::new(
+            0.0,
+            speed / 100.0,
+            speed,
+            distance,
+            0.0,
+            0.0,
+            0.0,
+            timestamp,
+            false,
+        )
+        .with_gear(gear)
+    }
+
+    #[test]
+    fn test_midpoints_are_interpolated() {
+        // Uneven frame spacing: 0.0, 0.25, 0.75 and 1.0
+        let lap = vec![
+            frame(0.0, 0.0, 40.0, 2),
+            frame(0.25, 10.0, 60.0, 3),
+            frame(0.75, 30.0, 80.0, 4),
+            frame(1.0, 40.0, 50.0, 3),
+        ];
+
+        let resampled = resample_by_distance(&lap, 5);
+
+        let distances: Vec<f64> = resampled.iter().map(|f| f.lap_distance).collect();
+        assert_eq!(distances, [0.0, 0.25, 0.5, 0.75, 1.0]);
+
+        // Halfway between the frames at 0.25 and 0.75
+        let mid = &resampled[2];
+        assert!((mid.speed - 70.0).abs() < 1e-10);
+        assert!((mid.throttle - 0.7).abs() < 1e-10);
+        assert!((mid.timestamp - 20.0).abs() < 1e-10);
+        assert_eq!(mid.gear, 4);
+
+        assert_eq!(resampled[1].speed, 60.0);
+        assert_eq!(resampled[4].speed, 50.0);
+    }
+
+    #[test]
+    fn test_quarter_points_between_frames() {
+        let lap = vec![frame(0.0, 0.0, 40.0, 2), frame(1.0, 40.0, 80.0, 3)];
+
+        let resampled = resample_by_distance(&lap, 5);
+
+        let speeds: Vec<f64> = resampled.iter().map(|f| f.speed).collect();
+        assert_eq!(speeds, [40.0, 50.0, 60.0, 70.0, 80.0]);
+        let gears: Vec<i32> = resampled.iter().map(|f| f.gear).collect();
+        assert_eq!(gears, [2, 2, 3, 3, 3]);
+    }
+
+    #[test]
+    fn test_distance_glitches_are_sorted_and_clamped() {
+        let lap = vec![
+            frame(0.1, 0.0, 40.0, 2),
+            frame(0.5, 10.0, 80.0, 3),
+            // Glitch back to just after the start
+            frame(0.3, 11.0, 60.0, 3),
+            frame(0.5, 12.0, 99.0, 3),
+            frame(1.2, 20.0, 50.0, 3),
+        ];
+
+        let resampled = resample_by_distance(&lap, 11);
+
+        assert_eq!(resampled.len(), 11);
+        for pair in resampled.windows(2) {
+            assert!(pair[1].lap_distance > pair[0].lap_distance);
+        }
+        // Before the first frame its values are held
+        assert_eq!(resampled[0].speed, 40.0);
+        // The glitch frame lies between 0.1 and 0.5 once sorted
+        assert!((resampled[2].speed - 50.0).abs() < 1e-10);
+        assert!((resampled[4].speed - 70.0).abs() < 1e-10);
+        // The first frame at 0.5 wins over the repeat
+        assert!((resampled[5].speed - 80.0).abs() < 1e-10);
+        // 1.2 is clamped to the end of the lap
+        assert!((resampled[10].speed - 50.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert!(resample_by_distance(&[], 10).is_empty());
+
+        let lap = vec![frame(0.4, 0.0, 40.0, 2)];
+        let resampled = resample_by_distance(&lap, 3);
+        assert_eq!(resampled.len(), 3);
+        assert!(resampled.iter().all(|f| f.speed == 40.0));
+
+        assert!(resample_by_distance(&lap, 0).is_empty());
+        assert_eq!(resample_by_distance(&lap, 1)[0].lap_distance, 0.0);
+    }
+}
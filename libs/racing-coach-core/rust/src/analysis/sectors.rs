@@ -0,0 +1,226 @@
+//! Sector timing.
+
+use super::delta::{distance_time_trace, interpolate_time};
+use crate::types::TelemetryFrame;
+
+/// Number of equal-length sectors a lap is split into.
+pub const DEFAULT_SECTOR_COUNT: usize = 3;
+
+/// How far from the start/finish line (normalized distance) a lap may begin
+/// or end and still be timed; the missing distance is extrapolated.
+const LINE_TOLERANCE: f64 = 0.02;
+
+/// Time spent in each of `sectors` equal-length sectors of a lap.
+///
+/// Sector boundaries are at evenly spaced lap distances, and crossing times
+/// are interpolated between frames.
+///
+/// # Returns
+/// * One time in seconds per sector, or an empty vec if the frames don't
+///   cover the whole lap
+pub fn compute_sector_times(frames: &[TelemetryFrame], sectors: usize) -> Vec<f64> {
+    if sectors == 0 {
+        return Vec::new();
+    }
+    MiniSectorTimer::evenly_spaced(sectors).sector_times(frames)
+}
+
+/// Times a lap split at arbitrary distance markers (mini-sectors).
+///
+/// Markers are lap distances (0-1) where one sector ends and the next
+/// begins; the start/finish line always bounds the first and last sector,
+/// so `n` markers give `n + 1` sectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MiniSectorTimer {
+    /// Sector boundaries, from 0.0 to 1.0 in increasing order
+    boundaries: Vec<f64>,
+}
+
+impl MiniSectorTimer {
+    /// A timer splitting the lap at `markers`, in any order.
+    ///
+    /// Markers on or beyond the start/finish line, and repeated markers,
+    /// would only add empty sectors and are dropped.
+    pub fn new(markers: &[f64]) -> Self {
+        let mut inner: Vec<f64> = markers
+            .iter()
+            .copied()
+            .filter(|&marker| marker > 0.0 && marker < 1.0)
+            .collect();
+        inner.sort_by(f64::total_cmp);
+        inner.dedup();
+
+        let mut boundaries = Vec::with_capacity(inner.len() + 2);
+        boundaries.push(0.0);
+        boundaries.extend(inner);
+        boundaries.push(1.0);
+        Self { boundaries }
+    }
+
+    /// A timer splitting the lap into `sectors` equal-length sectors.
+    pub fn evenly_spaced(sectors: usize) -> Self {
+        let markers: Vec<f64> = (1..sectors).map(|i| i as f64 / sectors as f64).collect();
+        Self::new(&markers)
+    }
+
+    /// Number of sectors the lap is split into.
+    pub fn sector_count(&self) -> usize {
+        self.boundaries.len() - 1
+    }
+
+    /// Time spent in each sector of the lap in `frames`.
+    ///
+    /// Crossing times are interpolated between the frames either side of
+    /// each boundary. A lap may begin or end a little way from the
+    /// start/finish line, in which case the missing distance is
+    /// extrapolated.
+    ///
+    /// # Returns
+    /// * One time in seconds per sector, or an empty vec if the frames
+    ///   don't cover the whole lap or skip too far past a boundary to time
+    ///   its crossing
+    pub fn sector_times(&self, frames: &[TelemetryFrame]) -> Vec<f64> {
+        let trace = distance_time_trace(frames);
+        if trace.len() < 2 {
+            return Vec::new();
+        }
+
+        let first = trace[0].0;
+        let last = trace[trace.len() - 1].0;
+        if first > LINE_TOLERANCE || last < 1.0 - LINE_TOLERANCE {
+            return Vec::new();
+        }
+
+        let mut crossings = Vec::with_capacity(self.boundaries.len());
+        for &boundary in &self.boundaries {
+            if !covers(&trace, boundary) {
+                return Vec::new();
+            }
+            crossings.push(interpolate_time(&trace, boundary));
+        }
+
+        crossings.windows(2).map(|pair| pair[1] - pair[0]).collect()
+    }
+}
+
+/// Whether `trace` has frames close enough either side of `distance` to
+/// interpolate its crossing time. Distances outside the trace are covered
+/// if they're within [`LINE_TOLERANCE`] of its ends.
+fn covers(trace: &[(f64, f64)], distance: f64) -> bool {
+    let upper = trace.partition_point(|&(d, _)| d < distance);
+    let before = upper.checked_sub(1).map(|idx| trace[idx].0);
+    let after = trace.get(upper).map(|&(d, _)| d);
+    match (before, after) {
+        (Some(before), Some(after)) => after - before <= LINE_TOLERANCE,
+        (None, Some(after)) => after - distance <= LINE_TOLERANCE,
+        (Some(before), None) => distance - before <= LINE_TOLERANCE,
+        (None, None) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Frames from `start` to `end` distance with each third of the lap taking
+    /// the given time.
+    fn make_lap(start: f64, end: f64, sector_times: [f64; 3]) -> Vec<TelemetryFrame> {
+        (0..=300)
+            .map(|i| start + (end - start) * i as f64 / 300.0)
+            .map(|distance| {
+                let sector = ((distance * 3.0) as usize).min(2);
+                let elapsed: f64 = sector_times[..sector].iter().sum::<f64>()
+                    + (distance * 3.0 - sector as f64) * sector_times[sector];
+                TelemetryFrame::new(0.0, 1.0, 50.0, distance, 0.0, 0.0, 0.0, elapsed, false)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sector_times() {
+        let lap = make_lap(0.0, 1.0, [30.0, 25.0, 35.0]);
+
+        let sectors = compute_sector_times(&lap, DEFAULT_SECTOR_COUNT);
+
+        assert_eq!(sectors.len(), 3);
+        for (actual, expected) in sectors.iter().zip([30.0, 25.0, 35.0]) {
+            assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_lap_starting_just_after_line_is_timed() {
+        let lap = make_lap(0.005, 0.995, [30.0, 30.0, 30.0]);
+
+        let sectors = compute_sector_times(&lap, DEFAULT_SECTOR_COUNT);
+
+        assert_eq!(sectors.len(), 3);
+        assert!((sectors.iter().sum::<f64>() - 90.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_lap_starting_just_before_line_is_timed() {
+        // One frame 0.005 before the line, at the same 100 s/lap pace
+        let mut lap = vec![TelemetryFrame::new(
+            0.0, 1.0, 50.0, 0.995, 0.0, 0.0, 0.0, -0.5, false,
+        )];
+        lap.extend(make_lap(0.0, 1.0, [30.0, 30.0, 40.0]));
+
+        let sectors = compute_sector_times(&lap, DEFAULT_SECTOR_COUNT);
+
+        assert_eq!(sectors.len(), 3);
+        for (actual, expected) in sectors.iter().zip([30.0, 30.0, 40.0]) {
+            assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_partial_lap_has_no_sectors() {
+        let lap = make_lap(0.0, 0.6, [30.0, 30.0, 30.0]);
+
+        assert!(compute_sector_times(&lap, DEFAULT_SECTOR_COUNT).is_empty());
+        assert!(compute_sector_times(&[], DEFAULT_SECTOR_COUNT).is_empty());
+    }
+
+    #[test]
+    fn test_mini_sectors_at_markers() {
+        let lap = make_lap(0.0, 1.0, [30.0, 25.0, 35.0]);
+        // Halfway through the first third, at its end, and a fifth of the
+        // way into the last (out of order)
+        let timer = MiniSectorTimer::new(&[2.2 / 3.0, 1.0 / 6.0, 1.0 / 3.0]);
+
+        let sectors = timer.sector_times(&lap);
+
+        assert_eq!(timer.sector_count(), 4);
+        assert_eq!(sectors.len(), 4);
+        for (actual, expected) in sectors.iter().zip([15.0, 15.0, 25.0 + 7.0, 28.0]) {
+            assert!((actual - expected).abs() < 1e-6, "{actual} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_markers_on_the_line_are_dropped() {
+        let lap = make_lap(0.0, 1.0, [30.0, 25.0, 35.0]);
+        let timer = MiniSectorTimer::new(&[0.0, 1.0 / 3.0, 1.0, 1.0 / 3.0]);
+
+        let sectors = timer.sector_times(&lap);
+
+        assert_eq!(sectors.len(), 2);
+        assert!((sectors[0] - 30.0).abs() < 1e-6);
+        assert!((sectors[1] - 60.0).abs() < 1e-6);
+        assert_eq!(MiniSectorTimer::new(&[]).sector_times(&lap).len(), 1);
+    }
+
+    #[test]
+    fn test_gap_around_marker_has_no_sectors() {
+        // No frames between 0.45 and 0.55, e.g. after a tow
+        let lap: Vec<_> = make_lap(0.0, 1.0, [30.0, 30.0, 30.0])
+            .into_iter()
+            .filter(|frame| !(0.45..0.55).contains(&frame.lap_distance))
+            .collect();
+
+        assert!(MiniSectorTimer::new(&[0.5]).sector_times(&lap).is_empty());
+        // Boundaries away from the gap are still timed
+        assert_eq!(MiniSectorTimer::new(&[0.25]).sector_times(&lap).len(), 2);
+    }
+}
@@ -0,0 +1,200 @@
+//! Driver input smoothness scoring.
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::TelemetryFrame;
+
+/// Mean absolute second derivative of a pedal (0-1 per s²) that scores 50.
+///
+/// A clean squeeze from 0 to full throttle over half a second averages a few
+/// units per s² while it happens; pedal chatter at 60 Hz reaches hundreds.
+const PEDAL_REFERENCE_ROUGHNESS: f64 = 10.0;
+
+/// Mean absolute second derivative of the steering angle (rad/s²) that
+/// scores 50. Steering spans a wider range than the pedals, so it gets more
+/// headroom.
+const STEERING_REFERENCE_ROUGHNESS: f64 = 20.0;
+
+/// How smoothly the driver worked the controls over a run of frames.
+///
+/// Each score runs from 0 to 100, higher meaning smoother, and falls as the
+/// mean absolute second derivative of the channel over time ("roughness")
+/// grows: a score of 50 means the roughness equals the channel's reference.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
+pub struct SmoothnessScore {
+    /// Throttle smoothness (0-100)
+    #[pyo3(get)]
+    pub throttle: f64,
+
+    /// Brake smoothness (0-100)
+    #[pyo3(get)]
+    pub brake: f64,
+
+    /// Steering smoothness (0-100)
+    #[pyo3(get)]
+    pub steering: f64,
+
+    /// Mean of the three channel scores (0-100)
+    #[pyo3(get)]
+    pub overall: f64,
+
+    /// Number of frame triples the scores were measured over
+    #[pyo3(get)]
+    pub samples: usize,
+}
+
+#[pymethods]
+impl SmoothnessScore {
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "SmoothnessScore(overall={:.1}, throttle={:.1}, brake={:.1}, steering={:.1})",
+            self.overall, self.throttle, self.brake, self.steering
+        )
+    }
+}
+
+/// Score the smoothness of the throttle, brake and steering inputs in `frames`.
+///
+/// Second derivatives are taken over each run of three consecutive frames
+/// using their actual timestamps, so uneven frame spacing doesn't skew the
+/// result. Triples whose timestamps don't strictly increase are skipped.
+/// With no usable triples every score is 100.
+pub fn compute_smoothness(frames: &[TelemetryFrame]) -> SmoothnessScore {
+    let mut roughness = [0.0; 3];
+    let mut samples = 0;
+
+    for window in frames.windows(3) {
+        let [a, b, c] = window else {
+            unreachable!("windows(3) yields three frames");
+        };
+        let h1 = b.timestamp - a.timestamp;
+        let h2 = c.timestamp - b.timestamp;
+        if !(h1 > 0.0 && h2 > 0.0) {
+            continue;
+        }
+
+        // Second derivative for non-uniform spacing
+        let second_derivative = |channel: fn(&TelemetryFrame) -> f64| {
+            let slope1 = (channel(b) - channel(a)) / h1;
+            let slope2 = (channel(c) - channel(b)) / h2;
+            2.0 * (slope2 - slope1) / (h1 + h2)
+        };
+        roughness[0] += second_derivative(|f| f.throttle).abs();
+        roughness[1] += second_derivative(|f| f.brake).abs();
+        roughness[2] += second_derivative(|f| f.steering_angle).abs();
+        samples += 1;
+    }
+
+    let score = |total: f64, reference: f64| {
+        if samples == 0 {
+            return 100.0;
+        }
+        100.0 / (1.0 + total / samples as f64 / reference)
+    };
+    let throttle = score(roughness[0], PEDAL_REFERENCE_ROUGHNESS);
+    let brake = score(roughness[1], PEDAL_REFERENCE_ROUGHNESS);
+    let steering = score(roughness[2], STEERING_REFERENCE_ROUGHNESS);
+
+    SmoothnessScore {
+        throttle,
+        brake,
+        steering,
+        overall: (throttle + brake + steering) / 3.0,
+        samples,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A lap sampled at the given timestamps: throttle and steering follow
+    /// slow sine waves, and the brake is applied once. `chatter` adds an
+    /// alternating offset to every input.
+    fn lap(timestamps: &[f64], chatter: f64) -> Vec<TelemetryFrame> {
+        timestamps
+            .iter()
+            .enumerate()
+            .map(|(idx, &t)| {
+                let wobble = if idx % 2 == 0 { chatter } else { -chatter };
+                let throttle = (0.5 + 0.5 * (t / 4.0).sin() + wobble).clamp(0.0, 1.0);
+                let brake = if (10.0..12.0).contains(&t) { 0.8 } else { 0.0 };
+                TelemetryFrame::new(
+                    (brake + wobble).clamp(0.0, 1.0),
+                    throttle,
+                    50.0,
+                    t / 60.0,
+                    0.3 * (t / 3.0).sin() + wobble,
+                    0.0,
+                    0.0,
+                    t,
+                    false,
+                )
+            })
+            .collect()
+    }
+
+    fn even_timestamps() -> Vec<f64> {
+        (0..3600).map(|i| i as f64 / 60.0).collect()
+    }
+
+    #[test]
+    fn test_smooth_lap_scores_higher_than_jerky_lap() {
+        let smooth = compute_smoothness(&lap(&even_timestamps(), 0.0));
+        let jerky = compute_smoothness(&lap(&even_timestamps(), 0.05));
+
+        assert!(smooth.overall > 80.0, "smooth lap scored {:?}", smooth);
+        assert!(jerky.overall < 20.0, "jerky lap scored {:?}", jerky);
+        assert!(smooth.throttle > jerky.throttle);
+        assert!(smooth.brake > jerky.brake);
+        assert!(smooth.steering > jerky.steering);
+        assert_eq!(smooth.samples, 3598);
+    }
+
+    #[test]
+    fn test_uneven_frame_spacing_is_normalized() {
+        // Frames alternately 10ms and 23ms apart, covering the same minute
+        let mut t = 0.0;
+        let uneven: Vec<f64> = (0..3600)
+            .map(|i| {
+                let now = t;
+                t += if i % 2 == 0 { 0.010 } else { 0.023 };
+                now
+            })
+            .take_while(|&t| t < 60.0)
+            .collect();
+
+        let even = compute_smoothness(&lap(&even_timestamps(), 0.0));
+        let uneven = compute_smoothness(&lap(&uneven, 0.0));
+
+        assert!(
+            (even.overall - uneven.overall).abs() < 5.0,
+            "even {:?} vs uneven {:?}",
+            even,
+            uneven
+        );
+    }
+
+    #[test]
+    fn test_repeated_timestamps_are_skipped() {
+        let mut frames = lap(&even_timestamps()[..10], 0.0);
+        frames[5].timestamp = frames[4].timestamp;
+
+        let score = compute_smoothness(&frames);
+
+        // Triples (3,4,5) and (4,5,6) are skipped
+        assert_eq!(score.samples, 6);
+        assert!(score.overall.is_finite());
+    }
+
+    #[test]
+    fn test_too_few_frames_scores_full_marks() {
+        let score = compute_smoothness(&lap(&[0.0, 0.1], 0.0));
+
+        assert_eq!(score.samples, 0);
+        assert_eq!(score.overall, 100.0);
+    }
+}
@@ -1,15 +1,22 @@
 //! Statistics accumulators for telemetry analysis.
 
+use pyo3::prelude::*;
+
+use crate::utils::percentile;
+
 /// Accumulator for tracking speed statistics during a single pass.
 ///
 /// This struct efficiently tracks min, max, and sum of speeds
-/// without requiring a separate pass through the data.
+/// without requiring a separate pass through the data. Samples are
+/// kept so spread statistics (standard deviation, median, percentiles)
+/// can be derived afterwards.
 #[derive(Debug, Clone)]
+#[pyclass]
 pub struct SpeedStatistics {
     min: f64,
     max: f64,
     sum: f64,
-    count: usize,
+    samples: Vec<f64>,
 }
 
 impl SpeedStatistics {
@@ -19,8 +26,18 @@ impl SpeedStatistics {
             min: f64::INFINITY,
             max: f64::NEG_INFINITY,
             sum: 0.0,
-            count: 0,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Create an accumulator pre-filled with `speeds`.
+    pub fn from_speeds(speeds: &[f64]) -> Self {
+        let mut stats = Self::new();
+        stats.samples.reserve(speeds.len());
+        for &speed in speeds {
+            stats.update(speed);
         }
+        stats
     }
 
     /// Update statistics with a new speed value.
@@ -29,14 +46,24 @@ impl SpeedStatistics {
         self.min = self.min.min(speed);
         self.max = self.max.max(speed);
         self.sum += speed;
-        self.count += 1;
+        self.samples.push(speed);
+    }
+}
+
+#[pymethods]
+impl SpeedStatistics {
+    /// Create statistics from a list of speeds (Python constructor).
+    #[new]
+    #[pyo3(signature = (speeds=Vec::new()))]
+    fn py_new(speeds: Vec<f64>) -> Self {
+        Self::from_speeds(&speeds)
     }
 
     /// Get the minimum speed observed.
     ///
     /// Returns 0.0 if no values have been added.
     pub fn min(&self) -> f64 {
-        if self.count == 0 {
+        if self.samples.is_empty() {
             0.0
         } else {
             self.min
@@ -47,7 +74,7 @@ impl SpeedStatistics {
     ///
     /// Returns 0.0 if no values have been added.
     pub fn max(&self) -> f64 {
-        if self.count == 0 {
+        if self.samples.is_empty() {
             0.0
         } else {
             self.max
@@ -58,16 +85,62 @@ impl SpeedStatistics {
     ///
     /// Returns 0.0 if no values have been added.
     pub fn mean(&self) -> f64 {
-        if self.count == 0 {
+        if self.samples.is_empty() {
             0.0
         } else {
-            self.sum / self.count as f64
+            self.sum / self.samples.len() as f64
+        }
+    }
+
+    /// Get the population standard deviation of speed.
+    ///
+    /// Returns 0.0 if no values have been added.
+    pub fn std_dev(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
         }
+
+        let mean = self.mean();
+        let variance = self
+            .samples
+            .iter()
+            .map(|speed| (speed - mean).powi(2))
+            .sum::<f64>()
+            / self.samples.len() as f64;
+        variance.sqrt()
+    }
+
+    /// Get the median speed (the 50th percentile).
+    ///
+    /// Returns 0.0 if no values have been added.
+    pub fn median(&self) -> f64 {
+        self.percentile(50.0)
+    }
+
+    /// Get the `p`th percentile of speed, with `p` in 0-100 (clamped).
+    ///
+    /// Uses linear interpolation between the closest ranks, matching numpy's
+    /// default: the percentile lies at rank `p / 100 * (count - 1)` of the
+    /// sorted samples. Returns 0.0 if no values have been added.
+    pub fn percentile(&self, p: f64) -> f64 {
+        percentile(&self.samples, p)
     }
 
     /// Get the count of values added.
     pub fn count(&self) -> usize {
-        self.count
+        self.samples.len()
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "SpeedStatistics(count={}, mean={:.1}, std_dev={:.1}, min={:.1}, max={:.1})",
+            self.count(),
+            self.mean(),
+            self.std_dev(),
+            self.min(),
+            self.max()
+        )
     }
 }
 
@@ -88,6 +161,9 @@ mod tests {
         assert_eq!(stats.max(), 0.0);
         assert_eq!(stats.mean(), 0.0);
         assert_eq!(stats.count(), 0);
+        assert_eq!(stats.std_dev(), 0.0);
+        assert_eq!(stats.median(), 0.0);
+        assert_eq!(stats.percentile(90.0), 0.0);
     }
 
     #[test]
@@ -99,6 +175,9 @@ mod tests {
         assert_eq!(stats.max(), 50.0);
         assert_eq!(stats.mean(), 50.0);
         assert_eq!(stats.count(), 1);
+        assert_eq!(stats.std_dev(), 0.0);
+        assert_eq!(stats.median(), 50.0);
+        assert_eq!(stats.percentile(10.0), 50.0);
     }
 
     #[test]
@@ -115,6 +194,27 @@ mod tests {
         assert_eq!(stats.count(), 4);
     }
 
+    #[test]
+    fn test_spread_statistics() {
+        // Sorted: 20, 30, 50, 100; mean 50
+        let stats = SpeedStatistics::from_speeds(&[30.0, 50.0, 100.0, 20.0]);
+
+        // Squared deviations: 900 + 400 + 0 + 2500 = 3800, / 4 = 950
+        assert!((stats.std_dev() - 950f64.sqrt()).abs() < 1e-10);
+
+        // Median interpolates between 30 and 50
+        assert_eq!(stats.median(), 40.0);
+
+        // Rank 0.25 * 3 = 0.75 -> 20 + 0.75 * (30 - 20)
+        assert!((stats.percentile(25.0) - 27.5).abs() < 1e-10);
+        assert_eq!(stats.percentile(0.0), 20.0);
+        assert_eq!(stats.percentile(100.0), 100.0);
+
+        // Out-of-range percentiles are clamped
+        assert_eq!(stats.percentile(-5.0), 20.0);
+        assert_eq!(stats.percentile(150.0), 100.0);
+    }
+
     #[test]
     fn test_default() {
         let stats = SpeedStatistics::default();
@@ -0,0 +1,238 @@
+//! Tire temperature spread analysis.
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{TelemetryFrame, TIRE_CORNERS};
+
+/// Inner-to-outer spread in Celsius above which a tire is flagged.
+///
+/// A few degrees hotter on the inside is the usual sign of working camber;
+/// much more than this means the tire is only loading its inner edge.
+pub const DEFAULT_MAX_TIRE_SPREAD: f64 = 8.0;
+
+/// Average temperatures across one tire's tread.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass]
+pub struct TireCornerTemps {
+    /// Tire position ("LF", "RF", "LR" or "RR")
+    #[pyo3(get)]
+    pub corner: String,
+
+    /// Average inner edge temperature in Celsius
+    #[pyo3(get)]
+    pub inner: f64,
+
+    /// Average middle temperature in Celsius
+    #[pyo3(get)]
+    pub middle: f64,
+
+    /// Average outer edge temperature in Celsius
+    #[pyo3(get)]
+    pub outer: f64,
+}
+
+#[pymethods]
+impl TireCornerTemps {
+    /// Average temperature across the tread.
+    pub fn average(&self) -> f64 {
+        (self.inner + self.middle + self.outer) / 3.0
+    }
+
+    /// Inner minus outer temperature; positive when the inside runs hotter.
+    pub fn spread(&self) -> f64 {
+        self.inner - self.outer
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "TireCornerTemps({}: in={:.1}, mid={:.1}, out={:.1})",
+            self.corner, self.inner, self.middle, self.outer
+        )
+    }
+}
+
+/// Per-corner tire temperatures over a run of frames.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass]
+pub struct TireAnalysis {
+    /// One entry per tire in LF, RF, LR, RR order
+    #[pyo3(get)]
+    pub corners: Vec<TireCornerTemps>,
+
+    /// Number of frames with tire temperatures
+    #[pyo3(get)]
+    pub frame_count: usize,
+}
+
+#[pymethods]
+impl TireAnalysis {
+    /// Temperatures of one tire ("LF", "RF", "LR" or "RR").
+    pub fn corner(&self, name: &str) -> Option<TireCornerTemps> {
+        self.corners
+            .iter()
+            .find(|corner| corner.corner == name)
+            .cloned()
+    }
+
+    /// Tires whose inner-to-outer spread exceeds `max_spread` either way.
+    #[pyo3(signature = (max_spread=DEFAULT_MAX_TIRE_SPREAD))]
+    pub fn flagged_corners(&self, max_spread: f64) -> Vec<String> {
+        self.corners
+            .iter()
+            .filter(|corner| corner.spread().abs() > max_spread)
+            .map(|corner| corner.corner.clone())
+            .collect()
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        let spreads: Vec<String> = self
+            .corners
+            .iter()
+            .map(|corner| format!("{}={:+.1}", corner.corner, corner.spread()))
+            .collect();
+        format!(
+            "TireAnalysis(frames={}, spread: {})",
+            self.frame_count,
+            spreads.join(", ")
+        )
+    }
+}
+
+/// Average each tire's inner, middle and outer temperature over `frames`.
+///
+/// Frames without tire temperatures (all zero) are skipped; temperatures
+/// read 0.0 when no frame has any.
+pub fn compute_tire_analysis(frames: &[TelemetryFrame]) -> TireAnalysis {
+    let mut sums = [[0.0; 3]; 4];
+    let mut frame_count = 0;
+
+    for frame in frames.iter().filter(|frame| !frame.tire_temps.is_empty()) {
+        for (sum, temps) in sums.iter_mut().zip(frame.tire_temps.corners()) {
+            for (sum, temp) in sum.iter_mut().zip(temps) {
+                *sum += temp;
+            }
+        }
+        frame_count += 1;
+    }
+
+    let corners = TIRE_CORNERS
+        .iter()
+        .zip(sums)
+        .map(|(&corner, [left, middle, right])| {
+            let average = |sum: f64| {
+                if frame_count == 0 {
+                    0.0
+                } else {
+                    sum / frame_count as f64
+                }
+            };
+            // Left-side tires have their inner edge on the right
+            let (inner, outer) = if corner.starts_with('L') {
+                (right, left)
+            } else {
+                (left, right)
+            };
+            TireCornerTemps {
+                corner: corner.to_string(),
+                inner: average(inner),
+                middle: average(middle),
+                outer: average(outer),
+            }
+        })
+        .collect();
+
+    TireAnalysis {
+        corners,
+        frame_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TireTemps;
+
+    fn make_frame(tire_temps: TireTemps) -> TelemetryFrame {
+        TelemetryFrame::new(0.0, 1.0, 50.0, 0.5, 0.0, 0.0, 0.0, 0.0, false)
+            .with_tire_temps(tire_temps)
+    }
+
+    fn balanced() -> TireTemps {
+        TireTemps {
+            lf: [82.0, 80.0, 84.0],
+            rf: [84.0, 80.0, 82.0],
+            lr: [75.0, 74.0, 77.0],
+            rr: [77.0, 74.0, 75.0],
+        }
+    }
+
+    #[test]
+    fn test_hot_inside_left_front_is_flagged() {
+        let frames: Vec<_> = (0..10)
+            .map(|i| {
+                let mut temps = balanced();
+                // Inner edge of the left front is its right side
+                temps.lf = [78.0, 85.0, 95.0 + i as f64];
+                make_frame(temps)
+            })
+            .collect();
+
+        let analysis = compute_tire_analysis(&frames);
+
+        let lf = analysis.corner("LF").unwrap();
+        assert_eq!(lf.inner, 99.5);
+        assert_eq!(lf.outer, 78.0);
+        assert_eq!(lf.spread(), 21.5);
+        assert_eq!(
+            analysis.flagged_corners(DEFAULT_MAX_TIRE_SPREAD),
+            vec!["LF"]
+        );
+    }
+
+    #[test]
+    fn test_inner_edge_follows_car_side() {
+        let analysis = compute_tire_analysis(&[make_frame(balanced())]);
+
+        for corner in &analysis.corners {
+            assert_eq!(
+                corner.spread(),
+                2.0,
+                "{} inner should be hotter",
+                corner.corner
+            );
+        }
+        assert_eq!(analysis.corner("RF").unwrap().inner, 84.0);
+        assert_eq!(analysis.corner("RF").unwrap().average(), 82.0);
+        assert!(analysis.flagged_corners(DEFAULT_MAX_TIRE_SPREAD).is_empty());
+    }
+
+    #[test]
+    fn test_frames_without_temps_are_skipped() {
+        let frames = [
+            make_frame(balanced()),
+            make_frame(TireTemps::default()),
+            make_frame(balanced()),
+        ];
+
+        let analysis = compute_tire_analysis(&frames);
+
+        assert_eq!(analysis.frame_count, 2);
+        assert_eq!(analysis.corner("LF").unwrap().inner, 84.0);
+    }
+
+    #[test]
+    fn test_empty_frames() {
+        let analysis = compute_tire_analysis(&[]);
+
+        assert_eq!(analysis.frame_count, 0);
+        assert_eq!(analysis.corners.len(), 4);
+        assert!(analysis
+            .corners
+            .iter()
+            .all(|corner| corner.average() == 0.0));
+        assert!(analysis.flagged_corners(0.0).is_empty());
+    }
+}
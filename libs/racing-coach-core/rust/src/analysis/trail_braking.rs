@@ -92,7 +92,17 @@ mod tests {
     use super::*;
 
     fn make_frame(brake: f64, steering: f64, lap_distance: f64) -> TelemetryFrame {
-        TelemetryFrame::new(brake, 0.0, 50.0, lap_distance, steering, 0.0, 0.0, 0.0)
+        TelemetryFrame::new(
+            brake,
+            0.0,
+            50.0,
+            lap_distance,
+            steering,
+            0.0,
+            0.0,
+            0.0,
+            false,
+        )
     }
 
     fn default_config() -> AnalysisConfig {
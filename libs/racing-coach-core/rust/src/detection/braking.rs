@@ -1,29 +1,25 @@
 //! Braking zone detection.
 
-use crate::analysis::{calculate_deceleration, detect_trail_braking};
+use std::borrow::Cow;
+
+use crate::analysis::{calculate_deceleration, deceleration_slope, detect_trail_braking};
 use crate::results::{BrakingMetrics, BrakingMetricsBuilder};
 use crate::types::{AnalysisConfig, TelemetryFrame};
 
-use super::EventDetector;
-
-/// State for braking zone detection.
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum BrakingState {
-    /// Not currently in a braking zone
-    Idle,
-    /// In a braking zone, tracking since start_idx
-    Active,
-}
+use super::{Activity, EventDetector, HysteresisGate};
 
 /// Detector for braking zones using a state machine approach.
 ///
 /// Tracks when brake pressure crosses the threshold and accumulates
-/// metrics until the braking zone ends.
+/// metrics until the braking zone ends. Brief releases are bridged according
+/// to the configured exit threshold and merge gap.
 pub struct BrakingDetector<'a> {
     config: &'a AnalysisConfig,
-    state: BrakingState,
+    gate: HysteresisGate,
     builder: Option<BrakingMetricsBuilder>,
     current_end_idx: usize,
+    /// Frames fitted for the initial deceleration
+    decel_window: usize,
 }
 
 impl<'a> BrakingDetector<'a> {
@@ -31,9 +27,29 @@ impl<'a> BrakingDetector<'a> {
     pub fn new(config: &'a AnalysisConfig) -> Self {
         Self {
             config,
-            state: BrakingState::Idle,
+            gate: HysteresisGate::new(
+                config.brake_threshold,
+                config.brake_exit(),
+                config.merge_gap_frames,
+            ),
             builder: None,
             current_end_idx: 0,
+            decel_window: config.decel_window,
+        }
+    }
+
+    /// Create a braking detector for `frames`, converting
+    /// [`AnalysisConfig::merge_gap_secs`] and
+    /// [`AnalysisConfig::decel_window_secs`] to frames at their sample rate.
+    pub fn for_frames(config: &'a AnalysisConfig, frames: &[TelemetryFrame]) -> Self {
+        Self {
+            gate: HysteresisGate::new(
+                config.brake_threshold,
+                config.brake_exit(),
+                config.merge_gap_for(frames),
+            ),
+            decel_window: config.decel_window_for(frames),
+            ..Self::new(config)
         }
     }
 
@@ -47,10 +63,10 @@ impl<'a> BrakingDetector<'a> {
         let end_frame = &frames[end_idx.min(frames.len() - 1)];
 
         // Calculate deceleration metrics
-        let initial_decel = calculate_deceleration(
+        let initial_decel = deceleration_slope(
             frames,
             builder.start_idx,
-            (builder.start_idx + self.config.decel_window).min(end_idx),
+            (builder.start_idx + self.decel_window).min(end_idx),
         );
         let avg_decel = calculate_deceleration(frames, builder.start_idx, end_idx);
 
@@ -80,6 +96,8 @@ impl<'a> BrakingDetector<'a> {
             trail_info.has_trail_braking,
             trail_info.distance,
             trail_info.percentage,
+            frames[builder.start_idx].lap_distance_meters,
+            end_frame.lap_distance_meters,
         )
     }
 }
@@ -87,58 +105,120 @@ impl<'a> BrakingDetector<'a> {
 impl EventDetector for BrakingDetector<'_> {
     type Builder = BrakingMetricsBuilder;
 
-    fn process_frame(&mut self, frame: &TelemetryFrame, index: usize) -> Option<Self::Builder> {
-        let is_braking = frame.brake > self.config.brake_threshold;
-
-        match self.state {
-            BrakingState::Idle => {
-                if is_braking {
-                    // Start new braking zone
-                    self.state = BrakingState::Active;
-                    self.builder = Some(BrakingMetricsBuilder::new(
-                        index,
-                        frame.lap_distance,
-                        frame.speed,
-                        frame.brake,
-                        frame.timestamp,
-                    ));
-                    self.current_end_idx = index;
-                }
+    fn process_frame(
+        &mut self,
+        frame: &TelemetryFrame,
+        index: usize,
+    ) -> Option<(Self::Builder, usize)> {
+        match self.gate.update(frame.brake) {
+            Activity::Started => {
+                // Start new braking zone
+                self.builder = Some(BrakingMetricsBuilder::new(
+                    index,
+                    frame.lap_distance,
+                    frame.speed,
+                    frame.brake,
+                    frame.timestamp,
+                ));
+                self.current_end_idx = index;
                 None
             }
-            BrakingState::Active => {
-                if is_braking {
-                    // Continue braking - update builder
-                    if let Some(ref mut builder) = self.builder {
-                        builder.update(frame.brake, frame.speed);
-                    }
-                    self.current_end_idx = index;
-                    None
-                } else {
-                    // End of braking zone - return completed builder
-                    self.state = BrakingState::Idle;
-                    self.builder.take()
+            Activity::Continued => {
+                // Continue braking - update builder
+                if let Some(ref mut builder) = self.builder {
+                    builder.update(frame.brake, frame.speed);
                 }
+                self.current_end_idx = index;
+                None
             }
+            // End of braking zone - return completed builder
+            Activity::Ended => self
+                .builder
+                .take()
+                .map(|builder| (builder, self.current_end_idx)),
+            Activity::Paused | Activity::Inactive => None,
         }
     }
 
-    fn finalize(&mut self) -> Option<Self::Builder> {
-        if self.state == BrakingState::Active {
-            self.state = BrakingState::Idle;
-            self.builder.take()
+    fn finalize(&mut self) -> Option<(Self::Builder, usize)> {
+        if self.gate.is_active() {
+            self.gate.reset();
+            self.builder
+                .take()
+                .map(|builder| (builder, self.current_end_idx))
         } else {
             None
         }
     }
 
     fn reset(&mut self) {
-        self.state = BrakingState::Idle;
+        self.gate.reset();
         self.builder = None;
         self.current_end_idx = 0;
     }
 }
 
+/// Lazily detected braking zones, see [`braking_zones_iter`].
+pub struct BrakingZones<'a> {
+    source: &'a [TelemetryFrame],
+    /// The analysis frames, filtered from `source` on the first poll
+    frames: Option<Cow<'a, [TelemetryFrame]>>,
+    detector: BrakingDetector<'a>,
+    /// Index of the next frame to feed the detector
+    next_idx: usize,
+}
+
+impl Iterator for BrakingZones<'_> {
+    type Item = BrakingMetrics;
+
+    fn next(&mut self) -> Option<BrakingMetrics> {
+        let frames = match &mut self.frames {
+            Some(frames) => frames,
+            frames @ None => {
+                let config = self.detector.config;
+                let filtered = frames.insert(config.analysis_frames(self.source));
+                self.detector = BrakingDetector::for_frames(config, filtered);
+                filtered
+            }
+        };
+
+        while let Some(frame) = frames.get(self.next_idx) {
+            let completed = self.detector.process_frame(frame, self.next_idx);
+            self.next_idx += 1;
+            if let Some((builder, end_idx)) = completed {
+                return Some(self.detector.finalize_builder(builder, frames, end_idx));
+            }
+        }
+
+        // Handle any in-progress braking zone at end of lap
+        let (builder, end_idx) = self.detector.finalize()?;
+        Some(self.detector.finalize_builder(builder, frames, end_idx))
+    }
+}
+
+/// Detect braking zones one at a time as the iterator is polled.
+///
+/// Nothing is scanned until the first call to `next`, and each call only
+/// runs the detector as far as the end of the next zone.
+///
+/// # Arguments
+/// * `frames` - Slice of telemetry frames
+/// * `config` - Analysis configuration
+///
+/// # Returns
+/// * Iterator of BrakingMetrics for each detected braking zone, in lap order
+pub fn braking_zones_iter<'a>(
+    frames: &'a [TelemetryFrame],
+    config: &'a AnalysisConfig,
+) -> BrakingZones<'a> {
+    BrakingZones {
+        source: frames,
+        frames: None,
+        detector: BrakingDetector::new(config),
+        next_idx: 0,
+    }
+}
+
 /// Extract all braking zones from telemetry frames.
 ///
 /// This is the standalone function for extracting braking zones
@@ -154,40 +234,26 @@ pub fn extract_braking_zones(
     frames: &[TelemetryFrame],
     config: &AnalysisConfig,
 ) -> Vec<BrakingMetrics> {
-    if frames.is_empty() {
-        return vec![];
-    }
-
-    let mut detector = BrakingDetector::new(config);
-    let mut results = Vec::with_capacity(20); // Pre-allocate for typical lap
-    let mut pending_builders: Vec<(BrakingMetricsBuilder, usize)> = Vec::new();
-
-    for (idx, frame) in frames.iter().enumerate() {
-        if let Some(builder) = detector.process_frame(frame, idx) {
-            // Braking zone ended at previous frame
-            pending_builders.push((builder, idx.saturating_sub(1)));
-        }
-    }
-
-    // Handle any in-progress braking zone at end of lap
-    if let Some(builder) = detector.finalize() {
-        pending_builders.push((builder, frames.len() - 1));
-    }
-
-    // Finalize all builders
-    for (builder, end_idx) in pending_builders {
-        results.push(detector.finalize_builder(builder, frames, end_idx));
-    }
-
-    results
+    braking_zones_iter(frames, config).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::FrameSequenceBuilder;
 
     fn make_frame(brake: f64, speed: f64, lap_distance: f64, timestamp: f64) -> TelemetryFrame {
-        TelemetryFrame::new(brake, 0.0, speed, lap_distance, 0.0, 0.0, 0.0, timestamp)
+        TelemetryFrame::new(
+            brake,
+            0.0,
+            speed,
+            lap_distance,
+            0.0,
+            0.0,
+            0.0,
+            timestamp,
+            false,
+        )
     }
 
     fn default_config() -> AnalysisConfig {
@@ -229,18 +295,90 @@ mod tests {
     }
 
     #[test]
-    fn test_multiple_braking_zones() {
+    fn test_repeated_timestamps_give_finite_deceleration() {
+        // The replay stalls for the first frames of the braking zone,
+        // repeating one timestamp, then carries on at 60 Hz
+        let mut frames = vec![make_frame(0.0, 80.0, 0.40, 0.0)];
+        frames.extend((0..6).map(|_| make_frame(0.9, 80.0, 0.41, 1.0 / 60.0)));
+        frames.extend((2..30).map(|i| {
+            let t = i as f64 / 60.0;
+            make_frame(0.9, 80.0 - 30.0 * (t - 1.0 / 60.0), 0.41 + t / 100.0, t)
+        }));
+        frames.push(make_frame(0.0, 60.0, 0.8, 0.5));
+
+        let zones = extract_braking_zones(&frames, &default_config());
+        assert_eq!(zones.len(), 1);
+
+        let zone = &zones[0];
+        assert!(
+            (zone.initial_deceleration - (-30.0)).abs() < 1e-6,
+            "initial deceleration {}",
+            zone.initial_deceleration
+        );
+        assert!(zone.average_deceleration.is_finite());
+        assert!(zone.average_deceleration < 0.0);
+        assert!(zone.braking_efficiency.is_finite());
+    }
+
+    #[test]
+    fn test_braking_point_in_meters() {
+        const TRACK_LENGTH: f64 = 4000.0;
+        let frames: Vec<_> = [
+            (0.0, 80.0, 0.40),
+            (0.8, 75.0, 0.45), // Start braking
+            (0.9, 60.0, 0.50),
+            (0.6, 45.0, 0.55), // Last braking frame
+            (0.0, 40.0, 0.60),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (brake, speed, pct))| {
+            make_frame(brake, speed, pct, i as f64 * 0.5)
+                .with_lap_distance_meters(pct * TRACK_LENGTH)
+        })
+        .collect();
+
+        let zones = extract_braking_zones(&frames, &default_config());
+        assert_eq!(zones.len(), 1);
+
+        let zone = &zones[0];
+        assert_eq!(
+            zone.braking_point_distance_meters,
+            zone.braking_point_distance * TRACK_LENGTH
+        );
+        assert_eq!(zone.end_distance_meters, zone.end_distance * TRACK_LENGTH);
+        assert_eq!(zone.braking_point_distance_meters, 1800.0);
+    }
+
+    #[test]
+    fn test_meters_unknown_without_lap_distance() {
         let frames = vec![
-            make_frame(0.0, 80.0, 0.0, 0.0),
-            make_frame(0.8, 60.0, 0.1, 1.0),
-            make_frame(0.0, 50.0, 0.2, 2.0), // End first
-            make_frame(0.0, 70.0, 0.5, 3.0),
-            make_frame(0.7, 55.0, 0.6, 4.0), // Start second
-            make_frame(0.0, 45.0, 0.7, 5.0), // End second
+            make_frame(0.8, 75.0, 0.45, 0.0),
+            make_frame(0.9, 60.0, 0.50, 0.5),
         ];
 
+        let zones = extract_braking_zones(&frames, &default_config());
+        assert_eq!(zones[0].braking_point_distance_meters, 0.0);
+        assert_eq!(zones[0].end_distance_meters, 0.0);
+    }
+
+    #[test]
+    fn test_multiple_braking_zones() {
+        let frames = FrameSequenceBuilder::new()
+            .straight(2.0, 80.0)
+            .braking(1.5, 80.0, 50.0)
+            .straight(3.0, 50.0)
+            .braking(1.0, 50.0, 35.0)
+            .straight(1.0, 35.0)
+            .build();
+
         let zones = extract_braking_zones(&frames, &default_config());
         assert_eq!(zones.len(), 2);
+        assert_eq!(zones[0].braking_point_speed, 80.0);
+        assert_eq!(zones[0].minimum_speed, 50.0);
+        assert_eq!(zones[1].braking_point_speed, 50.0);
+        assert_eq!(zones[1].minimum_speed, 35.0);
+        assert!(zones[1].braking_point_distance > zones[0].end_distance);
     }
 
     #[test]
@@ -261,4 +399,133 @@ mod tests {
         let zones = extract_braking_zones(&frames, &default_config());
         assert!(zones.is_empty());
     }
+
+    /// Trail-brake modulation that dips below the threshold for one frame.
+    fn frames_with_brake_dip() -> Vec<TelemetryFrame> {
+        vec![
+            make_frame(0.0, 80.0, 0.40, 0.0),
+            make_frame(0.8, 75.0, 0.45, 0.5),
+            make_frame(0.5, 65.0, 0.50, 1.0),
+            make_frame(0.03, 60.0, 0.52, 1.25), // Momentary release
+            make_frame(0.3, 55.0, 0.55, 1.5),
+            make_frame(0.1, 50.0, 0.58, 2.0),
+            make_frame(0.0, 50.0, 0.60, 2.5),
+        ]
+    }
+
+    #[test]
+    fn test_brake_dip_splits_zone_by_default() {
+        let zones = extract_braking_zones(&frames_with_brake_dip(), &default_config());
+        assert_eq!(zones.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_gap_joins_brake_dip() {
+        let config = AnalysisConfig {
+            merge_gap_frames: 2,
+            ..AnalysisConfig::default()
+        };
+
+        let zones = extract_braking_zones(&frames_with_brake_dip(), &config);
+        assert_eq!(zones.len(), 1);
+
+        let zone = &zones[0];
+        assert_eq!(zone.braking_point_distance, 0.45);
+        assert_eq!(zone.end_distance, 0.58);
+        assert_eq!(zone.minimum_speed, 50.0);
+    }
+
+    #[test]
+    fn test_exit_threshold_joins_brake_dip() {
+        let config = AnalysisConfig {
+            brake_threshold: 0.05,
+            brake_exit_threshold: Some(0.02),
+            ..AnalysisConfig::default()
+        };
+
+        let zones = extract_braking_zones(&frames_with_brake_dip(), &config);
+        assert_eq!(zones.len(), 1);
+        assert_eq!(zones[0].end_distance, 0.58);
+    }
+
+    #[test]
+    fn test_iter_matches_vec() {
+        let config = default_config();
+        let mut frames = frames_with_brake_dip();
+        // The same lap segment again, further round the lap
+        frames.extend(frames_with_brake_dip().into_iter().map(|frame| {
+            make_frame(
+                frame.brake,
+                frame.speed,
+                frame.lap_distance + 0.2,
+                frame.timestamp + 3.0,
+            )
+        }));
+
+        let zones: Vec<_> = braking_zones_iter(&frames, &config).collect();
+
+        assert_eq!(zones.len(), 4);
+        assert_eq!(zones, extract_braking_zones(&frames, &config));
+    }
+
+    #[test]
+    fn test_iter_is_lazy() {
+        let config = default_config();
+        let frames = frames_with_brake_dip();
+        let mut zones = braking_zones_iter(&frames, &config);
+
+        // Nothing is read until polled
+        assert!(zones.frames.is_none());
+        assert_eq!(zones.next_idx, 0);
+
+        // The first zone ends on the momentary release, at index 3
+        assert_eq!(zones.next().unwrap().end_distance, 0.50);
+        assert_eq!(zones.next_idx, 4);
+
+        assert!(zones.next().is_some());
+        assert!(zones.next().is_none());
+    }
+
+    /// Braking from 1.0 s to just before 2.0 s with a release around 1.5 s,
+    /// sampled at `hz`.
+    fn braking_with_release_at(hz: f64) -> Vec<TelemetryFrame> {
+        (0..(3.0 * hz) as usize)
+            .map(|i| {
+                let t = i as f64 / hz;
+                let braking = (1.0..1.98).contains(&t) && !(1.49..1.54).contains(&t);
+                make_frame(if braking { 0.8 } else { 0.0 }, 60.0, t / 3.0, t)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_gap_in_seconds_is_sample_rate_independent() {
+        let (slow, fast) = (braking_with_release_at(30.0), braking_with_release_at(60.0));
+
+        // The release spans 2 frames at 30 Hz but 3 at 60 Hz
+        let by_frames = AnalysisConfig {
+            merge_gap_frames: 2,
+            ..AnalysisConfig::default()
+        };
+        assert_eq!(extract_braking_zones(&slow, &by_frames).len(), 1);
+        assert_eq!(extract_braking_zones(&fast, &by_frames).len(), 2);
+
+        let by_secs = AnalysisConfig {
+            merge_gap_secs: Some(0.1),
+            ..by_frames
+        };
+        let slow_zones = extract_braking_zones(&slow, &by_secs);
+        let fast_zones = extract_braking_zones(&fast, &by_secs);
+        assert_eq!(slow_zones.len(), 1);
+        assert_eq!(fast_zones.len(), 1);
+        let (slow_zone, fast_zone) = (&slow_zones[0], &fast_zones[0]);
+        assert_eq!(
+            slow_zone.braking_point_distance,
+            fast_zone.braking_point_distance
+        );
+        assert_eq!(slow_zone.end_distance, fast_zone.end_distance);
+
+        let lazy: Vec<_> = braking_zones_iter(&fast, &by_secs).collect();
+        assert_eq!(lazy, fast_zones);
+    }
 }
@@ -1,27 +1,22 @@
 //! Corner detection.
 
+use crate::analysis::corner_balance;
 use crate::results::{CornerMetrics, CornerMetricsBuilder};
-use crate::types::{AnalysisConfig, TelemetryFrame};
+use crate::types::{AnalysisConfig, TelemetryFrame, TrackCorner};
 use crate::utils::wrap_distance;
 
-use super::EventDetector;
-
-/// State for corner detection.
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum CornerState {
-    /// Not currently in a corner
-    Idle,
-    /// In a corner
-    Active,
-}
+use super::{Activity, EventDetector, HysteresisGate};
 
 /// Detector for corners using a state machine approach.
 ///
-/// Tracks when steering angle crosses the threshold and accumulates
+/// Tracks when steering (angle, or percent of lock with
+/// [`AnalysisConfig::corner_steering_pct`]) crosses the threshold and accumulates
 /// metrics including apex (max lateral G), minimum speed, and throttle application.
+/// Brief unwinds are bridged according to the configured exit threshold and
+/// merge gap.
 pub struct CornerDetector<'a> {
     config: &'a AnalysisConfig,
-    state: CornerState,
+    gate: HysteresisGate,
     builder: Option<CornerMetricsBuilder>,
     current_end_idx: usize,
 }
@@ -31,12 +26,109 @@ impl<'a> CornerDetector<'a> {
     pub fn new(config: &'a AnalysisConfig) -> Self {
         Self {
             config,
-            state: CornerState::Idle,
+            gate: HysteresisGate::new(
+                config.steering_threshold,
+                config.steering_exit(),
+                config.merge_gap_frames,
+            ),
             builder: None,
             current_end_idx: 0,
         }
     }
 
+    /// Create a corner detector for `frames`, converting
+    /// [`AnalysisConfig::merge_gap_secs`] to frames at their sample rate.
+    pub fn for_frames(config: &'a AnalysisConfig, frames: &[TelemetryFrame]) -> Self {
+        Self {
+            gate: HysteresisGate::new(
+                config.steering_threshold,
+                config.steering_exit(),
+                config.merge_gap_for(frames),
+            ),
+            ..Self::new(config)
+        }
+    }
+
+    /// Split a detected corner at each dip in lateral G between two apexes,
+    /// see [`AnalysisConfig::apex_split_ratio`].
+    ///
+    /// The corner from `builder` to `end_idx` comes back whole when splitting
+    /// is off or it has a single apex. Otherwise each part ends at the bottom
+    /// of a dip and the next part turns in there.
+    pub fn split_apexes(
+        &self,
+        builder: CornerMetricsBuilder,
+        frames: &[TelemetryFrame],
+        end_idx: usize,
+    ) -> Vec<(CornerMetricsBuilder, usize)> {
+        let Some(ratio) = self.config.apex_split_ratio else {
+            return vec![(builder, end_idx)];
+        };
+        let start_idx = builder.turn_in_idx;
+        let end_idx = end_idx.min(frames.len() - 1);
+
+        let mut dips = Vec::new();
+        let mut peak = 0.0;
+        // Lowest point since lateral G fell below `ratio` of the last peak
+        let mut dip: Option<(usize, f64)> = None;
+        for (idx, frame) in frames[..=end_idx].iter().enumerate().skip(start_idx) {
+            let lateral_g = frame.lateral_acceleration.abs();
+            match dip {
+                None if lateral_g >= peak => peak = lateral_g,
+                None if lateral_g < peak * ratio => dip = Some((idx, lateral_g)),
+                None => {}
+                Some((_, lowest)) if lateral_g < lowest => dip = Some((idx, lateral_g)),
+                Some((dip_idx, lowest)) if lowest < lateral_g * ratio => {
+                    dips.push(dip_idx);
+                    peak = lateral_g;
+                    dip = None;
+                }
+                Some(_) => {}
+            }
+        }
+        if dips.is_empty() {
+            return vec![(builder, end_idx)];
+        }
+
+        let mut bounds = vec![start_idx];
+        bounds.extend(dips);
+        bounds.push(end_idx);
+        bounds
+            .windows(2)
+            .map(|part| (self.build_corner(frames, part[0], part[1]), part[1]))
+            .collect()
+    }
+
+    /// A builder for the corner from `start_idx` through `end_idx`.
+    fn build_corner(
+        &self,
+        frames: &[TelemetryFrame],
+        start_idx: usize,
+        end_idx: usize,
+    ) -> CornerMetricsBuilder {
+        let turn_in = &frames[start_idx];
+        let mut builder = CornerMetricsBuilder::new(
+            start_idx,
+            turn_in.lap_distance,
+            turn_in.speed,
+            turn_in.timestamp,
+            turn_in.lateral_acceleration,
+            turn_in.steering_angle,
+        );
+        for (idx, frame) in frames[..=end_idx].iter().enumerate().skip(start_idx + 1) {
+            builder.update(
+                idx,
+                frame.lap_distance,
+                frame.speed,
+                frame.lateral_acceleration,
+                frame.steering_angle,
+                frame.throttle,
+                self.config.throttle_threshold,
+            );
+        }
+        builder
+    }
+
     /// Finalize a builder into CornerMetrics using the full frame slice.
     pub fn finalize_builder(
         &self,
@@ -44,7 +136,8 @@ impl<'a> CornerDetector<'a> {
         frames: &[TelemetryFrame],
         end_idx: usize,
     ) -> CornerMetrics {
-        let exit_frame = &frames[end_idx.min(frames.len() - 1)];
+        let end_idx = end_idx.min(frames.len() - 1);
+        let exit_frame = &frames[end_idx];
 
         // Calculate time in corner
         let time_in_corner = exit_frame.timestamp - builder.turn_in_timestamp;
@@ -56,6 +149,28 @@ impl<'a> CornerDetector<'a> {
         let speed_loss = builder.turn_in_speed - builder.min_speed;
         let speed_gain = exit_frame.speed - builder.min_speed;
 
+        // Scan the whole corner so the true minimum is reported even when it
+        // falls after the point of max lateral G
+        let corner_frames = &frames[builder.turn_in_idx.min(end_idx)..=end_idx];
+        let min_speed = corner_frames
+            .iter()
+            .map(|frame| frame.speed)
+            .fold(builder.min_speed, f64::min);
+        let apex_frame = &frames[builder.apex_idx.min(end_idx)];
+        let throttle_frame = if builder.throttle_applied {
+            &frames[builder.throttle_idx.min(end_idx)]
+        } else {
+            exit_frame
+        };
+
+        // Mid-corner ends where the throttle comes back, but never before
+        // the apex, so the three phases always add up to the whole corner
+        let mid_end_frame = if builder.throttle_applied {
+            &frames[builder.throttle_idx.max(builder.apex_idx).min(end_idx)]
+        } else {
+            exit_frame
+        };
+
         CornerMetrics::new(
             builder.turn_in_distance,
             builder.apex_distance,
@@ -79,6 +194,20 @@ impl<'a> CornerDetector<'a> {
             builder.max_steering,
             speed_loss,
             speed_gain,
+            min_speed,
+            apex_frame.gear,
+            frames[builder.turn_in_idx.min(end_idx)].lap_distance_meters,
+            apex_frame.lap_distance_meters,
+            exit_frame.lap_distance_meters,
+            throttle_frame.lap_distance_meters,
+            corner_balance(corner_frames),
+            0,
+            None,
+            apex_frame.timestamp - builder.turn_in_timestamp,
+            mid_end_frame.timestamp - apex_frame.timestamp,
+            exit_frame.timestamp - mid_end_frame.timestamp,
+            apex_frame.speed,
+            mid_end_frame.speed,
         )
     }
 }
@@ -86,62 +215,63 @@ impl<'a> CornerDetector<'a> {
 impl EventDetector for CornerDetector<'_> {
     type Builder = CornerMetricsBuilder;
 
-    fn process_frame(&mut self, frame: &TelemetryFrame, index: usize) -> Option<Self::Builder> {
-        let is_turning = frame.steering_angle.abs() > self.config.steering_threshold;
-
-        match self.state {
-            CornerState::Idle => {
-                if is_turning {
-                    // Start new corner
-                    self.state = CornerState::Active;
-                    self.builder = Some(CornerMetricsBuilder::new(
+    fn process_frame(
+        &mut self,
+        frame: &TelemetryFrame,
+        index: usize,
+    ) -> Option<(Self::Builder, usize)> {
+        match self.gate.update(self.config.corner_steering(frame).abs()) {
+            Activity::Started => {
+                // Start new corner
+                self.builder = Some(CornerMetricsBuilder::new(
+                    index,
+                    frame.lap_distance,
+                    frame.speed,
+                    frame.timestamp,
+                    frame.lateral_acceleration,
+                    frame.steering_angle,
+                ));
+                self.current_end_idx = index;
+                None
+            }
+            Activity::Continued => {
+                // Continue in corner - update builder
+                if let Some(ref mut builder) = self.builder {
+                    builder.update(
                         index,
                         frame.lap_distance,
                         frame.speed,
-                        frame.timestamp,
                         frame.lateral_acceleration,
                         frame.steering_angle,
-                    ));
-                    self.current_end_idx = index;
+                        frame.throttle,
+                        self.config.throttle_threshold,
+                    );
                 }
+                self.current_end_idx = index;
                 None
             }
-            CornerState::Active => {
-                if is_turning {
-                    // Continue in corner - update builder
-                    if let Some(ref mut builder) = self.builder {
-                        builder.update(
-                            index,
-                            frame.lap_distance,
-                            frame.speed,
-                            frame.lateral_acceleration,
-                            frame.steering_angle,
-                            frame.throttle,
-                            self.config.throttle_threshold,
-                        );
-                    }
-                    self.current_end_idx = index;
-                    None
-                } else {
-                    // Exit corner - return completed builder
-                    self.state = CornerState::Idle;
-                    self.builder.take()
-                }
-            }
+            // Exit corner - return completed builder
+            Activity::Ended => self
+                .builder
+                .take()
+                .map(|builder| (builder, self.current_end_idx)),
+            Activity::Paused | Activity::Inactive => None,
         }
     }
 
-    fn finalize(&mut self) -> Option<Self::Builder> {
-        if self.state == CornerState::Active {
-            self.state = CornerState::Idle;
-            self.builder.take()
+    fn finalize(&mut self) -> Option<(Self::Builder, usize)> {
+        if self.gate.is_active() {
+            self.gate.reset();
+            self.builder
+                .take()
+                .map(|builder| (builder, self.current_end_idx))
         } else {
             None
         }
     }
 
     fn reset(&mut self) {
-        self.state = CornerState::Idle;
+        self.gate.reset();
         self.builder = None;
         self.current_end_idx = 0;
     }
@@ -159,34 +289,61 @@ impl EventDetector for CornerDetector<'_> {
 /// # Returns
 /// * Vector of CornerMetrics for each detected corner
 pub fn extract_corners(frames: &[TelemetryFrame], config: &AnalysisConfig) -> Vec<CornerMetrics> {
+    let frames = config.analysis_frames(frames);
+    let frames = &frames[..];
     if frames.is_empty() {
         return vec![];
     }
 
-    let mut detector = CornerDetector::new(config);
+    let mut detector = CornerDetector::for_frames(config, frames);
     let mut results = Vec::with_capacity(25); // Pre-allocate for typical lap
     let mut pending_builders: Vec<(CornerMetricsBuilder, usize)> = Vec::new();
 
     for (idx, frame) in frames.iter().enumerate() {
-        if let Some(builder) = detector.process_frame(frame, idx) {
-            // Corner ended at previous frame
-            pending_builders.push((builder, idx.saturating_sub(1)));
-        }
+        pending_builders.extend(detector.process_frame(frame, idx));
     }
 
     // Handle any in-progress corner at end of lap
-    if let Some(builder) = detector.finalize() {
-        pending_builders.push((builder, frames.len() - 1));
-    }
+    pending_builders.extend(detector.finalize());
 
     // Finalize all builders
     for (builder, end_idx) in pending_builders {
-        results.push(detector.finalize_builder(builder, frames, end_idx));
+        for (builder, end_idx) in detector.split_apexes(builder, frames, end_idx) {
+            results.push(detector.finalize_builder(builder, frames, end_idx));
+        }
     }
 
+    label_corners(&mut results, &config.track_corners);
     results
 }
 
+/// Number and name corners from a track map.
+///
+/// A corner whose apex falls inside a [`TrackCorner`] window takes the
+/// window's name, and its position in the map (1-based) as its number. Other
+/// corners are numbered in order after the map's last corner and left
+/// unnamed, so without a map corners are simply numbered from 1.
+pub fn label_corners(corners: &mut [CornerMetrics], track_corners: &[TrackCorner]) {
+    let mut next_number = track_corners.len() as u32 + 1;
+
+    for corner in corners {
+        match track_corners
+            .iter()
+            .position(|window| window.contains(corner.apex_distance))
+        {
+            Some(idx) => {
+                corner.corner_number = idx as u32 + 1;
+                corner.name = Some(track_corners[idx].name.clone());
+            }
+            None => {
+                corner.corner_number = next_number;
+                corner.name = None;
+                next_number += 1;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -208,6 +365,7 @@ mod tests {
             lateral_g,
             0.0,
             timestamp,
+            false,
         )
     }
 
@@ -250,6 +408,38 @@ mod tests {
         assert!(corner.throttle_application_distance > 0.0);
     }
 
+    #[test]
+    fn test_corner_points_in_meters() {
+        const TRACK_LENGTH: f64 = 5000.0;
+        let frames: Vec<_> = [
+            make_frame(0.0, 60.0, 0.0, 0.30, 0.0, 0.0), // Straight
+            make_frame(0.2, 55.0, 1.5, 0.32, 0.5, 0.0), // Turn in
+            make_frame(0.3, 45.0, 2.5, 0.35, 1.0, 0.0), // Apex
+            make_frame(0.2, 50.0, 2.0, 0.38, 1.5, 0.1), // Throttle applied
+            make_frame(0.0, 60.0, 0.5, 0.40, 2.0, 0.5), // Exit
+        ]
+        .into_iter()
+        .map(|frame| frame.with_lap_distance_meters(frame.lap_distance * TRACK_LENGTH))
+        .collect();
+
+        let corners = extract_corners(&frames, &default_config());
+        assert_eq!(corners.len(), 1);
+
+        let corner = &corners[0];
+        for (meters, pct) in [
+            (corner.turn_in_distance_meters, corner.turn_in_distance),
+            (corner.apex_distance_meters, corner.apex_distance),
+            (corner.exit_distance_meters, corner.exit_distance),
+            (
+                corner.throttle_application_distance_meters,
+                corner.throttle_application_distance,
+            ),
+        ] {
+            assert_eq!(meters, pct * TRACK_LENGTH);
+        }
+        assert_eq!(corner.apex_distance_meters, 0.35 * TRACK_LENGTH);
+    }
+
     #[test]
     fn test_apex_is_max_lateral_g() {
         // Apex should be where lateral G is maximum, not where speed is minimum
@@ -336,4 +526,232 @@ mod tests {
         assert_eq!(corner.throttle_application_distance, 0.38);
         assert_eq!(corner.throttle_application_speed, 48.0);
     }
+
+    #[test]
+    fn test_phase_times_split_corner_at_apex_and_throttle() {
+        let frames = vec![
+            make_frame(0.0, 60.0, 0.0, 0.30, 10.0, 0.0),
+            make_frame(0.3, 50.0, 2.0, 0.32, 10.5, 0.0), // Turn in
+            make_frame(0.3, 46.0, 2.4, 0.34, 11.0, 0.0),
+            make_frame(0.3, 45.0, 2.8, 0.35, 11.6, 0.0), // Apex
+            make_frame(0.3, 44.0, 2.5, 0.36, 12.3, 0.0),
+            make_frame(0.2, 48.0, 2.0, 0.38, 13.0, 0.1), // First throttle
+            make_frame(0.2, 52.0, 1.5, 0.40, 13.4, 0.3),
+            make_frame(0.0, 60.0, 0.5, 0.42, 13.9, 0.5), // Exit
+        ];
+
+        let corners = extract_corners(&frames, &default_config());
+        assert_eq!(corners.len(), 1);
+        let corner = &corners[0];
+
+        let apex = &frames[3];
+        let throttle = &frames[5];
+        let exit_time = frames
+            .iter()
+            .find(|frame| frame.lap_distance == corner.exit_distance)
+            .unwrap()
+            .timestamp;
+        assert_eq!(corner.apex_distance, apex.lap_distance);
+        assert!((corner.entry_time - (apex.timestamp - 10.5)).abs() < 1e-9);
+        assert!((corner.mid_time - (throttle.timestamp - apex.timestamp)).abs() < 1e-9);
+        assert!((corner.exit_time - (exit_time - throttle.timestamp)).abs() < 1e-9);
+        assert!(
+            (corner.entry_time + corner.mid_time + corner.exit_time - corner.time_in_corner).abs()
+                < 1e-9
+        );
+        assert_eq!(corner.mid_start_speed, 45.0);
+        assert_eq!(corner.mid_end_speed, 48.0);
+    }
+
+    #[test]
+    fn test_phase_times_without_throttle_end_mid_corner_at_exit() {
+        let frames = vec![
+            make_frame(0.0, 60.0, 0.0, 0.30, 0.0, 0.0),
+            make_frame(0.3, 50.0, 2.0, 0.32, 0.5, 0.0), // Turn in
+            make_frame(0.3, 45.0, 2.8, 0.35, 1.0, 0.0), // Apex
+            make_frame(0.3, 47.0, 2.0, 0.38, 1.5, 0.0),
+            make_frame(0.0, 50.0, 0.5, 0.40, 2.0, 0.0),
+        ];
+
+        let corners = extract_corners(&frames, &default_config());
+        let corner = &corners[0];
+
+        assert!((corner.entry_time - 0.5).abs() < 1e-9);
+        assert_eq!(corner.exit_time, 0.0);
+        assert!((corner.entry_time + corner.mid_time - corner.time_in_corner).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_min_speed_after_apex_and_apex_gear() {
+        let frames = vec![
+            make_frame(0.0, 60.0, 0.0, 0.30, 0.0, 0.0).with_gear(4),
+            make_frame(0.3, 52.0, 1.5, 0.32, 0.5, 0.0).with_gear(3),
+            make_frame(0.3, 48.0, 2.8, 0.34, 1.0, 0.0).with_gear(3), // Max lateral G
+            make_frame(0.3, 43.0, 2.2, 0.36, 1.5, 0.0).with_gear(2), // Slowest point
+            make_frame(0.2, 47.0, 1.5, 0.38, 2.0, 0.2).with_gear(2),
+            make_frame(0.0, 55.0, 0.3, 0.40, 2.5, 0.6).with_gear(2), // Exit
+        ];
+
+        let corners = extract_corners(&frames, &default_config());
+        assert_eq!(corners.len(), 1);
+
+        let corner = &corners[0];
+        assert_eq!(corner.apex_distance, 0.34);
+        assert_eq!(corner.min_speed, 43.0);
+        assert_eq!(corner.apex_gear, 3);
+    }
+
+    #[test]
+    fn test_merge_gap_joins_steering_dip() {
+        let frames = vec![
+            make_frame(0.0, 60.0, 0.0, 0.30, 0.0, 0.0),
+            make_frame(0.3, 50.0, 2.0, 0.32, 0.5, 0.0),
+            make_frame(0.1, 48.0, 1.0, 0.34, 1.0, 0.0), // Steering correction
+            make_frame(0.3, 45.0, 2.5, 0.36, 1.5, 0.0),
+            make_frame(0.0, 55.0, 0.5, 0.40, 2.0, 0.5),
+        ];
+
+        assert_eq!(extract_corners(&frames, &default_config()).len(), 2);
+
+        let config = AnalysisConfig {
+            merge_gap_frames: 1,
+            ..AnalysisConfig::default()
+        };
+        let corners = extract_corners(&frames, &config);
+        assert_eq!(corners.len(), 1);
+        assert_eq!(corners[0].turn_in_distance, 0.32);
+        assert_eq!(corners[0].exit_distance, 0.36);
+        assert_eq!(corners[0].max_lateral_g, 2.5);
+    }
+
+    #[test]
+    fn test_steering_pct_finds_the_same_corners() {
+        // Full lock in radians; a power of two so the scaled thresholds are exact
+        const FULL_LOCK: f64 = 8.0;
+        let mut frames = Vec::new();
+        for (corner, peak) in [0.3, -0.5, 0.2].into_iter().enumerate() {
+            let base = corner as f64 * 10.0;
+            let start = corner as f64 * 0.3;
+            for (step, steering) in [0.0, 0.5, 1.0, 0.5, 0.0].into_iter().enumerate() {
+                let t = step as f64;
+                let frame = make_frame(peak * steering, 50.0, 2.0, start + t * 0.02, base + t, 0.0);
+                frames.push(frame.with_steering_pct(frame.steering_angle / FULL_LOCK));
+            }
+        }
+        let by_angle = AnalysisConfig {
+            steering_exit_threshold: Some(0.1),
+            ..default_config()
+        };
+        let by_pct = AnalysisConfig {
+            steering_threshold: 0.15 / FULL_LOCK,
+            steering_exit_threshold: Some(0.1 / FULL_LOCK),
+            corner_steering_pct: true,
+            ..default_config()
+        };
+
+        let corners = extract_corners(&frames, &by_angle);
+
+        assert_eq!(corners.len(), 3);
+        assert_eq!(extract_corners(&frames, &by_pct), corners);
+        // The channel not selected is ignored
+        let no_pct: Vec<_> = frames.iter().map(|f| f.with_steering_pct(0.0)).collect();
+        assert!(extract_corners(&no_pct, &by_pct).is_empty());
+    }
+
+    #[test]
+    fn test_double_apex_split_when_enabled() {
+        // Constant lock through two lateral G peaks
+        let frames = vec![
+            make_frame(0.0, 60.0, 0.0, 0.30, 0.0, 0.0),
+            make_frame(0.3, 55.0, 1.5, 0.32, 0.5, 0.0), // Turn in
+            make_frame(0.3, 45.0, 2.5, 0.34, 1.0, 0.0), // First apex
+            make_frame(0.3, 50.0, 1.2, 0.36, 1.5, 0.0), // Between apexes
+            make_frame(0.3, 44.0, 2.4, 0.38, 2.0, 0.0), // Second apex
+            make_frame(0.3, 52.0, 1.0, 0.40, 2.5, 0.3),
+            make_frame(0.0, 60.0, 0.3, 0.42, 3.0, 0.6), // Exit
+        ];
+
+        let corners = extract_corners(&frames, &default_config());
+        assert_eq!(corners.len(), 1);
+        assert_eq!(corners[0].apex_distance, 0.34);
+
+        let split = AnalysisConfig {
+            apex_split_ratio: Some(0.7),
+            ..default_config()
+        };
+        let corners = extract_corners(&frames, &split);
+        assert_eq!(corners.len(), 2);
+        let ranges: Vec<_> = corners
+            .iter()
+            .map(|c| (c.turn_in_distance, c.apex_distance, c.exit_distance))
+            .collect();
+        assert_eq!(ranges, vec![(0.32, 0.34, 0.36), (0.36, 0.38, 0.40)]);
+        assert_eq!(corners[1].max_lateral_g, 2.4);
+        assert_eq!(corners[1].corner_number, 2);
+
+        // A shallower dip than the ratio asks for keeps one corner
+        let shallow = AnalysisConfig {
+            apex_split_ratio: Some(0.4),
+            ..default_config()
+        };
+        assert_eq!(extract_corners(&frames, &shallow).len(), 1);
+    }
+
+    #[test]
+    fn test_corners_labeled_from_track_map() {
+        // Three corners with apexes near 0.15, 0.45 and 0.75
+        let mut frames = Vec::new();
+        for (corner, start) in [0.1, 0.4, 0.7].into_iter().enumerate() {
+            let base = corner as f64 * 10.0;
+            frames.push(make_frame(0.0, 60.0, 0.0, start, base, 1.0));
+            frames.push(make_frame(0.3, 45.0, 1.5, start + 0.02, base + 1.0, 0.0));
+            frames.push(make_frame(0.4, 40.0, 2.5, start + 0.05, base + 2.0, 0.0));
+            frames.push(make_frame(0.3, 45.0, 1.5, start + 0.08, base + 3.0, 0.5));
+            frames.push(make_frame(0.0, 55.0, 0.0, start + 0.1, base + 4.0, 1.0));
+        }
+        let config = AnalysisConfig {
+            track_corners: vec![
+                TrackCorner::new("Hell Corner".to_string(), 0.1, 0.2),
+                TrackCorner::new("The Chase".to_string(), 0.7, 0.8),
+            ],
+            ..default_config()
+        };
+
+        let corners = extract_corners(&frames, &config);
+
+        let labels: Vec<(u32, Option<&str>)> = corners
+            .iter()
+            .map(|corner| (corner.corner_number, corner.name.as_deref()))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![(1, Some("Hell Corner")), (3, None), (2, Some("The Chase"))]
+        );
+    }
+
+    #[test]
+    fn test_corners_numbered_in_order_without_track_map() {
+        let mut corners = extract_corners(
+            &[
+                make_frame(0.0, 60.0, 0.0, 0.1, 0.0, 1.0),
+                make_frame(0.3, 45.0, 2.0, 0.12, 1.0, 0.0),
+                make_frame(0.0, 55.0, 0.0, 0.14, 2.0, 1.0),
+                make_frame(0.3, 45.0, 2.0, 0.16, 3.0, 0.0),
+                make_frame(0.0, 55.0, 0.0, 0.18, 4.0, 1.0),
+            ],
+            &default_config(),
+        );
+
+        let numbers: Vec<u32> = corners.iter().map(|corner| corner.corner_number).collect();
+        assert_eq!(numbers, vec![1, 2]);
+        assert!(corners.iter().all(|corner| corner.name.is_none()));
+
+        // Relabeling with a map replaces the sequential numbers
+        label_corners(
+            &mut corners,
+            &[TrackCorner::new("Turn 1".to_string(), 0.1, 0.13)],
+        );
+        assert_eq!(corners[0].name.as_deref(), Some("Turn 1"));
+        assert_eq!(corners[1].corner_number, 2);
+    }
 }
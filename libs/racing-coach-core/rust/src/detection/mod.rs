@@ -1,10 +1,14 @@
-//! Event detection for braking zones and corners.
+//! Event detection for braking zones, corners, pit stops, and gear shifts.
 
 mod braking;
 mod corner;
+mod pit;
+mod shifts;
 
-pub use braking::{extract_braking_zones, BrakingDetector};
-pub use corner::{extract_corners, CornerDetector};
+pub use braking::{braking_zones_iter, extract_braking_zones, BrakingDetector, BrakingZones};
+pub use corner::{extract_corners, label_corners, CornerDetector};
+pub use pit::{extract_pit_stops, PitDetector};
+pub use shifts::{extract_shifts, ShiftDetector};
 
 use crate::types::TelemetryFrame;
 
@@ -18,20 +22,169 @@ pub trait EventDetector {
 
     /// Process a single frame, potentially completing an event.
     ///
-    /// Returns `Some(builder)` when an event is completed (state transition
-    /// from active to idle). The caller should finalize the builder into
-    /// a result using the full frame slice.
-    fn process_frame(&mut self, frame: &TelemetryFrame, index: usize) -> Option<Self::Builder>;
+    /// Returns `Some((builder, end_idx))` when an event is completed (state
+    /// transition from active to idle), where `end_idx` is the index of the
+    /// event's last frame. The caller should finalize the builder into a
+    /// result using the full frame slice.
+    fn process_frame(
+        &mut self,
+        frame: &TelemetryFrame,
+        index: usize,
+    ) -> Option<(Self::Builder, usize)>;
 
     /// Finalize any in-progress event at the end of the sequence.
     ///
     /// Call this after processing all frames to handle events that
     /// extend to the end of the data (e.g., a braking zone that
     /// doesn't end before the lap ends).
-    fn finalize(&mut self) -> Option<Self::Builder>;
+    fn finalize(&mut self) -> Option<(Self::Builder, usize)>;
 
     /// Reset the detector to its initial state.
     ///
     /// Call this before processing a new lap to clear any accumulated state.
     fn reset(&mut self);
 }
+
+/// A detector supplied by the caller, run alongside the built-in ones.
+///
+/// [`extract_lap_metrics_with`](crate::pipeline::extract_lap_metrics_with)
+/// feeds it every frame of the lap in the same pass as the built-in
+/// detectors, then stores its result in [`LapMetrics::custom`] under its
+/// name.
+///
+/// [`LapMetrics::custom`]: crate::results::LapMetrics::custom
+pub trait CustomDetector {
+    /// Key the detector's result is stored under.
+    fn name(&self) -> &str;
+
+    /// Process a single frame.
+    fn process_frame(&mut self, frame: &TelemetryFrame, index: usize);
+
+    /// The detector's result for the frames processed since the last reset.
+    fn result(&self, frames: &[TelemetryFrame]) -> f64;
+
+    /// Reset the detector to its initial state.
+    ///
+    /// Called before each lap, so one detector can be reused across laps.
+    fn reset(&mut self);
+}
+
+/// Result of feeding a value to a [`HysteresisGate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Activity {
+    /// The value crossed the enter threshold, starting an event
+    Started,
+    /// The event continues on this frame
+    Continued,
+    /// The value dropped below the exit threshold, but the event may still
+    /// resume within the merge gap
+    Paused,
+    /// The event ended; its last active frame was before the pause
+    Ended,
+    /// No event in progress
+    Inactive,
+}
+
+/// Threshold state machine with hysteresis and gap merging.
+///
+/// An event starts when the value rises above `enter` and continues while it
+/// stays above `exit` (at most `enter`). After a drop below `exit`, the event
+/// resumes if the value rises above `enter` again within `merge_gap_frames`
+/// frames, so brief dips don't split one event into two.
+#[derive(Debug, Clone)]
+pub(crate) struct HysteresisGate {
+    enter: f64,
+    exit: f64,
+    merge_gap_frames: usize,
+    active: bool,
+    /// Frames since the value dropped below `exit` (0 while above it)
+    gap: usize,
+}
+
+impl HysteresisGate {
+    pub(crate) fn new(enter: f64, exit: f64, merge_gap_frames: usize) -> Self {
+        Self {
+            enter,
+            exit: exit.min(enter),
+            merge_gap_frames,
+            active: false,
+            gap: 0,
+        }
+    }
+
+    /// Whether an event is in progress (including a pause).
+    pub(crate) fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub(crate) fn update(&mut self, value: f64) -> Activity {
+        if !self.active {
+            if value > self.enter {
+                self.active = true;
+                self.gap = 0;
+                return Activity::Started;
+            }
+            return Activity::Inactive;
+        }
+
+        // Within a pause the event must re-cross the enter threshold
+        let threshold = if self.gap == 0 { self.exit } else { self.enter };
+        if value > threshold {
+            self.gap = 0;
+            return Activity::Continued;
+        }
+
+        self.gap += 1;
+        if self.gap > self.merge_gap_frames {
+            self.reset();
+            Activity::Ended
+        } else {
+            Activity::Paused
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.active = false;
+        self.gap = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gate_without_hysteresis() {
+        let mut gate = HysteresisGate::new(0.5, 0.5, 0);
+
+        assert_eq!(gate.update(0.1), Activity::Inactive);
+        assert_eq!(gate.update(0.6), Activity::Started);
+        assert_eq!(gate.update(0.7), Activity::Continued);
+        assert_eq!(gate.update(0.4), Activity::Ended);
+        assert!(!gate.is_active());
+    }
+
+    #[test]
+    fn test_gate_exit_threshold() {
+        let mut gate = HysteresisGate::new(0.5, 0.2, 0);
+
+        assert_eq!(gate.update(0.6), Activity::Started);
+        // Below enter but above exit keeps the event going
+        assert_eq!(gate.update(0.3), Activity::Continued);
+        assert_eq!(gate.update(0.1), Activity::Ended);
+        // Restarting needs the enter threshold again
+        assert_eq!(gate.update(0.3), Activity::Inactive);
+    }
+
+    #[test]
+    fn test_gate_merges_short_gaps() {
+        let mut gate = HysteresisGate::new(0.5, 0.5, 2);
+
+        assert_eq!(gate.update(0.6), Activity::Started);
+        assert_eq!(gate.update(0.1), Activity::Paused);
+        assert_eq!(gate.update(0.6), Activity::Continued);
+        assert_eq!(gate.update(0.1), Activity::Paused);
+        assert_eq!(gate.update(0.1), Activity::Paused);
+        assert_eq!(gate.update(0.1), Activity::Ended);
+    }
+}
@@ -0,0 +1,211 @@
+//! Pit road detection.
+
+use crate::results::{PitStop, PitStopBuilder};
+use crate::types::TelemetryFrame;
+
+use super::EventDetector;
+
+/// Speed below which the car is considered stationary in the pit box (m/s).
+const STOPPED_SPEED_THRESHOLD: f64 = 0.5;
+
+/// Detector for pit road visits.
+///
+/// Starts tracking when `on_pit_road` becomes true and completes a pit stop
+/// when it transitions back to false.
+#[derive(Debug, Default)]
+pub struct PitDetector {
+    builder: Option<PitStopBuilder>,
+    /// Index of the most recently processed frame
+    last_idx: usize,
+}
+
+impl PitDetector {
+    /// Create a new pit detector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Finalize a builder into a PitStop using the full frame slice.
+    pub fn finalize_builder(
+        &self,
+        builder: PitStopBuilder,
+        frames: &[TelemetryFrame],
+        end_idx: usize,
+    ) -> PitStop {
+        let exit_frame = &frames[end_idx.min(frames.len() - 1)];
+
+        PitStop::new(
+            builder.entry_distance,
+            exit_frame.lap_distance,
+            exit_frame.timestamp - builder.entry_timestamp,
+            builder.min_speed < STOPPED_SPEED_THRESHOLD,
+        )
+    }
+}
+
+impl EventDetector for PitDetector {
+    type Builder = PitStopBuilder;
+
+    fn process_frame(
+        &mut self,
+        frame: &TelemetryFrame,
+        index: usize,
+    ) -> Option<(Self::Builder, usize)> {
+        self.last_idx = index;
+
+        match (self.builder.as_mut(), frame.on_pit_road) {
+            (None, true) => {
+                // Entered pit road
+                self.builder = Some(PitStopBuilder::new(
+                    index,
+                    frame.lap_distance,
+                    frame.speed,
+                    frame.timestamp,
+                ));
+                None
+            }
+            (Some(builder), true) => {
+                builder.update(frame.speed);
+                None
+            }
+            // Left pit road at the previous frame - return completed builder
+            (Some(_), false) => self
+                .builder
+                .take()
+                .map(|builder| (builder, index.saturating_sub(1))),
+            (None, false) => None,
+        }
+    }
+
+    fn finalize(&mut self) -> Option<(Self::Builder, usize)> {
+        self.builder.take().map(|builder| (builder, self.last_idx))
+    }
+
+    fn reset(&mut self) {
+        self.builder = None;
+        self.last_idx = 0;
+    }
+}
+
+/// Extract all pit stops from telemetry frames.
+///
+/// A pit stop still in progress at the end of the frames is included,
+/// ending at the last frame.
+///
+/// # Arguments
+/// * `frames` - Slice of telemetry frames
+///
+/// # Returns
+/// * Vector of PitStop for each visit to pit road
+pub fn extract_pit_stops(frames: &[TelemetryFrame]) -> Vec<PitStop> {
+    if frames.is_empty() {
+        return vec![];
+    }
+
+    let mut detector = PitDetector::new();
+    let mut pending_builders: Vec<(PitStopBuilder, usize)> = Vec::new();
+
+    for (idx, frame) in frames.iter().enumerate() {
+        pending_builders.extend(detector.process_frame(frame, idx));
+    }
+
+    pending_builders.extend(detector.finalize());
+
+    pending_builders
+        .into_iter()
+        .map(|(builder, end_idx)| detector.finalize_builder(builder, frames, end_idx))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_frame(
+        on_pit_road: bool,
+        speed: f64,
+        lap_distance: f64,
+        timestamp: f64,
+    ) -> TelemetryFrame {
+        TelemetryFrame::new(
+            0.0,
+            0.0,
+            speed,
+            lap_distance,
+            0.0,
+            0.0,
+            0.0,
+            timestamp,
+            on_pit_road,
+        )
+    }
+
+    #[test]
+    fn test_no_pit_stops() {
+        let frames = vec![
+            make_frame(false, 50.0, 0.1, 0.0),
+            make_frame(false, 50.0, 0.2, 1.0),
+        ];
+
+        assert!(extract_pit_stops(&frames).is_empty());
+    }
+
+    #[test]
+    fn test_pit_stop_with_stop() {
+        let frames = vec![
+            make_frame(false, 50.0, 0.90, 0.0),
+            make_frame(true, 22.0, 0.92, 1.0),  // Pit entry
+            make_frame(true, 0.0, 0.95, 10.0),  // Stationary in box
+            make_frame(true, 22.0, 0.98, 30.0), // Pit exit
+            make_frame(false, 40.0, 0.99, 31.0),
+        ];
+
+        let stops = extract_pit_stops(&frames);
+        assert_eq!(stops.len(), 1);
+
+        let stop = &stops[0];
+        assert_eq!(stop.entry_distance, 0.92);
+        assert_eq!(stop.exit_distance, 0.98);
+        assert!((stop.time_on_pit_road - 29.0).abs() < 1e-10);
+        assert!(stop.stopped);
+    }
+
+    #[test]
+    fn test_drive_through_does_not_stop() {
+        let frames = vec![
+            make_frame(true, 22.0, 0.92, 1.0),
+            make_frame(true, 22.0, 0.95, 2.0),
+            make_frame(false, 40.0, 0.98, 3.0),
+        ];
+
+        let stops = extract_pit_stops(&frames);
+        assert_eq!(stops.len(), 1);
+        assert!(!stops[0].stopped);
+    }
+
+    #[test]
+    fn test_multiple_pit_visits() {
+        let frames = vec![
+            make_frame(true, 22.0, 0.10, 0.0),
+            make_frame(false, 40.0, 0.15, 1.0),
+            make_frame(false, 60.0, 0.50, 2.0),
+            make_frame(true, 22.0, 0.90, 3.0),
+            make_frame(false, 40.0, 0.95, 4.0),
+        ];
+
+        assert_eq!(extract_pit_stops(&frames).len(), 2);
+    }
+
+    #[test]
+    fn test_pit_stop_in_progress_at_end() {
+        let frames = vec![
+            make_frame(false, 50.0, 0.90, 0.0),
+            make_frame(true, 22.0, 0.95, 1.0),
+            make_frame(true, 0.0, 0.99, 5.0),
+        ];
+
+        let stops = extract_pit_stops(&frames);
+        assert_eq!(stops.len(), 1);
+        assert_eq!(stops[0].exit_distance, 0.99);
+    }
+}
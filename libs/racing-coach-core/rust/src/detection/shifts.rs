@@ -0,0 +1,222 @@
+//! Gear shift detection.
+
+use crate::results::{ShiftEvent, ShiftEventBuilder};
+use crate::types::TelemetryFrame;
+
+use super::EventDetector;
+
+/// Frames either side of a downshift searched for a throttle blip
+/// (a quarter of a second at 60 Hz).
+const BLIP_WINDOW_FRAMES: usize = 15;
+
+/// Throttle rise and fall (0-1) that counts as a blip rather than noise.
+const BLIP_THROTTLE_DELTA: f64 = 0.2;
+
+/// Detector for gear changes.
+///
+/// Emits one event per change in the `gear` channel. Downshifts are checked
+/// for a throttle blip (a brief rise and fall of the throttle) around the
+/// change, which indicates the driver rev-matched.
+#[derive(Debug, Default)]
+pub struct ShiftDetector {
+    /// Gear and RPM of the previous frame
+    last: Option<(i32, f64)>,
+}
+
+impl ShiftDetector {
+    /// Create a new shift detector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Finalize a builder into a ShiftEvent using the full frame slice.
+    pub fn finalize_builder(
+        &self,
+        builder: ShiftEventBuilder,
+        frames: &[TelemetryFrame],
+        _end_idx: usize,
+    ) -> ShiftEvent {
+        let throttle_blip = builder.to_gear < builder.from_gear && {
+            let start = builder.shift_idx.saturating_sub(BLIP_WINDOW_FRAMES);
+            let end = (builder.shift_idx + BLIP_WINDOW_FRAMES).min(frames.len() - 1);
+            has_throttle_spike(frames[start..=end].iter().map(|frame| frame.throttle))
+        };
+
+        ShiftEvent::new(
+            builder.from_gear,
+            builder.to_gear,
+            builder.rpm,
+            builder.distance,
+            throttle_blip,
+        )
+    }
+}
+
+/// Whether the throttle rises and falls back by at least
+/// [`BLIP_THROTTLE_DELTA`], in that order.
+fn has_throttle_spike(throttle: impl Iterator<Item = f64>) -> bool {
+    let mut low = f64::INFINITY;
+    let mut peak: Option<f64> = None;
+
+    for value in throttle {
+        match peak {
+            None => {
+                low = low.min(value);
+                if value - low >= BLIP_THROTTLE_DELTA {
+                    peak = Some(value);
+                }
+            }
+            Some(high) => {
+                if high - value >= BLIP_THROTTLE_DELTA {
+                    return true;
+                }
+                peak = Some(high.max(value));
+            }
+        }
+    }
+    false
+}
+
+impl EventDetector for ShiftDetector {
+    type Builder = ShiftEventBuilder;
+
+    fn process_frame(
+        &mut self,
+        frame: &TelemetryFrame,
+        index: usize,
+    ) -> Option<(Self::Builder, usize)> {
+        let shift = match self.last {
+            Some((gear, rpm)) if gear != frame.gear => Some((
+                ShiftEventBuilder::new(index, gear, frame.gear, rpm, frame.lap_distance),
+                index,
+            )),
+            _ => None,
+        };
+        self.last = Some((frame.gear, frame.rpm));
+        shift
+    }
+
+    fn finalize(&mut self) -> Option<(Self::Builder, usize)> {
+        // Shifts complete on the frame they happen
+        None
+    }
+
+    fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+/// Extract all gear changes from telemetry frames.
+///
+/// # Arguments
+/// * `frames` - Slice of telemetry frames
+///
+/// # Returns
+/// * Vector of ShiftEvent for each change in gear
+pub fn extract_shifts(frames: &[TelemetryFrame]) -> Vec<ShiftEvent> {
+    let mut detector = ShiftDetector::new();
+    let pending_builders: Vec<(ShiftEventBuilder, usize)> = frames
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, frame)| detector.process_frame(frame, idx))
+        .collect();
+
+    pending_builders
+        .into_iter()
+        .map(|(builder, end_idx)| detector.finalize_builder(builder, frames, end_idx))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_frame(gear: i32, rpm: f64, throttle: f64, brake: f64, idx: usize) -> TelemetryFrame {
+        TelemetryFrame::new(
+            brake,
+            throttle,
+            50.0,
+            idx as f64 * 0.001,
+            0.0,
+            0.0,
+            0.0,
+            idx as f64 / 60.0,
+            false,
+        )
+        .with_gear(gear)
+        .with_rpm(rpm)
+    }
+
+    /// Braking from 4th into 3rd, with `blip` as the throttle during the
+    /// three frames the clutch is in.
+    fn downshift(blip: f64) -> Vec<TelemetryFrame> {
+        (0..40)
+            .map(|idx| {
+                let (gear, rpm) = if idx < 20 { (4, 6200.0) } else { (3, 7100.0) };
+                let throttle = if (18..21).contains(&idx) { blip } else { 0.0 };
+                make_frame(gear, rpm, throttle, 0.8, idx)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_downshift_with_blip() {
+        let shifts = extract_shifts(&downshift(0.6));
+
+        assert_eq!(
+            shifts,
+            vec![ShiftEvent::new(4, 3, 6200.0, 20.0 * 0.001, true)]
+        );
+    }
+
+    #[test]
+    fn test_downshift_without_blip() {
+        let shifts = extract_shifts(&downshift(0.0));
+
+        assert_eq!(shifts.len(), 1);
+        assert!(shifts[0].is_downshift());
+        assert!(!shifts[0].throttle_blip);
+    }
+
+    #[test]
+    fn test_throttle_reapplied_is_not_a_blip() {
+        // Throttle rises after the downshift but never falls back
+        let frames: Vec<_> = downshift(0.0)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, mut frame)| {
+                frame.throttle = if idx >= 22 { 0.5 } else { 0.0 };
+                frame
+            })
+            .collect();
+
+        assert!(!extract_shifts(&frames)[0].throttle_blip);
+    }
+
+    #[test]
+    fn test_upshifts_are_never_blips() {
+        let frames: Vec<_> = (0..10)
+            .map(|idx| {
+                let gear = if idx < 5 { 2 } else { 3 };
+                let throttle = if idx == 5 { 0.3 } else { 1.0 };
+                make_frame(gear, 7800.0, throttle, 0.0, idx)
+            })
+            .collect();
+
+        let shifts = extract_shifts(&frames);
+
+        assert_eq!(shifts.len(), 1);
+        assert_eq!((shifts[0].from_gear, shifts[0].to_gear), (2, 3));
+        assert!(!shifts[0].throttle_blip);
+    }
+
+    #[test]
+    fn test_constant_gear_has_no_shifts() {
+        let frames: Vec<_> = (0..5)
+            .map(|idx| make_frame(3, 5000.0, 1.0, 0.0, idx))
+            .collect();
+
+        assert!(extract_shifts(&frames).is_empty());
+        assert!(extract_shifts(&[]).is_empty());
+    }
+}
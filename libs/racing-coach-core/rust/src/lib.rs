@@ -5,11 +5,14 @@
 //!
 //! # Modules
 //!
-//! - [`types`] - Input types (TelemetryFrame, AnalysisConfig)
-//! - [`results`] - Output types (BrakingMetrics, CornerMetrics, LapMetrics)
-//! - [`detection`] - Event detection (braking zones, corners)
-//! - [`analysis`] - Analysis functions (deceleration, trail braking)
-//! - [`pipeline`] - Unified metrics extraction
+//! - [`types`] - Input types (TelemetryFrame, AnalysisConfig, TrackCorner)
+//! - [`results`] - Output types (BrakingMetrics, CornerMetrics, LapMetrics, PitStop,
+//!   SessionMetrics, ShiftEvent)
+//! - [`detection`] - Event detection (braking zones, corners, pit stops, gear shifts)
+//! - [`analysis`] - Analysis functions (deceleration, trail braking, tire temperatures,
+//!   handling balance, threshold calibration, input smoothness, fuel and pedal usage,
+//!   slide corrections, ghost laps)
+//! - [`pipeline`] - Unified metrics extraction and session aggregation
 //! - [`utils`] - Utility functions
 
 use pyo3::prelude::*;
@@ -21,11 +24,27 @@ pub mod results;
 pub mod types;
 pub mod utils;
 
+#[cfg(test)]
+mod test_utils;
+
 // Re-export commonly used items
-pub use detection::{extract_braking_zones, extract_corners};
-pub use pipeline::extract_lap_metrics;
-pub use results::{BrakingMetrics, CornerMetrics, LapMetrics};
-pub use types::{AnalysisConfig, TelemetryFrame};
+pub use analysis::{
+    calibrate_thresholds, compute_fuel_usage, compute_pedal_usage, compute_sector_times,
+    compute_smoothness, compute_time_delta, compute_tire_analysis, detect_corrections,
+    estimate_balance, export_ghost, resample_by_distance, CornerBalance, CorrectionEvent,
+    DeltaPoint, FuelUsage, GhostLap, MiniSectorTimer, PedalUsage, SmoothnessScore, SpeedStatistics,
+    TireAnalysis, TireCornerTemps, TrackConditions, WetnessTransition,
+};
+pub use detection::{
+    extract_braking_zones, extract_corners, extract_pit_stops, extract_shifts, CustomDetector,
+};
+pub use pipeline::{aggregate_session, extract_lap_metrics, extract_lap_metrics_with};
+pub use results::{
+    BrakingDelta, BrakingMetrics, CornerDelta, CornerMetrics, LapMetrics, LapMetricsDiff, PitStop,
+    SessionMetrics, ShiftEvent, METRICS_SCHEMA_VERSION,
+};
+pub use types::{AnalysisConfig, TelemetryFrame, TireTemps, TrackCorner, PRESET_NAMES};
+pub use utils::{exponential_smoothing, moving_average};
 
 // ============================================================================
 // Python-facing wrapper functions
@@ -98,6 +117,247 @@ fn py_extract_corners(
     Ok(extract_corners(&frames, &config))
 }
 
+/// Extract pit stops from telemetry frames.
+///
+/// # Arguments
+/// * `frames` - List of TelemetryFrame objects
+///
+/// # Returns
+/// * List of PitStop for each visit to pit road
+#[pyfunction]
+fn py_extract_pit_stops(frames: Vec<TelemetryFrame>) -> PyResult<Vec<PitStop>> {
+    Ok(extract_pit_stops(&frames))
+}
+
+/// Extract gear changes from telemetry frames.
+///
+/// # Arguments
+/// * `frames` - List of TelemetryFrame objects with gear and RPM
+///
+/// # Returns
+/// * List of ShiftEvent for each change in gear, with downshifts flagged when
+///   the throttle was blipped
+#[pyfunction]
+fn py_extract_shifts(frames: Vec<TelemetryFrame>) -> PyResult<Vec<ShiftEvent>> {
+    Ok(extract_shifts(&frames))
+}
+
+/// Aggregate per-lap metrics into session statistics.
+///
+/// # Arguments
+/// * `laps` - LapMetrics for every lap of the session
+/// * `frames` - Optional session frames to record track conditions from
+///
+/// # Returns
+/// * SessionMetrics with best/average lap, lap time spread, and best sector
+///   times over the valid laps (timed, no pit road), plus track conditions
+///   when frames are given
+#[pyfunction]
+#[pyo3(signature = (laps, frames=None))]
+fn py_aggregate_session(
+    laps: Vec<LapMetrics>,
+    frames: Option<Vec<TelemetryFrame>>,
+) -> PyResult<SessionMetrics> {
+    let session = aggregate_session(&laps);
+    Ok(match frames {
+        Some(frames) => session.with_track_conditions(TrackConditions::from_frames(&frames)),
+        None => session,
+    })
+}
+
+/// Compute where the comparison lap gained or lost time versus a reference lap.
+///
+/// # Arguments
+/// * `reference` - Frames of the reference lap (e.g. the best lap)
+/// * `comparison` - Frames of the lap to compare
+/// * `samples` - Number of evenly spaced distance samples (default: 200)
+///
+/// # Returns
+/// * List of (distance_pct, delta_seconds) tuples over the distance range both
+///   laps cover; positive deltas mean the comparison lap is slower
+#[pyfunction]
+#[pyo3(signature = (reference, comparison, samples=analysis::DEFAULT_DELTA_SAMPLES))]
+fn py_compute_time_delta(
+    reference: Vec<TelemetryFrame>,
+    comparison: Vec<TelemetryFrame>,
+    samples: usize,
+) -> PyResult<Vec<(f64, f64)>> {
+    Ok(
+        analysis::compute_time_delta_with_samples(&reference, &comparison, samples)
+            .into_iter()
+            .map(|point| (point.distance, point.delta))
+            .collect(),
+    )
+}
+
+/// Average tire temperatures across the tread for each corner.
+///
+/// # Arguments
+/// * `frames` - List of TelemetryFrame objects with tire temperatures
+///
+/// # Returns
+/// * TireAnalysis with inner/middle/outer averages and inner-to-outer spread
+///   per tire; frames without tire temperatures are skipped
+#[pyfunction]
+fn py_compute_tire_analysis(frames: Vec<TelemetryFrame>) -> PyResult<TireAnalysis> {
+    Ok(compute_tire_analysis(&frames))
+}
+
+/// Estimate the oversteer/understeer balance of each corner.
+///
+/// # Arguments
+/// * `frames` - List of TelemetryFrame objects with yaw rate
+/// * `config` - Optional AnalysisConfig (uses defaults if not provided)
+///
+/// # Returns
+/// * List of CornerBalance for each detected corner; positive balance means
+///   oversteer, negative understeer
+#[pyfunction]
+#[pyo3(signature = (frames, config=None))]
+fn py_estimate_balance(
+    frames: Vec<TelemetryFrame>,
+    config: Option<AnalysisConfig>,
+) -> PyResult<Vec<CornerBalance>> {
+    let config = config.unwrap_or_default();
+    Ok(estimate_balance(&frames, &config))
+}
+
+/// Derive detection thresholds from the input distributions of a lap.
+///
+/// # Arguments
+/// * `frames` - List of TelemetryFrame objects, ideally a full lap
+///
+/// # Returns
+/// * AnalysisConfig with steering and brake thresholds scaled to the lap's
+///   typical peaks, for use in low-grip or otherwise unusual conditions
+#[pyfunction]
+fn py_calibrate_thresholds(frames: Vec<TelemetryFrame>) -> PyResult<AnalysisConfig> {
+    Ok(calibrate_thresholds(&frames))
+}
+
+/// Score how smoothly the throttle, brake and steering were worked.
+///
+/// # Arguments
+/// * `frames` - List of TelemetryFrame objects, typically one lap
+///
+/// # Returns
+/// * SmoothnessScore with 0-100 scores per channel (higher is smoother),
+///   based on the second derivative of each input over time
+#[pyfunction]
+fn py_compute_smoothness(frames: Vec<TelemetryFrame>) -> PyResult<SmoothnessScore> {
+    Ok(compute_smoothness(&frames))
+}
+
+/// Measure the fuel burned over a lap and the range left.
+///
+/// # Arguments
+/// * `frames` - List of TelemetryFrame objects, typically one lap
+///
+/// # Returns
+/// * FuelUsage with the burn (refueling excluded) and projected laps
+///   remaining, or None if the frames carry no fuel level
+#[pyfunction]
+fn py_compute_fuel_usage(frames: Vec<TelemetryFrame>) -> PyResult<Option<FuelUsage>> {
+    Ok(compute_fuel_usage(&frames))
+}
+
+/// Measure the share of a lap spent at full throttle, at full brake and coasting.
+///
+/// # Arguments
+/// * `frames` - List of TelemetryFrame objects, typically one lap
+/// * `config` - Optional AnalysisConfig with the pedal thresholds (uses
+///   defaults if not provided)
+///
+/// # Returns
+/// * PedalUsage with each state as a percentage of the lap time, weighted
+///   by the time between frames, or None if the frames span no time
+#[pyfunction]
+#[pyo3(signature = (frames, config=None))]
+fn py_compute_pedal_usage(
+    frames: Vec<TelemetryFrame>,
+    config: Option<AnalysisConfig>,
+) -> PyResult<Option<PedalUsage>> {
+    let config = config.unwrap_or_default();
+    Ok(compute_pedal_usage(&frames, &config))
+}
+
+/// Find slide corrections: rapid steering reversals through zero while the car rotates.
+///
+/// # Arguments
+/// * `frames` - List of TelemetryFrame objects with steering angle and yaw rate
+/// * `config` - Optional AnalysisConfig with the correction thresholds (uses
+///   defaults if not provided)
+///
+/// # Returns
+/// * List of CorrectionEvent in lap order
+#[pyfunction]
+#[pyo3(signature = (frames, config=None))]
+fn py_detect_corrections(
+    frames: Vec<TelemetryFrame>,
+    config: Option<AnalysisConfig>,
+) -> PyResult<Vec<CorrectionEvent>> {
+    let config = config.unwrap_or_default();
+    Ok(detect_corrections(&frames, &config))
+}
+
+/// Resample a lap onto evenly spaced lap distances for point-by-point comparison.
+///
+/// # Arguments
+/// * `frames` - List of TelemetryFrame objects, typically one lap
+/// * `num_points` - Number of distances from 0.0 to 1.0 to sample at
+///
+/// # Returns
+/// * List of `num_points` TelemetryFrame objects with every channel linearly
+///   interpolated at its distance; frames are ordered by distance first, so
+///   backwards glitches don't fold the trace over itself
+#[pyfunction]
+fn py_resample_by_distance(
+    frames: Vec<TelemetryFrame>,
+    num_points: usize,
+) -> PyResult<Vec<TelemetryFrame>> {
+    Ok(resample_by_distance(&frames, num_points))
+}
+
+/// Export a lap as a compact ghost for overlaying in external tools.
+///
+/// # Arguments
+/// * `frames` - List of TelemetryFrame objects, typically one lap
+/// * `num_points` - Number of distances from 0.0 to 1.0 to sample at
+///
+/// # Returns
+/// * GhostLap with distance, speed, throttle, brake and steering arrays of
+///   `num_points` values each, resampled as in `py_resample_by_distance`
+#[pyfunction]
+fn py_export_ghost(frames: Vec<TelemetryFrame>, num_points: usize) -> PyResult<GhostLap> {
+    Ok(export_ghost(&frames, num_points))
+}
+
+/// Smooth values with a centered moving average.
+///
+/// # Arguments
+/// * `values` - List of samples
+/// * `window` - Number of samples to average (0 or 1 leaves values unchanged)
+///
+/// # Returns
+/// * Smoothed values, the same length as the input
+#[pyfunction]
+fn py_moving_average(values: Vec<f64>, window: usize) -> PyResult<Vec<f64>> {
+    Ok(moving_average(&values, window))
+}
+
+/// Smooth values with an exponential moving average.
+///
+/// # Arguments
+/// * `values` - List of samples
+/// * `alpha` - Weight of each new sample (0.0-1.0, smaller smooths more)
+///
+/// # Returns
+/// * Smoothed values, the same length as the input
+#[pyfunction]
+fn py_exponential_smoothing(values: Vec<f64>, alpha: f64) -> PyResult<Vec<f64>> {
+    Ok(exponential_smoothing(&values, alpha))
+}
+
 /// A simple hello world function to verify Rust + PyO3 integration works.
 ///
 /// Call this from Python to verify the Rust extension is properly installed:
@@ -149,16 +409,51 @@ fn _rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Types
     m.add_class::<TelemetryFrame>()?;
     m.add_class::<AnalysisConfig>()?;
+    m.add_class::<TrackCorner>()?;
 
     // Results
+    m.add_class::<BrakingDelta>()?;
     m.add_class::<BrakingMetrics>()?;
+    m.add_class::<CornerDelta>()?;
     m.add_class::<CornerMetrics>()?;
+    m.add_class::<CornerBalance>()?;
     m.add_class::<LapMetrics>()?;
+    m.add_class::<LapMetricsDiff>()?;
+    m.add_class::<PitStop>()?;
+    m.add_class::<SessionMetrics>()?;
+    m.add_class::<ShiftEvent>()?;
+    m.add_class::<SmoothnessScore>()?;
+    m.add_class::<FuelUsage>()?;
+    m.add_class::<PedalUsage>()?;
+    m.add_class::<CorrectionEvent>()?;
+    m.add_class::<GhostLap>()?;
+    m.add_class::<SpeedStatistics>()?;
+    m.add_class::<TireAnalysis>()?;
+    m.add_class::<TireCornerTemps>()?;
+    m.add_class::<TrackConditions>()?;
+    m.add_class::<WetnessTransition>()?;
+    m.add("METRICS_SCHEMA_VERSION", METRICS_SCHEMA_VERSION)?;
+    m.add("ANALYSIS_PRESETS", PRESET_NAMES.to_vec())?;
 
     // Functions
     m.add_function(wrap_pyfunction!(py_extract_lap_metrics, m)?)?;
     m.add_function(wrap_pyfunction!(py_extract_braking_zones, m)?)?;
     m.add_function(wrap_pyfunction!(py_extract_corners, m)?)?;
+    m.add_function(wrap_pyfunction!(py_extract_pit_stops, m)?)?;
+    m.add_function(wrap_pyfunction!(py_extract_shifts, m)?)?;
+    m.add_function(wrap_pyfunction!(py_aggregate_session, m)?)?;
+    m.add_function(wrap_pyfunction!(py_compute_time_delta, m)?)?;
+    m.add_function(wrap_pyfunction!(py_compute_tire_analysis, m)?)?;
+    m.add_function(wrap_pyfunction!(py_estimate_balance, m)?)?;
+    m.add_function(wrap_pyfunction!(py_calibrate_thresholds, m)?)?;
+    m.add_function(wrap_pyfunction!(py_compute_smoothness, m)?)?;
+    m.add_function(wrap_pyfunction!(py_compute_fuel_usage, m)?)?;
+    m.add_function(wrap_pyfunction!(py_compute_pedal_usage, m)?)?;
+    m.add_function(wrap_pyfunction!(py_detect_corrections, m)?)?;
+    m.add_function(wrap_pyfunction!(py_resample_by_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(py_export_ghost, m)?)?;
+    m.add_function(wrap_pyfunction!(py_moving_average, m)?)?;
+    m.add_function(wrap_pyfunction!(py_exponential_smoothing, m)?)?;
     m.add_function(wrap_pyfunction!(hello_from_rust, m)?)?;
     m.add_function(wrap_pyfunction!(compute_speed_stats, m)?)?;
 
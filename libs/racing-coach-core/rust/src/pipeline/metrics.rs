@@ -1,15 +1,26 @@
 //! Unified single-pass metrics extraction.
 
-use crate::analysis::SpeedStatistics;
-use crate::detection::{BrakingDetector, CornerDetector, EventDetector};
-use crate::results::{BrakingMetrics, BrakingMetricsBuilder, CornerMetrics, CornerMetricsBuilder, LapMetrics};
+use std::collections::BTreeMap;
+
+use crate::analysis::{
+    compute_fuel_usage, compute_pedal_usage, compute_smoothness, detect_corrections,
+    MiniSectorTimer, SpeedStatistics, DEFAULT_SECTOR_COUNT,
+};
+use crate::detection::{
+    label_corners, BrakingDetector, CornerDetector, CustomDetector, EventDetector, ShiftDetector,
+};
+use crate::results::{
+    BrakingMetrics, BrakingMetricsBuilder, CornerMetrics, CornerMetricsBuilder, LapMetrics,
+    ShiftEvent, ShiftEventBuilder,
+};
 use crate::types::{AnalysisConfig, TelemetryFrame};
+use crate::utils::moving_average;
 
 /// Extract comprehensive lap metrics in a single pass through the telemetry data.
 ///
-/// This function combines braking zone and corner detection into a single
+/// This function combines braking zone, corner and shift detection into a single
 /// iteration over the frame data, maximizing cache efficiency and minimizing
-/// total iterations. Both detection algorithms run in parallel on each frame.
+/// total iterations. All detectors run in parallel on each frame.
 ///
 /// # Arguments
 /// * `frames` - Slice of telemetry frames for the lap
@@ -18,7 +29,9 @@ use crate::types::{AnalysisConfig, TelemetryFrame};
 /// * `lap_time` - Optional lap time in seconds
 ///
 /// # Returns
-/// * `LapMetrics` containing all detected braking zones, corners, and statistics
+/// * `LapMetrics` containing all detected braking zones, corners, gear shifts,
+///   sector times, input smoothness, fuel and pedal usage, slide corrections,
+///   and statistics
 ///
 /// # Performance
 /// This function is O(n) where n is the number of frames, with minimal memory
@@ -29,46 +42,80 @@ pub fn extract_lap_metrics(
     lap_number: i32,
     lap_time: Option<f64>,
 ) -> LapMetrics {
+    extract_lap_metrics_with(frames, config, lap_number, lap_time, &mut [])
+}
+
+/// Like [`extract_lap_metrics`], also running `detectors` in the same pass.
+///
+/// Each detector is reset, fed the frames the built-in detectors see
+/// (smoothed, without those below the minimum analysis speed), and its
+/// result stored in [`LapMetrics::custom`] under its name. A later detector
+/// with the same name replaces an earlier one's result.
+pub fn extract_lap_metrics_with(
+    frames: &[TelemetryFrame],
+    config: &AnalysisConfig,
+    lap_number: i32,
+    lap_time: Option<f64>,
+    detectors: &mut [Box<dyn CustomDetector>],
+) -> LapMetrics {
+    for detector in detectors.iter_mut() {
+        detector.reset();
+    }
+
+    // Leave out frames too slow to analyze, e.g. parked in the garage
+    let frames = config.analysis_frames(frames);
+    let frames = &frames[..];
     if frames.is_empty() {
-        return LapMetrics::from_detection(lap_number, lap_time, vec![], vec![], 0.0, 0.0);
+        return LapMetrics::from_detection(lap_number, lap_time, vec![], vec![], 0.0, 0.0, false)
+            .with_custom(custom_results(detectors, frames));
     }
 
+    // Score the driver's inputs as recorded, before any smoothing
+    let smoothness = compute_smoothness(frames);
+    let total_corrections = detect_corrections(frames, config).len();
+
+    // Smooth noisy channels so single-frame spikes don't split events
+    let smoothed;
+    let frames = if config.smoothing_window > 1 {
+        smoothed = smooth_frames(frames, config.smoothing_window);
+        &smoothed[..]
+    } else {
+        frames
+    };
+
     // Initialize detectors
-    let mut braking_detector = BrakingDetector::new(config);
-    let mut corner_detector = CornerDetector::new(config);
+    let mut braking_detector = BrakingDetector::for_frames(config, frames);
+    let mut corner_detector = CornerDetector::for_frames(config, frames);
+    let mut shift_detector = ShiftDetector::new();
 
     // Track lap-wide statistics
     let mut speed_stats = SpeedStatistics::new();
+    let mut contains_pit = false;
 
     // Collect builders that need finalization
     let mut pending_braking: Vec<(BrakingMetricsBuilder, usize)> = Vec::with_capacity(20);
     let mut pending_corners: Vec<(CornerMetricsBuilder, usize)> = Vec::with_capacity(25);
+    let mut pending_shifts: Vec<(ShiftEventBuilder, usize)> = Vec::with_capacity(40);
 
     // Single pass through all frames
     for (idx, frame) in frames.iter().enumerate() {
         // Update lap-wide statistics
         speed_stats.update(frame.speed);
+        contains_pit |= frame.on_pit_road;
 
-        // Process braking detection
-        if let Some(builder) = braking_detector.process_frame(frame, idx) {
-            // Braking zone ended - the end index is the previous frame
-            pending_braking.push((builder, idx.saturating_sub(1)));
-        }
-
-        // Process corner detection
-        if let Some(builder) = corner_detector.process_frame(frame, idx) {
-            // Corner ended - the end index is the previous frame
-            pending_corners.push((builder, idx.saturating_sub(1)));
+        // Process braking, corner and shift detection
+        pending_braking.extend(braking_detector.process_frame(frame, idx));
+        pending_corners.extend(corner_detector.process_frame(frame, idx));
+        pending_shifts.extend(shift_detector.process_frame(frame, idx));
+        for detector in detectors.iter_mut() {
+            detector.process_frame(frame, idx);
         }
     }
 
     // Finalize any in-progress events at end of lap
-    if let Some(builder) = braking_detector.finalize() {
-        pending_braking.push((builder, frames.len() - 1));
-    }
-    if let Some(builder) = corner_detector.finalize() {
-        pending_corners.push((builder, frames.len() - 1));
-    }
+    pending_braking.extend(braking_detector.finalize());
+    pending_corners.extend(corner_detector.finalize());
+    pending_shifts.extend(shift_detector.finalize());
 
     // Convert builders to final metrics
     let braking_zones: Vec<BrakingMetrics> = pending_braking
@@ -76,19 +123,82 @@ pub fn extract_lap_metrics(
         .map(|(builder, end_idx)| braking_detector.finalize_builder(builder, frames, end_idx))
         .collect();
 
-    let corners: Vec<CornerMetrics> = pending_corners
+    let mut corners: Vec<CornerMetrics> = pending_corners
         .into_iter()
+        .flat_map(|(builder, end_idx)| corner_detector.split_apexes(builder, frames, end_idx))
         .map(|(builder, end_idx)| corner_detector.finalize_builder(builder, frames, end_idx))
         .collect();
+    label_corners(&mut corners, &config.track_corners);
 
-    LapMetrics::from_detection(
+    let shifts: Vec<ShiftEvent> = pending_shifts
+        .into_iter()
+        .map(|(builder, end_idx)| shift_detector.finalize_builder(builder, frames, end_idx))
+        .collect();
+
+    let metrics = LapMetrics::from_detection(
         lap_number,
         lap_time,
         braking_zones,
         corners,
         speed_stats.max(),
         speed_stats.min(),
+        contains_pit,
     )
+    .with_sector_times(sector_timer(config).sector_times(frames))
+    .with_shifts(shifts)
+    .with_smoothness(smoothness)
+    .with_total_corrections(total_corrections)
+    .with_custom(custom_results(detectors, frames));
+    let metrics = match compute_fuel_usage(frames) {
+        Some(fuel) => metrics.with_fuel(fuel),
+        None => metrics,
+    };
+    match compute_pedal_usage(frames, config) {
+        Some(pedal_usage) => metrics.with_pedal_usage(pedal_usage),
+        None => metrics,
+    }
+}
+
+/// The configured mini-sectors, or equal sectors when none are configured.
+fn sector_timer(config: &AnalysisConfig) -> MiniSectorTimer {
+    if config.sector_markers.is_empty() {
+        MiniSectorTimer::evenly_spaced(DEFAULT_SECTOR_COUNT)
+    } else {
+        MiniSectorTimer::new(&config.sector_markers)
+    }
+}
+
+/// Each detector's result over `frames`, by name.
+fn custom_results(
+    detectors: &[Box<dyn CustomDetector>],
+    frames: &[TelemetryFrame],
+) -> BTreeMap<String, f64> {
+    detectors
+        .iter()
+        .map(|detector| (detector.name().to_string(), detector.result(frames)))
+        .collect()
+}
+
+/// Copy `frames` with steering angle, steering percent and lateral
+/// acceleration smoothed by a centered moving average of `window` frames.
+fn smooth_frames(frames: &[TelemetryFrame], window: usize) -> Vec<TelemetryFrame> {
+    let steering: Vec<f64> = frames.iter().map(|f| f.steering_angle).collect();
+    let steering_pct: Vec<f64> = frames.iter().map(|f| f.steering_pct).collect();
+    let lateral: Vec<f64> = frames.iter().map(|f| f.lateral_acceleration).collect();
+    let steering = moving_average(&steering, window);
+    let steering_pct = moving_average(&steering_pct, window);
+    let lateral = moving_average(&lateral, window);
+
+    frames
+        .iter()
+        .zip(steering.into_iter().zip(steering_pct).zip(lateral))
+        .map(|(frame, ((steering, pct), lateral))| TelemetryFrame {
+            steering_angle: steering,
+            steering_pct: pct,
+            lateral_acceleration: lateral,
+            ..*frame
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -113,6 +223,7 @@ mod tests {
             lateral_g,
             0.0,
             timestamp,
+            false,
         )
     }
 
@@ -148,6 +259,32 @@ mod tests {
         assert_eq!(metrics.min_speed, 30.0);
     }
 
+    #[test]
+    fn test_stationary_frames_below_min_analysis_speed_are_ignored() {
+        // Parked in the garage holding the brake, then a lap at 30-80 m/s
+        let mut frames: Vec<TelemetryFrame> = (0..60)
+            .map(|i| make_frame(1.0, 0.0, 0.0, 0.0, 0.0, 0.0, i as f64 / 60.0))
+            .collect();
+        frames.extend((0..50).map(|i| {
+            let t = 1.0 + i as f64 / 60.0;
+            make_frame(0.0, 1.0, 30.0 + i as f64, i as f64 * 0.02, 0.0, 0.0, t)
+        }));
+
+        let all = extract_lap_metrics(&frames, &default_config(), 1, None);
+        assert_eq!(all.min_speed, 0.0);
+        assert_eq!(all.braking_zones.len(), 1);
+
+        let config = AnalysisConfig {
+            min_analysis_speed: 1.0,
+            ..default_config()
+        };
+        let moving = extract_lap_metrics(&frames, &config, 1, None);
+        assert_eq!(moving.min_speed, 30.0);
+        assert_eq!(moving.max_speed, 79.0);
+        assert!(moving.braking_zones.is_empty());
+        assert!(crate::extract_braking_zones(&frames, &config).is_empty());
+    }
+
     #[test]
     fn test_combined_braking_and_corner() {
         // Simulate a corner with braking zone
@@ -229,4 +366,190 @@ mod tests {
         assert_eq!(metrics.total_corners, 2);
         assert_eq!(metrics.average_corner_speed, 50.0); // (45 + 55) / 2
     }
+
+    #[test]
+    fn test_contains_pit() {
+        let mut frames = vec![
+            make_frame(0.0, 0.5, 50.0, 0.90, 0.0, 0.0, 0.0),
+            make_frame(0.0, 0.5, 20.0, 0.95, 0.0, 0.0, 1.0),
+        ];
+
+        let metrics = extract_lap_metrics(&frames, &default_config(), 1, None);
+        assert!(!metrics.contains_pit);
+
+        frames[1].on_pit_road = true;
+        let metrics = extract_lap_metrics(&frames, &default_config(), 1, None);
+        assert!(metrics.contains_pit);
+    }
+
+    #[test]
+    fn test_shifts_are_included() {
+        let gears = [
+            (3, 6000.0),
+            (3, 7500.0),
+            (4, 5800.0),
+            (4, 6400.0),
+            (3, 7200.0),
+        ];
+        let frames: Vec<_> = gears
+            .iter()
+            .enumerate()
+            .map(|(idx, &(gear, rpm))| {
+                make_frame(0.0, 1.0, 60.0, idx as f64 * 0.1, 0.0, 0.0, idx as f64)
+                    .with_gear(gear)
+                    .with_rpm(rpm)
+            })
+            .collect();
+
+        let metrics = extract_lap_metrics(&frames, &default_config(), 1, None);
+
+        assert_eq!(metrics.shifts.len(), 2);
+        assert_eq!(metrics.shifts[0].from_gear, 3);
+        assert_eq!(metrics.shifts[0].to_gear, 4);
+        assert_eq!(metrics.shifts[0].rpm, 7500.0);
+        assert!(metrics.shifts[1].is_downshift());
+    }
+
+    #[test]
+    fn test_single_pass_matches_separate_extractors() {
+        use crate::detection::{extract_braking_zones, extract_corners, extract_shifts};
+
+        // Repeating straight, braking zone with a downshift, corner, exit
+        let frames: Vec<_> = (0..400)
+            .map(|idx| {
+                let (brake, throttle, steering, lateral_g, gear) = match idx % 40 {
+                    0..=19 => (0.0, 1.0, 0.0, 0.0, 5),
+                    20..=25 => (0.9, 0.0, 0.0, 0.0, 4),
+                    26..=33 => (0.2, 0.3, 0.4, 2.5, 3),
+                    _ => (0.0, 1.0, 0.05, 0.5, 4),
+                };
+                let speed = 70.0 - 25.0 * (brake + steering);
+                make_frame(
+                    brake,
+                    throttle,
+                    speed,
+                    idx as f64 / 400.0,
+                    steering,
+                    lateral_g,
+                    idx as f64 / 60.0,
+                )
+                .with_gear(gear)
+                .with_rpm(4000.0 + 1000.0 * gear as f64)
+            })
+            .collect();
+        let config = default_config();
+
+        let metrics = extract_lap_metrics(&frames, &config, 1, None);
+
+        assert_eq!(metrics.braking_zones.len(), 10);
+        assert_eq!(metrics.corners.len(), 10);
+        assert_eq!(
+            metrics.braking_zones,
+            extract_braking_zones(&frames, &config)
+        );
+        assert_eq!(metrics.corners, extract_corners(&frames, &config));
+        assert_eq!(metrics.shifts, extract_shifts(&frames));
+    }
+
+    #[test]
+    fn test_smoothing_merges_flickering_corner() {
+        // A single-frame dip below the steering threshold mid-corner
+        let frames = vec![
+            make_frame(0.0, 0.5, 60.0, 0.10, 0.0, 0.0, 0.0),
+            make_frame(0.0, 0.5, 55.0, 0.15, 0.3, 2.0, 0.5),
+            make_frame(0.0, 0.5, 50.0, 0.20, 0.3, 2.5, 1.0),
+            make_frame(0.0, 0.5, 50.0, 0.25, 0.05, 2.5, 1.5),
+            make_frame(0.0, 0.5, 50.0, 0.30, 0.3, 2.5, 2.0),
+            make_frame(0.0, 0.5, 55.0, 0.35, 0.3, 2.0, 2.5),
+            make_frame(0.0, 0.5, 60.0, 0.40, 0.0, 0.0, 3.0),
+        ];
+
+        let metrics = extract_lap_metrics(&frames, &default_config(), 1, None);
+        assert_eq!(metrics.total_corners, 2);
+
+        let config = AnalysisConfig {
+            smoothing_window: 3,
+            ..default_config()
+        };
+        let metrics = extract_lap_metrics(&frames, &config, 1, None);
+        assert_eq!(metrics.total_corners, 1);
+    }
+
+    #[test]
+    fn test_sector_markers_set_the_sectors() {
+        // A lap at constant pace over 100 seconds
+        let frames: Vec<_> = (0..=100)
+            .map(|idx| make_frame(0.0, 1.0, 60.0, idx as f64 / 100.0, 0.0, 0.0, idx as f64))
+            .collect();
+
+        let metrics = extract_lap_metrics(&frames, &default_config(), 1, None);
+        assert_eq!(metrics.sector_times.len(), DEFAULT_SECTOR_COUNT);
+
+        let config = AnalysisConfig {
+            sector_markers: vec![0.1, 0.25, 0.5, 0.8],
+            ..default_config()
+        };
+        let metrics = extract_lap_metrics(&frames, &config, 1, None);
+        assert_eq!(metrics.sector_times.len(), 5);
+        let expected = [10.0, 15.0, 25.0, 30.0, 20.0];
+        for (actual, expected) in metrics.sector_times.iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+        }
+    }
+
+    /// Counts frames at full throttle.
+    #[derive(Default)]
+    struct FullThrottleFrames {
+        count: usize,
+    }
+
+    impl CustomDetector for FullThrottleFrames {
+        fn name(&self) -> &str {
+            "full_throttle_frames"
+        }
+
+        fn process_frame(&mut self, frame: &TelemetryFrame, _index: usize) {
+            if frame.throttle >= 1.0 {
+                self.count += 1;
+            }
+        }
+
+        fn result(&self, _frames: &[TelemetryFrame]) -> f64 {
+            self.count as f64
+        }
+
+        fn reset(&mut self) {
+            self.count = 0;
+        }
+    }
+
+    #[test]
+    fn test_custom_detector_results_are_included() {
+        let frames: Vec<_> = (0..10)
+            .map(|idx| {
+                let throttle = if idx < 6 { 1.0 } else { 0.4 };
+                make_frame(0.0, throttle, 60.0, idx as f64 * 0.1, 0.0, 0.0, idx as f64)
+            })
+            .collect();
+        let mut detectors: Vec<Box<dyn CustomDetector>> =
+            vec![Box::new(FullThrottleFrames::default())];
+
+        let metrics = extract_lap_metrics_with(&frames, &default_config(), 1, None, &mut detectors);
+        assert_eq!(metrics.custom.get("full_throttle_frames"), Some(&6.0));
+        // Everything else matches a run without custom detectors
+        let plain = extract_lap_metrics(&frames, &default_config(), 1, None);
+        assert!(plain.custom.is_empty());
+        assert_eq!(
+            LapMetrics {
+                custom: BTreeMap::new(),
+                ..metrics
+            },
+            plain
+        );
+
+        // Detectors start over on each lap
+        let metrics =
+            extract_lap_metrics_with(&frames[..3], &default_config(), 2, None, &mut detectors);
+        assert_eq!(metrics.custom["full_throttle_frames"], 3.0);
+    }
 }
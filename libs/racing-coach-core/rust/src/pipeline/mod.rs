@@ -1,5 +1,7 @@
 //! Unified metrics extraction pipeline.
 
 mod metrics;
+mod session;
 
-pub use metrics::extract_lap_metrics;
+pub use metrics::{extract_lap_metrics, extract_lap_metrics_with};
+pub use session::aggregate_session;
@@ -0,0 +1,146 @@
+//! Session aggregation across laps.
+
+use crate::results::{LapMetrics, SessionMetrics};
+
+/// Aggregate per-lap metrics into session statistics.
+///
+/// A lap is valid if it has a positive lap time and never touched pit road;
+/// out-laps, in-laps and untimed laps are counted in `total_laps` only. Best
+/// sector times use the valid laps with sector times, and all such laps must
+/// have the same number of sectors as the first one to be included.
+///
+/// # Returns
+/// * `SessionMetrics`, with empty statistics if there are no valid laps
+pub fn aggregate_session(laps: &[LapMetrics]) -> SessionMetrics {
+    let valid: Vec<(&LapMetrics, f64)> = laps
+        .iter()
+        .filter(|lap| !lap.contains_pit)
+        .filter_map(|lap| lap.lap_time.filter(|&t| t > 0.0).map(|t| (lap, t)))
+        .collect();
+
+    let mut session = SessionMetrics {
+        total_laps: laps.len(),
+        valid_laps: valid.len(),
+        ..Default::default()
+    };
+
+    let Some(&(best_lap, best_time)) = valid.iter().min_by(|a, b| a.1.total_cmp(&b.1)) else {
+        return session;
+    };
+
+    let count = valid.len() as f64;
+    let mean = valid.iter().map(|(_, t)| t).sum::<f64>() / count;
+    let variance = valid.iter().map(|(_, t)| (t - mean).powi(2)).sum::<f64>() / count;
+
+    session.best_lap_time = Some(best_time);
+    session.best_lap_number = Some(best_lap.lap_number);
+    session.average_lap_time = Some(mean);
+    session.lap_time_std_dev = Some(variance.sqrt());
+    session.best_sector_times = best_sector_times(valid.iter().map(|(lap, _)| *lap));
+    if !session.best_sector_times.is_empty() {
+        session.theoretical_best_lap_time = Some(session.best_sector_times.iter().sum());
+    }
+
+    session
+}
+
+/// Element-wise minimum of the laps' sector times.
+fn best_sector_times<'a>(laps: impl Iterator<Item = &'a LapMetrics>) -> Vec<f64> {
+    let mut best: Vec<f64> = Vec::new();
+
+    for lap in laps.filter(|lap| !lap.sector_times.is_empty()) {
+        if best.is_empty() {
+            best = lap.sector_times.clone();
+        } else if lap.sector_times.len() == best.len() {
+            for (best, &time) in best.iter_mut().zip(&lap.sector_times) {
+                *best = best.min(time);
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_lap(lap_number: i32, lap_time: Option<f64>, sector_times: Vec<f64>) -> LapMetrics {
+        LapMetrics::from_detection(lap_number, lap_time, vec![], vec![], 80.0, 20.0, false)
+            .with_sector_times(sector_times)
+    }
+
+    fn timed_laps(times: &[f64]) -> Vec<LapMetrics> {
+        times
+            .iter()
+            .enumerate()
+            .map(|(i, &time)| make_lap(i as i32 + 1, Some(time), vec![]))
+            .collect()
+    }
+
+    #[test]
+    fn test_best_and_average_lap() {
+        let session = aggregate_session(&timed_laps(&[92.0, 90.0, 91.0, 93.0]));
+
+        assert_eq!(session.total_laps, 4);
+        assert_eq!(session.valid_laps, 4);
+        assert_eq!(session.best_lap_time, Some(90.0));
+        assert_eq!(session.best_lap_number, Some(2));
+        assert_eq!(session.average_lap_time, Some(91.5));
+        let std_dev = session.lap_time_std_dev.unwrap();
+        assert!((std_dev - 1.25f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_consistent_laps_have_lower_spread() {
+        let consistent = aggregate_session(&timed_laps(&[90.1, 90.0, 90.2, 90.1]));
+        let erratic = aggregate_session(&timed_laps(&[88.0, 93.5, 90.0, 95.0]));
+
+        assert!(consistent.lap_time_std_dev.unwrap() < erratic.lap_time_std_dev.unwrap());
+    }
+
+    #[test]
+    fn test_pit_and_untimed_laps_are_excluded() {
+        let mut laps = timed_laps(&[91.0, 90.0]);
+        laps.push(make_lap(3, None, vec![]));
+        let mut in_lap = make_lap(4, Some(80.0), vec![]);
+        in_lap.contains_pit = true;
+        laps.push(in_lap);
+
+        let session = aggregate_session(&laps);
+
+        assert_eq!(session.total_laps, 4);
+        assert_eq!(session.valid_laps, 2);
+        assert_eq!(session.best_lap_time, Some(90.0));
+        assert_eq!(session.average_lap_time, Some(90.5));
+    }
+
+    #[test]
+    fn test_best_sectors_combine_laps() {
+        let laps = vec![
+            make_lap(1, Some(90.0), vec![30.0, 29.0, 31.0]),
+            make_lap(2, Some(89.5), vec![29.5, 29.5, 30.5]),
+            make_lap(3, Some(92.0), vec![31.0, 28.5, 32.5]),
+        ];
+
+        let session = aggregate_session(&laps);
+
+        assert_eq!(session.best_sector_times, vec![29.5, 28.5, 30.5]);
+        assert_eq!(session.theoretical_best_lap_time, Some(88.5));
+    }
+
+    #[test]
+    fn test_no_valid_laps() {
+        let session = aggregate_session(&[make_lap(1, None, vec![])]);
+
+        assert_eq!(session.total_laps, 1);
+        assert_eq!(session.valid_laps, 0);
+        assert_eq!(session.best_lap_time, None);
+        assert_eq!(session.average_lap_time, None);
+        assert_eq!(session.lap_time_std_dev, None);
+        assert!(session.best_sector_times.is_empty());
+        assert_eq!(session.theoretical_best_lap_time, None);
+
+        assert_eq!(aggregate_session(&[]), SessionMetrics::default());
+    }
+}
@@ -1,12 +1,16 @@
 //! Braking metrics result structure.
 
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde::{Deserialize, Serialize};
+
+use super::to_py_dict;
 
 /// Comprehensive braking metrics for a single braking zone.
 ///
 /// Contains location, performance, deceleration, and trail braking data.
-#[derive(Debug, Clone)]
-#[pyclass]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
 pub struct BrakingMetrics {
     // Location metrics
     /// Lap distance where braking starts (normalized 0-1)
@@ -59,6 +63,17 @@ pub struct BrakingMetrics {
     /// Average brake pressure during trail braking phase
     #[pyo3(get)]
     pub trail_brake_percentage: f64,
+
+    // Location in meters, for consumers that don't work in normalized distance
+    /// Meters from start/finish where braking starts (0.0 when unknown)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub braking_point_distance_meters: f64,
+
+    /// Meters from start/finish where braking ends (0.0 when unknown)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub end_distance_meters: f64,
 }
 
 #[pymethods]
@@ -77,7 +92,9 @@ impl BrakingMetrics {
         braking_efficiency,
         has_trail_braking,
         trail_brake_distance,
-        trail_brake_percentage
+        trail_brake_percentage,
+        braking_point_distance_meters=0.0,
+        end_distance_meters=0.0
     ))]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -93,6 +110,8 @@ impl BrakingMetrics {
         has_trail_braking: bool,
         trail_brake_distance: f64,
         trail_brake_percentage: f64,
+        braking_point_distance_meters: f64,
+        end_distance_meters: f64,
     ) -> Self {
         Self {
             braking_point_distance,
@@ -107,9 +126,16 @@ impl BrakingMetrics {
             has_trail_braking,
             trail_brake_distance,
             trail_brake_percentage,
+            braking_point_distance_meters,
+            end_distance_meters,
         }
     }
 
+    /// All fields as a dict, with nested results as dicts.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        to_py_dict(py, self)
+    }
+
     /// String representation for debugging.
     fn __repr__(&self) -> String {
         format!(
@@ -161,11 +187,13 @@ mod tests {
     #[test]
     fn test_braking_metrics_creation() {
         let metrics = BrakingMetrics::new(
-            0.5, 80.0, 0.55, 0.9, 2.5, 40.0, -15.0, -12.0, 13.3, true, 0.02, 0.6,
+            0.5, 80.0, 0.55, 0.9, 2.5, 40.0, -15.0, -12.0, 13.3, true, 0.02, 0.6, 2000.0, 2200.0,
         );
         assert_eq!(metrics.braking_point_distance, 0.5);
         assert_eq!(metrics.max_brake_pressure, 0.9);
         assert!(metrics.has_trail_braking);
+        assert_eq!(metrics.braking_point_distance_meters, 2000.0);
+        assert_eq!(metrics.end_distance_meters, 2200.0);
     }
 
     #[test]
@@ -1,12 +1,16 @@
 //! Corner metrics result structure.
 
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde::{Deserialize, Serialize};
+
+use super::to_py_dict;
 
 /// Comprehensive corner metrics for a single corner.
 ///
 /// Contains key points, speeds, and performance data.
-#[derive(Debug, Clone)]
-#[pyclass]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
 pub struct CornerMetrics {
     // Key corner points (lap distances)
     /// Lap distance where steering input begins (turn-in point)
@@ -42,6 +46,16 @@ pub struct CornerMetrics {
     #[pyo3(get)]
     pub throttle_application_speed: f64,
 
+    /// Lowest speed reached anywhere between turn-in and exit
+    #[pyo3(get)]
+    #[serde(default)]
+    pub min_speed: f64,
+
+    /// Gear held at the apex
+    #[pyo3(get)]
+    #[serde(default)]
+    pub apex_gear: i32,
+
     // Performance metrics
     /// Maximum lateral acceleration (m/s², positive = cornering force)
     #[pyo3(get)]
@@ -67,6 +81,73 @@ pub struct CornerMetrics {
     /// Speed gained from apex to exit (positive = speed gained)
     #[pyo3(get)]
     pub speed_gain: f64,
+
+    // Key corner points in meters from start/finish (0.0 when unknown)
+    /// Turn-in point in meters
+    #[pyo3(get)]
+    #[serde(default)]
+    pub turn_in_distance_meters: f64,
+
+    /// Apex in meters
+    #[pyo3(get)]
+    #[serde(default)]
+    pub apex_distance_meters: f64,
+
+    /// Exit point in meters
+    #[pyo3(get)]
+    #[serde(default)]
+    pub exit_distance_meters: f64,
+
+    /// Throttle application point in meters
+    #[pyo3(get)]
+    #[serde(default)]
+    pub throttle_application_distance_meters: f64,
+
+    /// Oversteer/understeer balance index (positive = oversteer, 0.0 when
+    /// yaw rate is unknown)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub balance: f64,
+
+    // Phase breakdown: entry runs from turn-in to the apex, mid-corner from
+    // the apex to throttle application and exit from there to the exit point
+    /// Time from turn-in to the apex (seconds)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub entry_time: f64,
+
+    /// Time from the apex to throttle application, or to the exit when the
+    /// throttle isn't reapplied (seconds)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub mid_time: f64,
+
+    /// Time from throttle application to the exit (seconds)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub exit_time: f64,
+
+    /// Speed at the apex, where entry ends and mid-corner begins
+    #[pyo3(get)]
+    #[serde(default)]
+    pub mid_start_speed: f64,
+
+    /// Speed where mid-corner ends and exit begins
+    #[pyo3(get)]
+    #[serde(default)]
+    pub mid_end_speed: f64,
+
+    // Labels from the track map
+    /// Corner number: the matching track map window's position (1-based), or
+    /// the next number after the map for unmatched corners (0 until labeled)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub corner_number: u32,
+
+    /// Name of the matching track map window (None when unmatched)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub name: Option<String>,
 }
 
 #[pymethods]
@@ -87,7 +168,21 @@ impl CornerMetrics {
         corner_distance,
         max_steering_angle,
         speed_loss,
-        speed_gain
+        speed_gain,
+        min_speed=0.0,
+        apex_gear=0,
+        turn_in_distance_meters=0.0,
+        apex_distance_meters=0.0,
+        exit_distance_meters=0.0,
+        throttle_application_distance_meters=0.0,
+        balance=0.0,
+        corner_number=0,
+        name=None,
+        entry_time=0.0,
+        mid_time=0.0,
+        exit_time=0.0,
+        mid_start_speed=0.0,
+        mid_end_speed=0.0
     ))]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -105,6 +200,20 @@ impl CornerMetrics {
         max_steering_angle: f64,
         speed_loss: f64,
         speed_gain: f64,
+        min_speed: f64,
+        apex_gear: i32,
+        turn_in_distance_meters: f64,
+        apex_distance_meters: f64,
+        exit_distance_meters: f64,
+        throttle_application_distance_meters: f64,
+        balance: f64,
+        corner_number: u32,
+        name: Option<String>,
+        entry_time: f64,
+        mid_time: f64,
+        exit_time: f64,
+        mid_start_speed: f64,
+        mid_end_speed: f64,
     ) -> Self {
         Self {
             turn_in_distance,
@@ -115,20 +224,43 @@ impl CornerMetrics {
             apex_speed,
             exit_speed,
             throttle_application_speed,
+            min_speed,
+            apex_gear,
             max_lateral_g,
             time_in_corner,
             corner_distance,
             max_steering_angle,
             speed_loss,
             speed_gain,
+            turn_in_distance_meters,
+            apex_distance_meters,
+            exit_distance_meters,
+            throttle_application_distance_meters,
+            balance,
+            entry_time,
+            mid_time,
+            exit_time,
+            mid_start_speed,
+            mid_end_speed,
+            corner_number,
+            name,
         }
     }
 
+    /// All fields as a dict, with nested results as dicts.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        to_py_dict(py, self)
+    }
+
     /// String representation for debugging.
     fn __repr__(&self) -> String {
         format!(
-            "CornerMetrics(turn_in={:.3}, apex={:.3}, exit={:.3}, apex_speed={:.1})",
-            self.turn_in_distance, self.apex_distance, self.exit_distance, self.apex_speed
+            "CornerMetrics(turn_in={:.3}, apex={:.3}, exit={:.3}, apex_speed={:.1}, apex_gear={})",
+            self.turn_in_distance,
+            self.apex_distance,
+            self.exit_distance,
+            self.apex_speed,
+            self.apex_gear
         )
     }
 }
@@ -185,6 +317,7 @@ impl CornerMetricsBuilder {
     }
 
     /// Update builder with a new frame during corner.
+    #[allow(clippy::too_many_arguments)]
     pub fn update(
         &mut self,
         idx: usize,
@@ -233,11 +366,19 @@ mod tests {
     #[test]
     fn test_corner_metrics_creation() {
         let metrics = CornerMetrics::new(
-            0.3, 0.35, 0.4, 0.37, 60.0, 45.0, 70.0, 50.0, 2.5, 3.0, 0.1, 0.3, 15.0, 25.0,
+            0.3, 0.35, 0.4, 0.37, 60.0, 45.0, 70.0, 50.0, 2.5, 3.0, 0.1, 0.3, 15.0, 25.0, 44.0, 3,
+            1200.0, 1400.0, 1600.0, 1480.0, -0.2, 3, None, 1.2, 1.0, 0.8, 46.0, 48.0,
         );
         assert_eq!(metrics.turn_in_distance, 0.3);
         assert_eq!(metrics.apex_speed, 45.0);
         assert_eq!(metrics.max_lateral_g, 2.5);
+        assert_eq!(metrics.min_speed, 44.0);
+        assert_eq!(metrics.apex_gear, 3);
+        assert_eq!(metrics.apex_distance_meters, 1400.0);
+        assert_eq!(metrics.balance, -0.2);
+        assert_eq!(metrics.corner_number, 3);
+        assert_eq!(metrics.mid_time, 1.0);
+        assert_eq!(metrics.mid_end_speed, 48.0);
     }
 
     #[test]
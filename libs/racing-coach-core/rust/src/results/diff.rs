@@ -0,0 +1,488 @@
+//! Lap-to-lap comparison of metrics.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde::{Deserialize, Serialize};
+
+use super::{to_py_dict, LapMetrics};
+
+/// Largest lap distance (normalized 0-1) between two corners' apexes, or two
+/// braking points, for them to count as the same event on both laps.
+pub const MAX_MATCH_DISTANCE: f64 = 0.02;
+
+/// How a corner changed from one lap to the other.
+///
+/// Deltas are the other lap's value minus this lap's, so a positive apex
+/// speed delta means the other lap carried more speed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
+pub struct CornerDelta {
+    /// Index of the corner in this lap's `corners`
+    #[pyo3(get)]
+    pub index: usize,
+
+    /// Index of the matching corner in the other lap's `corners`
+    #[pyo3(get)]
+    pub other_index: usize,
+
+    /// Apex distance of the corner on this lap (normalized 0-1)
+    #[pyo3(get)]
+    pub apex_distance: f64,
+
+    /// Change in apex speed (m/s)
+    #[pyo3(get)]
+    pub apex_speed_delta: f64,
+
+    /// Change in exit speed (m/s)
+    #[pyo3(get)]
+    pub exit_speed_delta: f64,
+
+    /// Change in time spent in the corner (seconds)
+    #[pyo3(get)]
+    pub time_in_corner_delta: f64,
+}
+
+#[pymethods]
+impl CornerDelta {
+    /// All fields as a dict.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        to_py_dict(py, self)
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "CornerDelta(apex={:.3}, apex_speed={:+.1}, exit_speed={:+.1})",
+            self.apex_distance, self.apex_speed_delta, self.exit_speed_delta
+        )
+    }
+}
+
+/// How a braking zone changed from one lap to the other.
+///
+/// Deltas are the other lap's value minus this lap's, so a positive braking
+/// point delta means the other lap braked later.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
+pub struct BrakingDelta {
+    /// Index of the zone in this lap's `braking_zones`
+    #[pyo3(get)]
+    pub index: usize,
+
+    /// Index of the matching zone in the other lap's `braking_zones`
+    #[pyo3(get)]
+    pub other_index: usize,
+
+    /// Braking point of the zone on this lap (normalized 0-1)
+    #[pyo3(get)]
+    pub braking_point_distance: f64,
+
+    /// Change in braking point (normalized lap distance)
+    #[pyo3(get)]
+    pub braking_point_delta: f64,
+
+    /// Change in speed at the braking point (m/s)
+    #[pyo3(get)]
+    pub braking_point_speed_delta: f64,
+
+    /// Change in the minimum speed reached while braking (m/s)
+    #[pyo3(get)]
+    pub minimum_speed_delta: f64,
+}
+
+#[pymethods]
+impl BrakingDelta {
+    /// All fields as a dict.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        to_py_dict(py, self)
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "BrakingDelta(point={:.3}, point_delta={:+.4}, min_speed={:+.1})",
+            self.braking_point_distance, self.braking_point_delta, self.minimum_speed_delta
+        )
+    }
+}
+
+/// Differences between two laps' metrics, see [`LapMetrics::diff`].
+///
+/// Deltas are the other lap's value minus this lap's. Corners are paired by
+/// apex distance and braking zones by braking point; events without a
+/// counterpart within [`MAX_MATCH_DISTANCE`] are listed as unmatched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
+pub struct LapMetricsDiff {
+    /// Change in lap time in seconds (None unless both laps are timed)
+    #[pyo3(get)]
+    pub lap_time_delta: Option<f64>,
+
+    /// Change in maximum speed (m/s)
+    #[pyo3(get)]
+    pub max_speed_delta: f64,
+
+    /// Change in minimum speed (m/s)
+    #[pyo3(get)]
+    pub min_speed_delta: f64,
+
+    /// Paired corners, ordered by this lap's corners
+    #[pyo3(get)]
+    pub corners: Vec<CornerDelta>,
+
+    /// Paired braking zones, ordered by this lap's zones
+    #[pyo3(get)]
+    pub braking_zones: Vec<BrakingDelta>,
+
+    /// Indices of this lap's corners with no counterpart
+    #[pyo3(get)]
+    pub unmatched_corners: Vec<usize>,
+
+    /// Indices of the other lap's corners with no counterpart
+    #[pyo3(get)]
+    pub other_unmatched_corners: Vec<usize>,
+
+    /// Indices of this lap's braking zones with no counterpart
+    #[pyo3(get)]
+    pub unmatched_braking_zones: Vec<usize>,
+
+    /// Indices of the other lap's braking zones with no counterpart
+    #[pyo3(get)]
+    pub other_unmatched_braking_zones: Vec<usize>,
+}
+
+#[pymethods]
+impl LapMetricsDiff {
+    /// All fields as a dict, with nested deltas as dicts.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        to_py_dict(py, self)
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "LapMetricsDiff(lap_time={:?}, corners={}, braking_zones={}, unmatched={})",
+            self.lap_time_delta,
+            self.corners.len(),
+            self.braking_zones.len(),
+            self.unmatched_corners.len()
+                + self.other_unmatched_corners.len()
+                + self.unmatched_braking_zones.len()
+                + self.other_unmatched_braking_zones.len()
+        )
+    }
+}
+
+impl LapMetrics {
+    /// Compare these metrics with `other`'s, e.g. a reference lap.
+    ///
+    /// Each delta is `other`'s value minus this lap's.
+    pub fn diff(&self, other: &LapMetrics) -> LapMetricsDiff {
+        let apexes = |lap: &LapMetrics| -> Vec<f64> {
+            lap.corners.iter().map(|c| c.apex_distance).collect()
+        };
+        let corner_pairs = match_by_distance(&apexes(self), &apexes(other));
+        let braking_points = |lap: &LapMetrics| -> Vec<f64> {
+            lap.braking_zones
+                .iter()
+                .map(|b| b.braking_point_distance)
+                .collect()
+        };
+        let braking_pairs = match_by_distance(&braking_points(self), &braking_points(other));
+
+        let corners = corner_pairs
+            .pairs
+            .iter()
+            .map(|&(index, other_index)| {
+                let (this, that) = (&self.corners[index], &other.corners[other_index]);
+                CornerDelta {
+                    index,
+                    other_index,
+                    apex_distance: this.apex_distance,
+                    apex_speed_delta: that.apex_speed - this.apex_speed,
+                    exit_speed_delta: that.exit_speed - this.exit_speed,
+                    time_in_corner_delta: that.time_in_corner - this.time_in_corner,
+                }
+            })
+            .collect();
+
+        let braking_zones = braking_pairs
+            .pairs
+            .iter()
+            .map(|&(index, other_index)| {
+                let (this, that) = (
+                    &self.braking_zones[index],
+                    &other.braking_zones[other_index],
+                );
+                BrakingDelta {
+                    index,
+                    other_index,
+                    braking_point_distance: this.braking_point_distance,
+                    braking_point_delta: signed_gap(
+                        this.braking_point_distance,
+                        that.braking_point_distance,
+                    ),
+                    braking_point_speed_delta: that.braking_point_speed - this.braking_point_speed,
+                    minimum_speed_delta: that.minimum_speed - this.minimum_speed,
+                }
+            })
+            .collect();
+
+        LapMetricsDiff {
+            lap_time_delta: self
+                .lap_time
+                .zip(other.lap_time)
+                .map(|(this, that)| that - this),
+            max_speed_delta: other.max_speed - self.max_speed,
+            min_speed_delta: other.min_speed - self.min_speed,
+            corners,
+            braking_zones,
+            unmatched_corners: corner_pairs.unmatched,
+            other_unmatched_corners: corner_pairs.other_unmatched,
+            unmatched_braking_zones: braking_pairs.unmatched,
+            other_unmatched_braking_zones: braking_pairs.other_unmatched,
+        }
+    }
+}
+
+/// Events paired across two laps by [`match_by_distance`].
+struct Matching {
+    /// `(index, other_index)` pairs, ordered by `index`
+    pairs: Vec<(usize, usize)>,
+    unmatched: Vec<usize>,
+    other_unmatched: Vec<usize>,
+}
+
+/// Pair events on two laps by lap distance.
+///
+/// The closest remaining pair is matched first, so each event pairs with its
+/// nearest counterpart unless that counterpart is even closer to another
+/// event. Pairs further apart than [`MAX_MATCH_DISTANCE`] are never matched.
+/// Distances wrap across the start/finish line.
+fn match_by_distance(distances: &[f64], other_distances: &[f64]) -> Matching {
+    let mut candidates: Vec<(f64, usize, usize)> = distances
+        .iter()
+        .enumerate()
+        .flat_map(|(index, &distance)| {
+            other_distances
+                .iter()
+                .enumerate()
+                .map(move |(other_index, &other)| {
+                    (signed_gap(distance, other).abs(), index, other_index)
+                })
+        })
+        .filter(|&(gap, _, _)| gap <= MAX_MATCH_DISTANCE)
+        .collect();
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut matched = vec![false; distances.len()];
+    let mut other_matched = vec![false; other_distances.len()];
+    let mut pairs = Vec::new();
+    for (_, index, other_index) in candidates {
+        if !matched[index] && !other_matched[other_index] {
+            matched[index] = true;
+            other_matched[other_index] = true;
+            pairs.push((index, other_index));
+        }
+    }
+    pairs.sort_unstable();
+
+    let unmatched = |matched: Vec<bool>| {
+        matched
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, matched)| !matched)
+            .map(|(index, _)| index)
+            .collect()
+    };
+    Matching {
+        pairs,
+        unmatched: unmatched(matched),
+        other_unmatched: unmatched(other_matched),
+    }
+}
+
+/// Lap distance from `from` to `to`, taking the shorter way around the lap
+/// (positive = `to` is further along).
+fn signed_gap(from: f64, to: f64) -> f64 {
+    let gap = to - from;
+    if gap > 0.5 {
+        gap - 1.0
+    } else if gap < -0.5 {
+        gap + 1.0
+    } else {
+        gap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::{BrakingMetrics, CornerMetrics};
+
+    fn braking_zone(point: f64, speed: f64, minimum_speed: f64) -> BrakingMetrics {
+        BrakingMetrics::new(
+            point,
+            speed,
+            point + 0.03,
+            0.9,
+            2.5,
+            minimum_speed,
+            -15.0,
+            -12.0,
+            13.3,
+            false,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        )
+    }
+
+    fn corner(apex: f64, apex_speed: f64, exit_speed: f64, time: f64) -> CornerMetrics {
+        CornerMetrics::new(
+            apex - 0.02,
+            apex,
+            apex + 0.03,
+            apex + 0.01,
+            60.0,
+            apex_speed,
+            exit_speed,
+            50.0,
+            2.5,
+            time,
+            0.05,
+            0.3,
+            15.0,
+            25.0,
+            apex_speed,
+            3,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0,
+            None,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        )
+    }
+
+    fn lap(
+        lap_time: f64,
+        braking_zones: Vec<BrakingMetrics>,
+        corners: Vec<CornerMetrics>,
+    ) -> LapMetrics {
+        LapMetrics::from_detection(1, Some(lap_time), braking_zones, corners, 80.0, 25.0, false)
+    }
+
+    #[test]
+    fn test_faster_lap_deltas() {
+        let base = lap(
+            92.0,
+            vec![
+                braking_zone(0.10, 75.0, 30.0),
+                braking_zone(0.60, 70.0, 35.0),
+            ],
+            vec![
+                corner(0.15, 30.0, 45.0, 3.0),
+                corner(0.40, 50.0, 60.0, 2.0),
+                corner(0.65, 35.0, 50.0, 3.5),
+            ],
+        );
+        // Brakes later, carries more apex speed through turns 1 and 3, and
+        // its apexes sit slightly off the base lap's
+        let faster = LapMetrics {
+            max_speed: 81.5,
+            ..lap(
+                91.2,
+                vec![
+                    braking_zone(0.105, 75.5, 31.0),
+                    braking_zone(0.606, 70.0, 37.0),
+                ],
+                vec![
+                    corner(0.152, 31.5, 46.0, 2.8),
+                    corner(0.398, 50.0, 60.5, 2.0),
+                    corner(0.653, 36.0, 51.0, 3.3),
+                ],
+            )
+        };
+
+        let diff = base.diff(&faster);
+
+        assert!((diff.lap_time_delta.unwrap() + 0.8).abs() < 1e-9);
+        assert!((diff.max_speed_delta - 1.5).abs() < 1e-9);
+        assert_eq!(diff.min_speed_delta, 0.0);
+
+        let pairs: Vec<(usize, usize)> = diff
+            .corners
+            .iter()
+            .map(|c| (c.index, c.other_index))
+            .collect();
+        assert_eq!(pairs, [(0, 0), (1, 1), (2, 2)]);
+        assert!((diff.corners[0].apex_speed_delta - 1.5).abs() < 1e-9);
+        assert!((diff.corners[0].time_in_corner_delta + 0.2).abs() < 1e-9);
+        assert!((diff.corners[1].exit_speed_delta - 0.5).abs() < 1e-9);
+        assert!((diff.corners[2].apex_speed_delta - 1.0).abs() < 1e-9);
+
+        assert_eq!(diff.braking_zones.len(), 2);
+        assert!((diff.braking_zones[0].braking_point_delta - 0.005).abs() < 1e-9);
+        assert!((diff.braking_zones[1].braking_point_delta - 0.006).abs() < 1e-9);
+        assert!((diff.braking_zones[1].minimum_speed_delta - 2.0).abs() < 1e-9);
+
+        assert!(diff.unmatched_corners.is_empty());
+        assert!(diff.other_unmatched_braking_zones.is_empty());
+    }
+
+    #[test]
+    fn test_corners_pair_with_nearest_apex_and_leave_extras_unmatched() {
+        let base = lap(
+            90.0,
+            vec![],
+            vec![corner(0.30, 40.0, 50.0, 2.0), corner(0.50, 40.0, 50.0, 2.0)],
+        );
+        // The other lap splits the first corner in two, misses the second and
+        // has an extra one just before the line
+        let other = lap(
+            90.0,
+            vec![],
+            vec![
+                corner(0.290, 38.0, 50.0, 1.0),
+                corner(0.305, 42.0, 50.0, 1.0),
+                corner(0.999, 60.0, 70.0, 1.0),
+            ],
+        );
+
+        let diff = base.diff(&other);
+
+        assert_eq!(diff.corners.len(), 1);
+        assert_eq!(diff.corners[0].index, 0);
+        assert_eq!(diff.corners[0].other_index, 1);
+        assert!((diff.corners[0].apex_speed_delta - 2.0).abs() < 1e-9);
+        assert_eq!(diff.unmatched_corners, [1]);
+        assert_eq!(diff.other_unmatched_corners, [0, 2]);
+    }
+
+    #[test]
+    fn test_matching_wraps_across_start_finish() {
+        let base = lap(90.0, vec![braking_zone(0.995, 70.0, 40.0)], vec![]);
+        let other = lap(90.0, vec![braking_zone(0.004, 70.0, 40.0)], vec![]);
+
+        let diff = base.diff(&other);
+
+        assert_eq!(diff.braking_zones.len(), 1);
+        assert!((diff.braking_zones[0].braking_point_delta - 0.009).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_untimed_lap_has_no_lap_time_delta() {
+        let timed = lap(90.0, vec![], vec![]);
+        let untimed = LapMetrics::from_detection(2, None, vec![], vec![], 80.0, 25.0, false);
+
+        assert_eq!(timed.diff(&untimed).lap_time_delta, None);
+    }
+}
@@ -1,14 +1,37 @@
 //! Lap metrics aggregate structure.
 
+use std::collections::BTreeMap;
+
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde::{Deserialize, Serialize};
+
+use super::{to_py_dict, BrakingMetrics, CornerMetrics, LapMetricsDiff, ShiftEvent};
+use crate::analysis::{FuelUsage, PedalUsage, SmoothnessScore};
 
-use super::{BrakingMetrics, CornerMetrics};
+/// Version of the JSON schema produced by [`LapMetrics::to_json_value`].
+///
+/// Bump when a field is renamed, removed, or changes meaning so consumers of
+/// stored metrics can tell the formats apart.
+pub const METRICS_SCHEMA_VERSION: u32 = 1;
+
+/// JSON representation of [`LapMetrics`], tagged with the schema version.
+#[derive(Serialize, Deserialize)]
+struct VersionedLapMetrics {
+    schema_version: u32,
+    #[serde(flatten)]
+    metrics: LapMetrics,
+}
 
 /// Aggregate metrics for an entire lap.
 ///
 /// Contains collections of braking zones and corners along with lap-wide statistics.
-#[derive(Debug, Clone)]
-#[pyclass]
+///
+/// The serde representation uses the server API's field names for laps,
+/// braking zones and corners. Fields the API doesn't define are optional
+/// when deserializing, so the server's JSON parses directly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
 pub struct LapMetrics {
     /// Lap number
     #[pyo3(get)]
@@ -45,6 +68,47 @@ pub struct LapMetrics {
     /// Minimum speed during the lap (m/s)
     #[pyo3(get)]
     pub min_speed: f64,
+
+    /// Whether the car was on pit road at any point during the lap
+    #[pyo3(get)]
+    #[serde(default)]
+    pub contains_pit: bool,
+
+    /// Time in each sector in seconds (empty if the lap wasn't fully covered)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub sector_times: Vec<f64>,
+
+    /// Every gear change in the lap, in order
+    #[pyo3(get)]
+    #[serde(default)]
+    pub shifts: Vec<ShiftEvent>,
+
+    /// How smoothly the throttle, brake and steering were worked
+    #[pyo3(get)]
+    #[serde(default)]
+    pub smoothness: Option<SmoothnessScore>,
+
+    /// Fuel burned over the lap and the range left (None without fuel data)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub fuel: Option<FuelUsage>,
+
+    /// Share of the lap at full throttle, full brake and coasting (None if
+    /// the frames span no time)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub pedal_usage: Option<PedalUsage>,
+
+    /// Number of slide corrections (rapid steering reversals) in the lap
+    #[pyo3(get)]
+    #[serde(default)]
+    pub total_corrections: usize,
+
+    /// Results of custom detectors, by detector name
+    #[pyo3(get)]
+    #[serde(default)]
+    pub custom: BTreeMap<String, f64>,
 }
 
 #[pymethods]
@@ -60,7 +124,15 @@ impl LapMetrics {
         total_braking_zones,
         average_corner_speed,
         max_speed,
-        min_speed
+        min_speed,
+        contains_pit=false,
+        sector_times=Vec::new(),
+        shifts=Vec::new(),
+        smoothness=None,
+        fuel=None,
+        pedal_usage=None,
+        total_corrections=0,
+        custom=BTreeMap::new()
     ))]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -73,6 +145,14 @@ impl LapMetrics {
         average_corner_speed: f64,
         max_speed: f64,
         min_speed: f64,
+        contains_pit: bool,
+        sector_times: Vec<f64>,
+        shifts: Vec<ShiftEvent>,
+        smoothness: Option<SmoothnessScore>,
+        fuel: Option<FuelUsage>,
+        pedal_usage: Option<PedalUsage>,
+        total_corrections: usize,
+        custom: BTreeMap<String, f64>,
     ) -> Self {
         Self {
             lap_number,
@@ -84,14 +164,42 @@ impl LapMetrics {
             average_corner_speed,
             max_speed,
             min_speed,
+            contains_pit,
+            sector_times,
+            shifts,
+            smoothness,
+            fuel,
+            pedal_usage,
+            total_corrections,
+            custom,
         }
     }
 
+    /// Serialize the metrics to a JSON string (see `to_json_value`).
+    fn to_json(&self) -> String {
+        self.to_json_value().to_string()
+    }
+
+    /// All fields as a dict, with nested results as dicts.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        to_py_dict(py, self)
+    }
+
+    /// Compare with another lap's metrics (deltas are `other` minus this lap).
+    #[pyo3(name = "diff")]
+    fn py_diff(&self, other: &LapMetrics) -> LapMetricsDiff {
+        self.diff(other)
+    }
+
     /// String representation for debugging.
     fn __repr__(&self) -> String {
         format!(
-            "LapMetrics(lap={}, time={:?}, braking_zones={}, corners={})",
-            self.lap_number, self.lap_time, self.total_braking_zones, self.total_corners
+            "LapMetrics(lap={}, time={:?}, braking_zones={}, corners={}, contains_pit={})",
+            self.lap_number,
+            self.lap_time,
+            self.total_braking_zones,
+            self.total_corners,
+            self.contains_pit
         )
     }
 }
@@ -105,6 +213,7 @@ impl LapMetrics {
         corners: Vec<CornerMetrics>,
         max_speed: f64,
         min_speed: f64,
+        contains_pit: bool,
     ) -> Self {
         let total_braking_zones = braking_zones.len();
         let total_corners = corners.len();
@@ -125,8 +234,88 @@ impl LapMetrics {
             average_corner_speed,
             max_speed,
             min_speed,
+            contains_pit,
+            sector_times: Vec::new(),
+            shifts: Vec::new(),
+            smoothness: None,
+            fuel: None,
+            pedal_usage: None,
+            total_corrections: 0,
+            custom: BTreeMap::new(),
         }
     }
+
+    /// Set the per-sector times.
+    pub fn with_sector_times(mut self, sector_times: Vec<f64>) -> Self {
+        self.sector_times = sector_times;
+        self
+    }
+
+    /// Set the gear changes.
+    pub fn with_shifts(mut self, shifts: Vec<ShiftEvent>) -> Self {
+        self.shifts = shifts;
+        self
+    }
+
+    /// Set the input smoothness score.
+    pub fn with_smoothness(mut self, smoothness: SmoothnessScore) -> Self {
+        self.smoothness = Some(smoothness);
+        self
+    }
+
+    /// Set the fuel usage.
+    pub fn with_fuel(mut self, fuel: FuelUsage) -> Self {
+        self.fuel = Some(fuel);
+        self
+    }
+
+    /// Set the pedal usage.
+    pub fn with_pedal_usage(mut self, pedal_usage: PedalUsage) -> Self {
+        self.pedal_usage = Some(pedal_usage);
+        self
+    }
+
+    /// Set the number of slide corrections.
+    pub fn with_total_corrections(mut self, total_corrections: usize) -> Self {
+        self.total_corrections = total_corrections;
+        self
+    }
+
+    /// Set the results of custom detectors.
+    pub fn with_custom(mut self, custom: BTreeMap<String, f64>) -> Self {
+        self.custom = custom;
+        self
+    }
+
+    /// Convert the metrics to a JSON value.
+    ///
+    /// The object contains every field plus a `schema_version` key set to
+    /// [`METRICS_SCHEMA_VERSION`].
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(VersionedLapMetrics {
+            schema_version: METRICS_SCHEMA_VERSION,
+            metrics: self.clone(),
+        })
+        .expect("LapMetrics should always serialize")
+    }
+
+    /// Parse metrics produced by [`LapMetrics::to_json_value`].
+    ///
+    /// # Errors
+    /// Returns an error if the value doesn't match the schema or was written
+    /// by a newer schema version.
+    pub fn from_json_value(value: serde_json::Value) -> serde_json::Result<Self> {
+        let versioned: VersionedLapMetrics = serde_json::from_value(value)?;
+
+        if versioned.schema_version > METRICS_SCHEMA_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported metrics schema version {} (latest is {})",
+                versioned.schema_version, METRICS_SCHEMA_VERSION
+            )));
+        }
+
+        Ok(versioned.metrics)
+    }
 }
 
 #[cfg(test)]
@@ -135,26 +324,14 @@ mod tests {
 
     fn make_braking_metrics() -> BrakingMetrics {
         BrakingMetrics::new(
-            0.5, 80.0, 0.55, 0.9, 2.5, 40.0, -15.0, -12.0, 13.3, false, 0.0, 0.0,
+            0.5, 80.0, 0.55, 0.9, 2.5, 40.0, -15.0, -12.0, 13.3, false, 0.0, 0.0, 0.0, 0.0,
         )
     }
 
     fn make_corner_metrics(apex_speed: f64) -> CornerMetrics {
         CornerMetrics::new(
-            0.3,
-            0.35,
-            0.4,
-            0.37,
-            60.0,
-            apex_speed,
-            70.0,
-            50.0,
-            2.5,
-            3.0,
-            0.1,
-            0.3,
-            15.0,
-            25.0,
+            0.3, 0.35, 0.4, 0.37, 60.0, apex_speed, 70.0, 50.0, 2.5, 3.0, 0.1, 0.3, 15.0, 25.0,
+            apex_speed, 3, 0.0, 0.0, 0.0, 0.0, 0.0, 0, None, 0.0, 0.0, 0.0, 0.0, 0.0,
         )
     }
 
@@ -163,7 +340,8 @@ mod tests {
         let braking_zones = vec![make_braking_metrics()];
         let corners = vec![make_corner_metrics(45.0), make_corner_metrics(55.0)];
 
-        let metrics = LapMetrics::from_detection(1, Some(90.5), braking_zones, corners, 100.0, 30.0);
+        let metrics =
+            LapMetrics::from_detection(1, Some(90.5), braking_zones, corners, 100.0, 30.0, false);
 
         assert_eq!(metrics.lap_number, 1);
         assert_eq!(metrics.lap_time, Some(90.5));
@@ -172,11 +350,133 @@ mod tests {
         assert_eq!(metrics.average_corner_speed, 50.0); // (45 + 55) / 2
         assert_eq!(metrics.max_speed, 100.0);
         assert_eq!(metrics.min_speed, 30.0);
+        assert!(!metrics.contains_pit);
     }
 
     #[test]
     fn test_empty_corners_average() {
-        let metrics = LapMetrics::from_detection(1, None, vec![], vec![], 100.0, 30.0);
+        let metrics = LapMetrics::from_detection(1, None, vec![], vec![], 100.0, 30.0, false);
         assert_eq!(metrics.average_corner_speed, 0.0);
     }
+
+    #[test]
+    fn test_json_value_has_schema_version_and_fields() {
+        let metrics = LapMetrics::from_detection(
+            3,
+            Some(92.1),
+            vec![make_braking_metrics()],
+            vec![make_corner_metrics(45.0)],
+            100.0,
+            30.0,
+            false,
+        );
+
+        let value = metrics.to_json_value();
+
+        assert_eq!(value["schema_version"], METRICS_SCHEMA_VERSION);
+        assert_eq!(value["lap_number"], 3);
+        assert_eq!(value["lap_time"], 92.1);
+        assert_eq!(value["total_corners"], 1);
+        assert_eq!(value["braking_zones"][0]["braking_point_speed"], 80.0);
+        assert_eq!(value["corners"][0]["apex_speed"], 45.0);
+    }
+
+    #[test]
+    fn test_json_value_round_trip() {
+        let metrics = LapMetrics::from_detection(
+            1,
+            None,
+            vec![make_braking_metrics()],
+            vec![make_corner_metrics(45.0), make_corner_metrics(55.0)],
+            100.0,
+            30.0,
+            true,
+        );
+
+        let parsed = LapMetrics::from_json_value(metrics.to_json_value()).unwrap();
+        assert_eq!(parsed, metrics);
+    }
+
+    #[test]
+    fn test_newer_schema_version_is_rejected() {
+        let metrics = LapMetrics::from_detection(1, None, vec![], vec![], 100.0, 30.0, false);
+        let mut value = metrics.to_json_value();
+        value["schema_version"] = (METRICS_SCHEMA_VERSION + 1).into();
+
+        assert!(LapMetrics::from_json_value(value).is_err());
+    }
+
+    /// Lap metrics as the server API defines them, without the fields only
+    /// the core produces.
+    fn api_lap_json() -> serde_json::Value {
+        serde_json::json!({
+            "lap_number": 4,
+            "lap_time": 91.3,
+            "braking_zones": [{
+                "braking_point_distance": 0.5,
+                "braking_point_speed": 80.0,
+                "end_distance": 0.55,
+                "max_brake_pressure": 0.9,
+                "braking_duration": 2.5,
+                "minimum_speed": 40.0,
+                "initial_deceleration": -15.0,
+                "average_deceleration": -12.0,
+                "braking_efficiency": 13.3,
+                "has_trail_braking": false,
+                "trail_brake_distance": 0.0,
+                "trail_brake_percentage": 0.0,
+            }],
+            "corners": [{
+                "turn_in_distance": 0.3,
+                "apex_distance": 0.35,
+                "exit_distance": 0.4,
+                "throttle_application_distance": 0.37,
+                "turn_in_speed": 60.0,
+                "apex_speed": 45.0,
+                "exit_speed": 70.0,
+                "throttle_application_speed": 50.0,
+                "max_lateral_g": 2.5,
+                "time_in_corner": 3.0,
+                "corner_distance": 0.1,
+                "max_steering_angle": 0.3,
+                "speed_loss": 15.0,
+                "speed_gain": 25.0,
+            }],
+            "total_corners": 1,
+            "total_braking_zones": 1,
+            "average_corner_speed": 45.0,
+            "max_speed": 100.0,
+            "min_speed": 30.0,
+        })
+    }
+
+    #[test]
+    fn test_api_json_round_trip() {
+        let api = api_lap_json();
+
+        let metrics: LapMetrics = serde_json::from_value(api.clone()).unwrap();
+
+        assert_eq!(metrics.lap_number, 4);
+        assert_eq!(metrics.braking_zones[0].minimum_speed, 40.0);
+        assert_eq!(metrics.corners[0].apex_speed, 45.0);
+        assert!(!metrics.contains_pit);
+        assert!(metrics.sector_times.is_empty());
+
+        // Every field the API defines comes back out unchanged
+        let value = serde_json::to_value(&metrics).unwrap();
+        for (key, expected) in api.as_object().unwrap() {
+            if key == "braking_zones" || key == "corners" {
+                let expected = expected[0].as_object().unwrap();
+                for (field, expected) in expected {
+                    assert_eq!(&value[key][0][field], expected, "{key}.{field}");
+                }
+            } else {
+                assert_eq!(&value[key], expected, "{key}");
+            }
+        }
+        assert_eq!(
+            serde_json::from_value::<LapMetrics>(value).unwrap(),
+            metrics
+        );
+    }
 }
@@ -1,9 +1,106 @@
 //! Result types for telemetry analysis.
+//!
+//! Every result class compares by value from Python (`==`) and converts to a
+//! plain dict with `to_dict()`. They hold floats, so they are not hashable.
 
 mod braking;
 mod corner;
+mod diff;
 mod lap;
+mod pit;
+mod session;
+mod shift;
 
 pub use braking::{BrakingMetrics, BrakingMetricsBuilder};
 pub use corner::{CornerMetrics, CornerMetricsBuilder};
-pub use lap::LapMetrics;
+pub use diff::{BrakingDelta, CornerDelta, LapMetricsDiff, MAX_MATCH_DISTANCE};
+pub use lap::{LapMetrics, METRICS_SCHEMA_VERSION};
+pub use pit::{PitStop, PitStopBuilder};
+pub use session::SessionMetrics;
+pub use shift::{ShiftEvent, ShiftEventBuilder};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyList, PyString};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Convert a result into a Python dict of its fields.
+///
+/// Nested results become nested dicts and lists. Non-finite floats, which
+/// have no JSON representation, become `None`.
+pub(crate) fn to_py_dict<'py, T: Serialize>(
+    py: Python<'py>,
+    value: &T,
+) -> PyResult<Bound<'py, PyDict>> {
+    let value = serde_json::to_value(value).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(json_to_py(py, &value)?.downcast_into::<PyDict>()?)
+}
+
+fn json_to_py<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    Ok(match value {
+        Value::Null => py.None().into_bound(py),
+        Value::Bool(value) => PyBool::new(py, *value).to_owned().into_any(),
+        Value::Number(number) => match (number.as_i64(), number.as_u64()) {
+            (Some(value), _) => value.into_pyobject(py)?.into_any(),
+            (None, Some(value)) => value.into_pyobject(py)?.into_any(),
+            _ => number.as_f64().into_pyobject(py)?.into_any(),
+        },
+        Value::String(value) => PyString::new(py, value).into_any(),
+        Value::Array(items) => {
+            let items = items
+                .iter()
+                .map(|item| json_to_py(py, item))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, items)?.into_any()
+        }
+        Value::Object(fields) => {
+            let dict = PyDict::new(py);
+            for (key, value) in fields {
+                dict.set_item(key, json_to_py(py, value)?)?;
+            }
+            dict.into_any()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_dict_converts_nested_results() {
+        pyo3::prepare_freethreaded_python();
+        let metrics = LapMetrics::from_detection(3, Some(91.5), vec![], vec![], 70.0, 20.0, false)
+            .with_shifts(vec![ShiftEvent::new(4, 3, 6500.0, 0.2, true)]);
+
+        Python::with_gil(|py| {
+            let dict = to_py_dict(py, &metrics).unwrap();
+            let field = |name: &str| dict.get_item(name).unwrap().expect("field should exist");
+
+            assert_eq!(field("lap_number").extract::<i32>().unwrap(), 3);
+            assert_eq!(field("max_speed").extract::<f64>().unwrap(), 70.0);
+            assert!(field("braking_zones").is_instance_of::<PyList>());
+
+            let shift = field("shifts").get_item(0).unwrap();
+            let blip = shift.get_item("throttle_blip").unwrap();
+            assert!(blip.extract::<bool>().unwrap());
+        });
+    }
+
+    #[test]
+    fn test_python_equality_compares_values() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let shift = Bound::new(py, ShiftEvent::new(4, 3, 6500.0, 0.2, true)).unwrap();
+            let same = Bound::new(py, ShiftEvent::new(4, 3, 6500.0, 0.2, true)).unwrap();
+            let other = Bound::new(py, ShiftEvent::new(3, 4, 7800.0, 0.3, false)).unwrap();
+
+            assert!(shift.eq(&same).unwrap());
+            assert!(!shift.eq(&other).unwrap());
+            // Floats make results unsuitable as dict keys
+            assert!(shift.hash().is_err());
+        });
+    }
+}
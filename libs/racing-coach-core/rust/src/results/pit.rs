@@ -0,0 +1,105 @@
+//! Pit stop result structure.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde::{Deserialize, Serialize};
+
+use super::to_py_dict;
+
+/// Metrics for a single visit to pit road.
+///
+/// Emitted when the car leaves pit road, covering the span from pit entry to pit exit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
+pub struct PitStop {
+    /// Lap distance where the car entered pit road (normalized 0-1)
+    #[pyo3(get)]
+    pub entry_distance: f64,
+
+    /// Lap distance where the car left pit road (normalized 0-1)
+    #[pyo3(get)]
+    pub exit_distance: f64,
+
+    /// Time spent on pit road in seconds
+    #[pyo3(get)]
+    pub time_on_pit_road: f64,
+
+    /// Whether the car came to a stop while on pit road
+    #[pyo3(get)]
+    pub stopped: bool,
+}
+
+#[pymethods]
+impl PitStop {
+    /// Create a new PitStop instance.
+    #[new]
+    #[pyo3(signature = (entry_distance, exit_distance, time_on_pit_road, stopped))]
+    pub fn new(
+        entry_distance: f64,
+        exit_distance: f64,
+        time_on_pit_road: f64,
+        stopped: bool,
+    ) -> Self {
+        Self {
+            entry_distance,
+            exit_distance,
+            time_on_pit_road,
+            stopped,
+        }
+    }
+
+    /// All fields as a dict, with nested results as dicts.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        to_py_dict(py, self)
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "PitStop(dist={:.3}->{:.3}, time={:.1}s, stopped={})",
+            self.entry_distance, self.exit_distance, self.time_on_pit_road, self.stopped
+        )
+    }
+}
+
+/// Builder for accumulating pit stop data while on pit road.
+#[derive(Debug)]
+pub struct PitStopBuilder {
+    pub entry_idx: usize,
+    pub entry_distance: f64,
+    pub entry_timestamp: f64,
+    pub min_speed: f64,
+}
+
+impl PitStopBuilder {
+    /// Create a new builder from the pit entry frame.
+    pub fn new(idx: usize, distance: f64, speed: f64, timestamp: f64) -> Self {
+        Self {
+            entry_idx: idx,
+            entry_distance: distance,
+            entry_timestamp: timestamp,
+            min_speed: speed,
+        }
+    }
+
+    /// Update builder with a new frame on pit road.
+    pub fn update(&mut self, speed: f64) {
+        self.min_speed = self.min_speed.min(speed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_tracks_min_speed() {
+        let mut builder = PitStopBuilder::new(5, 0.95, 22.0, 100.0);
+        builder.update(10.0);
+        builder.update(0.0);
+        builder.update(15.0);
+
+        assert_eq!(builder.entry_idx, 5);
+        assert_eq!(builder.min_speed, 0.0);
+    }
+}
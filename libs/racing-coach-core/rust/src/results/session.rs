@@ -0,0 +1,82 @@
+//! Session-level aggregate metrics.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::TrackConditions;
+
+use super::to_py_dict;
+
+/// Lap time statistics across a session.
+///
+/// Only valid laps count: timed laps that didn't touch pit road. Every
+/// statistic is `None` (or empty) when the session has no valid laps.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[pyclass(eq)]
+pub struct SessionMetrics {
+    /// Number of laps aggregated
+    #[pyo3(get)]
+    pub total_laps: usize,
+
+    /// Number of laps that counted towards the statistics
+    #[pyo3(get)]
+    pub valid_laps: usize,
+
+    /// Fastest lap time in seconds
+    #[pyo3(get)]
+    pub best_lap_time: Option<f64>,
+
+    /// Lap number of the fastest lap
+    #[pyo3(get)]
+    pub best_lap_number: Option<i32>,
+
+    /// Mean lap time in seconds
+    #[pyo3(get)]
+    pub average_lap_time: Option<f64>,
+
+    /// Standard deviation of lap times in seconds (lower is more consistent)
+    #[pyo3(get)]
+    pub lap_time_std_dev: Option<f64>,
+
+    /// Fastest time in each sector across all valid laps, in seconds
+    #[pyo3(get)]
+    pub best_sector_times: Vec<f64>,
+
+    /// Sum of the best sector times: the lap time possible by combining them
+    #[pyo3(get)]
+    pub theoretical_best_lap_time: Option<f64>,
+
+    /// How track and weather conditions evolved, if session frames were given
+    #[pyo3(get)]
+    #[serde(default)]
+    pub track_conditions: Option<TrackConditions>,
+}
+
+impl SessionMetrics {
+    /// Attach the conditions the session was driven in.
+    pub fn with_track_conditions(mut self, conditions: TrackConditions) -> Self {
+        self.track_conditions = Some(conditions);
+        self
+    }
+}
+
+#[pymethods]
+impl SessionMetrics {
+    /// All fields as a dict, with nested results as dicts.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        to_py_dict(py, self)
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "SessionMetrics(valid_laps={}/{}, best={:?}, average={:?}, std_dev={:?})",
+            self.valid_laps,
+            self.total_laps,
+            self.best_lap_time,
+            self.average_lap_time,
+            self.lap_time_std_dev
+        )
+    }
+}
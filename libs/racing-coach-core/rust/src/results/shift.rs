@@ -0,0 +1,107 @@
+//! Gear shift result structure.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde::{Deserialize, Serialize};
+
+use super::to_py_dict;
+
+/// A single gear change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass(eq)]
+pub struct ShiftEvent {
+    /// Gear before the shift
+    #[pyo3(get)]
+    pub from_gear: i32,
+
+    /// Gear after the shift
+    #[pyo3(get)]
+    pub to_gear: i32,
+
+    /// Engine RPM on the last frame in the old gear
+    #[pyo3(get)]
+    pub rpm: f64,
+
+    /// Lap distance of the first frame in the new gear (normalized 0-1)
+    #[pyo3(get)]
+    pub distance_pct: f64,
+
+    /// Whether the throttle was blipped around a downshift (always false for
+    /// upshifts)
+    #[pyo3(get)]
+    pub throttle_blip: bool,
+}
+
+#[pymethods]
+impl ShiftEvent {
+    /// Create a new ShiftEvent instance.
+    #[new]
+    #[pyo3(signature = (from_gear, to_gear, rpm, distance_pct, throttle_blip=false))]
+    pub fn new(
+        from_gear: i32,
+        to_gear: i32,
+        rpm: f64,
+        distance_pct: f64,
+        throttle_blip: bool,
+    ) -> Self {
+        Self {
+            from_gear,
+            to_gear,
+            rpm,
+            distance_pct,
+            throttle_blip,
+        }
+    }
+
+    /// Whether the shift went to a lower gear.
+    pub fn is_downshift(&self) -> bool {
+        self.to_gear < self.from_gear
+    }
+
+    /// All fields as a dict, with nested results as dicts.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        to_py_dict(py, self)
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "ShiftEvent({} -> {}, rpm={:.0}, dist={:.3}, blip={})",
+            self.from_gear, self.to_gear, self.rpm, self.distance_pct, self.throttle_blip
+        )
+    }
+}
+
+/// Builder for a gear change awaiting blip detection.
+#[derive(Debug)]
+pub struct ShiftEventBuilder {
+    pub shift_idx: usize,
+    pub from_gear: i32,
+    pub to_gear: i32,
+    pub rpm: f64,
+    pub distance: f64,
+}
+
+impl ShiftEventBuilder {
+    /// Create a new builder from the first frame in the new gear.
+    pub fn new(idx: usize, from_gear: i32, to_gear: i32, rpm: f64, distance: f64) -> Self {
+        Self {
+            shift_idx: idx,
+            from_gear,
+            to_gear,
+            rpm,
+            distance,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shift_direction() {
+        assert!(ShiftEvent::new(4, 3, 6500.0, 0.2, true).is_downshift());
+        assert!(!ShiftEvent::new(3, 4, 7800.0, 0.3, false).is_downshift());
+    }
+}
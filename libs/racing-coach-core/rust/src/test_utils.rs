@@ -0,0 +1,183 @@
+//! Shared helpers for building telemetry in tests.
+
+use crate::types::TelemetryFrame;
+
+/// Brake pressure held through a [`FrameSequenceBuilder::braking`] segment.
+pub const BRAKE_PRESSURE: f64 = 0.9;
+
+/// Builds a lap segment by segment, at a fixed sample rate.
+///
+/// Timestamps advance by one sample interval per frame and lap distance by
+/// the distance covered at each frame's speed, wrapping at the line, so
+/// consecutive segments join up like a real lap.
+#[derive(Debug, Clone)]
+pub struct FrameSequenceBuilder {
+    frames: Vec<TelemetryFrame>,
+    sample_rate: f64,
+    track_length: f64,
+    lap_distance: f64,
+}
+
+impl FrameSequenceBuilder {
+    /// An empty sequence at 60 Hz on a 4 km track, starting at the line.
+    pub fn new() -> Self {
+        Self {
+            frames: Vec::new(),
+            sample_rate: 60.0,
+            track_length: 4000.0,
+            lap_distance: 0.0,
+        }
+    }
+
+    /// Sample at `hz` frames per second.
+    pub fn sample_rate(mut self, hz: f64) -> Self {
+        self.sample_rate = hz;
+        self
+    }
+
+    /// Cover lap distance as on a track `meters` long.
+    pub fn track_length(mut self, meters: f64) -> Self {
+        self.track_length = meters;
+        self
+    }
+
+    /// Start the next segment at `lap_distance` (0-1).
+    pub fn starting_at(mut self, lap_distance: f64) -> Self {
+        self.lap_distance = lap_distance;
+        self
+    }
+
+    /// Flat out in a straight line at a constant `speed` (m/s).
+    pub fn straight(self, secs: f64, speed: f64) -> Self {
+        self.segment(secs, |_| {
+            TelemetryFrame::new(0.0, 1.0, speed, 0.0, 0.0, 0.0, 0.0, 0.0, false)
+        })
+    }
+
+    /// Braking at [`BRAKE_PRESSURE`] in a straight line, slowing evenly
+    /// from `from` to `to` m/s.
+    pub fn braking(self, secs: f64, from: f64, to: f64) -> Self {
+        let decel = if secs > 0.0 { (to - from) / secs } else { 0.0 };
+        self.segment(secs, |progress| {
+            let speed = from + (to - from) * progress;
+            TelemetryFrame::new(BRAKE_PRESSURE, 0.0, speed, 0.0, 0.0, 0.0, decel, 0.0, false)
+        })
+    }
+
+    /// Coasting through a corner at the current speed, holding `steering`
+    /// (radians) with `lateral` m/s² of lateral acceleration.
+    pub fn corner(self, secs: f64, lateral: f64, steering: f64) -> Self {
+        let speed = self.frames.last().map_or(0.0, |frame| frame.speed);
+        let yaw_rate = if speed > 0.0 { lateral / speed } else { 0.0 };
+        self.segment(secs, |_| {
+            TelemetryFrame::new(0.0, 0.0, speed, 0.0, steering, lateral, 0.0, 0.0, false)
+                .with_yaw_rate(yaw_rate)
+        })
+    }
+
+    /// The frames built so far.
+    pub fn build(self) -> Vec<TelemetryFrame> {
+        self.frames
+    }
+
+    /// Append `secs` worth of frames made by `frame`, which gets how far
+    /// through the segment each frame is (0 at the first, 1 at the last).
+    /// Timestamp and lap distance are filled in.
+    fn segment(mut self, secs: f64, frame: impl Fn(f64) -> TelemetryFrame) -> Self {
+        let count = (secs * self.sample_rate).round() as usize;
+        let dt = 1.0 / self.sample_rate;
+        for i in 0..count {
+            let progress = if count > 1 {
+                i as f64 / (count - 1) as f64
+            } else {
+                0.0
+            };
+            let timestamp = self.frames.len() as f64 * dt;
+            let mut next = frame(progress);
+            next.timestamp = timestamp;
+            next.lap_distance = self.lap_distance;
+            next.lap_distance_meters = self.lap_distance * self.track_length;
+            self.lap_distance = (self.lap_distance + next.speed * dt / self.track_length) % 1.0;
+            self.frames.push(next);
+        }
+        self
+    }
+}
+
+impl Default for FrameSequenceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments_have_one_frame_per_sample() {
+        let frames = FrameSequenceBuilder::new()
+            .straight(2.0, 80.0)
+            .braking(1.0, 80.0, 50.0)
+            .corner(0.5, 20.0, 0.3)
+            .build();
+
+        assert_eq!(frames.len(), 120 + 60 + 30);
+        assert_eq!(
+            FrameSequenceBuilder::new()
+                .sample_rate(30.0)
+                .straight(2.0, 80.0)
+                .build()
+                .len(),
+            60
+        );
+    }
+
+    #[test]
+    fn test_time_and_distance_advance_with_speed() {
+        let frames = FrameSequenceBuilder::new()
+            .track_length(1000.0)
+            .straight(1.0, 60.0)
+            .braking(1.0, 60.0, 30.0)
+            .build();
+
+        for pair in frames.windows(2) {
+            assert!((pair[1].timestamp - pair[0].timestamp - 1.0 / 60.0).abs() < 1e-9);
+            assert!(pair[1].lap_distance > pair[0].lap_distance);
+        }
+        // A second at 60 m/s covers 6% of a 1 km lap
+        assert!((frames[60].lap_distance - 0.06).abs() < 1e-9);
+        assert!((frames[60].lap_distance_meters - 60.0).abs() < 1e-6);
+
+        let braking = &frames[60..];
+        assert_eq!(braking[0].speed, 60.0);
+        assert_eq!(braking[59].speed, 30.0);
+        assert!(braking.iter().all(|frame| frame.brake == BRAKE_PRESSURE));
+    }
+
+    #[test]
+    fn test_distance_wraps_at_the_line() {
+        let frames = FrameSequenceBuilder::new()
+            .track_length(1000.0)
+            .starting_at(0.95)
+            .straight(1.0, 60.0)
+            .build();
+
+        assert_eq!(frames[0].lap_distance, 0.95);
+        assert!(frames.last().unwrap().lap_distance < 0.05);
+    }
+
+    #[test]
+    fn test_corner_keeps_the_current_speed() {
+        let frames = FrameSequenceBuilder::new()
+            .braking(1.0, 70.0, 40.0)
+            .corner(1.0, 20.0, -0.25)
+            .build();
+
+        let corner = &frames[60..];
+        assert!(corner.iter().all(|frame| frame.speed == 40.0));
+        assert_eq!(corner[0].steering_angle, -0.25);
+        assert_eq!(corner[0].lateral_acceleration, 20.0);
+        assert_eq!(corner[0].yaw_rate, 0.5);
+    }
+}
@@ -1,18 +1,58 @@
 //! Analysis configuration and thresholds.
 
+use std::borrow::Cow;
+
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
+use super::{TelemetryFrame, TrackCorner};
+use crate::utils::percentile;
+
+/// Names accepted by [`AnalysisConfig::preset`].
+///
+/// | Preset           | Brake (exit) | Steering (exit) | Merge gap | For                           |
+/// |------------------|--------------|-----------------|-----------|-------------------------------|
+/// | `default`        | 0.05         | 0.15            | 0         | Anything else                 |
+/// | `road_low_power` | 0.03 (0.02)  | 0.20 (0.15)     | 3         | MX-5, GR86: light, momentum   |
+/// | `gt3`            | 0.05 (0.02)  | 0.10 (0.07)     | 2         | GT3 and similar GT cars       |
+/// | `formula`        | 0.10 (0.05)  | 0.08 (0.05)     | 2         | Downforce open-wheelers       |
+/// | `oval`           | 0.05 (0.03)  | 0.05 (0.03)     | 0         | Stock cars on ovals           |
+///
+/// Every preset keeps the default throttle threshold, deceleration window and
+/// smoothing. Quicker cars turn in with less lock, so their steering
+/// thresholds are lower; low-power cars brake lightly and briefly lift off
+/// the brake mid-corner, so their zones merge over a wider gap.
+pub const PRESET_NAMES: [&str; 5] = ["default", "road_low_power", "gt3", "formula", "oval"];
+
+/// Substrings of a lowercased car name and the preset they select, checked
+/// in order.
+const CAR_PRESETS: [(&str, &str); 12] = [
+    ("gt3", "gt3"),
+    ("gte", "gt3"),
+    ("mx-5", "road_low_power"),
+    ("mx5", "road_low_power"),
+    ("miata", "road_low_power"),
+    ("gr86", "road_low_power"),
+    ("formula", "formula"),
+    ("dallara", "formula"),
+    ("indycar", "formula"),
+    ("nascar", "oval"),
+    ("late model", "oval"),
+    ("stock car", "oval"),
+];
+
 /// Configuration for telemetry analysis thresholds.
 ///
 /// All thresholds have sensible defaults that work well for most racing scenarios.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 #[pyclass]
 pub struct AnalysisConfig {
     /// Minimum brake pressure to consider as braking (default: 0.05 = 5%)
     #[pyo3(get, set)]
     pub brake_threshold: f64,
 
-    /// Minimum steering angle to consider as turning (default: 0.15 radians ≈ 8.6°)
+    /// Minimum steering angle to consider as turning (default: 0.15 radians ≈ 8.6°),
+    /// or fraction of full lock with `corner_steering_pct`
     #[pyo3(get, set)]
     pub steering_threshold: f64,
 
@@ -23,6 +63,83 @@ pub struct AnalysisConfig {
     /// Number of frames to use for initial deceleration calculation (default: 5)
     #[pyo3(get, set)]
     pub decel_window: usize,
+
+    /// Initial deceleration window in seconds, converted to frames at the
+    /// lap's sample rate. Overrides `decel_window` when set (default: None)
+    #[pyo3(get, set)]
+    pub decel_window_secs: Option<f64>,
+
+    /// Moving-average window applied to steering angle and lateral acceleration
+    /// before detection (default: 0 = no smoothing)
+    #[pyo3(get, set)]
+    pub smoothing_window: usize,
+
+    /// Brake pressure below which a braking zone ends (default: None = same
+    /// as `brake_threshold`). A lower value adds hysteresis so modulation
+    /// around the threshold doesn't split the zone.
+    #[pyo3(get, set)]
+    pub brake_exit_threshold: Option<f64>,
+
+    /// Steering angle below which a corner ends (default: None = same as
+    /// `steering_threshold`)
+    #[pyo3(get, set)]
+    pub steering_exit_threshold: Option<f64>,
+
+    /// Braking zones or corners separated by at most this many frames are
+    /// merged into one (default: 0 = no merging)
+    #[pyo3(get, set)]
+    pub merge_gap_frames: usize,
+
+    /// Merge gap in seconds, converted to frames at the lap's sample rate so
+    /// merging behaves the same at 30 Hz as at 60 Hz. Overrides
+    /// `merge_gap_frames` when set (default: None)
+    #[pyo3(get, set)]
+    pub merge_gap_secs: Option<f64>,
+
+    /// Named corner windows used to number and name detected corners
+    /// (default: empty = corners numbered in order)
+    #[pyo3(get, set)]
+    pub track_corners: Vec<TrackCorner>,
+
+    /// Speed in m/s below which frames are ignored for statistics and
+    /// detection, e.g. while parked in the garage (default: 0.0 = keep all)
+    #[pyo3(get, set)]
+    pub min_analysis_speed: f64,
+
+    /// Detect corners on `steering_pct` (input as a fraction of full lock)
+    /// instead of `steering_angle`; the steering thresholds are then
+    /// fractions too (default: false)
+    #[pyo3(get, set)]
+    pub corner_steering_pct: bool,
+
+    /// Split a corner in two where lateral G dips between two apexes (e.g. a
+    /// double-apex corner taken on constant lock), when the dip falls below
+    /// this fraction of the peak on either side (default: None = never split)
+    #[pyo3(get, set)]
+    pub apex_split_ratio: Option<f64>,
+
+    /// Throttle position counted as flat out in pedal usage (default: 0.98)
+    #[pyo3(get, set)]
+    pub full_throttle_threshold: f64,
+
+    /// Brake pressure counted as full braking in pedal usage (default: 0.9)
+    #[pyo3(get, set)]
+    pub full_brake_threshold: f64,
+
+    /// Steering rate in rad/s a reversal must reach to count as a slide
+    /// correction (default: 3.0)
+    #[pyo3(get, set)]
+    pub correction_steering_rate: f64,
+
+    /// Yaw rate in rad/s the car must reach during a reversal for it to count
+    /// as a slide correction (default: 0.3)
+    #[pyo3(get, set)]
+    pub correction_yaw_rate: f64,
+
+    /// Lap distances (0-1) splitting the lap into mini-sectors for
+    /// `sector_times` (default: empty = three equal sectors)
+    #[pyo3(get, set)]
+    pub sector_markers: Vec<f64>,
 }
 
 impl Default for AnalysisConfig {
@@ -32,26 +149,169 @@ impl Default for AnalysisConfig {
             steering_threshold: 0.15,
             throttle_threshold: 0.05,
             decel_window: 5,
+            decel_window_secs: None,
+            smoothing_window: 0,
+            brake_exit_threshold: None,
+            steering_exit_threshold: None,
+            merge_gap_frames: 0,
+            merge_gap_secs: None,
+            track_corners: Vec::new(),
+            min_analysis_speed: 0.0,
+            corner_steering_pct: false,
+            apex_split_ratio: None,
+            full_throttle_threshold: 0.98,
+            full_brake_threshold: 0.9,
+            correction_steering_rate: 3.0,
+            correction_yaw_rate: 0.3,
+            sector_markers: Vec::new(),
+        }
+    }
+}
+
+impl AnalysisConfig {
+    /// Thresholds of one of the [`PRESET_NAMES`], or `None` for any other name.
+    pub fn preset(name: &str) -> Option<Self> {
+        let (brake, brake_exit, steering, steering_exit, merge_gap_frames) = match name {
+            "default" => return Some(Self::default()),
+            "road_low_power" => (0.03, 0.02, 0.20, 0.15, 3),
+            "gt3" => (0.05, 0.02, 0.10, 0.07, 2),
+            "formula" => (0.10, 0.05, 0.08, 0.05, 2),
+            "oval" => (0.05, 0.03, 0.05, 0.03, 0),
+            _ => return None,
+        };
+        Some(Self {
+            brake_threshold: brake,
+            brake_exit_threshold: Some(brake_exit),
+            steering_threshold: steering,
+            steering_exit_threshold: Some(steering_exit),
+            merge_gap_frames,
+            ..Self::default()
+        })
+    }
+
+    /// The preset suited to a car, judged from its name as shown in the
+    /// session info (e.g. "Mazda MX-5 Cup"), or `"default"` if unrecognized.
+    pub fn preset_name_for_car(car_name: &str) -> &'static str {
+        let car_name = car_name.to_lowercase();
+        CAR_PRESETS
+            .iter()
+            .find(|(keyword, _)| car_name.contains(keyword))
+            .map_or("default", |&(_, preset)| preset)
+    }
+
+    /// Effective brake exit threshold, never above `brake_threshold`.
+    pub fn brake_exit(&self) -> f64 {
+        self.brake_exit_threshold
+            .map_or(self.brake_threshold, |exit| exit.min(self.brake_threshold))
+    }
+
+    /// Effective steering exit threshold, never above `steering_threshold`.
+    pub fn steering_exit(&self) -> f64 {
+        self.steering_exit_threshold
+            .map_or(self.steering_threshold, |exit| {
+                exit.min(self.steering_threshold)
+            })
+    }
+
+    /// The steering channel corners are detected on, see `corner_steering_pct`.
+    pub fn corner_steering(&self, frame: &TelemetryFrame) -> f64 {
+        if self.corner_steering_pct {
+            frame.steering_pct
+        } else {
+            frame.steering_angle
+        }
+    }
+
+    /// Merge gap in frames for `frames`, see `merge_gap_secs`.
+    ///
+    /// Falls back to `merge_gap_frames` when the sample interval can't be
+    /// measured (fewer than two distinct timestamps).
+    pub fn merge_gap_for(&self, frames: &[TelemetryFrame]) -> usize {
+        match (self.merge_gap_secs, sample_interval(frames)) {
+            (Some(secs), Some(interval)) => (secs.max(0.0) / interval).round() as usize,
+            _ => self.merge_gap_frames,
+        }
+    }
+
+    /// Initial deceleration window in frames for `frames`, see
+    /// `decel_window_secs`.
+    ///
+    /// Falls back to `decel_window` when the sample interval can't be
+    /// measured.
+    pub fn decel_window_for(&self, frames: &[TelemetryFrame]) -> usize {
+        match (self.decel_window_secs, sample_interval(frames)) {
+            (Some(secs), Some(interval)) => (secs.max(0.0) / interval).round() as usize,
+            _ => self.decel_window,
         }
     }
+
+    /// The frames fast enough to analyze, see `min_analysis_speed`.
+    ///
+    /// Borrows `frames` when none are dropped.
+    pub fn analysis_frames<'a>(&self, frames: &'a [TelemetryFrame]) -> Cow<'a, [TelemetryFrame]> {
+        if frames
+            .iter()
+            .all(|frame| frame.speed >= self.min_analysis_speed)
+        {
+            return Cow::Borrowed(frames);
+        }
+        Cow::Owned(
+            frames
+                .iter()
+                .filter(|frame| frame.speed >= self.min_analysis_speed)
+                .copied()
+                .collect(),
+        )
+    }
 }
 
 #[pymethods]
 impl AnalysisConfig {
     /// Create a new AnalysisConfig with custom thresholds.
     #[new]
-    #[pyo3(signature = (brake_threshold=0.05, steering_threshold=0.15, throttle_threshold=0.05, decel_window=5))]
+    #[pyo3(signature = (brake_threshold=0.05, steering_threshold=0.15, throttle_threshold=0.05, decel_window=5, smoothing_window=0, brake_exit_threshold=None, steering_exit_threshold=None, merge_gap_frames=0, track_corners=Vec::new(), min_analysis_speed=0.0, merge_gap_secs=None, corner_steering_pct=false, apex_split_ratio=None, full_throttle_threshold=0.98, full_brake_threshold=0.9, decel_window_secs=None, correction_steering_rate=3.0, correction_yaw_rate=0.3, sector_markers=Vec::new()))]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         brake_threshold: f64,
         steering_threshold: f64,
         throttle_threshold: f64,
         decel_window: usize,
+        smoothing_window: usize,
+        brake_exit_threshold: Option<f64>,
+        steering_exit_threshold: Option<f64>,
+        merge_gap_frames: usize,
+        track_corners: Vec<TrackCorner>,
+        min_analysis_speed: f64,
+        merge_gap_secs: Option<f64>,
+        corner_steering_pct: bool,
+        apex_split_ratio: Option<f64>,
+        full_throttle_threshold: f64,
+        full_brake_threshold: f64,
+        decel_window_secs: Option<f64>,
+        correction_steering_rate: f64,
+        correction_yaw_rate: f64,
+        sector_markers: Vec<f64>,
     ) -> Self {
         Self {
             brake_threshold,
             steering_threshold,
             throttle_threshold,
             decel_window,
+            decel_window_secs,
+            smoothing_window,
+            brake_exit_threshold,
+            steering_exit_threshold,
+            merge_gap_frames,
+            merge_gap_secs,
+            track_corners,
+            min_analysis_speed,
+            corner_steering_pct,
+            apex_split_ratio,
+            full_throttle_threshold,
+            full_brake_threshold,
+            correction_steering_rate,
+            correction_yaw_rate,
+            sector_markers,
         }
     }
 
@@ -60,6 +320,35 @@ impl AnalysisConfig {
     pub fn defaults() -> Self {
         Self::default()
     }
+
+    /// Create a config from a named preset (see `PRESET_NAMES`).
+    #[staticmethod]
+    #[pyo3(name = "preset")]
+    fn py_preset(name: &str) -> PyResult<Self> {
+        Self::preset(name).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "unknown preset {name:?}, expected one of: {}",
+                PRESET_NAMES.join(", ")
+            ))
+        })
+    }
+
+    /// Create a config from the preset suited to a car, by its name.
+    #[staticmethod]
+    fn for_car(car_name: &str) -> Self {
+        Self::preset(Self::preset_name_for_car(car_name))
+            .expect("car presets are all in PRESET_NAMES")
+    }
+}
+
+/// Median time between consecutive frames, ignoring repeated timestamps.
+fn sample_interval(frames: &[TelemetryFrame]) -> Option<f64> {
+    let intervals: Vec<f64> = frames
+        .windows(2)
+        .map(|pair| pair[1].timestamp - pair[0].timestamp)
+        .filter(|&dt| dt > 0.0)
+        .collect();
+    (!intervals.is_empty()).then(|| percentile(&intervals, 50.0))
 }
 
 #[cfg(test)]
@@ -73,14 +362,154 @@ mod tests {
         assert_eq!(config.steering_threshold, 0.15);
         assert_eq!(config.throttle_threshold, 0.05);
         assert_eq!(config.decel_window, 5);
+        assert_eq!(config.smoothing_window, 0);
+        assert_eq!(config.brake_exit(), 0.05);
+        assert_eq!(config.steering_exit(), 0.15);
+        assert_eq!(config.merge_gap_frames, 0);
+        assert!(config.track_corners.is_empty());
+        assert_eq!(config.min_analysis_speed, 0.0);
     }
 
     #[test]
     fn test_custom_config() {
-        let config = AnalysisConfig::new(0.1, 0.2, 0.1, 10);
+        let config = AnalysisConfig::new(
+            0.1,
+            0.2,
+            0.1,
+            10,
+            3,
+            Some(0.02),
+            Some(0.5),
+            4,
+            vec![TrackCorner::new("Turn 1".to_string(), 0.05, 0.1)],
+            1.5,
+            None,
+            false,
+            None,
+            0.95,
+            0.8,
+            Some(0.1),
+            2.0,
+            0.5,
+            vec![0.25, 0.5],
+        );
         assert_eq!(config.brake_threshold, 0.1);
         assert_eq!(config.steering_threshold, 0.2);
         assert_eq!(config.throttle_threshold, 0.1);
         assert_eq!(config.decel_window, 10);
+        assert_eq!(config.smoothing_window, 3);
+        assert_eq!(config.brake_exit(), 0.02);
+        // Exit thresholds above the enter threshold are clamped
+        assert_eq!(config.steering_exit(), 0.2);
+        assert_eq!(config.merge_gap_frames, 4);
+        assert_eq!(config.track_corners.len(), 1);
+        assert_eq!(config.min_analysis_speed, 1.5);
+        assert_eq!(config.full_throttle_threshold, 0.95);
+        assert_eq!(config.full_brake_threshold, 0.8);
+        assert_eq!(config.decel_window_secs, Some(0.1));
+        assert_eq!(config.correction_steering_rate, 2.0);
+        assert_eq!(config.correction_yaw_rate, 0.5);
+        assert_eq!(config.sector_markers, [0.25, 0.5]);
+    }
+
+    #[test]
+    fn test_merge_gap_in_seconds() {
+        let frames_at = |hz: f64| -> Vec<TelemetryFrame> {
+            (0..10)
+                .map(|i| {
+                    TelemetryFrame::new(0.0, 0.0, 50.0, 0.0, 0.0, 0.0, 0.0, i as f64 / hz, false)
+                })
+                .collect()
+        };
+        let config = AnalysisConfig {
+            merge_gap_frames: 2,
+            merge_gap_secs: Some(0.1),
+            ..AnalysisConfig::default()
+        };
+
+        assert_eq!(config.merge_gap_for(&frames_at(60.0)), 6);
+        assert_eq!(config.merge_gap_for(&frames_at(30.0)), 3);
+        // No measurable interval falls back to the frame count
+        assert_eq!(config.merge_gap_for(&frames_at(60.0)[..1]), 2);
+
+        let frames_only = AnalysisConfig {
+            merge_gap_frames: 2,
+            ..AnalysisConfig::default()
+        };
+        assert_eq!(frames_only.merge_gap_for(&frames_at(60.0)), 2);
+    }
+
+    #[test]
+    fn test_decel_window_in_seconds() {
+        let frames_at = |hz: f64| -> Vec<TelemetryFrame> {
+            (0..10)
+                .map(|i| {
+                    TelemetryFrame::new(0.0, 0.0, 50.0, 0.0, 0.0, 0.0, 0.0, i as f64 / hz, false)
+                })
+                .collect()
+        };
+        let config = AnalysisConfig {
+            decel_window_secs: Some(0.1),
+            ..AnalysisConfig::default()
+        };
+
+        assert_eq!(config.decel_window_for(&frames_at(60.0)), 6);
+        assert_eq!(config.decel_window_for(&frames_at(30.0)), 3);
+        assert_eq!(config.decel_window_for(&frames_at(60.0)[..1]), 5);
+        assert_eq!(
+            AnalysisConfig::default().decel_window_for(&frames_at(60.0)),
+            5
+        );
+    }
+
+    #[test]
+    fn test_presets() {
+        let thresholds = |name| {
+            let config = AnalysisConfig::preset(name).unwrap();
+            (
+                config.brake_threshold,
+                config.brake_exit(),
+                config.steering_threshold,
+                config.steering_exit(),
+                config.merge_gap_frames,
+            )
+        };
+
+        assert_eq!(thresholds("default"), (0.05, 0.05, 0.15, 0.15, 0));
+        assert_eq!(thresholds("road_low_power"), (0.03, 0.02, 0.20, 0.15, 3));
+        assert_eq!(thresholds("gt3"), (0.05, 0.02, 0.10, 0.07, 2));
+        assert_eq!(thresholds("formula"), (0.10, 0.05, 0.08, 0.05, 2));
+        assert_eq!(thresholds("oval"), (0.05, 0.03, 0.05, 0.03, 0));
+
+        for name in PRESET_NAMES {
+            let config = AnalysisConfig::preset(name).unwrap();
+            assert_eq!(config.throttle_threshold, 0.05, "{name}");
+            assert_eq!(config.decel_window, 5, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_unknown_preset_is_none() {
+        assert!(AnalysisConfig::preset("rallycross").is_none());
+        assert!(AnalysisConfig::preset("GT3").is_none());
+        assert!(AnalysisConfig::preset("gt-3").is_none());
+    }
+
+    #[test]
+    fn test_preset_for_car() {
+        for (car_name, preset) in [
+            ("Mazda MX-5 Cup", "road_low_power"),
+            ("Toyota GR86", "road_low_power"),
+            ("Ferrari 296 GT3", "gt3"),
+            ("Dallara F3", "formula"),
+            ("NASCAR Cup Series Next Gen Chevrolet Camaro ZL1", "oval"),
+            ("Skip Barber", "default"),
+        ] {
+            assert_eq!(AnalysisConfig::preset_name_for_car(car_name), preset);
+        }
+
+        for (_, preset) in CAR_PRESETS {
+            assert!(PRESET_NAMES.contains(&preset), "{preset}");
+        }
     }
 }
@@ -1,6 +1,76 @@
 //! Telemetry frame input structure.
 
+use std::collections::HashMap;
+
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyType};
+
+/// Tire positions in the order [`TireTemps`] stores them, named as in the API.
+pub const TIRE_CORNERS: [&str; 4] = ["LF", "RF", "LR", "RR"];
+
+/// Tread positions across a tire, named as in the API.
+const TREAD_POSITIONS: [&str; 3] = ["left", "middle", "right"];
+
+/// Tire carcass temperatures in Celsius, mirroring the API's `tire_temps`.
+///
+/// Each corner holds `[left, middle, right]` across the tread as seen from
+/// the driver's seat, so the inner edge is `right` on the left-side tires and
+/// `left` on the right-side tires.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TireTemps {
+    pub lf: [f64; 3],
+    pub rf: [f64; 3],
+    pub lr: [f64; 3],
+    pub rr: [f64; 3],
+}
+
+impl TireTemps {
+    /// Temperatures per corner in [`TIRE_CORNERS`] order.
+    pub fn corners(&self) -> [[f64; 3]; 4] {
+        [self.lf, self.rf, self.lr, self.rr]
+    }
+
+    /// Whether no temperatures were recorded (all zero).
+    pub fn is_empty(&self) -> bool {
+        self.corners().iter().flatten().all(|&temp| temp == 0.0)
+    }
+
+    /// Read the API's `{"LF": {"left": .., "middle": .., "right": ..}, ..}`
+    /// layout. Missing corners or positions read as 0.0.
+    pub fn from_map(map: &HashMap<String, HashMap<String, f64>>) -> Self {
+        let corner = |name: &str| {
+            let temps = map.get(name);
+            TREAD_POSITIONS.map(|position| {
+                temps
+                    .and_then(|temps| temps.get(position))
+                    .copied()
+                    .unwrap_or(0.0)
+            })
+        };
+        Self {
+            lf: corner("LF"),
+            rf: corner("RF"),
+            lr: corner("LR"),
+            rr: corner("RR"),
+        }
+    }
+
+    /// The API's nested map layout.
+    pub fn to_map(&self) -> HashMap<String, HashMap<String, f64>> {
+        TIRE_CORNERS
+            .iter()
+            .zip(self.corners())
+            .map(|(corner, temps)| {
+                let temps = TREAD_POSITIONS
+                    .iter()
+                    .zip(temps)
+                    .map(|(position, temp)| (position.to_string(), temp))
+                    .collect();
+                (corner.to_string(), temps)
+            })
+            .collect()
+    }
+}
 
 /// A single telemetry frame containing all data needed for analysis.
 ///
@@ -14,6 +84,9 @@ pub struct TelemetryFrame {
     pub brake: f64,
     #[pyo3(get)]
     pub steering_angle: f64,
+    /// Steering input as a fraction of full lock, positive left (0.0 when unknown)
+    #[pyo3(get)]
+    pub steering_pct: f64,
     #[pyo3(get)]
     pub throttle: f64,
     #[pyo3(get)]
@@ -22,6 +95,9 @@ pub struct TelemetryFrame {
     // Warm path: accessed for event metrics
     #[pyo3(get)]
     pub lap_distance: f64,
+    /// Meters from the start/finish line (0.0 when unknown)
+    #[pyo3(get)]
+    pub lap_distance_meters: f64,
     #[pyo3(get)]
     pub timestamp: f64,
 
@@ -30,11 +106,109 @@ pub struct TelemetryFrame {
     pub lateral_acceleration: f64,
     #[pyo3(get)]
     pub longitudinal_acceleration: f64,
+    /// Yaw rate in rad/s, positive turning left (0.0 when unknown)
+    #[pyo3(get)]
+    pub yaw_rate: f64,
+    #[pyo3(get)]
+    pub on_pit_road: bool,
+    /// Current gear (-1 = reverse, 0 = neutral)
+    #[pyo3(get)]
+    pub gear: i32,
+    /// Engine RPM (0.0 when unknown)
+    #[pyo3(get)]
+    pub rpm: f64,
+    /// Track temperature in Celsius
+    #[pyo3(get)]
+    pub track_temp: f64,
+    /// Air temperature in Celsius
+    #[pyo3(get)]
+    pub air_temp: f64,
+    /// iRacing track wetness level (0 = unknown, 1 = dry up to 7 = extremely wet)
+    #[pyo3(get)]
+    pub track_wetness: i32,
+    /// Tire carcass temperatures (all zero when unknown)
+    pub tire_temps: TireTemps,
+    /// Fuel in the tank in liters (0.0 when unknown)
+    #[pyo3(get)]
+    pub fuel_level: f64,
 }
 
 #[pymethods]
 impl TelemetryFrame {
-    /// Create a new TelemetryFrame.
+    /// Create a new TelemetryFrame (Python constructor).
+    ///
+    /// Every field defaults to zero (or false, or no tire temperatures), so
+    /// tests can set only the channels they care about.
+    #[new]
+    #[pyo3(signature = (brake=0.0, throttle=0.0, speed=0.0, lap_distance=0.0, steering_angle=0.0, lateral_acceleration=0.0, longitudinal_acceleration=0.0, timestamp=0.0, on_pit_road=false, gear=0, track_temp=0.0, air_temp=0.0, track_wetness=0, tire_temps=None, lap_distance_meters=0.0, yaw_rate=0.0, rpm=0.0, fuel_level=0.0, steering_pct=0.0))]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
+        brake: f64,
+        throttle: f64,
+        speed: f64,
+        lap_distance: f64,
+        steering_angle: f64,
+        lateral_acceleration: f64,
+        longitudinal_acceleration: f64,
+        timestamp: f64,
+        on_pit_road: bool,
+        gear: i32,
+        track_temp: f64,
+        air_temp: f64,
+        track_wetness: i32,
+        tire_temps: Option<HashMap<String, HashMap<String, f64>>>,
+        lap_distance_meters: f64,
+        yaw_rate: f64,
+        rpm: f64,
+        fuel_level: f64,
+        steering_pct: f64,
+    ) -> Self {
+        let frame = Self::new(
+            brake,
+            throttle,
+            speed,
+            lap_distance,
+            steering_angle,
+            lateral_acceleration,
+            longitudinal_acceleration,
+            timestamp,
+            on_pit_road,
+        )
+        .with_gear(gear)
+        .with_conditions(track_temp, air_temp, track_wetness)
+        .with_lap_distance_meters(lap_distance_meters)
+        .with_yaw_rate(yaw_rate)
+        .with_rpm(rpm)
+        .with_fuel_level(fuel_level)
+        .with_steering_pct(steering_pct);
+        match tire_temps {
+            Some(temps) => frame.with_tire_temps(TireTemps::from_map(&temps)),
+            None => frame,
+        }
+    }
+
+    /// Create a frame from a dict of constructor keyword arguments.
+    ///
+    /// Missing keys take their defaults; unknown keys raise `TypeError`.
+    #[classmethod]
+    fn from_dict(cls: &Bound<'_, PyType>, data: &Bound<'_, PyDict>) -> PyResult<Self> {
+        cls.call((), Some(data))?.extract()
+    }
+
+    /// Tire temperatures as `{"LF": {"left": .., "middle": .., "right": ..}, ..}`.
+    #[getter(tire_temps)]
+    fn py_tire_temps(&self) -> HashMap<String, HashMap<String, f64>> {
+        self.tire_temps.to_map()
+    }
+}
+
+impl TelemetryFrame {
+    /// Create a new TelemetryFrame in neutral with unknown RPM, track conditions,
+    /// tire temperatures, distance in meters, yaw rate, fuel level and steering
+    /// percent (see [`TelemetryFrame::with_gear`], [`TelemetryFrame::with_rpm`],
+    /// [`TelemetryFrame::with_conditions`], [`TelemetryFrame::with_tire_temps`],
+    /// [`TelemetryFrame::with_lap_distance_meters`], [`TelemetryFrame::with_yaw_rate`],
+    /// [`TelemetryFrame::with_fuel_level`] and [`TelemetryFrame::with_steering_pct`]).
     ///
     /// # Arguments
     /// * `brake` - Brake pressure (0.0-1.0)
@@ -45,8 +219,8 @@ impl TelemetryFrame {
     /// * `lateral_acceleration` - Lateral acceleration in m/s²
     /// * `longitudinal_acceleration` - Longitudinal acceleration in m/s²
     /// * `timestamp` - Timestamp in seconds
-    #[new]
-    #[pyo3(signature = (brake, throttle, speed, lap_distance, steering_angle, lateral_acceleration, longitudinal_acceleration, timestamp))]
+    /// * `on_pit_road` - Whether the car is on pit road
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         brake: f64,
         throttle: f64,
@@ -56,18 +230,80 @@ impl TelemetryFrame {
         lateral_acceleration: f64,
         longitudinal_acceleration: f64,
         timestamp: f64,
+        on_pit_road: bool,
     ) -> Self {
         Self {
             brake,
             throttle,
             speed,
             lap_distance,
+            lap_distance_meters: 0.0,
             steering_angle,
+            steering_pct: 0.0,
             lateral_acceleration,
             longitudinal_acceleration,
+            yaw_rate: 0.0,
             timestamp,
+            on_pit_road,
+            gear: 0,
+            rpm: 0.0,
+            track_temp: 0.0,
+            air_temp: 0.0,
+            track_wetness: 0,
+            tire_temps: TireTemps::default(),
+            fuel_level: 0.0,
         }
     }
+
+    /// Set the gear the car is in.
+    pub fn with_gear(mut self, gear: i32) -> Self {
+        self.gear = gear;
+        self
+    }
+
+    /// Set the engine RPM.
+    pub fn with_rpm(mut self, rpm: f64) -> Self {
+        self.rpm = rpm;
+        self
+    }
+
+    /// Set the track and weather conditions.
+    pub fn with_conditions(mut self, track_temp: f64, air_temp: f64, track_wetness: i32) -> Self {
+        self.track_temp = track_temp;
+        self.air_temp = air_temp;
+        self.track_wetness = track_wetness;
+        self
+    }
+
+    /// Set the distance from the start/finish line in meters.
+    pub fn with_lap_distance_meters(mut self, lap_distance_meters: f64) -> Self {
+        self.lap_distance_meters = lap_distance_meters;
+        self
+    }
+
+    /// Set the yaw rate in rad/s.
+    pub fn with_yaw_rate(mut self, yaw_rate: f64) -> Self {
+        self.yaw_rate = yaw_rate;
+        self
+    }
+
+    /// Set the tire carcass temperatures.
+    pub fn with_tire_temps(mut self, tire_temps: TireTemps) -> Self {
+        self.tire_temps = tire_temps;
+        self
+    }
+
+    /// Set the fuel level in liters.
+    pub fn with_fuel_level(mut self, fuel_level: f64) -> Self {
+        self.fuel_level = fuel_level;
+        self
+    }
+
+    /// Set the steering input as a fraction of full lock.
+    pub fn with_steering_pct(mut self, steering_pct: f64) -> Self {
+        self.steering_pct = steering_pct;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -76,7 +312,7 @@ mod tests {
 
     #[test]
     fn test_telemetry_frame_creation() {
-        let frame = TelemetryFrame::new(0.5, 0.0, 50.0, 0.25, 0.1, 5.0, -8.0, 100.0);
+        let frame = TelemetryFrame::new(0.5, 0.0, 50.0, 0.25, 0.1, 5.0, -8.0, 100.0, false);
         assert_eq!(frame.brake, 0.5);
         assert_eq!(frame.throttle, 0.0);
         assert_eq!(frame.speed, 50.0);
@@ -85,11 +321,81 @@ mod tests {
         assert_eq!(frame.lateral_acceleration, 5.0);
         assert_eq!(frame.longitudinal_acceleration, -8.0);
         assert_eq!(frame.timestamp, 100.0);
+        assert!(!frame.on_pit_road);
+        assert_eq!(frame.gear, 0);
+        assert_eq!(frame.with_gear(3).gear, 3);
+
+        let frame = frame.with_conditions(32.5, 21.0, 2);
+        assert_eq!(frame.track_temp, 32.5);
+        assert_eq!(frame.air_temp, 21.0);
+        assert_eq!(frame.track_wetness, 2);
+
+        assert_eq!(frame.lap_distance_meters, 0.0);
+        assert_eq!(
+            frame.with_lap_distance_meters(1250.0).lap_distance_meters,
+            1250.0
+        );
+    }
+
+    #[test]
+    fn test_tire_temps_map_round_trip() {
+        let temps = TireTemps {
+            lf: [90.0, 85.0, 80.0],
+            rf: [78.0, 84.0, 88.0],
+            lr: [70.0, 71.0, 72.0],
+            rr: [73.0, 74.0, 75.0],
+        };
+
+        let map = temps.to_map();
+        assert_eq!(map["LF"]["left"], 90.0);
+        assert_eq!(map["RF"]["right"], 88.0);
+        assert_eq!(TireTemps::from_map(&map), temps);
+
+        let mut partial = HashMap::new();
+        partial.insert(
+            "LR".to_string(),
+            HashMap::from([("middle".to_string(), 65.0)]),
+        );
+        let temps = TireTemps::from_map(&partial);
+        assert_eq!(temps.lr, [0.0, 65.0, 0.0]);
+        assert_eq!(temps.lf, [0.0; 3]);
+        assert!(!temps.is_empty());
+        assert!(TireTemps::default().is_empty());
+    }
+
+    #[test]
+    fn test_python_constructor_defaults_and_from_dict() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let cls = py.get_type::<TelemetryFrame>();
+
+            let frame: TelemetryFrame = cls.call0().unwrap().extract().unwrap();
+            assert_eq!(frame.speed, 0.0);
+            assert!(!frame.on_pit_road);
+            assert!(frame.tire_temps.is_empty());
+
+            let data = PyDict::new(py);
+            data.set_item("brake", 0.8).unwrap();
+            data.set_item("speed", 42.0).unwrap();
+            data.set_item("gear", 3).unwrap();
+            let frame: TelemetryFrame = cls
+                .call_method1("from_dict", (&data,))
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!((frame.brake, frame.speed, frame.gear), (0.8, 42.0, 3));
+            assert_eq!(frame.throttle, 0.0);
+            assert_eq!(frame.timestamp, 0.0);
+
+            data.set_item("not_a_channel", 1.0).unwrap();
+            let err = cls.call_method1("from_dict", (&data,)).unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyTypeError>(py));
+        });
     }
 
     #[test]
     fn test_telemetry_frame_is_copy() {
-        let frame = TelemetryFrame::new(0.5, 0.0, 50.0, 0.25, 0.1, 5.0, -8.0, 100.0);
+        let frame = TelemetryFrame::new(0.5, 0.0, 50.0, 0.25, 0.1, 5.0, -8.0, 100.0, false);
         let frame_copy = frame; // Copy, not move
         assert_eq!(frame.brake, frame_copy.brake); // Original still accessible
     }
@@ -2,6 +2,8 @@
 
 mod config;
 mod frame;
+mod track_map;
 
-pub use config::AnalysisConfig;
-pub use frame::TelemetryFrame;
+pub use config::{AnalysisConfig, PRESET_NAMES};
+pub use frame::{TelemetryFrame, TireTemps, TIRE_CORNERS};
+pub use track_map::TrackCorner;
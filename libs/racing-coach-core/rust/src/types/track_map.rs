@@ -0,0 +1,75 @@
+//! Named corner windows for labeling detected corners.
+
+use pyo3::prelude::*;
+
+use crate::utils::wrap_distance;
+
+/// A named stretch of track containing one corner, e.g. "Turn 3".
+///
+/// Detected corners whose apex falls inside the window take its name.
+#[derive(Debug, Clone, PartialEq)]
+#[pyclass]
+pub struct TrackCorner {
+    /// Name shown to the driver
+    #[pyo3(get, set)]
+    pub name: String,
+
+    /// Lap distance where the window starts (normalized 0-1)
+    #[pyo3(get, set)]
+    pub start_pct: f64,
+
+    /// Lap distance where the window ends (normalized 0-1). A window ending
+    /// before it starts wraps across the start/finish line.
+    #[pyo3(get, set)]
+    pub end_pct: f64,
+}
+
+#[pymethods]
+impl TrackCorner {
+    /// Create a new TrackCorner instance.
+    #[new]
+    pub fn new(name: String, start_pct: f64, end_pct: f64) -> Self {
+        Self {
+            name,
+            start_pct,
+            end_pct,
+        }
+    }
+
+    /// Whether `distance` (normalized 0-1) lies inside the window.
+    pub fn contains(&self, distance: f64) -> bool {
+        wrap_distance(distance - self.start_pct) <= wrap_distance(self.end_pct - self.start_pct)
+    }
+
+    /// String representation for debugging.
+    fn __repr__(&self) -> String {
+        format!(
+            "TrackCorner({:?}, {:.3}-{:.3})",
+            self.name, self.start_pct, self.end_pct
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let corner = TrackCorner::new("Turn 2".to_string(), 0.2, 0.3);
+
+        assert!(corner.contains(0.2));
+        assert!(corner.contains(0.25));
+        assert!(!corner.contains(0.35));
+        assert!(!corner.contains(0.1));
+    }
+
+    #[test]
+    fn test_contains_across_start_finish() {
+        let corner = TrackCorner::new("Turn 1".to_string(), 0.95, 0.05);
+
+        assert!(corner.contains(0.98));
+        assert!(corner.contains(0.02));
+        assert!(!corner.contains(0.5));
+    }
+}
@@ -24,6 +24,34 @@ pub fn wrap_distance(delta: f64) -> f64 {
     }
 }
 
+/// The `p`th percentile of `values`, with `p` in 0-100 (clamped).
+///
+/// Uses linear interpolation between the closest ranks, matching numpy's
+/// default: the percentile lies at rank `p / 100 * (count - 1)` of the
+/// sorted values. Returns 0.0 for no values.
+///
+/// # Examples
+/// ```
+/// use racing_coach_core::utils::percentile;
+///
+/// assert_eq!(percentile(&[30.0, 10.0, 20.0], 50.0), 20.0);
+/// assert_eq!(percentile(&[10.0, 20.0], 25.0), 12.5);
+/// ```
+pub fn percentile(values: &[f64], p: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let rank = p.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let fraction = rank - lower as f64;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * fraction
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
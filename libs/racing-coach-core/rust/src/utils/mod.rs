@@ -1,5 +1,7 @@
 //! Utility functions for telemetry analysis.
 
 mod math;
+mod smoothing;
 
-pub use math::wrap_distance;
+pub use math::{percentile, wrap_distance};
+pub use smoothing::{exponential_smoothing, moving_average};
@@ -0,0 +1,128 @@
+//! Low-pass filters for noisy telemetry channels.
+
+/// Smooth values with a centered moving average.
+///
+/// Each output is the mean of the `window` values centered on it. Near the
+/// edges the window is truncated to the available values, so the output has
+/// the same length as the input and is not phase-shifted.
+///
+/// A `window` of 0 or 1 returns the input unchanged. A window larger than the
+/// input averages over all available values.
+///
+/// # Examples
+/// ```
+/// use racing_coach_core::utils::moving_average;
+///
+/// assert_eq!(moving_average(&[0.0, 3.0, 0.0], 3), vec![1.5, 1.0, 1.5]);
+/// ```
+pub fn moving_average(values: &[f64], window: usize) -> Vec<f64> {
+    if window <= 1 || values.is_empty() {
+        return values.to_vec();
+    }
+
+    let before = window / 2;
+    let after = window - 1 - before;
+
+    // Prefix sums make each window O(1)
+    let mut prefix = Vec::with_capacity(values.len() + 1);
+    prefix.push(0.0);
+    for value in values {
+        prefix.push(prefix[prefix.len() - 1] + value);
+    }
+
+    (0..values.len())
+        .map(|i| {
+            let start = i.saturating_sub(before);
+            let end = (i + after + 1).min(values.len());
+            (prefix[end] - prefix[start]) / (end - start) as f64
+        })
+        .collect()
+}
+
+/// Smooth values with an exponential moving average.
+///
+/// `alpha` is the weight of each new sample (clamped to 0.0-1.0): 1.0 returns
+/// the input unchanged and smaller values smooth more heavily. The first
+/// output equals the first input.
+///
+/// # Examples
+/// ```
+/// use racing_coach_core::utils::exponential_smoothing;
+///
+/// assert_eq!(exponential_smoothing(&[0.0, 1.0, 1.0], 0.5), vec![0.0, 0.5, 0.75]);
+/// ```
+pub fn exponential_smoothing(values: &[f64], alpha: f64) -> Vec<f64> {
+    let alpha = alpha.clamp(0.0, 1.0);
+    let mut smoothed = Vec::with_capacity(values.len());
+    let mut previous = match values.first() {
+        Some(first) => *first,
+        None => return smoothed,
+    };
+
+    for value in values {
+        previous = alpha * value + (1.0 - alpha) * previous;
+        smoothed.push(previous);
+    }
+
+    smoothed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Slow sine wave with alternating +/- noise on every sample.
+    fn noisy_signal(len: usize) -> (Vec<f64>, Vec<f64>) {
+        let clean: Vec<f64> = (0..len).map(|i| (i as f64 * 0.05).sin()).collect();
+        let noisy = clean
+            .iter()
+            .enumerate()
+            .map(|(i, v)| if i % 2 == 0 { v + 0.3 } else { v - 0.3 })
+            .collect();
+        (clean, noisy)
+    }
+
+    fn error_variance(signal: &[f64], reference: &[f64]) -> f64 {
+        signal
+            .iter()
+            .zip(reference)
+            .map(|(s, r)| (s - r).powi(2))
+            .sum::<f64>()
+            / signal.len() as f64
+    }
+
+    #[test]
+    fn test_moving_average_reduces_noise() {
+        let (clean, noisy) = noisy_signal(200);
+        let smoothed = moving_average(&noisy, 4);
+
+        assert_eq!(smoothed.len(), noisy.len());
+        assert!(error_variance(&smoothed, &clean) < error_variance(&noisy, &clean) / 10.0);
+    }
+
+    #[test]
+    fn test_moving_average_edges() {
+        assert!(moving_average(&[], 5).is_empty());
+        assert_eq!(moving_average(&[1.0, 2.0], 1), vec![1.0, 2.0]);
+        assert_eq!(moving_average(&[1.0, 2.0], 0), vec![1.0, 2.0]);
+
+        // Window larger than input averages everything available
+        assert_eq!(moving_average(&[1.0, 2.0, 3.0], 10), vec![2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_exponential_smoothing_reduces_noise() {
+        let (clean, noisy) = noisy_signal(200);
+        let smoothed = exponential_smoothing(&noisy, 0.3);
+
+        assert_eq!(smoothed.len(), noisy.len());
+        assert!(error_variance(&smoothed, &clean) < error_variance(&noisy, &clean));
+    }
+
+    #[test]
+    fn test_exponential_smoothing_edges() {
+        assert!(exponential_smoothing(&[], 0.5).is_empty());
+        assert_eq!(exponential_smoothing(&[1.0, 3.0], 1.0), vec![1.0, 3.0]);
+        assert_eq!(exponential_smoothing(&[1.0, 3.0], 0.0), vec![1.0, 1.0]);
+    }
+}
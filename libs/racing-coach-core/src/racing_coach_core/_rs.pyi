@@ -1,6 +1,6 @@
 """Type stubs for the Rust extension module."""
 
-from typing import Optional
+from typing import Any, Optional
 
 # ============================================================================
 # Input Types
@@ -21,29 +21,77 @@ class TelemetryFrame:
     lap_distance: float
     """Normalized lap distance (0.0-1.0)"""
 
+    lap_distance_meters: float
+    """Meters from the start/finish line (0.0 when unknown)"""
+
     steering_angle: float
     """Steering angle in radians"""
 
+    steering_pct: float
+    """Steering input as a fraction of full lock, positive left (0.0 when unknown)"""
+
     lateral_acceleration: float
     """Lateral acceleration in m/s²"""
 
     longitudinal_acceleration: float
     """Longitudinal acceleration in m/s²"""
 
+    yaw_rate: float
+    """Yaw rate in rad/s, positive turning left (0.0 when unknown)"""
+
     timestamp: float
     """Timestamp in seconds"""
 
+    on_pit_road: bool
+    """Whether the car is on pit road"""
+
+    gear: int
+    """Current gear (-1 = reverse, 0 = neutral)"""
+
+    rpm: float
+    """Engine RPM (0.0 when unknown)"""
+
+    track_temp: float
+    """Track temperature in Celsius"""
+
+    air_temp: float
+    """Air temperature in Celsius"""
+
+    track_wetness: int
+    """iRacing track wetness level (0 = unknown, 1 = dry up to 7 = extremely wet)"""
+
+    tire_temps: dict[str, dict[str, float]]
+    """Tire temperatures in Celsius (LF,RF,LR,RR: left,middle,right; all 0.0 when unknown)"""
+
+    fuel_level: float
+    """Fuel in the tank in liters (0.0 when unknown)"""
+
     def __init__(
         self,
-        brake: float,
-        throttle: float,
-        speed: float,
-        lap_distance: float,
-        steering_angle: float,
-        lateral_acceleration: float,
-        longitudinal_acceleration: float,
-        timestamp: float,
+        brake: float = 0.0,
+        throttle: float = 0.0,
+        speed: float = 0.0,
+        lap_distance: float = 0.0,
+        steering_angle: float = 0.0,
+        lateral_acceleration: float = 0.0,
+        longitudinal_acceleration: float = 0.0,
+        timestamp: float = 0.0,
+        on_pit_road: bool = False,
+        gear: int = 0,
+        track_temp: float = 0.0,
+        air_temp: float = 0.0,
+        track_wetness: int = 0,
+        tire_temps: dict[str, dict[str, float]] | None = None,
+        lap_distance_meters: float = 0.0,
+        yaw_rate: float = 0.0,
+        rpm: float = 0.0,
+        fuel_level: float = 0.0,
+        steering_pct: float = 0.0,
     ) -> None: ...
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> TelemetryFrame:
+        """Create a frame from constructor keyword arguments; missing keys take their defaults."""
+        ...
 
 class AnalysisConfig:
     """Configuration for telemetry analysis thresholds."""
@@ -52,7 +100,7 @@ class AnalysisConfig:
     """Minimum brake pressure to consider as braking (default: 0.05)"""
 
     steering_threshold: float
-    """Minimum steering angle to consider as turning (default: 0.15 radians)"""
+    """Minimum steering angle to consider as turning (default: 0.15 radians), or fraction of full lock with corner_steering_pct"""
 
     throttle_threshold: float
     """Minimum throttle position to consider as accelerating (default: 0.05)"""
@@ -60,17 +108,124 @@ class AnalysisConfig:
     decel_window: int
     """Number of frames for initial deceleration calculation (default: 5)"""
 
+    decel_window_secs: float | None
+    """Initial deceleration window in seconds, converted to frames at the lap's sample rate; overrides decel_window (default: None)"""
+
+    smoothing_window: int
+    """Moving-average window for steering and lateral acceleration (default: 0 = off)"""
+
+    brake_exit_threshold: float | None
+    """Brake pressure below which a braking zone ends (default: None = brake_threshold)"""
+
+    steering_exit_threshold: float | None
+    """Steering angle below which a corner ends (default: None = steering_threshold)"""
+
+    merge_gap_frames: int
+    """Merge braking zones or corners separated by at most this many frames (default: 0)"""
+
+    merge_gap_secs: float | None
+    """Merge gap in seconds, converted to frames at the lap's sample rate; overrides merge_gap_frames (default: None)"""
+
+    track_corners: list[TrackCorner]
+    """Named corner windows used to number and name detected corners (default: empty)"""
+
+    min_analysis_speed: float
+    """Speed in m/s below which frames are ignored for statistics and detection (default: 0.0 = keep all)"""
+
+    corner_steering_pct: bool
+    """Detect corners on steering_pct instead of steering_angle; steering thresholds are then fractions of full lock (default: False)"""
+
+    apex_split_ratio: float | None
+    """Split a corner where lateral G dips below this fraction of the peak on either side, e.g. a double apex (default: None = never split)"""
+
+    full_throttle_threshold: float
+    """Throttle position counted as flat out in pedal usage (default: 0.98)"""
+
+    full_brake_threshold: float
+    """Brake pressure counted as full braking in pedal usage (default: 0.9)"""
+
+    correction_steering_rate: float
+    """Steering rate in rad/s a reversal must reach to count as a slide correction (default: 3.0)"""
+
+    correction_yaw_rate: float
+    """Yaw rate in rad/s the car must reach during a reversal for it to count as a slide correction (default: 0.3)"""
+
+    sector_markers: list[float]
+    """Lap distances (0-1) splitting the lap into mini-sectors for `sector_times` (default: empty = three equal sectors)"""
+
     def __init__(
         self,
         brake_threshold: float = 0.05,
         steering_threshold: float = 0.15,
         throttle_threshold: float = 0.05,
         decel_window: int = 5,
+        smoothing_window: int = 0,
+        brake_exit_threshold: float | None = None,
+        steering_exit_threshold: float | None = None,
+        merge_gap_frames: int = 0,
+        track_corners: list[TrackCorner] = [],
+        min_analysis_speed: float = 0.0,
+        merge_gap_secs: float | None = None,
+        corner_steering_pct: bool = False,
+        apex_split_ratio: float | None = None,
+        full_throttle_threshold: float = 0.98,
+        full_brake_threshold: float = 0.9,
+        decel_window_secs: float | None = None,
+        correction_steering_rate: float = 3.0,
+        correction_yaw_rate: float = 0.3,
+        sector_markers: list[float] = [],
     ) -> None: ...
     @staticmethod
     def defaults() -> AnalysisConfig:
         """Create a config with default values."""
         ...
+    @staticmethod
+    def preset(name: str) -> AnalysisConfig:
+        """Create a config from a named preset.
+
+        Args:
+            name: One of ANALYSIS_PRESETS, e.g. "gt3" or "road_low_power"
+
+        Returns:
+            AnalysisConfig with the preset's thresholds
+
+        Raises:
+            ValueError: If the name is not one of ANALYSIS_PRESETS
+        """
+        ...
+    @staticmethod
+    def for_car(car_name: str) -> AnalysisConfig:
+        """Create a config from the preset suited to a car.
+
+        Args:
+            car_name: Car name as shown in the session info, e.g. "Mazda MX-5 Cup"
+
+        Returns:
+            AnalysisConfig with the matching preset's thresholds, or the
+            defaults for an unrecognized car
+        """
+        ...
+
+ANALYSIS_PRESETS: list[str]
+"""Names accepted by `AnalysisConfig.preset`."""
+
+class TrackCorner:
+    """A named stretch of track containing one corner, e.g. "Turn 3"."""
+
+    name: str
+    """Name shown to the driver"""
+
+    start_pct: float
+    """Lap distance where the window starts (normalized 0-1)"""
+
+    end_pct: float
+    """Lap distance where the window ends (normalized 0-1); wraps across
+    start/finish when it ends before it starts"""
+
+    def __init__(self, name: str, start_pct: float, end_pct: float) -> None: ...
+    def contains(self, distance: float) -> bool:
+        """Whether distance (normalized 0-1) lies inside the window."""
+        ...
 
 # ============================================================================
 # Result Types
@@ -119,6 +274,13 @@ class BrakingMetrics:
     trail_brake_percentage: float
     """Average brake pressure during trail braking phase"""
 
+    # Location in meters
+    braking_point_distance_meters: float
+    """Meters from start/finish where braking starts (0.0 when unknown)"""
+
+    end_distance_meters: float
+    """Meters from start/finish where braking ends (0.0 when unknown)"""
+
     def __init__(
         self,
         braking_point_distance: float,
@@ -133,7 +295,13 @@ class BrakingMetrics:
         has_trail_braking: bool,
         trail_brake_distance: float,
         trail_brake_percentage: float,
+        braking_point_distance_meters: float = 0.0,
+        end_distance_meters: float = 0.0,
     ) -> None: ...
+    def to_dict(self) -> dict[str, Any]:
+        """All fields as a dict, with nested results converted to dicts."""
+        ...
+    def __eq__(self, other: object) -> bool: ...
 
 class CornerMetrics:
     """Comprehensive corner metrics for a single corner."""
@@ -164,6 +332,12 @@ class CornerMetrics:
     throttle_application_speed: float
     """Speed when throttle is first applied (m/s)"""
 
+    min_speed: float
+    """Lowest speed reached anywhere between turn-in and exit (m/s)"""
+
+    apex_gear: int
+    """Gear held at the apex"""
+
     # Performance metrics
     max_lateral_g: float
     """Maximum lateral acceleration (m/s²)"""
@@ -184,6 +358,44 @@ class CornerMetrics:
     speed_gain: float
     """Speed gained from apex to exit"""
 
+    # Key corner points in meters from start/finish (0.0 when unknown)
+    turn_in_distance_meters: float
+    """Turn-in point in meters"""
+
+    apex_distance_meters: float
+    """Apex in meters"""
+
+    exit_distance_meters: float
+    """Exit point in meters"""
+
+    throttle_application_distance_meters: float
+    """Throttle application point in meters"""
+
+    balance: float
+    """Oversteer/understeer balance index (positive = oversteer, 0.0 when yaw rate is unknown)"""
+
+    # Phase breakdown: turn-in -> apex -> throttle application -> exit
+    entry_time: float
+    """Time from turn-in to the apex (seconds)"""
+
+    mid_time: float
+    """Time from the apex to throttle application, or to the exit when the throttle isn't reapplied (seconds)"""
+
+    exit_time: float
+    """Time from throttle application to the exit (seconds)"""
+
+    mid_start_speed: float
+    """Speed at the apex, where entry ends and mid-corner begins (m/s)"""
+
+    mid_end_speed: float
+    """Speed where mid-corner ends and exit begins (m/s)"""
+
+    corner_number: int
+    """Track map position (1-based), or the next number after the map when unmatched"""
+
+    name: str | None
+    """Name of the matching track map window (None when unmatched)"""
+
     def __init__(
         self,
         turn_in_distance: float,
@@ -200,7 +412,38 @@ class CornerMetrics:
         max_steering_angle: float,
         speed_loss: float,
         speed_gain: float,
+        min_speed: float = 0.0,
+        apex_gear: int = 0,
+        turn_in_distance_meters: float = 0.0,
+        apex_distance_meters: float = 0.0,
+        exit_distance_meters: float = 0.0,
+        throttle_application_distance_meters: float = 0.0,
+        balance: float = 0.0,
+        corner_number: int = 0,
+        name: str | None = None,
+        entry_time: float = 0.0,
+        mid_time: float = 0.0,
+        exit_time: float = 0.0,
+        mid_start_speed: float = 0.0,
+        mid_end_speed: float = 0.0,
     ) -> None: ...
+    def to_dict(self) -> dict[str, Any]:
+        """All fields as a dict, with nested results converted to dicts."""
+        ...
+    def __eq__(self, other: object) -> bool: ...
+
+class CornerBalance:
+    """Handling balance of one corner."""
+
+    turn_in_distance: float
+    """Lap distance where steering input begins (turn-in point)"""
+
+    apex_distance: float
+    """Lap distance at corner apex"""
+
+    balance: float
+    """Signed balance index: positive when the car rotated more than the
+    steering asked for (oversteer), negative when less (understeer)"""
 
 class LapMetrics:
     """Aggregate metrics for an entire lap."""
@@ -232,6 +475,30 @@ class LapMetrics:
     min_speed: float
     """Minimum speed during the lap (m/s)"""
 
+    contains_pit: bool
+    """Whether the car was on pit road at any point during the lap"""
+
+    sector_times: list[float]
+    """Time in each sector in seconds (empty if the lap wasn't fully covered)"""
+
+    shifts: list[ShiftEvent]
+    """Every gear change in the lap, in order"""
+
+    smoothness: SmoothnessScore | None
+    """How smoothly the throttle, brake and steering were worked"""
+
+    fuel: FuelUsage | None
+    """Fuel burned over the lap and the range left (None without fuel data)"""
+
+    pedal_usage: PedalUsage | None
+    """Share of the lap at full throttle, full brake and coasting (None if the frames span no time)"""
+
+    total_corrections: int
+    """Number of slide corrections (rapid steering reversals) in the lap"""
+
+    custom: dict[str, float]
+    """Results of custom detectors, by detector name"""
+
     def __init__(
         self,
         lap_number: int,
@@ -243,7 +510,442 @@ class LapMetrics:
         average_corner_speed: float,
         max_speed: float,
         min_speed: float,
+        contains_pit: bool = False,
+        sector_times: list[float] = ...,
+        shifts: list[ShiftEvent] = ...,
+        smoothness: SmoothnessScore | None = None,
+        fuel: FuelUsage | None = None,
+        pedal_usage: PedalUsage | None = None,
+        total_corrections: int = 0,
+        custom: dict[str, float] = ...,
+    ) -> None: ...
+    def to_json(self) -> str:
+        """Serialize to a JSON object including a `schema_version` key."""
+        ...
+    def to_dict(self) -> dict[str, Any]:
+        """All fields as a dict, with nested results converted to dicts."""
+        ...
+    def diff(self, other: LapMetrics) -> LapMetricsDiff:
+        """Compare with another lap's metrics.
+
+        Args:
+            other: The lap to compare against
+
+        Returns:
+            Deltas of `other` minus this lap, with corners and braking zones
+            paired by lap distance
+        """
+        ...
+    def __eq__(self, other: object) -> bool: ...
+
+class CornerDelta:
+    """How a corner changed from one lap to the other (other minus this)."""
+
+    index: int
+    """Index of the corner in this lap's `corners`"""
+
+    other_index: int
+    """Index of the matching corner in the other lap's `corners`"""
+
+    apex_distance: float
+    """Apex distance of the corner on this lap (normalized 0-1)"""
+
+    apex_speed_delta: float
+    """Change in apex speed (m/s)"""
+
+    exit_speed_delta: float
+    """Change in exit speed (m/s)"""
+
+    time_in_corner_delta: float
+    """Change in time spent in the corner (seconds)"""
+
+    def to_dict(self) -> dict[str, Any]:
+        """All fields as a dict."""
+        ...
+    def __eq__(self, other: object) -> bool: ...
+
+class BrakingDelta:
+    """How a braking zone changed from one lap to the other (other minus this)."""
+
+    index: int
+    """Index of the zone in this lap's `braking_zones`"""
+
+    other_index: int
+    """Index of the matching zone in the other lap's `braking_zones`"""
+
+    braking_point_distance: float
+    """Braking point of the zone on this lap (normalized 0-1)"""
+
+    braking_point_delta: float
+    """Change in braking point (normalized lap distance, positive is later)"""
+
+    braking_point_speed_delta: float
+    """Change in speed at the braking point (m/s)"""
+
+    minimum_speed_delta: float
+    """Change in the minimum speed reached while braking (m/s)"""
+
+    def to_dict(self) -> dict[str, Any]:
+        """All fields as a dict."""
+        ...
+    def __eq__(self, other: object) -> bool: ...
+
+class LapMetricsDiff:
+    """Differences between two laps' metrics, from `LapMetrics.diff`."""
+
+    lap_time_delta: float | None
+    """Change in lap time in seconds (None unless both laps are timed)"""
+
+    max_speed_delta: float
+    """Change in maximum speed (m/s)"""
+
+    min_speed_delta: float
+    """Change in minimum speed (m/s)"""
+
+    corners: list[CornerDelta]
+    """Paired corners, ordered by this lap's corners"""
+
+    braking_zones: list[BrakingDelta]
+    """Paired braking zones, ordered by this lap's zones"""
+
+    unmatched_corners: list[int]
+    """Indices of this lap's corners with no counterpart"""
+
+    other_unmatched_corners: list[int]
+    """Indices of the other lap's corners with no counterpart"""
+
+    unmatched_braking_zones: list[int]
+    """Indices of this lap's braking zones with no counterpart"""
+
+    other_unmatched_braking_zones: list[int]
+    """Indices of the other lap's braking zones with no counterpart"""
+
+    def to_dict(self) -> dict[str, Any]:
+        """All fields as a dict, with nested deltas as dicts."""
+        ...
+    def __eq__(self, other: object) -> bool: ...
+
+METRICS_SCHEMA_VERSION: int
+"""Version of the JSON schema produced by `LapMetrics.to_json`."""
+
+class ShiftEvent:
+    """A single gear change."""
+
+    from_gear: int
+    """Gear before the shift"""
+
+    to_gear: int
+    """Gear after the shift"""
+
+    rpm: float
+    """Engine RPM on the last frame in the old gear"""
+
+    distance_pct: float
+    """Lap distance of the first frame in the new gear (normalized 0-1)"""
+
+    throttle_blip: bool
+    """Whether the throttle was blipped around a downshift (always false for upshifts)"""
+
+    def __init__(
+        self,
+        from_gear: int,
+        to_gear: int,
+        rpm: float,
+        distance_pct: float,
+        throttle_blip: bool = False,
+    ) -> None: ...
+    def is_downshift(self) -> bool:
+        """Whether the shift went to a lower gear"""
+        ...
+    def to_dict(self) -> dict[str, Any]:
+        """All fields as a dict, with nested results converted to dicts."""
+        ...
+    def __eq__(self, other: object) -> bool: ...
+
+class PitStop:
+    """Metrics for a single visit to pit road."""
+
+    entry_distance: float
+    """Lap distance where the car entered pit road (normalized 0-1)"""
+
+    exit_distance: float
+    """Lap distance where the car left pit road (normalized 0-1)"""
+
+    time_on_pit_road: float
+    """Time spent on pit road in seconds"""
+
+    stopped: bool
+    """Whether the car came to a stop while on pit road"""
+
+    def __init__(
+        self,
+        entry_distance: float,
+        exit_distance: float,
+        time_on_pit_road: float,
+        stopped: bool,
     ) -> None: ...
+    def to_dict(self) -> dict[str, Any]:
+        """All fields as a dict, with nested results converted to dicts."""
+        ...
+    def __eq__(self, other: object) -> bool: ...
+
+class SessionMetrics:
+    """Lap time statistics across a session.
+
+    Only valid laps count: timed laps that didn't touch pit road. Every
+    statistic is None (or empty) when the session has no valid laps.
+    """
+
+    total_laps: int
+    """Number of laps aggregated"""
+
+    valid_laps: int
+    """Number of laps that counted towards the statistics"""
+
+    best_lap_time: float | None
+    """Fastest lap time in seconds"""
+
+    best_lap_number: int | None
+    """Lap number of the fastest lap"""
+
+    average_lap_time: float | None
+    """Mean lap time in seconds"""
+
+    lap_time_std_dev: float | None
+    """Standard deviation of lap times in seconds (lower is more consistent)"""
+
+    best_sector_times: list[float]
+    """Fastest time in each sector across all valid laps, in seconds"""
+
+    theoretical_best_lap_time: float | None
+    """Sum of the best sector times: the lap time possible by combining them"""
+
+    track_conditions: TrackConditions | None
+    """How track and weather conditions evolved, if session frames were given"""
+
+    def to_dict(self) -> dict[str, Any]:
+        """All fields as a dict, with nested results converted to dicts."""
+        ...
+    def __eq__(self, other: object) -> bool: ...
+
+class SpeedStatistics:
+    """Speed statistics over a set of samples.
+
+    All statistics return 0.0 when there are no samples.
+    """
+
+    def __init__(self, speeds: list[float] = ...) -> None: ...
+    def min(self) -> float:
+        """Minimum speed (m/s)"""
+        ...
+    def max(self) -> float:
+        """Maximum speed (m/s)"""
+        ...
+    def mean(self) -> float:
+        """Average speed (m/s)"""
+        ...
+    def std_dev(self) -> float:
+        """Population standard deviation of speed (m/s)"""
+        ...
+    def median(self) -> float:
+        """Median speed (m/s)"""
+        ...
+    def percentile(self, p: float) -> float:
+        """Percentile of speed for p in 0-100, linearly interpolated like numpy's default"""
+        ...
+    def count(self) -> int:
+        """Number of samples"""
+        ...
+
+class WetnessTransition:
+    """A change in track wetness."""
+
+    timestamp: float
+    """Timestamp of the first frame at the new level, in seconds"""
+
+    from_level: int
+    """Wetness level before the change"""
+
+    to_level: int
+    """Wetness level after the change"""
+
+class TireCornerTemps:
+    """Average temperatures across one tire's tread."""
+
+    corner: str
+    """Tire position ("LF", "RF", "LR" or "RR")"""
+
+    inner: float
+    """Average inner edge temperature in Celsius"""
+
+    middle: float
+    """Average middle temperature in Celsius"""
+
+    outer: float
+    """Average outer edge temperature in Celsius"""
+
+    def average(self) -> float:
+        """Average temperature across the tread"""
+        ...
+    def spread(self) -> float:
+        """Inner minus outer temperature; positive when the inside runs hotter"""
+        ...
+
+class SmoothnessScore:
+    """How smoothly the driver worked the controls over a run of frames.
+
+    Each score runs from 0 to 100, higher meaning smoother, and falls as the
+    mean absolute second derivative of the channel over time grows.
+    """
+
+    throttle: float
+    """Throttle smoothness (0-100)"""
+
+    brake: float
+    """Brake smoothness (0-100)"""
+
+    steering: float
+    """Steering smoothness (0-100)"""
+
+    overall: float
+    """Mean of the three channel scores (0-100)"""
+
+    samples: int
+    """Number of frame triples the scores were measured over"""
+
+    def __eq__(self, other: object) -> bool: ...
+
+class FuelUsage:
+    """Fuel used over a run of frames, typically one lap, and how far the
+    remaining fuel would go at the same rate."""
+
+    fuel_used: float
+    """Fuel burned in liters, not counting any fuel added"""
+
+    fuel_remaining: float
+    """Fuel level on the last frame in liters"""
+
+    refueled: bool
+    """Whether fuel was added along the way"""
+
+    laps_remaining: float | None
+    """Laps the remaining fuel lasts at this burn rate (None if no fuel was burned)"""
+
+    def __eq__(self, other: object) -> bool: ...
+
+class PedalUsage:
+    """How a lap's time divides between pedal states, each as a percentage of
+    the lap time (0-100). The states don't overlap; the rest is part throttle
+    or part brake."""
+
+    full_throttle_pct: float
+    """Time at or above full_throttle_threshold without braking"""
+
+    full_brake_pct: float
+    """Time at or above full_brake_threshold"""
+
+    coasting_pct: float
+    """Time below both the throttle and brake thresholds"""
+
+    def __eq__(self, other: object) -> bool: ...
+
+class CorrectionEvent:
+    """A rapid steering reversal made while the car was rotating, typically
+    the driver catching a slide."""
+
+    distance: float
+    """Track distance (0-1) where the reversal started"""
+
+    magnitude: float
+    """Steering angle swept through in radians"""
+
+    duration: float
+    """Time from the start to the end of the reversal in seconds"""
+
+    peak_yaw_rate: float
+    """Highest absolute yaw rate during the reversal in rad/s"""
+
+    def __eq__(self, other: object) -> bool: ...
+
+class GhostLap:
+    """A lap's driver inputs and speed on an evenly spaced distance axis.
+
+    Each list holds one value per point, in distance order."""
+
+    distance: list[float]
+    """Lap distance (normalized 0-1) of each point"""
+
+    speed: list[float]
+    """Speed in m/s"""
+
+    throttle: list[float]
+    """Throttle position (0-1)"""
+
+    brake: list[float]
+    """Brake pressure (0-1)"""
+
+    steering: list[float]
+    """Steering angle in radians"""
+
+    def to_json(self) -> str:
+        """Serialize the ghost to a JSON string."""
+        ...
+    def __len__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+
+class TireAnalysis:
+    """Per-corner tire temperatures over a run of frames."""
+
+    corners: list[TireCornerTemps]
+    """One entry per tire in LF, RF, LR, RR order"""
+
+    frame_count: int
+    """Number of frames with tire temperatures"""
+
+    def corner(self, name: str) -> TireCornerTemps | None:
+        """Temperatures of one tire ("LF", "RF", "LR" or "RR")"""
+        ...
+    def flagged_corners(self, max_spread: float = 8.0) -> list[str]:
+        """Tires whose inner-to-outer spread exceeds max_spread either way"""
+        ...
+
+class TrackConditions:
+    """How track and weather conditions evolve over a session.
+
+    Temperatures return 0.0 when no frames have been added.
+    """
+
+    def __init__(self, frames: list[TelemetryFrame] = ...) -> None: ...
+    def track_temp_min(self) -> float:
+        """Lowest track temperature in Celsius"""
+        ...
+    def track_temp_max(self) -> float:
+        """Highest track temperature in Celsius"""
+        ...
+    def track_temp_mean(self) -> float:
+        """Average track temperature in Celsius"""
+        ...
+    def air_temp_min(self) -> float:
+        """Lowest air temperature in Celsius"""
+        ...
+    def air_temp_max(self) -> float:
+        """Highest air temperature in Celsius"""
+        ...
+    def air_temp_mean(self) -> float:
+        """Average air temperature in Celsius"""
+        ...
+    def initial_wetness(self) -> int | None:
+        """Wetness level of the first frame, if any"""
+        ...
+    def final_wetness(self) -> int | None:
+        """Wetness level of the latest frame, if any"""
+        ...
+    def wetness_transitions(self) -> list[WetnessTransition]:
+        """Every change in wetness level, in order"""
+        ...
+    def count(self) -> int:
+        """Number of frames recorded"""
+        ...
 
 # ============================================================================
 # Functions
@@ -301,6 +1003,212 @@ def py_extract_corners(
     """
     ...
 
+def py_extract_pit_stops(frames: list[TelemetryFrame]) -> list[PitStop]:
+    """Extract pit stops from telemetry frames.
+
+    Args:
+        frames: List of TelemetryFrame objects
+
+    Returns:
+        List of PitStop for each visit to pit road
+    """
+    ...
+
+def py_extract_shifts(frames: list[TelemetryFrame]) -> list[ShiftEvent]:
+    """Extract gear changes from telemetry frames.
+
+    Args:
+        frames: List of TelemetryFrame objects with gear and RPM
+
+    Returns:
+        List of ShiftEvent for each change in gear, with downshifts flagged when
+        the throttle was blipped
+    """
+    ...
+
+def py_aggregate_session(
+    laps: list[LapMetrics],
+    frames: list[TelemetryFrame] | None = None,
+) -> SessionMetrics:
+    """Aggregate per-lap metrics into session statistics.
+
+    Args:
+        laps: LapMetrics for every lap of the session
+        frames: Optional session frames to record track conditions from
+
+    Returns:
+        SessionMetrics with best/average lap, lap time spread, and best sector
+        times over the valid laps (timed, no pit road), plus track conditions
+        when frames are given
+    """
+    ...
+
+def py_compute_time_delta(
+    reference: list[TelemetryFrame],
+    comparison: list[TelemetryFrame],
+    samples: int = 200,
+) -> list[tuple[float, float]]:
+    """Compute where the comparison lap gained or lost time versus a reference lap.
+
+    Args:
+        reference: Frames of the reference lap (e.g. the best lap)
+        comparison: Frames of the lap to compare
+        samples: Number of evenly spaced distance samples (default: 200)
+
+    Returns:
+        List of (distance_pct, delta_seconds) tuples over the distance range both
+        laps cover; positive deltas mean the comparison lap is slower
+    """
+    ...
+
+def py_compute_tire_analysis(frames: list[TelemetryFrame]) -> TireAnalysis:
+    """Average tire temperatures across the tread for each corner.
+
+    Args:
+        frames: List of TelemetryFrame objects with tire temperatures
+
+    Returns:
+        TireAnalysis with inner/middle/outer averages and inner-to-outer spread
+        per tire; frames without tire temperatures are skipped
+    """
+    ...
+
+def py_estimate_balance(
+    frames: list[TelemetryFrame],
+    config: AnalysisConfig | None = None,
+) -> list[CornerBalance]:
+    """Estimate the oversteer/understeer balance of each corner.
+
+    Args:
+        frames: List of TelemetryFrame objects with yaw rate
+        config: Optional AnalysisConfig (uses defaults if not provided)
+
+    Returns:
+        List of CornerBalance for each detected corner; positive balance means
+        oversteer, negative understeer
+    """
+    ...
+
+def py_calibrate_thresholds(frames: list[TelemetryFrame]) -> AnalysisConfig:
+    """Derive detection thresholds from the input distributions of a lap.
+
+    Args:
+        frames: List of TelemetryFrame objects, ideally a full lap
+
+    Returns:
+        AnalysisConfig with steering and brake thresholds scaled to the lap's
+        typical peaks, for use in low-grip or otherwise unusual conditions
+    """
+    ...
+
+def py_compute_smoothness(frames: list[TelemetryFrame]) -> SmoothnessScore:
+    """Score how smoothly the throttle, brake and steering were worked.
+
+    Args:
+        frames: List of TelemetryFrame objects, typically one lap
+
+    Returns:
+        SmoothnessScore with 0-100 scores per channel (higher is smoother),
+        based on the second derivative of each input over time
+    """
+    ...
+
+def py_compute_fuel_usage(frames: list[TelemetryFrame]) -> FuelUsage | None:
+    """Measure the fuel burned over a lap and the range left.
+
+    Args:
+        frames: List of TelemetryFrame objects, typically one lap
+
+    Returns:
+        FuelUsage with the burn (refueling excluded) and projected laps
+        remaining, or None if the frames carry no fuel level
+    """
+    ...
+
+def py_compute_pedal_usage(
+    frames: list[TelemetryFrame], config: AnalysisConfig | None = None
+) -> PedalUsage | None:
+    """Measure the share of a lap spent at full throttle, at full brake and coasting.
+
+    Args:
+        frames: List of TelemetryFrame objects, typically one lap
+        config: Optional AnalysisConfig with the pedal thresholds (uses
+            defaults if not provided)
+
+    Returns:
+        PedalUsage with each state as a percentage of the lap time, weighted
+        by the time between frames, or None if the frames span no time
+    """
+    ...
+
+def py_detect_corrections(
+    frames: list[TelemetryFrame], config: AnalysisConfig | None = None
+) -> list[CorrectionEvent]:
+    """Find slide corrections: rapid steering reversals through zero while the car rotates.
+
+    Args:
+        frames: List of TelemetryFrame objects with steering angle and yaw rate
+        config: Optional AnalysisConfig with the correction thresholds (uses
+            defaults if not provided)
+
+    Returns:
+        List of CorrectionEvent in lap order
+    """
+    ...
+
+def py_resample_by_distance(
+    frames: list[TelemetryFrame], num_points: int
+) -> list[TelemetryFrame]:
+    """Resample a lap onto evenly spaced lap distances for point-by-point comparison.
+
+    Args:
+        frames: List of TelemetryFrame objects, typically one lap
+        num_points: Number of distances from 0.0 to 1.0 to sample at
+
+    Returns:
+        List of num_points TelemetryFrame objects with every channel linearly
+        interpolated at its distance; frames are ordered by distance first, so
+        backwards glitches don't fold the trace over itself
+    """
+    ...
+
+def py_export_ghost(frames: list[TelemetryFrame], num_points: int) -> GhostLap:
+    """Export a lap as a compact ghost for overlaying in external tools.
+
+    Args:
+        frames: List of TelemetryFrame objects, typically one lap
+        num_points: Number of distances from 0.0 to 1.0 to sample at
+
+    Returns:
+        GhostLap with distance, speed, throttle, brake and steering lists of
+        num_points values each, resampled as in py_resample_by_distance
+    """
+    ...
+
+def py_moving_average(values: list[float], window: int) -> list[float]:
+    """Smooth values with a centered moving average.
+
+    Args:
+        values: List of samples
+        window: Number of samples to average (0 or 1 leaves values unchanged)
+
+    Returns:
+        Smoothed values, the same length as the input
+    """
+    ...
+
+def py_exponential_smoothing(values: list[float], alpha: float) -> list[float]:
+    """Smooth values with an exponential moving average.
+
+    Args:
+        values: List of samples
+        alpha: Weight of each new sample (0.0-1.0, smaller smooths more)
+
+    Returns:
+        Smoothed values, the same length as the input
+    """
+    ...
+
 def hello_from_rust(name: str | None = None) -> str:
     """A simple hello world function to verify Rust + PyO3 integration works."""
     ...
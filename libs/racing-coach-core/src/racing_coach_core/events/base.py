@@ -2,7 +2,7 @@ import asyncio
 import logging
 from collections.abc import Callable
 from concurrent.futures import ThreadPoolExecutor
-from dataclasses import dataclass, field
+from dataclasses import dataclass, field, replace
 from datetime import datetime
 from typing import Any
 
@@ -157,6 +157,14 @@ class EventBus:
             future = asyncio.run_coroutine_threadsafe(_put_event(), self._loop)
             future.result(timeout=5.0)
 
+    async def publish_with_timestamp(self, event: Event[Any], timestamp: datetime) -> None:
+        """Publish an event stamped with `timestamp` instead of its creation time.
+
+        Used when replaying recorded events, so handlers see when each event
+        originally happened.
+        """
+        await self.publish(replace(event, timestamp=timestamp))
+
     def thread_safe_publish(self, event: Event[Any]) -> None:
         """Called from non-async code or different threads to publish events."""
         if not self._running or self._loop is None or self._queue is None:
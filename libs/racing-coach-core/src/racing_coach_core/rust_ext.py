@@ -115,6 +115,15 @@ def _convert_sequence_to_rust_frames(sequence: TelemetrySequence) -> list:
             lateral_acceleration=frame.lateral_acceleration,
             longitudinal_acceleration=frame.longitudinal_acceleration,
             timestamp=frame.timestamp.timestamp(),
+            on_pit_road=frame.on_pit_road,
+            gear=frame.gear,
+            track_temp=frame.track_temp,
+            air_temp=frame.air_temp,
+            track_wetness=frame.track_wetness,
+            tire_temps=frame.tire_temps,
+            lap_distance_meters=frame.lap_distance,
+            yaw_rate=frame.yaw_rate,
+            rpm=frame.rpm,
         )
         for frame in sequence.frames
     ]
@@ -2,6 +2,7 @@
 
 import asyncio
 import time
+from datetime import datetime
 from typing import Any
 
 import pytest
@@ -344,6 +345,27 @@ class TestEventBusPublishIntegration:
         # The success handler should still have been called
         assert received_data == ["test data"]
 
+    async def test_publish_with_timestamp_preserves_original_time(
+        self, running_event_bus: EventBus
+    ):
+        """Test that a replayed event reaches handlers with its original timestamp."""
+        event_type = EventType[str](name="TEST", data_type=str)
+        received_timestamps: list[datetime] = []
+
+        def handler(context: HandlerContext[str]) -> None:
+            received_timestamps.append(context.event.timestamp)
+
+        running_event_bus.subscribe(event_type, handler)
+
+        original = datetime(2024, 5, 1, 14, 30, 15)
+        await running_event_bus.publish_with_timestamp(
+            Event(type=event_type, data="replayed"), original
+        )
+
+        await asyncio.sleep(0.2)
+
+        assert received_timestamps == [original]
+
 
 @pytest.mark.integration
 class TestEventBusThreadSafePublish: